@@ -0,0 +1,41 @@
+//! Criterion benchmarks of the search steps that don't need the binary's internals:
+//! parsing a page of results into octocrab's models. `github-search-cli --bench` times the rest
+//! (query build, API round-trip, and frame render) against the real API.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use octocrab::models::Repository;
+use serde::Deserialize;
+
+/// The body of a search response, as octocrab parses it
+#[derive(Deserialize)]
+struct SearchPage {
+    items: Vec<Repository>,
+}
+
+const PAGES: &[&str] = &[
+    include_str!("../tests/fixtures/search_page1.json"),
+    include_str!("../tests/fixtures/search_page2.json"),
+];
+
+/// The fixture repositories repeated into a full page of `count` results
+fn page_of(count: usize) -> String {
+    let items: Vec<serde_json::Value> = PAGES
+        .iter()
+        .flat_map(|page| serde_json::from_str::<serde_json::Value>(page).unwrap()["items"].as_array().unwrap().clone())
+        .cycle()
+        .take(count)
+        .collect();
+    serde_json::json!({ "total_count": count, "items": items }).to_string()
+}
+
+fn parse_results(c: &mut Criterion) {
+    for count in [10, 100] {
+        let body = page_of(count);
+        c.bench_function(&format!("parse {} results", count), |b| {
+            b.iter(|| serde_json::from_str::<SearchPage>(black_box(&body)).unwrap().items.len())
+        });
+    }
+}
+
+criterion_group!(benches, parse_results);
+criterion_main!(benches);
@@ -0,0 +1,470 @@
+# English messages (the reference catalog: every message lives here first)
+# Placeholders like { $count } are filled in by the app; keep them in translations
+
+## Main screen
+
+search-title = Search GitHub Repositories
+results-title = Results
+tab-new = New search
+details-title = Details
+help-title = Help
+welcome = Welcome to Mr Krabz GitHub Search!
+welcome-type = Type a search query and press Enter to search.
+welcome-examples = Examples: 'rust game', 'web framework', 'machine learning'
+welcome-navigate = Use { $keys } to navigate results, Enter to open in browser.
+details-empty = Select a repository to see details
+searching = Searching... { $progress }
+error-line = Error: { $error }
+results-total = Results ({ $total } total)
+results-collection =
+    { $count ->
+        [one] { $list } (1 linked repository)
+       *[other] { $list } ({ $count } linked repositories)
+    }
+results-pages = Results (page { $page }/{ $pages } · { $loaded } of { $total } loaded)
+results-offline = [offline: cached { $age }]
+results-cached = [cached { $age }, Ctrl+R refreshes]
+results-sorted-by = [by { $sort }]
+results-selected = [{ $count } selected]
+no-results = No repositories found for "{ $query }".
+did-you-mean = Did you mean { $query }?
+press-to-search = Press { $key } to search for it.
+try-different = Try fewer or different words, or looser qualifiers.
+
+## Help bar
+
+help-search = Search
+help-open = Open
+help-type = Type
+help-browse = Browse
+help-navigate = Navigate
+help-count = Count
+help-analyze = Analyze
+help-clone = Clone
+help-mark = Mark
+help-bookmark = Bookmark
+help-copy-url = Copy URL
+help-jobs = Jobs
+help-my-clones = My clones
+help-filter = Filter
+help-syntax = Syntax
+help-cancel = Cancel
+help-quit = Quit
+help-vim = Vim
+help-try-suggestion = Try suggestion
+help-compare = Compare
+help-page = Page
+help-more = More
+help-tabs = Tabs
+
+## Errors and what to do about them
+
+error-cancelled = cancelled
+error-deferred = skipped to save the rate limit for requests you make
+error-rate-limited = GitHub API rate limit exceeded
+error-auth-required = repository not found or requires authentication
+error-not-found = not found
+error-invalid-query = invalid query: { $message }
+error-network = network error: { $message }
+error-git-failed = git error: { $stderr }
+error-git-missing = partial clones need the git binary: { $message }
+error-too-large = too large for GitHub to list
+hint-rate-limited-retry = Retry in { $seconds }s, or add a token (-t or GITHUB_TOKEN) for a higher limit
+hint-rate-limited = Wait a minute and retry, or add a token (-t or GITHUB_TOKEN) for a higher limit
+hint-rate-limited-resume = Searching again in { $time } (Esc to cancel), or add a token (-t or GITHUB_TOKEN) for a higher limit
+hint-auth-required = Add a token (-t or GITHUB_TOKEN) with access, or check it hasn't expired
+hint-not-found = Check the owner and name; private repositories need a token
+hint-invalid-query = Check the query's qualifiers, e.g. stars:>100 or language:rust
+hint-certificate = If a proxy or GitHub Enterprise uses its own CA, set ca_bundle in config.toml to its PEM file
+hint-network = Check your internet connection and try again
+hint-git-missing = Install git, or set clone_mode = "full" in config.toml
+
+## Clone, file count, and analysis prompt
+
+prompt-update = Update repository
+prompt-clone =
+    { $count ->
+        [one] Clone repository
+       *[other] Clone { $count } repositories
+    }
+prompt-count-files =
+    { $count ->
+        [one] Count files
+       *[other] Count files in { $count } repositories
+    }
+prompt-analyze =
+    { $count ->
+        [one] Analyze languages
+       *[other] Analyze languages in { $count } repositories
+    }
+prompt-already-cloned = Already cloned here: Enter fetches and fast-forwards it
+prompt-destination-batch = Directory to clone into (each repo gets a subdirectory)
+prompt-destination = Path to clone into (the last part names the directory)
+prompt-depth = Commits to fetch, or "full" for full history
+prompt-mode-full = Download every file
+prompt-mode-blobless = Full tree, file contents fetched on demand (uses git)
+prompt-mode-treeless = Commits only, trees fetched on demand (uses git)
+prompt-submodules = Clone submodules recursively after checkout
+prompt-size = ~{ $size } (reported by GitHub)
+prompt-yes = yes
+prompt-no = no
+prompt-keys = Tab/{ $updown } next field, { $leftright }/Space change, Enter confirm, Esc cancel
+field-destination = Destination
+field-depth = Depth
+field-mode = Mode
+field-submodules = Submodules
+field-size = Size
+prompt-destination-empty = Destination can't be empty
+prompt-depth-invalid = Depth must be a number of commits or "full"
+prompt-confirm-size = ~{ $size } is over { $limit } MB: press Enter again to clone
+
+## Page, export, scope, and awesome-list prompts
+
+page-title = Go to page
+page-label = Page (1-{ $pages })
+page-keys = Enter go, Esc cancel
+export-title =
+    { $count ->
+        [one] Export 1 repository
+       *[other] Export { $count } repositories
+    }
+export-label = File
+export-keys = .md, .json, or .csv picks the format; Enter write, Esc cancel
+scope-title = Search one owner
+scope-label = User or org
+scope-keys = name, user:name, or org:name; empty clears; Enter set, Esc cancel
+awesome-title = Find awesome lists
+awesome-label = Topic
+awesome-keys = e.g. rust or game engine; Enter search, Esc cancel
+
+## Confirmations and pickers
+
+confirm-keys = y to confirm, any other key to cancel
+picker-keys = { $updown } select, Enter run, Esc close
+palette-title = Commands
+
+## Details panel (labels get ": " added after them)
+
+details-no-description = No description
+details-unknown = Unknown
+details-description = Description
+details-stars = Stars
+details-forks = Forks
+details-language = Language
+details-size = Size
+details-computed = (computed { $age }, Alt+N refreshes)
+details-releases = Releases
+details-contributor-count = { $count } contributors
+details-unavailable = unavailable
+details-loading = Loading...
+details-issues = Issues
+details-pulls = Pull requests
+details-contributors = Contributors
+details-pushed = Pushed
+details-updated = Updated
+details-matched = Matched
+details-visibility =
+    { $visibility ->
+        [private] Private repository
+        [internal] Internal repository
+       *[other] { $visibility } repository
+    }
+details-bookmarked = Bookmarked
+details-license = License
+details-license-conflict = { $conflict } (project_license is { $project })
+details-cloned = Cloned
+details-build-with = Build with
+details-toolchain = Toolchain
+details-advisories = Advisories
+details-checking = Checking...
+details-analysis = Analysis
+details-running = Running...
+details-more-lines = ... { $count } more lines
+details-clone = Clone
+details-queued = Queued...
+details-cloning = Cloning
+details-cloning-counting = Cloning and counting... { $progress }
+details-cloning-analyzing = Cloning and analyzing... { $progress }
+details-files = Files
+details-please-wait = Please wait... { $progress }
+details-file-count = File Count
+details-press-count = Press 'f' to count
+details-languages = Languages
+details-star-history = Star history
+
+## My clones and jobs
+
+clone-count =
+    { $count ->
+        [one] 1 clone
+       *[other] { $count } clones
+    }
+clones-title = My clones: { $clones }, { $size } (sorted by { $sort })
+clones-by-size = size
+clones-by-name = name
+clones-empty = No cloned repositories yet (Alt+G clones the selected result)
+jobs-empty = No jobs yet
+job-commands-failed = { $failed } of { $total } commands failed
+delete-title = Delete clones
+delete-message = Delete { $what } ({ $size })?
+sort-files = file count
+sort-loc = lines of code
+
+## Result rows, status bar, and language table
+
+row-license = license
+row-clone-queued = clone queued
+row-queued = queued
+row-analysis-queued = analysis queued
+row-cloning = cloning
+row-counting = counting
+row-analyzing = analyzing
+status-anonymous = anonymous
+status-resets-in = resets in { $time }
+status-no-filters = no filters
+mode-typing = TYPING
+mode-browsing = BROWSING
+mode-insert = INSERT
+mode-normal = NORMAL
+languages-none = No recognised source files
+languages-language = Language
+languages-files = Files
+languages-code = Code
+languages-comments = Comments
+languages-blanks = Blanks
+
+## Notifications
+
+toast-no-such-page = There is no page { $page } (1-{ $pages })
+toast-one-page = All results are on one page
+toast-enter-page = Enter a page from 1 to { $pages }
+toast-nothing-to-refresh = Nothing stored from an earlier session to refresh
+toast-sorted-by = Sorted by { $sort }; results not analyzed yet go last
+toast-github-order = Back to GitHub's order
+toast-offline = Offline: showing cached results from { $age }
+toast-gone =
+    { $more ->
+        [0] No longer found: { $names }
+       *[other] No longer found: { $names } and { $more } more
+    }
+toast-count-failed = File count failed for { $url }: { $error }
+toast-analysis-failed = Analysis failed for { $url }: { $error }
+toast-updated = Updated { $path }: { $outcome }
+toast-updated-many = Updated { $count } repositories
+toast-update-failed = Update failed ({ $failed }/{ $total }): { $errors }
+toast-cloned = Cloned to { $path }
+toast-cloned-pane = Cloned to { $path } (Alt+D opens a pane there)
+toast-clone-failed = Clone failed for { $url }: { $error }
+toast-page-failed = Loading page { $page } failed: { $error }
+toast-activity-failed = Couldn't load releases and contributors for { $url }: { $error }
+toast-sbom-written = Wrote the SBOM of { $clone } ({ $components } components) to { $path }
+toast-sbom-failed = SBOM of { $clone } failed: { $error }
+toast-no-advisories = No known advisories against { $name }
+toast-advisories = { $name } depends on crates with advisories: { $advisories }
+toast-audit-failed = Audit of { $name } failed: { $error }
+toast-list-empty = { $list } doesn't link to any GitHub repositories
+toast-list-failed = Couldn't load { $list }: { $error }
+toast-loading-list = Loading the repositories { $list } links to
+toast-cancelled = Cancelled { $job }: { $target }
+toast-resuming = Rate limit reset: searching for "{ $query }" again
+toast-retry-cancelled = Automatic retry cancelled
+toast-rate-low = Search API: { $remaining } of { $limit } requests left, resets in { $seconds }s
+toast-bookmarks-updated = Bookmarks updated
+toast-only-tab = This is the only tab
+toast-nothing-to-export = No results to export
+toast-enter-file = Enter a file to write to
+toast-exported =
+    { $count ->
+        [one] Exported 1 repository to { $path } ({ $format })
+       *[other] Exported { $count } repositories to { $path } ({ $format })
+    }
+toast-bad-owner = '{ $owner }' isn't a GitHub user or organization name
+toast-scope = Searching only { $scope }
+toast-scope-cleared = Searching all of GitHub
+toast-deleted = Deleted { $clones } ({ $size } freed)
+toast-delete-failed = Delete failed: { $error }
+toast-no-suggestion = No suggestion to search for
+toast-nothing-similar = { $name } has no topics or language to search by
+toast-similar = Searching for repositories like { $name }
+toast-exclude-needs-search = Only search results can leave an owner out
+toast-already-excluded = { $owner } is already left out
+toast-excluding = Searching again without { $owner }'s repositories
+toast-no-search = No search to refresh
+toast-enter-topic = Enter a topic, e.g. rust
+toast-not-cloned = Not cloned yet: Alt+G clones it
+toast-no-multiplexer = Not inside tmux or WezTerm: set pane_command in config.toml
+toast-pane-opened = Opened a pane in { $path }
+toast-pane-failed = Opening a pane failed: { $error }
+toast-raw-query = --raw-query sends searches as typed: size filters and owner scopes are off
+toast-mark-two = Mark two repositories to compare ({ $marked } marked)
+toast-copied = Copied { $text }
+toast-copy-failed = Copy failed: { $error }
+
+## Comparison view
+
+compare-stars = Stars
+compare-forks = Forks
+compare-last-push = Last push
+compare-releases = Releases
+compare-contributors = Contributors
+compare-lines-of-code = Lines of code
+compare-license = License
+compare-unavailable = unavailable
+compare-loading = loading…
+compare-not-loaded = not loaded
+compare-unknown = unknown
+compare-too-many = too many to list
+compare-analysis-failed = analysis failed
+compare-not-analyzed = not analyzed (Alt+A)
+compare-incompatible = incompatible: { $conflict }
+
+## Ages, spans, and release cadences
+
+age-just-now = just now
+age-minutes = { $count } min ago
+age-hours = { $count } h ago
+age-days =
+    { $count ->
+        [one] 1 day ago
+       *[other] { $count } days ago
+    }
+age-months =
+    { $count ->
+        [one] 1 month ago
+       *[other] { $count } months ago
+    }
+age-years =
+    { $count ->
+        [one] 1 year ago
+       *[other] { $count } years ago
+    }
+span-under-hour = under an hour
+span-hours =
+    { $count ->
+        [one] ~1 hour
+       *[other] ~{ $count } hours
+    }
+span-days =
+    { $count ->
+        [one] ~1 day
+       *[other] ~{ $count } days
+    }
+span-weeks =
+    { $count ->
+        [one] ~1 week
+       *[other] ~{ $count } weeks
+    }
+span-months =
+    { $count ->
+        [one] ~1 month
+       *[other] ~{ $count } months
+    }
+span-years =
+    { $count ->
+        [one] ~1 year
+       *[other] ~{ $count } years
+    }
+every-more-than-daily = more than daily
+every-days =
+    { $count ->
+        [one] every ~1 day
+       *[other] every ~{ $count } days
+    }
+every-weeks =
+    { $count ->
+        [one] every ~1 week
+       *[other] every ~{ $count } weeks
+    }
+every-months =
+    { $count ->
+        [one] every ~1 month
+       *[other] every ~{ $count } months
+    }
+every-years =
+    { $count ->
+        [one] every ~1 year
+       *[other] every ~{ $count } years
+    }
+releases-none = no releases
+releases-one = 1 release, { $age }
+releases-cadence = { $cadence }, last { $age }
+changes-new = +{ $count } new
+changes-gone = { $count } gone
+changes-since = { $changes } since { $age }
+
+## Maintenance figures
+
+issues-none = no recent issues
+issues-no-replies = no replies yet
+issues-first-reply = first reply { $span }
+issues-none-closed = none closed
+issues-closed-in = closed in { $span }
+issues-summary = { $replies }, { $closes } (median of { $count } recent)
+pulls-none = no recent pull requests
+pulls-merged = { $merged } of { $count } merged ({ $percent }%)
+pulls-merged-in = { $merged } of { $count } merged ({ $percent }%) in { $span }
+pulls-still-open = { $pulls }, { $open } still open
+contributors-not-listed = not listed (too many commits, or none)
+contributors-single = a single contributor made every commit
+contributors-top-one = the top one of { $listed } contributors made { $share }% of commits
+contributors-top = top { $top } of { $listed } contributors made { $share }% of commits
+contributors-commits = commits
+contributors-others = { $count } others
+
+## File counts and clone updates
+
+files-by-extension = Files by extension:
+files-no-extension = (none)
+files-extension =
+    { $count ->
+        [one] { $extension }: 1 file | { $size }
+       *[other] { $extension }: { $count } files | { $size }
+    }
+files-total =
+    { $count ->
+        [one] Total: 1 file, { $size }
+       *[other] Total: { $count } files, { $size }
+    }
+update-up-to-date = already up to date
+update-fast-forwarded = fast-forwarded
+update-fetched-only = fetched (branch not fast-forwarded)
+
+## Linear mode (--linear): plain sentences for screen readers
+
+linear-welcome = Mr Krabz GitHub Search, linear mode.
+linear-start = Type a search query and press Enter, or q to quit.
+linear-search = Search
+linear-details = Details
+linear-results-prompt = Type a result number for details, a new search, or q to quit.
+linear-details-prompt = Type o to open in the browser, c to clone, f to count files, a to analyze languages, b to bookmark, l to list the results again, another number, a new search, or q to quit.
+linear-no-results-yet = No results yet. Type a search query.
+linear-no-such-result = There is no result { $number }. Choose 1 to { $count }.
+linear-goodbye = Goodbye.
+linear-searching = Searching for { $query }...
+linear-offline = GitHub is unreachable. These are cached results from { $age }.
+linear-gone = No longer found: { $names }.
+linear-none-found = No repositories found.
+linear-did-you-mean = Did you mean { $query }? Type it, or another search.
+linear-search-again = Type another search.
+linear-found = Found { $total } repositories, showing { $shown }.
+linear-search-failed = Search failed: { $error }
+linear-unknown = unknown
+linear-unknown-language = unknown language
+linear-result = { $number }. { $name }, { $stars } stars, { $language }
+linear-result-of = Result { $number } of { $total }: { $name }
+linear-no-description = none
+linear-url = URL
+linear-last-activity = Last activity
+linear-cloned-to = Cloned to { $path }
+linear-opened = Opened { $url } in the browser.
+linear-bookmarked = Bookmarked { $name }.
+linear-unbookmarked = Removed the bookmark for { $name }.
+linear-cloning = Cloning { $name } into { $path }. This may take a while.
+linear-cloned = Cloned { $name }.
+linear-clone-failed = Clone failed: { $error }
+linear-counting = Counting files in { $name }...
+linear-count-failed = File count failed: { $error }
+linear-analyzing = Analyzing languages in { $name }. This clones the repository first.
+linear-no-sources = No recognised source files.
+linear-language = { $language }: { $files } files, { $code } lines of code, { $comments } comments, { $blanks } blank lines
+linear-analysis-failed = Analysis failed: { $error }
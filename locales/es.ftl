@@ -0,0 +1,469 @@
+# Mensajes en español (traducción de en.ftl; los { $marcadores } no se traducen)
+
+## Pantalla principal
+
+search-title = Buscar repositorios de GitHub
+results-title = Resultados
+tab-new = Nueva búsqueda
+details-title = Detalles
+help-title = Ayuda
+welcome = ¡Bienvenido a Mr Krabz GitHub Search!
+welcome-type = Escribe una búsqueda y pulsa Enter para buscar.
+welcome-examples = Ejemplos: 'rust game', 'web framework', 'machine learning'
+welcome-navigate = Usa { $keys } para recorrer los resultados y Enter para abrirlos en el navegador.
+details-empty = Selecciona un repositorio para ver sus detalles
+searching = Buscando... { $progress }
+error-line = Error: { $error }
+results-total = Resultados ({ $total } en total)
+results-collection =
+    { $count ->
+        [one] { $list } (1 repositorio enlazado)
+       *[other] { $list } ({ $count } repositorios enlazados)
+    }
+results-pages = Resultados (página { $page }/{ $pages } · { $loaded } de { $total } cargados)
+results-offline = [sin conexión: caché de { $age }]
+results-cached = [caché de { $age }, Ctrl+R actualiza]
+results-sorted-by = [por { $sort }]
+results-selected = [{ $count } seleccionados]
+no-results = No se encontraron repositorios para "{ $query }".
+did-you-mean = ¿Quisiste decir { $query }?
+press-to-search = Pulsa { $key } para buscarlo.
+try-different = Prueba con menos palabras, otras distintas, o calificadores menos estrictos.
+
+## Barra de ayuda
+
+help-search = Buscar
+help-open = Abrir
+help-type = Escribir
+help-browse = Explorar
+help-navigate = Mover
+help-count = Contar
+help-analyze = Analizar
+help-clone = Clonar
+help-mark = Marcar
+help-bookmark = Favorito
+help-copy-url = Copiar URL
+help-jobs = Tareas
+help-my-clones = Mis clones
+help-filter = Filtrar
+help-syntax = Sintaxis
+help-cancel = Cancelar
+help-quit = Salir
+help-vim = Vim
+help-try-suggestion = Probar sugerencia
+help-compare = Comparar
+help-page = Página
+help-more = Más
+help-tabs = Pestañas
+
+## Errores y qué hacer
+
+error-cancelled = cancelado
+error-deferred = omitido para reservar el límite de peticiones a las que pides tú
+error-rate-limited = se superó el límite de peticiones de la API de GitHub
+error-auth-required = el repositorio no existe o requiere autenticación
+error-not-found = no encontrado
+error-invalid-query = búsqueda no válida: { $message }
+error-network = error de red: { $message }
+error-git-failed = error de git: { $stderr }
+error-git-missing = los clones parciales necesitan el programa git: { $message }
+error-too-large = demasiado grande para que GitHub lo liste
+hint-rate-limited-retry = Reintenta en { $seconds } s, o añade un token (-t o GITHUB_TOKEN) para un límite mayor
+hint-rate-limited = Espera un minuto y reintenta, o añade un token (-t o GITHUB_TOKEN) para un límite mayor
+hint-rate-limited-resume = Buscando de nuevo en { $time } (Esc para cancelar), o añade un token (-t o GITHUB_TOKEN) para un límite mayor
+hint-auth-required = Añade un token (-t o GITHUB_TOKEN) con acceso, o comprueba que no haya caducado
+hint-not-found = Comprueba el propietario y el nombre; los repositorios privados necesitan un token
+hint-invalid-query = Revisa los calificadores de la búsqueda, p. ej. stars:>100 o language:rust
+hint-certificate = Si un proxy o GitHub Enterprise usa su propia CA, pon su archivo PEM en ca_bundle en config.toml
+hint-network = Comprueba tu conexión a internet y vuelve a intentarlo
+hint-git-missing = Instala git, o pon clone_mode = "full" en config.toml
+
+## Diálogo de clonado, recuento de archivos y análisis
+
+prompt-update = Actualizar repositorio
+prompt-clone =
+    { $count ->
+        [one] Clonar repositorio
+       *[other] Clonar { $count } repositorios
+    }
+prompt-count-files =
+    { $count ->
+        [one] Contar archivos
+       *[other] Contar archivos de { $count } repositorios
+    }
+prompt-analyze =
+    { $count ->
+        [one] Analizar lenguajes
+       *[other] Analizar lenguajes de { $count } repositorios
+    }
+prompt-already-cloned = Ya está clonado aquí: Enter lo descarga y avanza
+prompt-destination-batch = Directorio donde clonar (cada repositorio en un subdirectorio)
+prompt-destination = Ruta donde clonar (la última parte da nombre al directorio)
+prompt-depth = Commits a descargar, o "full" para el historial completo
+prompt-mode-full = Descargar todos los archivos
+prompt-mode-blobless = Árbol completo, contenidos bajo demanda (usa git)
+prompt-mode-treeless = Solo commits, árboles bajo demanda (usa git)
+prompt-submodules = Clonar los submódulos recursivamente tras el checkout
+prompt-size = ~{ $size } (según GitHub)
+prompt-yes = sí
+prompt-no = no
+prompt-keys = Tab/{ $updown } siguiente campo, { $leftright }/Espacio cambiar, Enter confirmar, Esc cancelar
+field-destination = Destino
+field-depth = Profundidad
+field-mode = Modo
+field-submodules = Submódulos
+field-size = Tamaño
+prompt-destination-empty = El destino no puede estar vacío
+prompt-depth-invalid = La profundidad debe ser un número de commits o "full"
+prompt-confirm-size = ~{ $size } supera { $limit } MB: pulsa Enter otra vez para clonar
+
+## Diálogos de página, exportación, ámbito y listas awesome
+
+page-title = Ir a la página
+page-label = Página (1-{ $pages })
+page-keys = Enter ir, Esc cancelar
+export-title =
+    { $count ->
+        [one] Exportar 1 repositorio
+       *[other] Exportar { $count } repositorios
+    }
+export-label = Archivo
+export-keys = .md, .json o .csv eligen el formato; Enter guardar, Esc cancelar
+scope-title = Buscar en un propietario
+scope-label = Usuario u org.
+scope-keys = nombre, user:nombre u org:nombre; vacío lo quita; Enter fijar, Esc cancelar
+awesome-title = Buscar listas awesome
+awesome-label = Tema
+awesome-keys = p. ej. rust o game engine; Enter buscar, Esc cancelar
+
+## Confirmaciones y listas
+
+confirm-keys = y para confirmar, cualquier otra tecla para cancelar
+picker-keys = { $updown } elegir, Enter ejecutar, Esc cerrar
+palette-title = Comandos
+
+## Panel de detalles (las etiquetas llevan ": " detrás)
+
+details-no-description = Sin descripción
+details-unknown = Desconocido
+details-description = Descripción
+details-stars = Estrellas
+details-forks = Forks
+details-language = Lenguaje
+details-size = Tamaño
+details-computed = (calculado { $age }, Alt+N lo actualiza)
+details-releases = Versiones
+details-contributor-count = { $count } colaboradores
+details-unavailable = no disponible
+details-loading = Cargando...
+details-issues = Issues
+details-pulls = Pull requests
+details-contributors = Colaboradores
+details-pushed = Último push
+details-updated = Actualizado
+details-matched = Coincide
+details-visibility =
+    { $visibility ->
+        [private] Repositorio privado
+        [internal] Repositorio interno
+       *[other] Repositorio { $visibility }
+    }
+details-bookmarked = En marcadores
+details-license = Licencia
+details-license-conflict = { $conflict } (project_license es { $project })
+details-cloned = Clonado
+details-build-with = Compilar con
+details-toolchain = Toolchain
+details-advisories = Avisos
+details-checking = Comprobando...
+details-analysis = Análisis
+details-running = Ejecutando...
+details-more-lines = ... { $count } líneas más
+details-clone = Clon
+details-queued = En cola...
+details-cloning = Clonando
+details-cloning-counting = Clonando y contando... { $progress }
+details-cloning-analyzing = Clonando y analizando... { $progress }
+details-files = Archivos
+details-please-wait = Espera... { $progress }
+details-file-count = Archivos
+details-press-count = Pulsa 'f' para contar
+details-languages = Lenguajes
+details-star-history = Historial de estrellas
+
+## Mis clones y tareas
+
+clone-count =
+    { $count ->
+        [one] 1 clon
+       *[other] { $count } clones
+    }
+clones-title = Mis clones: { $clones }, { $size } (por { $sort })
+clones-by-size = tamaño
+clones-by-name = nombre
+clones-empty = Aún no hay repositorios clonados (Alt+G clona el resultado elegido)
+jobs-empty = Aún no hay tareas
+job-commands-failed = { $failed } de { $total } comandos fallaron
+delete-title = Borrar clones
+delete-message = ¿Borrar { $what } ({ $size })?
+sort-files = número de archivos
+sort-loc = líneas de código
+
+## Filas de resultados, barra de estado y tabla de lenguajes
+
+row-license = licencia
+row-clone-queued = clon en cola
+row-queued = en cola
+row-analysis-queued = análisis en cola
+row-cloning = clonando
+row-counting = contando
+row-analyzing = analizando
+status-anonymous = anónimo
+status-resets-in = se restablece en { $time }
+status-no-filters = sin filtros
+mode-typing = ESCRIBIENDO
+mode-browsing = NAVEGANDO
+mode-insert = INSERTAR
+mode-normal = NORMAL
+languages-none = No hay archivos de código reconocidos
+languages-language = Lenguaje
+languages-files = Archivos
+languages-code = Código
+languages-comments = Comentarios
+languages-blanks = En blanco
+
+## Avisos
+
+toast-no-such-page = No hay página { $page } (1-{ $pages })
+toast-one-page = Todos los resultados caben en una página
+toast-enter-page = Escribe una página del 1 al { $pages }
+toast-nothing-to-refresh = No hay nada guardado de una sesión anterior que actualizar
+toast-sorted-by = Ordenado por { $sort }; los resultados sin analizar van al final
+toast-github-order = De vuelta al orden de GitHub
+toast-offline = Sin conexión: resultados en caché de { $age }
+toast-gone =
+    { $more ->
+        [0] Ya no aparecen: { $names }
+       *[other] Ya no aparecen: { $names } y { $more } más
+    }
+toast-count-failed = Falló el recuento de archivos de { $url }: { $error }
+toast-analysis-failed = Falló el análisis de { $url }: { $error }
+toast-updated = { $path } actualizado: { $outcome }
+toast-updated-many = { $count } repositorios actualizados
+toast-update-failed = Falló la actualización ({ $failed }/{ $total }): { $errors }
+toast-cloned = Clonado en { $path }
+toast-cloned-pane = Clonado en { $path } (Alt+D abre un panel allí)
+toast-clone-failed = Falló la clonación de { $url }: { $error }
+toast-page-failed = Falló la carga de la página { $page }: { $error }
+toast-activity-failed = No se pudieron cargar versiones y colaboradores de { $url }: { $error }
+toast-sbom-written = SBOM de { $clone } ({ $components } componentes) guardado en { $path }
+toast-sbom-failed = Falló el SBOM de { $clone }: { $error }
+toast-no-advisories = No hay avisos conocidos contra { $name }
+toast-advisories = { $name } depende de crates con avisos: { $advisories }
+toast-audit-failed = Falló la auditoría de { $name }: { $error }
+toast-list-empty = { $list } no enlaza a ningún repositorio de GitHub
+toast-list-failed = No se pudo cargar { $list }: { $error }
+toast-loading-list = Cargando los repositorios que enlaza { $list }
+toast-cancelled = Cancelado { $job }: { $target }
+toast-resuming = Límite restablecido: buscando "{ $query }" de nuevo
+toast-retry-cancelled = Reintento automático cancelado
+toast-rate-low = API de búsqueda: quedan { $remaining } de { $limit } peticiones, se restablece en { $seconds } s
+toast-bookmarks-updated = Marcadores actualizados
+toast-only-tab = Esta es la única pestaña
+toast-nothing-to-export = No hay resultados que exportar
+toast-enter-file = Escribe un archivo donde guardar
+toast-exported =
+    { $count ->
+        [one] 1 repositorio exportado a { $path } ({ $format })
+       *[other] { $count } repositorios exportados a { $path } ({ $format })
+    }
+toast-bad-owner = '{ $owner }' no es un nombre de usuario u organización de GitHub
+toast-scope = Buscando solo en { $scope }
+toast-scope-cleared = Buscando en todo GitHub
+toast-deleted = { $clones } borrados ({ $size } liberados)
+toast-delete-failed = Falló el borrado: { $error }
+toast-no-suggestion = No hay sugerencia que buscar
+toast-nothing-similar = { $name } no tiene temas ni lenguaje por los que buscar
+toast-similar = Buscando repositorios como { $name }
+toast-exclude-needs-search = Solo los resultados de búsqueda pueden excluir a un propietario
+toast-already-excluded = { $owner } ya está excluido
+toast-excluding = Buscando de nuevo sin los repositorios de { $owner }
+toast-no-search = No hay búsqueda que actualizar
+toast-enter-topic = Escribe un tema, p. ej. rust
+toast-not-cloned = Aún no está clonado: Alt+G lo clona
+toast-no-multiplexer = No estás en tmux ni WezTerm: define pane_command en config.toml
+toast-pane-opened = Panel abierto en { $path }
+toast-pane-failed = Falló la apertura del panel: { $error }
+toast-raw-query = --raw-query envía las búsquedas tal cual: los filtros de tamaño y de propietario están desactivados
+toast-mark-two = Marca dos repositorios para comparar ({ $marked } marcados)
+toast-copied = Copiado { $text }
+toast-copy-failed = Falló la copia: { $error }
+
+## Vista de comparación
+
+compare-stars = Estrellas
+compare-forks = Forks
+compare-last-push = Último push
+compare-releases = Versiones
+compare-contributors = Colaboradores
+compare-lines-of-code = Líneas de código
+compare-license = Licencia
+compare-unavailable = no disponible
+compare-loading = cargando…
+compare-not-loaded = sin cargar
+compare-unknown = desconocido
+compare-too-many = demasiados para listar
+compare-analysis-failed = falló el análisis
+compare-not-analyzed = sin analizar (Alt+A)
+compare-incompatible = incompatible: { $conflict }
+
+## Antigüedad, duraciones y ritmo de versiones
+
+age-just-now = ahora mismo
+age-minutes = hace { $count } min
+age-hours = hace { $count } h
+age-days =
+    { $count ->
+        [one] hace 1 día
+       *[other] hace { $count } días
+    }
+age-months =
+    { $count ->
+        [one] hace 1 mes
+       *[other] hace { $count } meses
+    }
+age-years =
+    { $count ->
+        [one] hace 1 año
+       *[other] hace { $count } años
+    }
+span-under-hour = menos de una hora
+span-hours =
+    { $count ->
+        [one] ~1 hora
+       *[other] ~{ $count } horas
+    }
+span-days =
+    { $count ->
+        [one] ~1 día
+       *[other] ~{ $count } días
+    }
+span-weeks =
+    { $count ->
+        [one] ~1 semana
+       *[other] ~{ $count } semanas
+    }
+span-months =
+    { $count ->
+        [one] ~1 mes
+       *[other] ~{ $count } meses
+    }
+span-years =
+    { $count ->
+        [one] ~1 año
+       *[other] ~{ $count } años
+    }
+every-more-than-daily = más de una vez al día
+every-days =
+    { $count ->
+        [one] cada ~1 día
+       *[other] cada ~{ $count } días
+    }
+every-weeks =
+    { $count ->
+        [one] cada ~1 semana
+       *[other] cada ~{ $count } semanas
+    }
+every-months =
+    { $count ->
+        [one] cada ~1 mes
+       *[other] cada ~{ $count } meses
+    }
+every-years =
+    { $count ->
+        [one] cada ~1 año
+       *[other] cada ~{ $count } años
+    }
+releases-none = sin versiones
+releases-one = 1 versión, { $age }
+releases-cadence = { $cadence }, la última { $age }
+changes-new = +{ $count } nuevos
+changes-gone = { $count } desaparecidos
+changes-since = { $changes } desde { $age }
+
+## Indicadores de mantenimiento
+
+issues-none = sin issues recientes
+issues-no-replies = aún sin respuestas
+issues-first-reply = primera respuesta en { $span }
+issues-none-closed = ninguna cerrada
+issues-closed-in = cerradas en { $span }
+issues-summary = { $replies }, { $closes } (mediana de { $count } recientes)
+pulls-none = sin pull requests recientes
+pulls-merged = { $merged } de { $count } fusionadas ({ $percent }%)
+pulls-merged-in = { $merged } de { $count } fusionadas ({ $percent }%) en { $span }
+pulls-still-open = { $pulls }, { $open } aún abiertas
+contributors-not-listed = sin listar (demasiados commits, o ninguno)
+contributors-single = un solo colaborador hizo todos los commits
+contributors-top-one = el primero de { $listed } colaboradores hizo el { $share }% de los commits
+contributors-top = los { $top } primeros de { $listed } colaboradores hicieron el { $share }% de los commits
+contributors-commits = commits
+contributors-others = { $count } más
+
+## Recuentos de archivos y actualización de clones
+
+files-by-extension = Archivos por extensión:
+files-no-extension = (ninguna)
+files-extension =
+    { $count ->
+        [one] { $extension }: 1 archivo | { $size }
+       *[other] { $extension }: { $count } archivos | { $size }
+    }
+files-total =
+    { $count ->
+        [one] Total: 1 archivo, { $size }
+       *[other] Total: { $count } archivos, { $size }
+    }
+update-up-to-date = ya estaba al día
+update-fast-forwarded = avanzado (fast-forward)
+update-fetched-only = descargado (la rama no avanzó)
+
+## Modo lineal (--linear): frases simples para lectores de pantalla
+
+linear-welcome = Mr Krabz GitHub Search, modo lineal.
+linear-start = Escribe una búsqueda y pulsa Enter, o q para salir.
+linear-search = Buscar
+linear-details = Detalles
+linear-results-prompt = Escribe el número de un resultado para ver sus detalles, una nueva búsqueda, o q para salir.
+linear-details-prompt = Escribe o para abrirlo en el navegador, c para clonarlo, f para contar archivos, a para analizar lenguajes, b para añadirlo a marcadores, l para volver a listar los resultados, otro número, una nueva búsqueda, o q para salir.
+linear-no-results-yet = Aún no hay resultados. Escribe una búsqueda.
+linear-no-such-result = No existe el resultado { $number }. Elige del 1 al { $count }.
+linear-goodbye = Adiós.
+linear-searching = Buscando { $query }...
+linear-offline = No se puede conectar con GitHub. Estos son resultados guardados de { $age }.
+linear-gone = Ya no aparecen: { $names }.
+linear-none-found = No se encontraron repositorios.
+linear-did-you-mean = ¿Quisiste decir { $query }? Escríbelo, u otra búsqueda.
+linear-search-again = Escribe otra búsqueda.
+linear-found = Se encontraron { $total } repositorios, se muestran { $shown }.
+linear-search-failed = Falló la búsqueda: { $error }
+linear-unknown = desconocido
+linear-unknown-language = lenguaje desconocido
+linear-result = { $number }. { $name }, { $stars } estrellas, { $language }
+linear-result-of = Resultado { $number } de { $total }: { $name }
+linear-no-description = ninguna
+linear-url = URL
+linear-last-activity = Última actividad
+linear-cloned-to = Clonado en { $path }
+linear-opened = Se abrió { $url } en el navegador.
+linear-bookmarked = { $name } añadido a marcadores.
+linear-unbookmarked = { $name } quitado de marcadores.
+linear-cloning = Clonando { $name } en { $path }. Puede tardar un poco.
+linear-cloned = { $name } clonado.
+linear-clone-failed = Falló el clon: { $error }
+linear-counting = Contando archivos en { $name }...
+linear-count-failed = Falló el recuento de archivos: { $error }
+linear-analyzing = Analizando lenguajes en { $name }. Primero se clona el repositorio.
+linear-no-sources = No hay archivos de código reconocidos.
+linear-language = { $language }: { $files } archivos, { $code } líneas de código, { $comments } comentarios, { $blanks } líneas en blanco
+linear-analysis-failed = Falló el análisis: { $error }
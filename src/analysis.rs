@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tokei::{Config, Languages};
+
+use crate::filecount::CountFilter;
+
+/// Lines of code for one language in a repository
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LanguageStats {
+    pub name: String,
+    pub files: usize,
+    pub code: usize,
+    pub comments: usize,
+    pub blanks: usize,
+}
+
+/// Count lines per language under `root` with tokei, most code first
+/// Embedded languages (e.g. code blocks in Markdown) are folded into their parent
+pub fn analyze(root: &Path, filter: &CountFilter) -> Vec<LanguageStats> {
+    let excluded = filter.excluded_globs();
+    let excluded: Vec<&str> = excluded.iter().map(String::as_str).collect();
+    let config = Config { no_ignore_vcs: Some(!filter.respect_gitignore), ..Config::default() };
+    let mut languages = Languages::new();
+    languages.get_statistics(&[root], &excluded, &config);
+
+    let mut stats: Vec<LanguageStats> = languages
+        .iter()
+        .filter(|(_, language)| !language.reports.is_empty())
+        .map(|(language_type, language)| {
+            let summary = language.summarise();
+            LanguageStats {
+                name: language_type.name().to_string(),
+                files: language.reports.len(),
+                code: summary.code,
+                comments: summary.comments,
+                blanks: summary.blanks,
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.code.cmp(&a.code).then_with(|| a.name.cmp(&b.name)));
+    stats
+}
+
+/// Column sums over every language
+pub fn total(stats: &[LanguageStats]) -> LanguageStats {
+    stats.iter().fold(
+        LanguageStats { name: "Total".to_string(), files: 0, code: 0, comments: 0, blanks: 0 },
+        |mut total, language| {
+            total.files += language.files;
+            total.code += language.code;
+            total.comments += language.comments;
+            total.blanks += language.blanks;
+            total
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_analyze_counts_lines_per_language() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("src/main.rs"), "// entry point\nfn main() {\n\n    println!(\"hi\");\n}\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn f() {}\n").unwrap();
+        fs::write(root.join("script.py"), "# comment\nprint(1)\n").unwrap();
+        fs::write(root.join(".git/config.rs"), "fn ignored() {}\n").unwrap();
+        fs::create_dir_all(root.join("vendor")).unwrap();
+        fs::write(root.join("vendor/dep.py"), "skipped = 1\n").unwrap();
+
+        let stats = analyze(root, &CountFilter::default());
+        assert_eq!(
+            stats[0],
+            LanguageStats { name: "Rust".to_string(), files: 2, code: 4, comments: 1, blanks: 1 }
+        );
+        assert_eq!(
+            stats[1],
+            LanguageStats { name: "Python".to_string(), files: 1, code: 1, comments: 1, blanks: 0 }
+        );
+
+        let total = total(&stats);
+        assert_eq!((total.files, total.code, total.comments, total.blanks), (3, 5, 2, 1));
+
+        let excluding = CountFilter { exclude: vec!["*.py".to_string()], ..Default::default() };
+        assert_eq!(analyze(root, &excluding).len(), 1);
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::{Context, Result};
+use octocrab::models::Repository;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::analysis::LanguageStats;
+use crate::filecount::FileStats;
+use crate::maintenance::Maintenance;
+use crate::paths;
+
+const STORE_FILE: &str = "analysis_store.json";
+
+/// Repositories kept in the store; the least recently updated is dropped first
+const MAX_ENTRIES: usize = 500;
+
+/// The results the store keeps for a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kind {
+    FileCount,
+    Languages,
+    Maintenance,
+}
+
+/// A result and when it was computed (Unix seconds)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Stored<T> {
+    pub computed_at: u64,
+    pub value: T,
+}
+
+impl<T> Stored<T> {
+    pub fn computed_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.computed_at)
+    }
+}
+
+/// File count, language analysis, and maintenance metrics of one repository as of one push
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct StoredAnalysis {
+    pub repo_id: u64,
+    pub pushed_at: Option<i64>, // Unix seconds of the push the results describe
+    // Stores from before the counts were kept as figures have a `file_count` report instead,
+    // which is ignored: the count runs again
+    pub files: Option<Stored<FileStats>>,
+    pub languages: Option<Stored<Vec<LanguageStats>>>,
+    pub maintenance: Option<Stored<Maintenance>>,
+}
+
+/// Analysis results from earlier sessions, persisted to analysis_store.json, keyed by repository id and
+/// last push so a repository that changed since is analyzed afresh
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct AnalysisStore {
+    entries: Vec<StoredAnalysis>, // Least recently updated first
+}
+
+fn pushed_at(repo: &Repository) -> Option<i64> {
+    repo.pushed_at.map(|time| time.timestamp())
+}
+
+impl AnalysisStore {
+    /// Load the store from analysis_store.json
+    /// A missing or unreadable store is just empty: the results can be computed again
+    pub fn load() -> Self {
+        fs::read_to_string(paths::data_file(STORE_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the store back to analysis_store.json
+    pub fn save(&self) -> Result<()> {
+        let contents = serde_json::to_string(self).context("Failed to serialize the analysis store")?;
+        fs::write(paths::data_file(STORE_FILE), contents).context("Failed to write analysis_store.json")
+    }
+
+    /// The results stored for the repository's current push
+    pub fn get(&self, repo: &Repository) -> Option<&StoredAnalysis> {
+        self.entries.iter().find(|entry| entry.repo_id == repo.id.0 && entry.pushed_at == pushed_at(repo))
+    }
+
+    /// Change the results stored for the repository's current push; results of an older push are dropped
+    pub fn update(&mut self, repo: &Repository, change: impl FnOnce(&mut StoredAnalysis)) {
+        let position = self.entries.iter().position(|entry| entry.repo_id == repo.id.0);
+        let mut entry = position.map(|index| self.entries.remove(index)).unwrap_or_default();
+        if entry.repo_id != repo.id.0 || entry.pushed_at != pushed_at(repo) {
+            entry = StoredAnalysis { repo_id: repo.id.0, pushed_at: pushed_at(repo), ..StoredAnalysis::default() };
+        }
+        change(&mut entry);
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock;
+
+    fn files(total_files: u64) -> FileStats {
+        FileStats { total_files, ..FileStats::default() }
+    }
+
+    fn repo(id: u64, pushed_at: &str) -> Repository {
+        mock::repo("owner/repo").id(id).pushed_at(pushed_at).build()
+    }
+
+    #[test]
+    fn test_results_are_kept_per_push() {
+        let mut store = AnalysisStore::default();
+        let monday = repo(1, "2024-01-01T00:00:00Z");
+        store.update(&monday, |entry| entry.files = Some(Stored { computed_at: 10, value: files(3) }));
+        store.update(&monday, |entry| {
+            entry.languages = Some(Stored { computed_at: 20, value: Vec::new() });
+        });
+        let entry = store.get(&monday).unwrap();
+        assert_eq!(entry.files.as_ref().unwrap().value, files(3));
+        assert_eq!(entry.languages.as_ref().unwrap().computed_time(), UNIX_EPOCH + Duration::from_secs(20));
+        assert!(store.get(&repo(2, "2024-01-01T00:00:00Z")).is_none());
+
+        // A new push makes the stored results stale, and the next update starts over
+        let tuesday = repo(1, "2024-01-02T00:00:00Z");
+        assert!(store.get(&tuesday).is_none());
+        store.update(&tuesday, |entry| entry.files = Some(Stored { computed_at: 30, value: files(4) }));
+        assert!(store.get(&monday).is_none());
+        assert!(store.get(&tuesday).unwrap().languages.is_none());
+
+        // Round-trips through JSON
+        let json = serde_json::to_string(&store).unwrap();
+        let loaded: AnalysisStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get(&tuesday), store.get(&tuesday));
+
+        // Stores written when counts were kept as report text load, without the count
+        let old = r#"{"entries":[{"repo_id":1,"pushed_at":null,"file_count":{"computed_at":1,"value":"Total: 3 files, 1 KiB"}}]}"#;
+        let loaded: AnalysisStore = serde_json::from_str(old).unwrap();
+        assert_eq!(loaded.entries.len(), 1);
+        assert!(loaded.entries[0].files.is_none());
+    }
+
+    #[test]
+    fn test_oldest_entries_are_evicted() {
+        let mut store = AnalysisStore::default();
+        for id in 0..=MAX_ENTRIES as u64 {
+            store.update(&repo(id, "2024-01-01T00:00:00Z"), |entry| entry.files = Some(Stored { computed_at: id, value: files(id) }));
+        }
+        assert_eq!(store.entries.len(), MAX_ENTRIES);
+        assert!(store.get(&repo(0, "2024-01-01T00:00:00Z")).is_none());
+        assert!(store.get(&repo(1, "2024-01-01T00:00:00Z")).is_some());
+    }
+}
@@ -0,0 +1,143 @@
+use anyhow::{Context, Result};
+use rustsec::advisory::Severity;
+use rustsec::repository::git::{Repository, DEFAULT_URL};
+use rustsec::{Database, Lockfile};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::paths;
+
+/// How long to wait for another process (e.g. cargo-audit) to finish updating the advisory database
+const LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A RustSec advisory against a crate version a Cargo.lock pins
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub id: String,
+    pub package: String,
+    pub version: String,
+    pub title: String,
+    pub severity: Option<Severity>, // From the advisory's CVSS score; None when it has none
+}
+
+/// Whether the clone at `clone` has a Cargo.lock to audit
+pub fn has_lockfile(clone: &Path) -> bool {
+    clone.join("Cargo.lock").is_file()
+}
+
+/// Fetch the RustSec advisory database (kept in the data directory) and check the clone's
+/// Cargo.lock against it
+pub fn audit(clone: &Path) -> Result<Vec<Finding>> {
+    let repo = Repository::fetch(DEFAULT_URL, paths::data_file("advisory-db"), true, LOCK_TIMEOUT)
+        .context("Failed to fetch the RustSec advisory database")?;
+    let db = Database::load_from_repo(&repo).context("Failed to load the RustSec advisory database")?;
+    check(&db, clone)
+}
+
+/// Advisories in `db` against crates.io packages of the clone's Cargo.lock, most severe first
+/// (withdrawn and informational ones, e.g. "unmaintained", left out)
+fn check(db: &Database, clone: &Path) -> Result<Vec<Finding>> {
+    let path = clone.join("Cargo.lock");
+    let lockfile = Lockfile::load(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut findings: Vec<Finding> = db
+        .vulnerabilities(&lockfile)
+        .into_iter()
+        .map(|vulnerability| Finding {
+            id: vulnerability.advisory.id.to_string(),
+            package: vulnerability.package.name.to_string(),
+            version: vulnerability.package.version.to_string(),
+            title: vulnerability.advisory.title.clone(),
+            severity: vulnerability.advisory.cvss.as_ref().map(|cvss| cvss.severity()),
+        })
+        .collect();
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.id.cmp(&b.id)));
+    Ok(findings)
+}
+
+/// The worst severity among `findings` (None when there are none or none is rated)
+pub fn worst(findings: &[Finding]) -> Option<Severity> {
+    findings.iter().filter_map(|finding| finding.severity).max()
+}
+
+/// "none", or e.g. "3 (1 critical, 1 high, 1 unrated)"
+pub fn label(findings: &[Finding]) -> String {
+    if findings.is_empty() {
+        return "none".to_string();
+    }
+    // Findings are sorted most severe first, so equal severities are adjacent
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for finding in findings {
+        let severity = finding.severity.map_or("unrated", Severity::as_str);
+        match counts.last_mut() {
+            Some((last, count)) if *last == severity => *count += 1,
+            _ => counts.push((severity, 1)),
+        }
+    }
+    let counts: Vec<String> = counts.iter().map(|(severity, count)| format!("{} {}", count, severity)).collect();
+    format!("{} ({})", findings.len(), counts.join(", "))
+}
+
+/// One line per finding, e.g. "RUSTSEC-2023-0001 critical  foo 1.0.0: Remote code execution"
+pub fn finding_rows(findings: &[Finding]) -> Vec<String> {
+    findings
+        .iter()
+        .map(|finding| {
+            let severity = finding.severity.map_or("unrated", Severity::as_str);
+            format!("  {} {:<8}  {} {}: {}", finding.id, severity, finding.package, finding.version, finding.title)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn advisory(id: &str, package: &str, patched: &str, cvss: Option<&str>) -> String {
+        let cvss = cvss.map(|vector| format!("cvss = \"{}\"\n", vector)).unwrap_or_default();
+        format!(
+            "```toml\n[advisory]\nid = \"{}\"\npackage = \"{}\"\ndate = \"2024-01-01\"\n{}\n[versions]\npatched = [\"{}\"]\n```\n\n# Flaw in {}\n\nDetails.\n",
+            id, package, cvss, patched, package
+        )
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_dir = dir.path().join("advisory-db");
+        let critical = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H";
+        for (id, package, patched, cvss) in [
+            ("RUSTSEC-2024-0001", "foo", ">= 1.2.0", None),
+            ("RUSTSEC-2024-0002", "bar", ">= 0.3.0", Some(critical)),
+            ("RUSTSEC-2024-0003", "baz", ">= 2.0.0", Some(critical)),
+        ] {
+            fs::create_dir_all(db_dir.join("crates").join(package)).unwrap();
+            fs::write(db_dir.join(format!("crates/{}/{}.md", package, id)), advisory(id, package, patched, cvss)).unwrap();
+        }
+        let db = Database::open(&db_dir).unwrap();
+
+        let clone = dir.path().join("clone");
+        fs::create_dir_all(&clone).unwrap();
+        assert!(!has_lockfile(&clone));
+        let registry = "registry+https://github.com/rust-lang/crates.io-index";
+        let lock = format!(
+            "version = 3\n\n[[package]]\nname = \"foo\"\nversion = \"1.0.0\"\nsource = \"{r}\"\n\n\
+             [[package]]\nname = \"bar\"\nversion = \"0.2.1\"\nsource = \"{r}\"\n\n\
+             [[package]]\nname = \"baz\"\nversion = \"2.1.0\"\nsource = \"{r}\"\n",
+            r = registry
+        );
+        fs::write(clone.join("Cargo.lock"), lock).unwrap();
+        assert!(has_lockfile(&clone));
+
+        // baz 2.1.0 is patched; the rated advisory sorts first
+        let findings = check(&db, &clone).unwrap();
+        let ids: Vec<&str> = findings.iter().map(|finding| finding.id.as_str()).collect();
+        assert_eq!(ids, ["RUSTSEC-2024-0002", "RUSTSEC-2024-0001"]);
+        assert_eq!((findings[0].package.as_str(), findings[0].version.as_str()), ("bar", "0.2.1"));
+        assert_eq!(findings[0].title, "Flaw in bar");
+        assert_eq!(worst(&findings), Some(Severity::Critical));
+        assert_eq!(label(&findings), "2 (1 critical, 1 unrated)");
+        assert_eq!(label(&[]), "none");
+        assert!(finding_rows(&findings)[1].contains("RUSTSEC-2024-0001 unrated   foo 1.0.0: Flaw in foo"));
+    }
+}
@@ -0,0 +1,130 @@
+use anyhow::Result;
+use octocrab::models::Repository;
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::github::GitHub;
+
+/// First path segments of github.com links that are GitHub's own pages, not owners
+const NOT_OWNERS: &[&str] = &[
+    "about", "apps", "collections", "customer-stories", "enterprise", "events", "explore", "features",
+    "login", "marketplace", "orgs", "pricing", "search", "security", "settings", "site", "sponsors",
+    "topics", "trending",
+];
+
+/// The search for awesome lists about `topic`, e.g. "awesome-game-engine in:name" for "game engine"
+/// None for a blank topic
+pub fn query(topic: &str) -> Option<String> {
+    let topic = topic.split_whitespace().collect::<Vec<_>>().join("-").to_lowercase();
+    let topic = topic.strip_prefix("awesome-").unwrap_or(&topic);
+    (!topic.is_empty()).then(|| format!("awesome-{} in:name", topic))
+}
+
+/// Whether `repo` is an awesome list, going by its name
+pub fn is_list(repo: &Repository) -> bool {
+    repo.name.to_lowercase().starts_with("awesome")
+}
+
+/// Fetch the README of owner/name and the repositories it links to
+pub async fn fetch_list(github: &dyn GitHub, owner: &str, name: &str) -> Result<Vec<Repository>> {
+    let readme = github.readme(owner, name).await?;
+    Ok(linked_repos(&readme, &format!("{}/{}", owner, name)))
+}
+
+/// The GitHub repositories `readme` links to, in order and each once, leaving out the `list` itself
+/// and links into a repository (files, issues, ...). Each one's description is what the list says
+/// about it; nothing else is known without fetching it, so stars, language, and so on are empty
+pub fn linked_repos(readme: &str, list: &str) -> Vec<Repository> {
+    let mut seen = HashSet::from([list.to_lowercase()]);
+    let mut repos = Vec::new();
+    for line in readme.lines() {
+        let mut rest = line;
+        while let Some(start) = rest.find("github.com/") {
+            let link = &rest[start + "github.com/".len()..];
+            let end = link.find(|c: char| c.is_whitespace() || ")>]\"'#?".contains(c)).unwrap_or(link.len());
+            rest = &link[end..];
+            let Some((owner, name)) = repo_path(&link[..end]) else {
+                continue;
+            };
+            let full_name = format!("{}/{}", owner, name);
+            if seen.insert(full_name.to_lowercase()) {
+                repos.push(entry(owner, name, description(rest)));
+            }
+        }
+    }
+    repos
+}
+
+/// Owner and name of a github.com path that is a repository's home page
+fn repo_path(path: &str) -> Option<(&str, &str)> {
+    let mut segments = path.trim_end_matches('/').split('/');
+    let (owner, name) = (segments.next()?, segments.next()?);
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    let valid = |segment: &str, extra: &str| {
+        !segment.is_empty() && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || extra.contains(c))
+    };
+    let home = segments.next().is_none() && valid(owner, "") && valid(name, "_.");
+    (home && !NOT_OWNERS.contains(&owner.to_lowercase().as_str())).then_some((owner, name))
+}
+
+/// What a list entry says after its link, e.g. "Fast ECS." for "[bevy](…) - Fast ECS."
+fn description(after_link: &str) -> Option<String> {
+    let text = after_link.trim_start_matches([')', '>']).trim_start_matches([' ', '-', '–', '—', ':']).trim();
+    (!text.is_empty() && !text.starts_with('[')).then(|| text.to_string())
+}
+
+/// A result-list entry for owner/name with the details a list gives
+fn entry(owner: &str, name: &str, description: Option<String>) -> Repository {
+    let full_name = format!("{}/{}", owner, name);
+    let mut hasher = DefaultHasher::new();
+    full_name.to_lowercase().hash(&mut hasher);
+    serde_json::from_value(serde_json::json!({
+        // Never shown; only tells entries apart
+        "id": hasher.finish() >> 1,
+        "name": name,
+        "full_name": full_name,
+        "description": description,
+        "url": format!("https://api.github.com/repos/{}", full_name),
+        "html_url": format!("https://github.com/{}", full_name),
+    }))
+    .expect("invalid list entry")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock::MockGitHub;
+
+    #[test]
+    fn test_query() {
+        assert_eq!(query("Game Engine").as_deref(), Some("awesome-game-engine in:name"));
+        assert_eq!(query("awesome-rust").as_deref(), Some("awesome-rust in:name"));
+        assert_eq!(query("  "), None);
+    }
+
+    #[test]
+    fn test_linked_repos() {
+        let readme = "\
+# Awesome Rust [![Awesome](https://awesome.re/badge.svg)](https://github.com/sindresorhus/awesome)
+See [contributing](https://github.com/rust-unofficial/awesome-rust/blob/main/CONTRIBUTING.md).
+* [bevy](https://github.com/bevyengine/bevy) - A refreshingly simple data-driven game engine
+* [fyrox](https://github.com/FyroxEngine/Fyrox/) [[fyrox](https://crates.io/crates/fyrox)]
+* <https://github.com/rust-lang/rust.git> and [bevy again](https://github.com/BevyEngine/Bevy#readme)
+* [Topic](https://github.com/topics/rust), [issue](https://github.com/bevyengine/bevy/issues/1)
+";
+        let repos = linked_repos(readme, "rust-unofficial/awesome-rust");
+        let names: Vec<&str> = repos.iter().filter_map(|repo| repo.full_name.as_deref()).collect();
+        assert_eq!(names, ["sindresorhus/awesome", "bevyengine/bevy", "FyroxEngine/Fyrox", "rust-lang/rust"]);
+        assert_eq!(repos[1].description.as_deref(), Some("A refreshingly simple data-driven game engine"));
+        assert_eq!(repos[2].description, None);
+        assert_eq!(repos[1].html_url.as_ref().unwrap().as_str(), "https://github.com/bevyengine/bevy");
+        assert_ne!(repos[1].id, repos[2].id);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_list() {
+        let repos = fetch_list(&MockGitHub::new(), "rust-unofficial", "awesome-rust").await.unwrap();
+        assert_eq!(repos.len(), 3);
+        assert_eq!(repos[0].full_name.as_deref(), Some("bevyengine/bevy"));
+    }
+}
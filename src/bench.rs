@@ -0,0 +1,162 @@
+use anyhow::Result;
+use colored::Colorize;
+use octocrab::models::Repository;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use serde::Deserialize;
+use std::hint::black_box;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::github::GitHub;
+use crate::tui::{self, App};
+use crate::{Args, SearchRequest};
+
+/// Query builds timed per run; one is too quick to measure on its own
+const QUERY_BUILDS: u32 = 1000;
+/// Frames drawn per run
+const FRAMES: u32 = 100;
+/// Size of the off-screen terminal frames are drawn to
+const SCREEN: (u16, u16) = (120, 45);
+
+/// The body of a search response, as octocrab parses it
+#[derive(Deserialize)]
+struct SearchPage {
+    items: Vec<Repository>,
+}
+
+/// One measured step of a search and its timings, one per run
+#[derive(Debug)]
+pub struct Stage {
+    pub name: &'static str,
+    pub samples: Vec<Duration>,
+}
+
+impl Stage {
+    fn mean(&self) -> Duration {
+        self.samples.iter().sum::<Duration>() / self.samples.len().max(1) as u32
+    }
+
+    /// "name  mean  min  max"
+    fn summary(&self) -> String {
+        let min = self.samples.iter().min().copied().unwrap_or_default();
+        let max = self.samples.iter().max().copied().unwrap_or_default();
+        format!(
+            "{:<16}{:>10}{:>10}{:>10}",
+            self.name,
+            format_duration(self.mean()),
+            format_duration(min),
+            format_duration(max)
+        )
+    }
+}
+
+/// `duration` in the unit that suits it, e.g. "3.2µs", "412.5ms", "1.20s"
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1e6;
+    if micros < 1000.0 {
+        format!("{:.1}µs", micros)
+    } else if micros < 1e6 {
+        format!("{:.1}ms", micros / 1000.0)
+    } else {
+        format!("{:.2}s", micros / 1e6)
+    }
+}
+
+/// Time each step of searching for `query` `runs` times: building the query, the API round-trip
+/// (one search request per run, parsing included), parsing a response, and drawing a frame of results
+pub async fn measure(github: &dyn GitHub, query: &str, args: &Args, runs: u32) -> Result<Vec<Stage>, Error> {
+    let mut stages = ["Query build", "API round-trip", "Result parsing", "Frame render"]
+        .map(|name| Stage { name, samples: Vec::new() });
+    let mut app = App::new();
+    let mut terminal = Terminal::new(TestBackend::new(SCREEN.0, SCREEN.1)).map_err(|e| Error::Other(e.to_string()))?;
+
+    for _ in 0..runs {
+        let started = Instant::now();
+        for _ in 0..QUERY_BUILDS {
+            black_box(SearchRequest::new(black_box(query), args, &None, args.owner.as_deref(), 1)?);
+        }
+        stages[0].samples.push(started.elapsed() / QUERY_BUILDS);
+
+        let request = SearchRequest::new(query, args, &None, args.owner.as_deref(), 1)?;
+        let started = Instant::now();
+        let sort = request.sort.as_deref();
+        let (items, total, _) = github.search_repositories(&request.query, sort, request.per_page, request.page).await?;
+        stages[1].samples.push(started.elapsed());
+
+        // The same JSON GitHub sent, give or take the fields octocrab doesn't keep
+        let body = serde_json::json!({ "total_count": total, "items": items }).to_string();
+        let started = Instant::now();
+        let page: SearchPage = serde_json::from_str(black_box(&body)).map_err(|e| Error::Other(e.to_string()))?;
+        stages[2].samples.push(started.elapsed());
+
+        app.set_results(page.items, total);
+        let started = Instant::now();
+        for _ in 0..FRAMES {
+            terminal.draw(|f| tui::ui(f, &mut app)).map_err(|e| Error::Other(e.to_string()))?;
+        }
+        stages[3].samples.push(started.elapsed() / FRAMES);
+    }
+    Ok(stages.into())
+}
+
+/// The printed report: a header, then a line per stage
+pub fn report(query: &str, runs: u32, stages: &[Stage]) -> String {
+    let mut report = format!("Benchmark of \"{}\", {} run{}\n", query, runs, if runs == 1 { "" } else { "s" });
+    report.push_str(&format!("{:<16}{:>10}{:>10}{:>10}\n", "Stage", "Mean", "Min", "Max"));
+    for stage in stages {
+        report.push_str(&stage.summary());
+        report.push('\n');
+    }
+    report
+}
+
+/// `--bench`: measure a search for the query (or "rust") and print the summary
+pub async fn run(github: &dyn GitHub, args: &Args, runs: u32) -> Result<ExitCode> {
+    let query = if args.query.is_empty() { "rust".to_string() } else { args.query.join(" ") };
+    let runs = runs.max(1);
+    match measure(github, &query, args, runs).await {
+        Ok(stages) => {
+            print!("{}", report(&query, runs, &stages));
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red(), e.with_guidance());
+            Ok(ExitCode::from(e.exit_code()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock::MockGitHub;
+    use clap::Parser;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(3200)), "3.2µs");
+        assert_eq!(format_duration(Duration::from_micros(412_500)), "412.5ms");
+        assert_eq!(format_duration(Duration::from_millis(1200)), "1.20s");
+    }
+
+    #[tokio::test]
+    async fn test_measure() {
+        let github = MockGitHub::new();
+        let args = Args::parse_from(["gh", "--bench=2", "-L", "rust", "game"]);
+        let stages = measure(&github, "game", &args, 2).await.unwrap();
+        assert!(stages.iter().all(|stage| stage.samples.len() == 2));
+        assert_eq!(github.searches().len(), 2);
+        assert_eq!(github.searches()[0].query, "game language:rust");
+
+        let report = report("game", 2, &stages);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "Benchmark of \"game\", 2 runs");
+        assert!(lines[1].starts_with("Stage") && lines[1].ends_with("Max"));
+        assert!(lines[2].starts_with("Query build") && lines[5].starts_with("Frame render"));
+
+        let failing = MockGitHub::new().failing(1, MockGitHub::status(401, "Bad credentials"));
+        assert_eq!(measure(&failing, "game", &args, 2).await.unwrap_err(), Error::AuthRequired);
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::paths;
+
+const BOOKMARKS_FILE: &str = "bookmarks.toml";
+
+/// A bookmarked repository
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Bookmark {
+    pub full_name: String,
+    pub url: String,
+}
+
+/// Bookmarked repositories, persisted to bookmarks.toml
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Bookmarks {
+    #[serde(default, rename = "bookmark")]
+    pub items: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    /// Load bookmarks from bookmarks.toml
+    /// Returns an empty list if the file doesn't exist
+    pub fn load() -> Result<Self> {
+        let path = paths::data_file(BOOKMARKS_FILE);
+
+        if !path.exists() {
+            return Ok(Bookmarks::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .context("Failed to read bookmarks.toml")?;
+
+        toml::from_str(&contents).context("Failed to parse bookmarks.toml")
+    }
+
+    /// Write bookmarks back to bookmarks.toml
+    pub fn save(&self) -> Result<()> {
+        let contents = toml::to_string(self).context("Failed to serialize bookmarks")?;
+        fs::write(paths::data_file(BOOKMARKS_FILE), contents).context("Failed to write bookmarks.toml")
+    }
+
+    /// Check whether a repository URL is bookmarked
+    pub fn contains(&self, url: &str) -> bool {
+        self.items.iter().any(|b| b.url == url)
+    }
+
+    /// Add a bookmark, or remove it if it already exists
+    /// Returns true if the repository is bookmarked afterwards
+    pub fn toggle(&mut self, full_name: &str, url: &str) -> bool {
+        if self.contains(url) {
+            self.items.retain(|b| b.url != url);
+            false
+        } else {
+            self.items.push(Bookmark {
+                full_name: full_name.to_string(),
+                url: url.to_string(),
+            });
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_adds_and_removes() {
+        let mut bookmarks = Bookmarks::default();
+
+        assert!(bookmarks.toggle("rust-lang/rust", "https://github.com/rust-lang/rust"));
+        assert!(bookmarks.contains("https://github.com/rust-lang/rust"));
+
+        assert!(!bookmarks.toggle("rust-lang/rust", "https://github.com/rust-lang/rust"));
+        assert!(!bookmarks.contains("https://github.com/rust-lang/rust"));
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.toggle("bevyengine/bevy", "https://github.com/bevyengine/bevy");
+
+        let serialized = toml::to_string(&bookmarks).unwrap();
+        assert!(serialized.contains("[[bookmark]]"));
+
+        let parsed: Bookmarks = toml::from_str(&serialized).unwrap();
+        assert_eq!(parsed.items, bookmarks.items);
+    }
+}
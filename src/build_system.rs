@@ -0,0 +1,221 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Build systems recognised from the files at the top of a checkout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildSystem {
+    Cargo,
+    Npm,
+    Yarn,
+    Pnpm,
+    Poetry,
+    Pip,
+    CMake,
+    Make,
+}
+
+impl BuildSystem {
+    /// Language family the build system belongs to ("rust", "node", "python", "c")
+    pub fn language(self) -> &'static str {
+        match self {
+            BuildSystem::Cargo => "rust",
+            BuildSystem::Npm | BuildSystem::Yarn | BuildSystem::Pnpm => "node",
+            BuildSystem::Poetry | BuildSystem::Pip => "python",
+            BuildSystem::CMake | BuildSystem::Make => "c",
+        }
+    }
+
+    /// Command that builds a fresh checkout
+    fn build_command(self, root: &Path) -> String {
+        match self {
+            BuildSystem::Cargo => "cargo build".to_string(),
+            BuildSystem::Npm | BuildSystem::Yarn | BuildSystem::Pnpm => {
+                let tool = match self {
+                    BuildSystem::Yarn => "yarn",
+                    BuildSystem::Pnpm => "pnpm",
+                    _ => "npm",
+                };
+                let install = if self == BuildSystem::Npm { "npm install" } else { tool };
+                // Only mention the build script when package.json has one
+                let has_build = read_json(&root.join("package.json"))
+                    .is_some_and(|package| package.pointer("/scripts/build").is_some());
+                if has_build {
+                    format!("{} && {} run build", install, tool)
+                } else {
+                    install.to_string()
+                }
+            }
+            BuildSystem::Poetry => "poetry install".to_string(),
+            BuildSystem::Pip => "pip install .".to_string(),
+            BuildSystem::CMake => "cmake -B build && cmake --build build".to_string(),
+            BuildSystem::Make => "make".to_string(),
+        }
+    }
+}
+
+/// What the details panel says about building a local clone
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildInfo {
+    pub systems: Vec<BuildSystem>,
+    pub commands: Vec<String>,   // One "build with" command per build system, in the same order
+    pub toolchains: Vec<String>, // Pinned or required toolchain versions, e.g. "Rust 1.75", "Node >=18"
+}
+
+impl BuildInfo {
+    /// "cargo build", or several commands joined with " | "
+    pub fn hint(&self) -> String {
+        self.commands.join(" | ")
+    }
+}
+
+/// Detect the build systems and toolchain versions of the checkout at `root`
+/// Only the top-level files are looked at; a project built from a subdirectory isn't recognised
+pub fn detect(root: &Path) -> BuildInfo {
+    let has = |file: &str| root.join(file).is_file();
+    let mut systems = Vec::new();
+    if has("Cargo.toml") {
+        systems.push(BuildSystem::Cargo);
+    }
+    if has("package.json") {
+        systems.push(if has("pnpm-lock.yaml") {
+            BuildSystem::Pnpm
+        } else if has("yarn.lock") {
+            BuildSystem::Yarn
+        } else {
+            BuildSystem::Npm
+        });
+    }
+    if has("pyproject.toml") {
+        let poetry = read_toml(&root.join("pyproject.toml")).is_some_and(|pyproject| pyproject_poetry(&pyproject));
+        systems.push(if poetry || has("poetry.lock") { BuildSystem::Poetry } else { BuildSystem::Pip });
+    }
+    if has("CMakeLists.txt") {
+        systems.push(BuildSystem::CMake);
+    // A Makefile next to CMakeLists.txt is usually a wrapper around cmake
+    } else if has("Makefile") || has("makefile") || has("GNUmakefile") {
+        systems.push(BuildSystem::Make);
+    }
+
+    let commands = systems.iter().map(|system| system.build_command(root)).collect();
+    BuildInfo { toolchains: toolchains(root, &systems), systems, commands }
+}
+
+fn pyproject_poetry(pyproject: &toml::Value) -> bool {
+    pyproject.get("tool").and_then(|tool| tool.get("poetry")).is_some()
+}
+
+/// Toolchain versions the checkout pins (rust-toolchain, .nvmrc, .python-version) or requires
+/// (rust-version, engines.node, requires-python, cmake_minimum_required)
+fn toolchains(root: &Path, systems: &[BuildSystem]) -> Vec<String> {
+    let mut toolchains = Vec::new();
+    let languages: Vec<&str> = systems.iter().map(|system| system.language()).collect();
+
+    if languages.contains(&"rust") {
+        let pinned = read_toml(&root.join("rust-toolchain.toml"))
+            .and_then(|file| file.get("toolchain")?.get("channel")?.as_str().map(str::to_string))
+            .or_else(|| first_line(&root.join("rust-toolchain")));
+        let required = read_toml(&root.join("Cargo.toml")).and_then(|manifest| {
+            let package = manifest.get("package").or_else(|| manifest.get("workspace")?.get("package"))?;
+            package.get("rust-version")?.as_str().map(|version| format!(">={}", version))
+        });
+        toolchains.extend(pinned.or(required).map(|version| format!("Rust {}", version)));
+    }
+    if languages.contains(&"node") {
+        let pinned = first_line(&root.join(".nvmrc")).or_else(|| first_line(&root.join(".node-version")));
+        let required = read_json(&root.join("package.json"))
+            .and_then(|package| package.pointer("/engines/node")?.as_str().map(str::to_string));
+        toolchains.extend(pinned.or(required).map(|version| format!("Node {}", version)));
+    }
+    if languages.contains(&"python") {
+        let pinned = first_line(&root.join(".python-version"));
+        let required = read_toml(&root.join("pyproject.toml")).and_then(|pyproject| {
+            let version = pyproject.get("project").and_then(|project| project.get("requires-python")).or_else(|| {
+                pyproject.get("tool")?.get("poetry")?.get("dependencies")?.get("python")
+            })?;
+            version.as_str().map(str::to_string)
+        });
+        toolchains.extend(pinned.or(required).map(|version| format!("Python {}", version)));
+    }
+    if systems.contains(&BuildSystem::CMake) {
+        let required = fs::read_to_string(root.join("CMakeLists.txt")).ok().and_then(|text| cmake_minimum(&text));
+        toolchains.extend(required.map(|version| format!("CMake >={}", version)));
+    }
+    toolchains
+}
+
+/// The VERSION of `cmake_minimum_required(VERSION 3.16)` (the lower end of a 3.16...3.28 range)
+fn cmake_minimum(text: &str) -> Option<String> {
+    let start = text.to_ascii_lowercase().find("cmake_minimum_required")?;
+    let args = &text[start..];
+    let args = &args[args.find('(')? + 1..args.find(')')?];
+    let mut words = args.split_whitespace();
+    words.find(|word| word.eq_ignore_ascii_case("VERSION"))?;
+    let version = words.next()?;
+    Some(version.split("...").next().unwrap_or(version).to_string())
+}
+
+fn first_line(path: &Path) -> Option<String> {
+    let text = fs::read_to_string(path).ok()?;
+    text.lines().map(str::trim).find(|line| !line.is_empty() && !line.starts_with('#')).map(str::to_string)
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    toml::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        assert_eq!(detect(root), BuildInfo::default());
+
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"x\"\nrust-version = \"1.70\"\n").unwrap();
+        fs::write(root.join("package.json"), r#"{"scripts": {"build": "vite build"}, "engines": {"node": ">=18"}}"#).unwrap();
+        fs::write(root.join("yarn.lock"), "").unwrap();
+        fs::write(root.join("CMakeLists.txt"), "cmake_minimum_required(VERSION 3.16...3.28)\nproject(x)\n").unwrap();
+        fs::write(root.join("Makefile"), "all:\n").unwrap();
+        let info = detect(root);
+        assert_eq!(info.systems, [BuildSystem::Cargo, BuildSystem::Yarn, BuildSystem::CMake]);
+        assert_eq!(info.hint(), "cargo build | yarn && yarn run build | cmake -B build && cmake --build build");
+        assert_eq!(info.toolchains, ["Rust >=1.70", "Node >=18", "CMake >=3.16"]);
+
+        // Pinned versions win over required ones
+        fs::write(root.join("rust-toolchain.toml"), "[toolchain]\nchannel = \"1.75.0\"\n").unwrap();
+        fs::write(root.join(".nvmrc"), "20\n").unwrap();
+        assert_eq!(detect(root).toolchains[..2], ["Rust 1.75.0", "Node 20"]);
+    }
+
+    #[test]
+    fn test_detect_python_and_make() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("pyproject.toml"), "[project]\nname = \"x\"\nrequires-python = \">=3.9\"\n").unwrap();
+        fs::write(root.join("Makefile"), "all:\n").unwrap();
+        fs::write(root.join("package.json"), "{}").unwrap();
+        let info = detect(root);
+        assert_eq!(info.systems, [BuildSystem::Npm, BuildSystem::Pip, BuildSystem::Make]);
+        assert_eq!(info.commands, ["npm install", "pip install .", "make"]);
+        assert_eq!(info.toolchains, ["Python >=3.9"]);
+
+        fs::write(root.join("pyproject.toml"), "[tool.poetry.dependencies]\npython = \"^3.11\"\n").unwrap();
+        let info = detect(root);
+        assert_eq!(info.systems[1], BuildSystem::Poetry);
+        assert_eq!(info.toolchains, ["Python ^3.11"]);
+    }
+
+    #[test]
+    fn test_cmake_minimum() {
+        assert_eq!(cmake_minimum("CMAKE_MINIMUM_REQUIRED(VERSION 2.8.12)").as_deref(), Some("2.8.12"));
+        assert_eq!(cmake_minimum("cmake_minimum_required( VERSION 3.5 FATAL_ERROR )").as_deref(), Some("3.5"));
+        assert_eq!(cmake_minimum("project(x)"), None);
+    }
+}
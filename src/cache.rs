@@ -0,0 +1,298 @@
+use anyhow::{Context, Result};
+use octocrab::models::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::clones;
+use crate::i18n::tr;
+use crate::paths;
+use crate::text_match::TextMatches;
+
+const CACHE_FILE: &str = "search_cache.json";
+
+/// Searches kept in the cache; the least recently fetched is dropped first
+const MAX_ENTRIES: usize = 50;
+
+/// One search's results as they were last fetched
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CachedSearch {
+    pub key: String,
+    pub fetched_at: u64, // Unix seconds
+    pub total_count: u64,
+    pub items: Vec<Repository>,
+    #[serde(default)]
+    pub text_matches: TextMatches, // Missing from caches written before text matches were kept
+}
+
+impl CachedSearch {
+    /// When the results were fetched
+    pub fn fetched_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.fetched_at)
+    }
+
+    /// Whether the results were fetched less than `ttl` seconds before `now` (Unix seconds)
+    pub fn is_fresh(&self, ttl: u64, now: u64) -> bool {
+        now.saturating_sub(self.fetched_at) < ttl
+    }
+
+    /// How `items`, fetched just now for the same search, differ from these results
+    pub fn changes(&self, items: &[Repository]) -> Changes {
+        let before: HashSet<u64> = self.items.iter().map(|repo| repo.id.0).collect();
+        let now: HashSet<u64> = items.iter().map(|repo| repo.id.0).collect();
+        Changes {
+            since: self.fetched_time(),
+            added: now.difference(&before).copied().collect(),
+            removed: self
+                .items
+                .iter()
+                .filter(|repo| !now.contains(&repo.id.0))
+                .map(|repo| repo.full_name.clone().unwrap_or_else(|| repo.name.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// How a search's results changed since it last ran
+#[derive(Debug, Clone, PartialEq)]
+pub struct Changes {
+    pub since: SystemTime,    // When the previous results were fetched
+    pub added: HashSet<u64>,  // Ids of results that weren't there before
+    pub removed: Vec<String>, // Full names of earlier results missing now, in their old order
+}
+
+impl Changes {
+    /// "+7 new, 2 gone since 3 days ago", or None when the results are the same
+    pub fn summary(&self, now: SystemTime) -> Option<String> {
+        let mut parts = Vec::new();
+        if !self.added.is_empty() {
+            parts.push(tr!("changes-new", count = self.added.len()));
+        }
+        if !self.removed.is_empty() {
+            parts.push(tr!("changes-gone", count = self.removed.len()));
+        }
+        (!parts.is_empty()).then(|| tr!("changes-since", changes = parts.join(", "), age = clones::format_age(self.since, now)))
+    }
+}
+
+/// Results served from the cache instead of GitHub, and when they were fetched
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hit {
+    Fresh(SystemTime),   // Fetched within search_cache_ttl_secs, so GitHub wasn't asked
+    Offline(SystemTime), // GitHub was unreachable; the last results fetched
+}
+
+/// Recent search results, persisted to search_cache.json, shown when GitHub can't be reached
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SearchCache {
+    entries: Vec<CachedSearch>, // Oldest first
+    #[serde(skip)]
+    unreadable: bool, // The file couldn't be read, so saving would wipe whatever it holds
+}
+
+/// Cache key for a search page: the composed query plus everything else that changes the results
+/// The first page keeps the key it had before pages were cached separately
+pub fn key(query: &str, sort: Option<&str>, per_page: u8, page: u32) -> String {
+    let key = format!("{}|sort={}|per_page={}", query, sort.unwrap_or("best-match"), per_page);
+    if page > 1 {
+        format!("{}|page={}", key, page)
+    } else {
+        key
+    }
+}
+
+impl SearchCache {
+    /// A missing cache is just empty: it only saves a round trip
+    /// One that can't be read is empty too, but logged and left alone on disk (see `save_to`)
+    fn load_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Self::default(),
+            Err(e) => return Self::unreadable(path, &e),
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|e| Self::unreadable(path, &e))
+    }
+
+    fn unreadable(path: &Path, error: &dyn std::fmt::Display) -> Self {
+        tracing::warn!(path = %path.display(), %error, "search cache unreadable; it won't be saved over");
+        Self { entries: Vec::new(), unreadable: true }
+    }
+
+    /// Write to a temporary file beside `path`, then rename it over `path`, so a crash mid-write
+    /// leaves the previous cache whole; a cache whose file couldn't be read isn't written
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if self.unreadable {
+            return Ok(());
+        }
+        let contents = serde_json::to_string(self).context("Failed to serialize the search cache")?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let mut file = tempfile::NamedTempFile::new_in(dir).context("Failed to write search_cache.json")?;
+        file.write_all(contents.as_bytes()).context("Failed to write search_cache.json")?;
+        file.persist(path).context("Failed to replace search_cache.json")?;
+        Ok(())
+    }
+
+    /// The last results fetched for `key`
+    pub fn get(&self, key: &str) -> Option<&CachedSearch> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    /// Store fresh results for `key`, replacing older ones and dropping the oldest search if full
+    pub fn insert(&mut self, key: String, total_count: u64, items: Vec<Repository>, text_matches: TextMatches, fetched_at: u64) {
+        self.entries.retain(|entry| entry.key != key);
+        self.entries.push(CachedSearch { key, fetched_at, total_count, items, text_matches });
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.drain(..self.entries.len() - MAX_ENTRIES);
+        }
+    }
+}
+
+/// The search cache every search shares: read from disk once, kept in memory, and written back
+/// after each change, so searches running at once don't overwrite each other's entries
+#[derive(Debug)]
+pub struct SharedCache {
+    path: PathBuf,
+    cache: Mutex<SearchCache>,
+    writing: tokio::sync::Mutex<()>, // One save at a time, each writing the latest entries
+}
+
+impl SharedCache {
+    /// Load search_cache.json from the data directory
+    pub fn load() -> Self {
+        Self::open(paths::data_file(CACHE_FILE))
+    }
+
+    pub fn open(path: PathBuf) -> Self {
+        let cache = Mutex::new(SearchCache::load_from(&path));
+        Self { path, cache, writing: tokio::sync::Mutex::new(()) }
+    }
+
+    /// The last results fetched for `key`
+    pub fn get(&self, key: &str) -> Option<CachedSearch> {
+        self.lock().get(key).cloned()
+    }
+
+    /// Store fresh results for `key`, and say how they differ from the ones they replace
+    pub fn insert(&self, key: String, total_count: u64, items: Vec<Repository>, text_matches: TextMatches, fetched_at: u64) -> Option<Changes> {
+        let mut cache = self.lock();
+        let changes = cache.get(&key).map(|previous| previous.changes(&items));
+        cache.insert(key, total_count, items, text_matches, fetched_at);
+        changes
+    }
+
+    /// Write the entries to disk on the blocking pool
+    pub async fn save(&self) -> Result<()> {
+        let _writing = self.writing.lock().await;
+        let snapshot = self.lock().clone();
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || snapshot.save_to(&path)).await.context("Failed to write search_cache.json")?
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SearchCache> {
+        // Entries are replaced whole, so a panic mid-change can't leave one half-written
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock;
+
+    fn repo(full_name: &str) -> Repository {
+        repo_with_id(1, full_name)
+    }
+
+    fn repo_with_id(id: u64, full_name: &str) -> Repository {
+        mock::repo(full_name).id(id).build()
+    }
+
+    #[test]
+    fn test_key_includes_sort_and_limit() {
+        assert_eq!(key("rust language:rust", None, 100, 1), "rust language:rust|sort=best-match|per_page=100");
+        assert_ne!(key("rust", Some("stars"), 100, 1), key("rust", None, 100, 1));
+        assert_ne!(key("rust", None, 10, 1), key("rust", None, 100, 1));
+        assert_eq!(key("rust", None, 100, 3), "rust|sort=best-match|per_page=100|page=3");
+    }
+
+    #[test]
+    fn test_changes_since_last_run() {
+        let mut cache = SearchCache::default();
+        let before = vec![repo_with_id(1, "owner/kept"), repo_with_id(2, "owner/gone"), repo_with_id(3, "owner/moved")];
+        cache.insert("a".to_string(), 3, before, TextMatches::new(), 0);
+        let after = vec![repo_with_id(3, "owner/moved"), repo_with_id(4, "owner/new"), repo_with_id(1, "owner/kept")];
+        let changes = cache.get("a").unwrap().changes(&after);
+        assert_eq!(changes.added, HashSet::from([4]));
+        assert_eq!(changes.removed, ["owner/gone"]);
+        let now = UNIX_EPOCH + Duration::from_secs(3 * 86400);
+        assert_eq!(changes.summary(now).as_deref(), Some("+1 new, 1 gone since 3 days ago"));
+
+        let same = cache.get("a").unwrap().changes(&cache.get("a").unwrap().items);
+        assert_eq!(same.summary(now), None);
+    }
+
+    #[test]
+    fn test_insert_replaces_and_evicts_oldest() {
+        let mut cache = SearchCache::default();
+        cache.insert("a".to_string(), 1, vec![repo("owner/old")], TextMatches::new(), 10);
+        cache.insert("a".to_string(), 2, vec![repo("owner/new")], TextMatches::new(), 20);
+        let hit = cache.get("a").unwrap();
+        assert_eq!((hit.total_count, hit.fetched_at), (2, 20));
+        assert_eq!(hit.items[0].full_name.as_deref(), Some("owner/new"));
+
+        for i in 0..MAX_ENTRIES {
+            cache.insert(format!("q{}", i), 0, Vec::new(), TextMatches::new(), 30);
+        }
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("q0").is_some());
+
+        // Round-trips through the file
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILE);
+        cache.save_to(&path).unwrap();
+        assert_eq!(SearchCache::load_from(&path).entries.len(), MAX_ENTRIES);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_saves_keep_every_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CACHE_FILE);
+        let shared = std::sync::Arc::new(SharedCache::open(path.clone()));
+        let saves = (0..8).map(|page| {
+            let shared = shared.clone();
+            tokio::spawn(async move {
+                shared.insert(key("rust", None, 30, page), 1, vec![repo_with_id(page as u64, "owner/repo")], TextMatches::new(), 0);
+                shared.save().await.unwrap();
+            })
+        });
+        for save in saves.collect::<Vec<_>>() {
+            save.await.unwrap();
+        }
+        let loaded = SearchCache::load_from(&path);
+        assert!((0..8).all(|page| loaded.get(&key("rust", None, 30, page)).is_some()));
+
+        // Changes come back from the insert they were found by
+        let changes = shared.insert(key("rust", None, 30, 1), 1, vec![repo_with_id(9, "owner/new")], TextMatches::new(), 0);
+        assert_eq!(changes.unwrap().added, HashSet::from([9]));
+    }
+
+    #[test]
+    fn test_corrupt_file_is_left_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(SearchCache::load_from(&dir.path().join("missing.json")).entries.is_empty());
+
+        // A write cut short by a crash
+        let path = dir.path().join(CACHE_FILE);
+        fs::write(&path, r#"{"entries":[{"key":"rust|sort=best-ma"#).unwrap();
+        let mut cache = SearchCache::load_from(&path);
+        assert!(cache.get("rust|sort=best-match|per_page=30").is_none());
+        cache.insert("a".to_string(), 1, vec![repo("owner/new")], TextMatches::new(), 10);
+        cache.save_to(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"entries":[{"key":"rust|sort=best-ma"#);
+    }
+}
@@ -0,0 +1,270 @@
+use anyhow::{Context, Result};
+use http::header::USER_AGENT;
+use http::{HeaderValue, Response, Uri};
+use hyper_rustls::HttpsConnectorBuilder;
+use hyper_util::rt::TokioExecutor;
+use jsonwebtoken::EncodingKey;
+use octocrab::auth::AppAuth;
+use octocrab::models::{AppId, InstallationId};
+use octocrab::service::middleware::auth_header::AuthHeaderLayer;
+use octocrab::service::middleware::base_uri::BaseUriLayer;
+use octocrab::service::middleware::extra_headers::ExtraHeadersLayer;
+use octocrab::{AuthState, Octocrab, OctocrabBuilder};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use tower::{Layer, Service};
+use tower_http::follow_redirect::FollowRedirectLayer;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::scheduler::RateLimits;
+
+const API_URI: &str = "https://api.github.com";
+const UPLOAD_URI: &str = "https://uploads.github.com";
+
+/// Build the GitHub API client, authenticating as the configured GitHub App installation if
+/// github_app_id is set, otherwise with `token` if given
+/// The rate-limit headers of every response go to `rate_limits`
+pub async fn github(token: Option<&str>, config: &Config, rate_limits: Arc<RateLimits>) -> Result<Octocrab> {
+    let (Some(app_id), Some(key)) = (config.github_app_id, &config.github_app_key) else {
+        return build(token, None, config, rate_limits);
+    };
+    let app = AppAuth { app_id: AppId(app_id), key: load_app_key(key)? };
+    let octocrab = build(None, Some(app), config, rate_limits)?;
+    let installation = match config.github_app_installation_id {
+        Some(id) => InstallationId(id),
+        None => only_installation(&octocrab).await?,
+    };
+    // Installation tokens are fetched on first use and renewed before they expire
+    Ok(octocrab.installation(installation))
+}
+
+/// The app's installation when it has exactly one; otherwise github_app_installation_id must pick one
+async fn only_installation(octocrab: &Octocrab) -> Result<InstallationId> {
+    let installations = octocrab
+        .apps()
+        .installations()
+        .send()
+        .await
+        .context("Failed to list the GitHub App's installations (check github_app_id and github_app_key)")?
+        .items;
+    match installations.as_slice() {
+        [installation] => Ok(installation.id),
+        [] => anyhow::bail!("The GitHub App isn't installed on any account"),
+        _ => {
+            let choices: Vec<String> =
+                installations.iter().map(|i| format!("{} ({})", i.id, i.account.login)).collect();
+            anyhow::bail!("The GitHub App has several installations; set github_app_installation_id to one of: {}", choices.join(", "))
+        }
+    }
+}
+
+/// The client itself, signed in with `token` or as `app`
+/// The service stack is octocrab's own (retries, redirects, base URI, user agent, auth) plus a layer
+/// that reads the rate-limit headers; TLS trusts the system roots, plus ca_bundle if set
+fn build(token: Option<&str>, app: Option<AppAuth>, config: &Config, rate_limits: Arc<RateLimits>) -> Result<Octocrab> {
+    let tls = tls_config(config.ca_bundle.as_deref(), config.danger_accept_invalid_certs)?;
+    let connector = HttpsConnectorBuilder::new()
+        .with_tls_config(tls)
+        .https_only()
+        .enable_http1()
+        .build();
+    let auth_header = token
+        .map(|token| HeaderValue::from_str(&format!("Bearer {}", token)))
+        .transpose()
+        .context("The GitHub token contains characters that can't be sent in a header")?;
+
+    let client = hyper_util::client::legacy::Client::builder(TokioExecutor::new()).build(connector);
+    let client = Octocrab::builder().set_connector_retry_service(client);
+    let octocrab = OctocrabBuilder::new_empty()
+        .with_service(client)
+        .with_layer(&RateLimitLayer(rate_limits))
+        .with_layer(&FollowRedirectLayer::new())
+        .with_layer(&BaseUriLayer::new(Uri::from_static(API_URI)))
+        .with_layer(&ExtraHeadersLayer::new(Arc::new(vec![(USER_AGENT, HeaderValue::from_static("octocrab"))])))
+        .with_layer(&AuthHeaderLayer::new(auth_header, Uri::from_static(API_URI), Uri::from_static(UPLOAD_URI)))
+        .with_auth(app.map_or(AuthState::None, AuthState::App))
+        .build()?;
+    Ok(octocrab)
+}
+
+/// Hands the x-ratelimit-* headers of every response to the scheduler's budgets
+struct RateLimitLayer(Arc<RateLimits>);
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService { inner, rate_limits: self.0.clone() }
+    }
+}
+
+#[derive(Clone)]
+struct RateLimitService<S> {
+    inner: S,
+    rate_limits: Arc<RateLimits>,
+}
+
+impl<S, Request, B> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response<B>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response<B>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<B>, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let response = self.inner.call(request);
+        let rate_limits = self.rate_limits.clone();
+        Box::pin(async move {
+            let response = response.await?;
+            rate_limits.observe(response.headers());
+            Ok(response)
+        })
+    }
+}
+
+/// Trust the system roots plus `ca_bundle`, or every certificate when `accept_invalid` is set
+fn tls_config(ca_bundle: Option<&Path>, accept_invalid: bool) -> Result<ClientConfig> {
+    if accept_invalid {
+        let verifier = AcceptAnyCertificate(Arc::new(rustls::crypto::ring::default_provider()));
+        return Ok(ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier))
+            .with_no_client_auth());
+    }
+
+    let mut roots = RootCertStore::empty();
+    let system = rustls_native_certs::load_native_certs().context("Failed to load the system root certificates")?;
+    // Skip system certificates rustls can't parse, as octocrab's own client does
+    roots.add_parsable_certificates(system);
+    if let Some(path) = ca_bundle {
+        for certificate in load_ca_bundle(path)? {
+            roots
+                .add(certificate)
+                .with_context(|| format!("Invalid certificate in {}", path.display()))?;
+        }
+    }
+    Ok(ClientConfig::builder().with_root_certificates(roots).with_no_client_auth())
+}
+
+/// The PEM certificates in a CA bundle file; an error if there are none
+pub fn load_ca_bundle(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let certificates = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    if certificates.is_empty() {
+        anyhow::bail!("no PEM certificates found in {}", path.display());
+    }
+    Ok(certificates)
+}
+
+/// The GitHub App private key in a PEM file, as GitHub generates it
+pub fn load_app_key(path: &Path) -> Result<EncodingKey> {
+    let pem = std::fs::read(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    EncodingKey::from_rsa_pem(&pem).with_context(|| format!("{} isn't an RSA private key in PEM format", path.display()))
+}
+
+/// Certificate verifier for danger_accept_invalid_certs: any certificate is accepted,
+/// but handshake signatures are still checked
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, certificate, signature, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, certificate, signature, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_load_ca_bundle_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.pem");
+        assert!(load_ca_bundle(&missing).unwrap_err().to_string().starts_with("Failed to open"));
+
+        let empty = dir.path().join("empty.pem");
+        fs::write(&empty, "not a certificate\n").unwrap();
+        assert!(load_ca_bundle(&empty).unwrap_err().to_string().starts_with("no PEM certificates found"));
+    }
+
+    #[tokio::test]
+    async fn test_client_with_custom_tls() {
+        let config = Config { danger_accept_invalid_certs: true, ..Config::default() };
+        assert!(github(Some("token"), &config, Arc::default()).await.is_ok());
+        assert!(github(Some("bad\ntoken"), &config, Arc::default()).await.is_err());
+        assert!(github(None, &Config::default(), Arc::default()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_client_as_app_installation() {
+        let key = PathBuf::from("tests/fixtures/app_key.pem");
+        let config = Config {
+            github_app_id: Some(1),
+            github_app_key: Some(key.clone()),
+            github_app_installation_id: Some(2),
+            ..Config::default()
+        };
+        // With the installation given, nothing is fetched until the first request
+        assert!(github(Some("ignored"), &config, Arc::default()).await.is_ok());
+        let tls = Config { danger_accept_invalid_certs: true, ..config };
+        assert!(github(None, &tls, Arc::default()).await.is_ok());
+
+        assert!(load_app_key(&key).is_ok());
+        let dir = tempfile::tempdir().unwrap();
+        let bad = dir.path().join("key.pem");
+        fs::write(&bad, "not a key\n").unwrap();
+        let error = load_app_key(&bad).err().unwrap().to_string();
+        assert!(error.ends_with("isn't an RSA private key in PEM format"));
+    }
+}
@@ -0,0 +1,410 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::i18n::tr;
+use crate::paths;
+
+const CLONES_FILE: &str = "clones.toml";
+
+/// A repository cloned with Alt+G
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ClonedRepo {
+    pub url: String,
+    pub path: PathBuf,
+    pub cloned_at: u64, // Unix timestamp
+}
+
+/// Ledger of cloned repositories, persisted to clones.toml
+/// Remembers clones made outside the default clone directory
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct CloneLedger {
+    #[serde(default, rename = "clone")]
+    pub items: Vec<ClonedRepo>,
+}
+
+impl CloneLedger {
+    /// Load the ledger from clones.toml
+    /// Returns an empty ledger if the file doesn't exist
+    pub fn load() -> Result<Self> {
+        let path = paths::data_file(CLONES_FILE);
+
+        if !path.exists() {
+            return Ok(CloneLedger::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .context("Failed to read clones.toml")?;
+
+        toml::from_str(&contents).context("Failed to parse clones.toml")
+    }
+
+    /// Write the ledger back to clones.toml
+    pub fn save(&self) -> Result<()> {
+        let contents = toml::to_string(self).context("Failed to serialize clones")?;
+        fs::write(paths::data_file(CLONES_FILE), contents).context("Failed to write clones.toml")
+    }
+
+    /// Record a clone, replacing any earlier entry for the same URL
+    pub fn record(&mut self, url: &str, path: &Path) {
+        self.items.retain(|item| item.url != url);
+        let cloned_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        self.items.push(ClonedRepo {
+            url: url.to_string(),
+            path: path.to_path_buf(),
+            cloned_at,
+        });
+    }
+
+    /// Forget the clone at `path`
+    pub fn remove_path(&mut self, path: &Path) {
+        self.items.retain(|item| item.path != path);
+    }
+
+    /// Local path of a recorded clone that still exists on disk
+    pub fn find(&self, url: &str) -> Option<&Path> {
+        self.items
+            .iter()
+            .find(|item| item.url == url && is_git_repo(&item.path))
+            .map(|item| item.path.as_path())
+    }
+}
+
+/// Check whether a directory looks like a git working copy
+pub fn is_git_repo(path: &Path) -> bool {
+    path.join(".git").exists()
+}
+
+/// Check whether `path` is a git repository whose origin points at `url`
+pub fn is_clone_of(path: &Path, url: &str) -> bool {
+    if !is_git_repo(path) {
+        return false;
+    }
+    let Ok(repo) = git2::Repository::open(path) else {
+        return false;
+    };
+    let Ok(remote) = repo.find_remote("origin") else {
+        return false;
+    };
+    remote.url().is_some_and(|remote_url| same_remote(remote_url, url))
+}
+
+/// A clone found on disk, with the details shown by `repos list` and the clones view
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalClone {
+    pub name: String,                   // owner/name from the origin URL (directory name if there's none)
+    pub url: Option<String>,            // origin URL
+    pub path: PathBuf,
+    pub branch: Option<String>,         // Checked-out branch (None when detached)
+    pub size: u64,                      // Bytes on disk, including .git
+    pub last_fetch: Option<SystemTime>, // Last fetch, or when it was cloned
+}
+
+/// Every known clone: ledger entries that still exist, plus git repos under `clone_dir`
+/// (one or two levels deep, covering both clone layouts), sorted by name
+pub fn local_clones(ledger: &CloneLedger, clone_dir: &Path) -> Vec<LocalClone> {
+    let mut paths: Vec<PathBuf> = ledger
+        .items
+        .iter()
+        .filter(|item| is_git_repo(&item.path))
+        .map(|item| item.path.clone())
+        .collect();
+    for entry in subdirectories(clone_dir) {
+        if is_git_repo(&entry) {
+            paths.push(entry);
+        } else {
+            paths.extend(subdirectories(&entry).into_iter().filter(|path| is_git_repo(path)));
+        }
+    }
+
+    // The same clone may be listed by the ledger (absolute) and the scan (relative)
+    let mut seen = std::collections::HashSet::new();
+    paths.retain(|path| seen.insert(fs::canonicalize(path).unwrap_or_else(|_| path.clone())));
+
+    let mut clones: Vec<LocalClone> = paths.into_iter().map(|path| inspect(ledger, path)).collect();
+    clones.sort_by_key(|clone| clone.name.to_lowercase());
+    clones
+}
+
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| entry.path())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Gather name, branch, size, and fetch time for the clone at `path`
+fn inspect(ledger: &CloneLedger, path: PathBuf) -> LocalClone {
+    let repo = git2::Repository::open(&path).ok();
+    let url = repo
+        .as_ref()
+        .and_then(|repo| repo.find_remote("origin").ok()?.url().map(str::to_string));
+    let branch = repo.as_ref().and_then(|repo| {
+        let head = repo.head().ok()?;
+        head.is_branch().then(|| head.shorthand().map(str::to_string)).flatten()
+    });
+    let name = url
+        .as_deref()
+        .and_then(full_name_from_url)
+        .unwrap_or_else(|| path.file_name().map_or_else(String::new, |name| name.to_string_lossy().to_string()));
+
+    // FETCH_HEAD is rewritten on every fetch; fall back to when the ledger says it was cloned
+    let last_fetch = fs::metadata(path.join(".git").join("FETCH_HEAD"))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .or_else(|| {
+            let item = ledger.items.iter().find(|item| item.path == path)?;
+            Some(UNIX_EPOCH + Duration::from_secs(item.cloned_at))
+        });
+
+    LocalClone {
+        name,
+        url,
+        size: dir_size(&path),
+        path,
+        branch,
+        last_fetch,
+    }
+}
+
+/// "owner/name" from a GitHub URL, e.g. "https://github.com/foo/awesome.git" -> "foo/awesome"
+fn full_name_from_url(url: &str) -> Option<String> {
+    let mut segments = url.trim_end_matches('/').trim_end_matches(".git").rsplit('/');
+    let name = segments.next().filter(|name| !name.is_empty())?;
+    let owner = segments.next().filter(|owner| !owner.is_empty() && !owner.contains(':'))?;
+    Some(format!("{}/{}", owner, name))
+}
+
+/// Total size of the files under `path` (symlinks are not followed)
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Sort clones largest first (ties by name)
+pub fn sort_by_size(clones: &mut [LocalClone]) {
+    clones.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+}
+
+/// Find a clone by "owner/name", plain name, or path
+/// Errors if nothing matches or a plain name is ambiguous
+pub fn find_clone<'a>(clones: &'a [LocalClone], query: &str) -> anyhow::Result<&'a LocalClone> {
+    let query_lower = query.to_lowercase();
+    if let Some(clone) = clones.iter().find(|clone| clone.name.to_lowercase() == query_lower) {
+        return Ok(clone);
+    }
+    if let Some(clone) = clones.iter().find(|clone| clone.path == Path::new(query)) {
+        return Ok(clone);
+    }
+
+    let matches: Vec<&LocalClone> = clones
+        .iter()
+        .filter(|clone| clone.name.rsplit('/').next().is_some_and(|name| name.to_lowercase() == query_lower))
+        .collect();
+    match matches.as_slice() {
+        [clone] => Ok(clone),
+        [] => Err(anyhow::anyhow!("No cloned repository named '{}'", query)),
+        _ => Err(anyhow::anyhow!(
+            "'{}' is ambiguous: {}",
+            query,
+            matches.iter().map(|clone| clone.name.as_str()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Human-readable size, e.g. "12.3 MiB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Short count for stars and forks, e.g. 12803 -> "12.8k", 1260000 -> "1.3M"
+pub fn format_count(count: u64) -> String {
+    let short = |value: f64, suffix: &str| {
+        let digits = if value < 99.95 { format!("{:.1}", value) } else { format!("{:.0}", value) };
+        format!("{}{}", digits.trim_end_matches(".0"), suffix)
+    };
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_499 => short(count as f64 / 1e3, "k"),
+        _ => short(count as f64 / 1e6, "M"),
+    }
+}
+
+/// How long ago `time` was, e.g. "3 days ago" or "2 years ago"
+pub fn format_age(time: SystemTime, now: SystemTime) -> String {
+    let seconds = now.duration_since(time).map_or(0, |age| age.as_secs());
+    match seconds {
+        0..=59 => tr!("age-just-now"),
+        60..=3599 => tr!("age-minutes", count = seconds / 60),
+        3600..=86399 => tr!("age-hours", count = seconds / 3600),
+        _ => match seconds / 86400 {
+            days @ 0..=29 => tr!("age-days", count = days),
+            days @ 30..=364 => tr!("age-months", count = days / 30),
+            days => tr!("age-years", count = days / 365),
+        },
+    }
+}
+
+/// Compare remote URLs ignoring case, a trailing slash, and a ".git" suffix
+fn same_remote(a: &str, b: &str) -> bool {
+    let normalize = |url: &str| url.trim_end_matches('/').trim_end_matches(".git").to_lowercase();
+    normalize(a) == normalize(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_replaces_existing_entry() {
+        let mut ledger = CloneLedger::default();
+        ledger.record("https://github.com/foo/awesome", Path::new("repositories/awesome"));
+        ledger.record("https://github.com/foo/awesome", Path::new("elsewhere/awesome"));
+
+        assert_eq!(ledger.items.len(), 1);
+        assert_eq!(ledger.items[0].path, PathBuf::from("elsewhere/awesome"));
+    }
+
+    fn local_clone(name: &str, path: &str) -> LocalClone {
+        LocalClone {
+            name: name.to_string(),
+            url: None,
+            path: PathBuf::from(path),
+            branch: None,
+            size: 0,
+            last_fetch: None,
+        }
+    }
+
+    #[test]
+    fn test_find_clone() {
+        let clones = vec![
+            local_clone("foo/awesome", "repositories/foo/awesome"),
+            local_clone("bar/awesome", "repositories/bar/awesome"),
+            local_clone("foo/tool", "repositories/tool"),
+        ];
+
+        assert_eq!(find_clone(&clones, "Bar/Awesome").unwrap().name, "bar/awesome");
+        assert_eq!(find_clone(&clones, "tool").unwrap().name, "foo/tool");
+        assert_eq!(find_clone(&clones, "repositories/foo/awesome").unwrap().name, "foo/awesome");
+        assert!(find_clone(&clones, "awesome").unwrap_err().to_string().contains("ambiguous"));
+        assert!(find_clone(&clones, "missing").is_err());
+    }
+
+    #[test]
+    fn test_full_name_from_url() {
+        assert_eq!(full_name_from_url("https://github.com/foo/awesome.git").as_deref(), Some("foo/awesome"));
+        assert_eq!(full_name_from_url("https://github.com/foo/awesome/").as_deref(), Some("foo/awesome"));
+        assert_eq!(full_name_from_url("awesome"), None);
+    }
+
+    #[test]
+    fn test_format_size_and_age() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1000), "1k");
+        assert_eq!(format_count(12803), "12.8k");
+        assert_eq!(format_count(99_960), "100k");
+        assert_eq!(format_count(184_233), "184k");
+        assert_eq!(format_count(1_260_000), "1.3M");
+
+        let now = SystemTime::now();
+        assert_eq!(format_age(now, now), "just now");
+        assert_eq!(format_age(now - Duration::from_secs(7200), now), "2 h ago");
+        assert_eq!(format_age(now - Duration::from_secs(3 * 86400), now), "3 days ago");
+        assert_eq!(format_age(now - Duration::from_secs(86400), now), "1 day ago");
+        assert_eq!(format_age(now - Duration::from_secs(75 * 86400), now), "2 months ago");
+        assert_eq!(format_age(now - Duration::from_secs(400 * 86400), now), "1 year ago");
+        assert_eq!(format_age(now - Duration::from_secs(800 * 86400), now), "2 years ago");
+    }
+
+    #[test]
+    fn test_local_clones_scans_both_layouts() {
+        let dir = std::env::temp_dir().join(format!("mrkrabz-local-clones-{}", std::process::id()));
+        fs::create_dir_all(dir.join("flat/.git")).unwrap();
+        fs::create_dir_all(dir.join("owner/nested/.git")).unwrap();
+        fs::create_dir_all(dir.join("not-a-repo")).unwrap();
+        fs::write(dir.join("flat/file.txt"), "12345").unwrap();
+
+        let clones = local_clones(&CloneLedger::default(), &dir);
+        let names: Vec<&str> = clones.iter().map(|clone| clone.name.as_str()).collect();
+        assert_eq!(names, vec!["flat", "nested"]);
+        assert!(clones[0].size >= 5);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_same_remote() {
+        assert!(same_remote("https://github.com/Foo/Awesome.git", "https://github.com/foo/awesome"));
+        assert!(same_remote("https://github.com/foo/awesome/", "https://github.com/foo/awesome"));
+        assert!(!same_remote("https://github.com/bar/awesome", "https://github.com/foo/awesome"));
+    }
+
+    #[test]
+    fn test_sort_by_size() {
+        let clone = |name: &str, size| LocalClone {
+            name: name.to_string(),
+            url: None,
+            path: PathBuf::from(name),
+            branch: None,
+            size,
+            last_fetch: None,
+        };
+        let mut clones = vec![clone("b", 10), clone("a", 500), clone("c", 10)];
+        sort_by_size(&mut clones);
+        let names: Vec<&str> = clones.iter().map(|clone| clone.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_find_skips_deleted_clones() {
+        let dir = std::env::temp_dir().join(format!("mrkrabz-clones-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join(".git")).unwrap();
+
+        let mut ledger = CloneLedger::default();
+        ledger.record("https://github.com/foo/present", &dir);
+        ledger.record("https://github.com/foo/deleted", &dir.join("missing"));
+
+        assert_eq!(ledger.find("https://github.com/foo/present"), Some(dir.as_path()));
+        assert_eq!(ledger.find("https://github.com/foo/deleted"), None);
+
+        let serialized = toml::to_string(&ledger).unwrap();
+        assert!(serialized.contains("[[clone]]"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,269 @@
+use anyhow::{bail, Result};
+use octocrab::models::Repository;
+use ratatui::text::Span;
+use std::time::SystemTime;
+
+use crate::clones;
+use crate::icons::Icon;
+
+/// What a results-list column shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnKind {
+    Name,     // owner/name, or just the name when the owner has its own column
+    Owner,
+    Stars,
+    Forks,
+    Language,
+    Size,
+    Pushed,   // Relative time of the last push (or update)
+    License,  // SPDX id
+    Files,    // Total files counted by Alt+F (or Alt+A)
+    Loc,      // Lines of code from Alt+A
+}
+
+/// Every column name result_columns accepts
+const KINDS: &[(&str, ColumnKind)] = &[
+    ("name", ColumnKind::Name),
+    ("owner", ColumnKind::Owner),
+    ("stars", ColumnKind::Stars),
+    ("forks", ColumnKind::Forks),
+    ("language", ColumnKind::Language),
+    ("size", ColumnKind::Size),
+    ("pushed", ColumnKind::Pushed),
+    ("license", ColumnKind::License),
+    ("files", ColumnKind::Files),
+    ("loc", ColumnKind::Loc),
+];
+
+/// Columns the list shows unless config.toml says otherwise
+pub const DEFAULT_COLUMNS: &[&str] = &["name", "stars", "language", "size", "pushed"];
+
+/// Narrowest the name column gets squeezed on narrow terminals before later columns are dropped
+const MIN_NAME_WIDTH: usize = 12;
+
+/// A results-list column and its fixed width in cells, if it has one (config: result_columns)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Column {
+    pub kind: ColumnKind,
+    pub width: Option<usize>,
+}
+
+/// Parse a result_columns entry: a column name, optionally followed by a width ("name:30")
+pub fn parse(entry: &str) -> Result<Column> {
+    let (name, width) = match entry.split_once(':') {
+        Some((name, width)) => match width.trim().parse::<usize>() {
+            Ok(width) if width > 0 => (name, Some(width)),
+            _ => bail!("invalid width in \"{}\" (use a number of cells, e.g. \"{}:20\")", entry, name.trim()),
+        },
+        None => (entry, None),
+    };
+    let name = name.trim();
+    match KINDS.iter().find(|(known, _)| known.eq_ignore_ascii_case(name)) {
+        Some((_, kind)) => Ok(Column { kind: *kind, width }),
+        None => {
+            let known: Vec<&str> = KINDS.iter().map(|(known, _)| *known).collect();
+            bail!("unknown column \"{}\" (use {})", name, known.join(", "))
+        }
+    }
+}
+
+/// The columns DEFAULT_COLUMNS names
+pub fn defaults() -> Vec<Column> {
+    DEFAULT_COLUMNS.iter().filter_map(|name| parse(name).ok()).collect()
+}
+
+/// The valid entries of result_columns, in order; invalid ones are reported when the config loads
+pub fn parse_all(entries: &[String]) -> Vec<Column> {
+    entries.iter().filter_map(|entry| parse(entry).ok()).collect()
+}
+
+/// Figures computed for a repository in this session or an earlier one, for the files and loc columns
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Analyzed {
+    pub files: Option<u64>,
+    pub loc: Option<u64>,
+}
+
+/// `columns` plus the files and loc columns, when they aren't configured and some listed repository
+/// has the figure, so analyzed results compare side by side
+pub fn with_analyzed(columns: &[Column], files: bool, loc: bool) -> Vec<Column> {
+    let mut columns = columns.to_vec();
+    for (kind, shown) in [(ColumnKind::Files, files), (ColumnKind::Loc, loc)] {
+        if shown && !columns.iter().any(|column| column.kind == kind) {
+            columns.push(Column { kind, width: None });
+        }
+    }
+    columns
+}
+
+impl Column {
+    /// Cell text for `repo`; `owner_column` is whether the owner is shown in a column of its own
+    pub fn text(self, repo: &Repository, analyzed: Analyzed, now: SystemTime, owner_column: bool) -> String {
+        // Awesome-list entries only have a full name
+        let from_name = || repo.full_name.as_deref()?.split_once('/').map(|(owner, _)| owner);
+        let owner = repo.owner.as_ref().map(|owner| owner.login.as_str()).or_else(from_name);
+        match self.kind {
+            ColumnKind::Name if owner_column => repo.name.clone(),
+            ColumnKind::Name => repo.full_name.clone().unwrap_or_else(|| repo.name.clone()),
+            ColumnKind::Owner => owner.unwrap_or("unknown").to_string(),
+            ColumnKind::Stars => Icon::Stars.label(clones::format_count(repo.stargazers_count.unwrap_or(0) as u64)),
+            ColumnKind::Forks => Icon::Forks.label(clones::format_count(repo.forks_count.unwrap_or(0) as u64)),
+            ColumnKind::Language => repo.language.as_ref().and_then(|v| v.as_str()).unwrap_or("Unknown").to_string(),
+            ColumnKind::Size => Icon::Package.label(clones::format_size(repo.size.unwrap_or(0) as u64 * 1024)),
+            ColumnKind::Pushed => crate::tui::activity_label(repo, now).unwrap_or_default(),
+            ColumnKind::License => license_label(repo),
+            ColumnKind::Files => analyzed.files.map_or("-".to_string(), |files| format!("{} files", clones::format_count(files))),
+            ColumnKind::Loc => analyzed.loc.map_or("-".to_string(), |loc| format!("{} LOC", clones::format_count(loc))),
+        }
+    }
+}
+
+/// SPDX id of the repo's license, "other license" when GitHub can't tell, or "no license"
+pub fn license_label(repo: &Repository) -> String {
+    match repo.license.as_ref().map(|license| license.spdx_id.as_str()) {
+        Some("NOASSERTION") => "other license".to_string(),
+        Some(spdx) => spdx.to_string(),
+        None => "no license".to_string(),
+    }
+}
+
+/// "private" or "internal" for a repository only some people can see; None for a public one
+pub fn visibility_label(repo: &Repository) -> Option<&'static str> {
+    match repo.visibility.as_deref() {
+        Some("internal") => Some("internal"),
+        Some("private") => Some("private"),
+        _ => repo.private.unwrap_or(false).then_some("private"),
+    }
+}
+
+/// Display width of `text` in terminal cells
+fn width(text: &str) -> usize {
+    Span::raw(text).width()
+}
+
+/// `text` cut to at most `max` cells, ending in "…" when anything was cut
+pub fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    let mut cut = text.to_string();
+    while !cut.is_empty() && width(&cut) + 1 > max {
+        cut.pop();
+    }
+    if max > 0 {
+        cut.push('…');
+    }
+    cut
+}
+
+/// Lay out one row's cells in `available` cells, with `separator` cells between columns
+/// Fixed-width cells are padded or cut to their width; when the row is still too wide, the name
+/// is shortened (down to MIN_NAME_WIDTH) and then columns are dropped from the end
+pub fn fit(cells: Vec<(Column, String)>, available: usize, separator: usize) -> Vec<(Column, String)> {
+    let mut cells: Vec<(Column, String)> = cells
+        .into_iter()
+        .map(|(column, text)| match column.width {
+            Some(max) => {
+                let text = truncate(&text, max);
+                let padding = max.saturating_sub(width(&text));
+                (column, format!("{}{}", text, " ".repeat(padding)))
+            }
+            None => (column, text),
+        })
+        .collect();
+    let total = |cells: &[(Column, String)]| {
+        cells.iter().map(|(_, text)| width(text)).sum::<usize>() + separator * cells.len().saturating_sub(1)
+    };
+
+    let overflow = total(&cells).saturating_sub(available);
+    if overflow > 0 {
+        if let Some((_, name)) = cells.iter_mut().find(|(column, _)| column.kind == ColumnKind::Name) {
+            let name_width = width(name);
+            if name_width > MIN_NAME_WIDTH {
+                *name = truncate(name.trim_end(), name_width.saturating_sub(overflow).max(MIN_NAME_WIDTH));
+            }
+        }
+    }
+    while cells.len() > 1 && total(&cells) > available {
+        cells.pop();
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock;
+
+    fn column(kind: ColumnKind, width: Option<usize>) -> Column {
+        Column { kind, width }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("stars").unwrap(), column(ColumnKind::Stars, None));
+        assert_eq!(parse(" Name:30 ").unwrap(), column(ColumnKind::Name, Some(30)));
+        assert!(parse("watchers").unwrap_err().to_string().starts_with("unknown column \"watchers\" (use name, owner,"));
+        assert!(parse("name:wide").unwrap_err().to_string().starts_with("invalid width in \"name:wide\""));
+        assert!(parse("name:0").is_err());
+
+        let entries = ["name".to_string(), "bogus".to_string(), "license:10".to_string()];
+        let kinds: Vec<ColumnKind> = parse_all(&entries).iter().map(|column| column.kind).collect();
+        assert_eq!(kinds, [ColumnKind::Name, ColumnKind::License]);
+        assert!(DEFAULT_COLUMNS.iter().all(|name| parse(name).is_ok()));
+    }
+
+    #[test]
+    fn test_text() {
+        let owner = serde_json::json!({
+            "login": "rust-lang", "id": 2, "node_id": "", "avatar_url": "https://example.com/a",
+            "gravatar_id": "", "url": "https://example.com/u", "html_url": "https://example.com/h",
+            "followers_url": "https://example.com/f", "following_url": "https://example.com/f",
+            "gists_url": "https://example.com/g", "starred_url": "https://example.com/s",
+            "subscriptions_url": "https://example.com/s", "organizations_url": "https://example.com/o",
+            "repos_url": "https://example.com/r", "events_url": "https://example.com/e",
+            "received_events_url": "https://example.com/e", "type": "Organization", "site_admin": false
+        });
+        let repo = mock::repo("rust-lang/rust").set("owner", owner).stars(98000).build();
+        let now = SystemTime::now();
+        assert_eq!(column(ColumnKind::Name, None).text(&repo, Analyzed::default(), now, false), "rust-lang/rust");
+        assert_eq!(column(ColumnKind::Name, None).text(&repo, Analyzed::default(), now, true), "rust");
+        assert_eq!(column(ColumnKind::Owner, None).text(&repo, Analyzed::default(), now, true), "rust-lang");
+        assert!(column(ColumnKind::Stars, None).text(&repo, Analyzed::default(), now, false).ends_with("98k"));
+        assert_eq!(column(ColumnKind::License, None).text(&repo, Analyzed::default(), now, false), "no license");
+        assert_eq!(visibility_label(&repo), None);
+        assert_eq!(column(ColumnKind::Files, None).text(&repo, Analyzed::default(), now, false), "-");
+        let analyzed = Analyzed { files: Some(1234), loc: Some(56000) };
+        assert_eq!(column(ColumnKind::Files, None).text(&repo, analyzed, now, false), "1.2k files");
+        assert_eq!(column(ColumnKind::Loc, None).text(&repo, analyzed, now, false), "56k LOC");
+    }
+
+    #[test]
+    fn test_with_analyzed() {
+        let kinds = |columns: Vec<Column>| columns.iter().map(|column| column.kind).collect::<Vec<_>>();
+        let configured = [column(ColumnKind::Name, None), column(ColumnKind::Loc, Some(8))];
+        assert_eq!(kinds(with_analyzed(&configured, false, false)), [ColumnKind::Name, ColumnKind::Loc]);
+        assert_eq!(kinds(with_analyzed(&configured, true, true)), [ColumnKind::Name, ColumnKind::Loc, ColumnKind::Files]);
+        assert_eq!(with_analyzed(&configured, true, true)[1].width, Some(8), "configured columns keep their width");
+    }
+
+    #[test]
+    fn test_fit() {
+        let cells = || {
+            vec![
+                (column(ColumnKind::Name, None), "rust-lang/rust-analyzer".to_string()),
+                (column(ColumnKind::Language, Some(6)), "Rust".to_string()),
+                (column(ColumnKind::License, Some(4)), "Apache-2.0".to_string()),
+            ]
+        };
+        let texts = |cells: Vec<(Column, String)>| cells.into_iter().map(|(_, text)| text).collect::<Vec<_>>();
+
+        // Fixed widths pad and cut
+        assert_eq!(texts(fit(cells(), 80, 3)), ["rust-lang/rust-analyzer", "Rust  ", "Apa…"]);
+        // Too narrow: the name shrinks first...
+        assert_eq!(texts(fit(cells(), 30, 3)), ["rust-lang/rus…", "Rust  ", "Apa…"]);
+        // ...then trailing columns go
+        assert_eq!(texts(fit(cells(), 21, 3)), ["rust-lang/r…", "Rust  "]);
+        assert_eq!(texts(fit(cells(), 5, 3)), ["rust-lang/r…"]);
+    }
+}
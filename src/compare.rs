@@ -0,0 +1,190 @@
+use anyhow::Result;
+use octocrab::models::Repository;
+use std::time::{Duration, SystemTime};
+
+use crate::analysis::{self, LanguageStats};
+use crate::clones;
+use crate::columns;
+use crate::error::Error;
+use crate::github::GitHub;
+use crate::i18n::tr;
+
+/// Latest releases fetched to work out how often a repository releases
+const RELEASES_SAMPLED: u8 = 20;
+
+/// Release and contributor figures the search results don't include, fetched for a comparison
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoActivity {
+    pub releases: Vec<SystemTime>, // Publish dates of the latest releases, newest first
+    pub contributors: Option<u64>, // None when GitHub won't list them (very large histories)
+}
+
+/// Fetch the latest releases and the number of contributors of owner/name (two API requests)
+pub async fn fetch_activity(github: &dyn GitHub, owner: &str, name: &str) -> Result<RepoActivity> {
+    let mut releases = github.release_dates(owner, name, RELEASES_SAMPLED).await?;
+    releases.sort_by(|a, b| b.cmp(a));
+
+    let contributors = match github.contributor_count(owner, name).await {
+        Ok(count) => Some(count),
+        Err(Error::TooLarge) => {
+            tracing::info!(owner, name, "contributor list too large to count");
+            None
+        }
+        Err(e) => return Err(e.into()),
+    };
+    Ok(RepoActivity { releases, contributors })
+}
+
+/// Average time between `releases` (any order), None with fewer than two
+pub fn release_cadence(releases: &[SystemTime]) -> Option<Duration> {
+    let newest = releases.iter().max()?;
+    let oldest = releases.iter().min()?;
+    let gaps = u32::try_from(releases.len().checked_sub(1)?).ok().filter(|&gaps| gaps > 0)?;
+    Some(newest.duration_since(*oldest).ok()? / gaps)
+}
+
+/// How often something happens, every `interval`, e.g. "every ~3 weeks"
+fn interval_label(interval: Duration) -> String {
+    match interval.as_secs() / 86400 {
+        0 => tr!("every-more-than-daily"),
+        days @ 1..=13 => tr!("every-days", count = days),
+        days @ 14..=59 => tr!("every-weeks", count = days / 7),
+        days @ 60..=729 => tr!("every-months", count = days / 30),
+        days => tr!("every-years", count = days / 365),
+    }
+}
+
+/// Cadence and age of the latest release, e.g. "every ~2 weeks, last 3 days ago"
+pub fn releases_label(releases: &[SystemTime], now: SystemTime) -> String {
+    match (releases.first(), release_cadence(releases)) {
+        (None, _) => tr!("releases-none"),
+        (Some(latest), None) => tr!("releases-one", age = clones::format_age(*latest, now)),
+        (Some(latest), Some(cadence)) => {
+            tr!("releases-cadence", cadence = interval_label(cadence), age = clones::format_age(*latest, now))
+        }
+    }
+}
+
+/// One repository of a comparison and what's known about it so far
+pub struct Side<'a> {
+    pub repo: &'a Repository,
+    pub activity: Option<&'a Result<RepoActivity, String>>, // None until fetched
+    pub loading: bool,                                      // Activity is being fetched
+    pub analysis: Option<&'a Result<Vec<LanguageStats>, String>>, // Language analysis (Alt+A), if run
+    pub license_conflict: Option<String>, // Why its license clashes with project_license, if it does
+}
+
+/// A line of the comparison: what it measures and each repository's value
+#[derive(Debug, PartialEq)]
+pub struct Row {
+    pub label: String,
+    pub values: [String; 2],
+}
+
+/// The aligned rows of the comparison view, one value per side
+pub fn rows(sides: &[Side; 2], now: SystemTime) -> Vec<Row> {
+    let row = |label: &str, value: &dyn Fn(&Side) -> String| Row {
+        label: tr!(label),
+        values: [value(&sides[0]), value(&sides[1])],
+    };
+    let activity = |side: &Side, value: &dyn Fn(&RepoActivity) -> String| match side.activity {
+        Some(Ok(activity)) => value(activity),
+        Some(Err(_)) => tr!("compare-unavailable"),
+        None if side.loading => tr!("compare-loading"),
+        None => tr!("compare-not-loaded"),
+    };
+    vec![
+        row("compare-stars", &|side| clones::format_count(side.repo.stargazers_count.unwrap_or(0) as u64)),
+        row("compare-forks", &|side| clones::format_count(side.repo.forks_count.unwrap_or(0) as u64)),
+        row("compare-last-push", &|side| match side.repo.pushed_at.or(side.repo.updated_at) {
+            Some(date) => clones::format_age(date.into(), now),
+            None => tr!("compare-unknown"),
+        }),
+        row("compare-releases", &|side| activity(side, &|activity| releases_label(&activity.releases, now))),
+        row("compare-contributors", &|side| {
+            activity(side, &|activity| match activity.contributors {
+                Some(count) => clones::format_count(count),
+                None => tr!("compare-too-many"),
+            })
+        }),
+        row("compare-lines-of-code", &|side| match side.analysis {
+            Some(Ok(stats)) => clones::format_count(analysis::total(stats).code as u64),
+            Some(Err(_)) => tr!("compare-analysis-failed"),
+            None => tr!("compare-not-analyzed"),
+        }),
+        row("compare-license", &|side| match &side.license_conflict {
+            Some(conflict) => tr!("compare-incompatible", conflict = conflict.as_str()),
+            None => columns::license_label(side.repo),
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock;
+
+    const DAY: Duration = Duration::from_secs(86400);
+
+    fn repo(name: &str, stars: u64) -> Repository {
+        mock::repo(&format!("owner/{}", name))
+            .id(stars)
+            .stars(stars)
+            .set("forks_count", 12)
+            .pushed_at("2024-05-01T12:00:00Z")
+            .build()
+    }
+
+    #[test]
+    fn test_release_cadence() {
+        let now = SystemTime::now();
+        assert_eq!(release_cadence(&[]), None);
+        assert_eq!(release_cadence(&[now]), None);
+        // Three releases 30 days apart in all: one every 15 days
+        assert_eq!(release_cadence(&[now, now - DAY * 10, now - DAY * 30]), Some(DAY * 15));
+        assert_eq!(release_cadence(&[now - DAY * 30, now]), Some(DAY * 30));
+
+        assert_eq!(interval_label(Duration::from_secs(3600)), "more than daily");
+        assert_eq!(interval_label(DAY), "every ~1 day");
+        assert_eq!(interval_label(DAY * 21), "every ~3 weeks");
+        assert_eq!(interval_label(DAY * 400), "every ~13 months");
+        assert_eq!(interval_label(DAY * 800), "every ~2 years");
+    }
+
+    #[test]
+    fn test_rows() {
+        let now = SystemTime::now();
+        let (serde, nom) = (repo("serde", 9000), repo("nom", 1500));
+        let activity = Ok(RepoActivity { releases: vec![now - DAY * 3, now - DAY * 17], contributors: Some(180) });
+        let analysis = Ok(vec![LanguageStats { name: "Rust".to_string(), files: 10, code: 42000, comments: 0, blanks: 0 }]);
+        let sides = [
+            Side { repo: &serde, activity: Some(&activity), loading: false, analysis: Some(&analysis), license_conflict: None },
+            Side { repo: &nom, activity: None, loading: true, analysis: None, license_conflict: Some("no license".to_string()) },
+        ];
+        let rows = rows(&sides, now);
+        let row = |label: &str| &rows.iter().find(|row| row.label == label).unwrap().values;
+
+        assert_eq!(rows.iter().map(|row| row.label.as_str()).collect::<Vec<_>>(), [
+            "Stars", "Forks", "Last push", "Releases", "Contributors", "Lines of code", "License"
+        ]);
+        assert_eq!(row("Stars"), &["9k", "1.5k"]);
+        assert_eq!(row("Releases"), &["every ~2 weeks, last 3 days ago", "loading…"]);
+        assert_eq!(row("Contributors"), &["180", "loading…"]);
+        assert_eq!(row("Lines of code"), &["42k", "not analyzed (Alt+A)"]);
+        assert_eq!(row("License"), &["no license", "incompatible: no license"]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_activity() {
+        let mut github = mock::MockGitHub::new();
+        github.contributors = Ok(37);
+        assert_eq!(fetch_activity(&github, "owner", "repo").await.unwrap().contributors, Some(37));
+
+        // Only a history too large to list leaves the count out
+        github.contributors = Err(Error::TooLarge);
+        assert_eq!(fetch_activity(&github, "owner", "repo").await.unwrap().contributors, None);
+        github.contributors = Err(Error::RateLimited { reset: None });
+        let error = fetch_activity(&github, "owner", "repo").await.unwrap_err();
+        assert_eq!(error.downcast_ref::<Error>(), Some(&Error::RateLimited { reset: None }));
+    }
+}
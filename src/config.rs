@@ -1,84 +1,827 @@
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use clap::Subcommand;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
-#[derive(Debug, Deserialize)]
+use crate::filecount::CountFilter;
+use crate::git::CloneMode;
+use crate::i18n::Language;
+use crate::icons::IconStyle;
+use crate::paths;
+use crate::profiles::{self, AnalysisProfile};
+
+/// How Alt+G clones are laid out under the destination directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum CloneLayout {
+    /// repositories/<name>
+    #[default]
+    #[serde(rename = "name")]
+    Name,
+    /// repositories/<owner>/<name>, so same-named repos from different owners don't collide
+    #[serde(rename = "owner/name")]
+    OwnerName,
+}
+
+impl CloneLayout {
+    /// Relative path of a repository under the clone directory
+    pub fn path(self, owner: &str, name: &str) -> PathBuf {
+        match self {
+            CloneLayout::Name => PathBuf::from(name),
+            CloneLayout::OwnerName => PathBuf::from(owner).join(name),
+        }
+    }
+}
+
+/// Which TUI shortcuts the help bar leads with, and where focus goes after a search
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Keymap {
+    /// Alt+letter shortcuts; focus stays in the search box after searching
+    #[default]
+    Alt,
+    /// Searching moves focus to the results, where plain o/c/f/a/b/y act on the selection
+    Letters,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
-    #[serde(default = "default_filecount_script")]
-    pub filecount_script: String,
+    /// Enable vim-style navigation (j/k, gg/G, Ctrl+d/u, '/' filter) in the TUI
+    #[serde(default)]
+    pub vim_mode: bool,
+
+    /// Shortcut scheme: "alt" (default) or "letters", for terminals that swallow Alt
+    #[serde(default)]
+    pub keymap: Keymap,
+
+    /// Results-list columns in order, each optionally with a width: ["name:40", "stars", ...]
+    #[serde(default = "default_result_columns")]
+    pub result_columns: Vec<String>,
+
+    /// Search automatically once typing pauses, instead of waiting for Enter
+    #[serde(default)]
+    pub live_search: bool,
+
+    /// Debounce delay for live search, in milliseconds
+    #[serde(default = "default_live_search_delay_ms")]
+    pub live_search_delay_ms: u64,
+
+    /// Fetch the next page of results in the background when the selection nears the end of the list
+    #[serde(default = "default_true")]
+    pub infinite_scroll: bool,
+
+    /// Seconds the TUI reuses a search's results instead of asking GitHub again (0 always asks)
+    #[serde(default = "default_search_cache_ttl_secs")]
+    pub search_cache_ttl_secs: u64,
+
+    /// Fetch the selected result's releases and contributors for the details panel (two API requests each)
+    #[serde(default = "default_true")]
+    pub fetch_details: bool,
+
+    /// Results after the selected one whose details are fetched ahead, so moving down shows them at once
+    #[serde(default = "default_prefetch_ahead")]
+    pub prefetch_ahead: usize,
+
+    /// Language every search is limited to, e.g. "rust" (--language overrides)
+    #[serde(default)]
+    pub default_language: Option<String>,
+
+    /// Minimum stars of every search (--stars overrides)
+    #[serde(default)]
+    pub default_min_stars: Option<u32>,
+
+    /// Size filter sessions start with: "small", "medium", or "large" (--repo-size overrides, 0-3 in the TUI)
+    #[serde(default)]
+    pub default_repo_size: Option<String>,
+
+    /// Result order: "stars", "forks", "updated", or "help-wanted-issues"; unset is best match (--sort overrides)
+    #[serde(default)]
+    pub default_sort: Option<String>,
+
+    /// Commits of history to fetch when cloning (0 = full history)
+    /// Unset: Alt+G clones full history and file counts fetch 1 commit
+    #[serde(default)]
+    pub clone_depth: Option<u32>,
+
+    /// Default clone mode: "full", "blobless" (--filter=blob:none), or "treeless" (--filter=tree:0)
+    #[serde(default)]
+    pub clone_mode: CloneMode,
+
+    /// Also clone submodules (recursively) by default
+    #[serde(default)]
+    pub clone_submodules: bool,
+
+    /// Directory layout for clones: "name" (default) or "owner/name"
+    #[serde(default)]
+    pub clone_layout: CloneLayout,
+
+    /// Alt+G asks for confirmation when the repos GitHub reports are larger than this, in MB (0 = never ask)
+    #[serde(default = "default_clone_confirm_size_mb")]
+    pub clone_confirm_size_mb: u64,
+
+    /// Number of Alt+G clones that run at once; the rest wait in a queue
+    #[serde(default = "default_clone_parallelism")]
+    pub clone_parallelism: usize,
+
+    /// Check the Cargo.lock of Rust repos against the RustSec advisory database after cloning them
+    #[serde(default)]
+    pub audit_after_clone: bool,
+
+    /// Command Alt+D runs to open a pane in the selected clone, its path appended, e.g. "tmux new-window -c"
+    /// Unset uses a split of tmux or WezTerm when the TUI runs inside one
+    #[serde(default)]
+    pub pane_command: Option<String>,
+
+    /// Repositories kept cloned and up to date by `sync` ("owner/name" or clone URLs)
+    #[serde(default)]
+    pub sync_repos: Vec<String>,
+
+    /// Skip files matched by .gitignore when counting files and analyzing languages
+    #[serde(default = "default_true")]
+    pub count_respect_gitignore: bool,
+
+    /// Count dependency and build directories (node_modules, target, vendor, ...) too
+    #[serde(default)]
+    pub count_include_vendored: bool,
+
+    /// Extra gitignore-style globs to skip when counting, e.g. ["*.min.js", "docs/"]
+    #[serde(default)]
+    pub count_exclude: Vec<String>,
+
+    /// What gets written to logs/: "error", "warn", "info", "debug", "trace", or "off" (RUST_LOG overrides)
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+
+    /// Start interactive mode as plain sequential text instead of the TUI, for screen readers (like --linear)
+    #[serde(default)]
+    pub linear_mode: bool,
+
+    /// Decorative glyphs: "emoji", "ascii" (for terminals without emoji), or "none"
+    #[serde(default)]
+    pub icons: IconStyle,
+
+    /// Show the query in the terminal title and clone progress in its taskbar (OSC 0 and OSC 9;4)
+    #[serde(default = "default_true")]
+    pub terminal_status: bool,
+
+    /// Language of the TUI and error messages: "en" (English) or "es" (Spanish)
+    #[serde(default)]
+    pub language: Language,
+
+    /// PEM file of extra root certificates to trust for the GitHub API, e.g. a corporate proxy's CA
+    #[serde(default)]
+    pub ca_bundle: Option<PathBuf>,
+
+    /// Accept any TLS certificate from the GitHub API (for test instances with self-signed certs only)
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+
+    /// Sign in as this GitHub App (its numeric App ID) instead of with a token, for organizations that
+    /// don't allow personal access tokens; needs github_app_key
+    #[serde(default)]
+    pub github_app_id: Option<u64>,
+
+    /// The GitHub App's private key: the .pem file downloaded from its settings page
+    #[serde(default)]
+    pub github_app_key: Option<PathBuf>,
+
+    /// Which installation of the app to act as; only needed when the app is installed on several accounts
+    #[serde(default)]
+    pub github_app_installation_id: Option<u64>,
+
+    /// Your project's license(s) as SPDX ids, e.g. "MIT OR Apache-2.0"; results whose license can't be
+    /// used in it are flagged
+    #[serde(default)]
+    pub project_license: Option<String>,
+
+    /// Seconds an analysis command may run before it's killed, unless its profile or itself sets timeout_secs
+    #[serde(default = "default_script_timeout_secs")]
+    pub script_timeout_secs: u64,
+
+    /// Commands run in fresh clones, per language: [analysis.rust], [analysis.node], ...
+    #[serde(default)]
+    pub analysis: BTreeMap<String, AnalysisProfile>,
+}
+
+fn default_live_search_delay_ms() -> u64 {
+    500
+}
+
+fn default_search_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_prefetch_ahead() -> usize {
+    2
+}
+
+fn default_clone_parallelism() -> usize {
+    crate::jobs::DEFAULT_CLONE_PARALLELISM
+}
+
+fn default_clone_confirm_size_mb() -> u64 {
+    crate::tui::DEFAULT_CLONE_CONFIRM_SIZE_MB
+}
+
+fn default_script_timeout_secs() -> u64 {
+    crate::profiles::DEFAULT_TIMEOUT_SECS
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_result_columns() -> Vec<String> {
+    crate::columns::DEFAULT_COLUMNS.iter().map(|column| column.to_string()).collect()
+}
+
+fn default_true() -> bool {
+    true
 }
 
-fn default_filecount_script() -> String {
-    "mac_zsh".to_string()
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            vim_mode: false,
+            keymap: Keymap::Alt,
+            result_columns: default_result_columns(),
+            live_search: false,
+            live_search_delay_ms: default_live_search_delay_ms(),
+            infinite_scroll: true,
+            search_cache_ttl_secs: default_search_cache_ttl_secs(),
+            fetch_details: true,
+            prefetch_ahead: default_prefetch_ahead(),
+            default_language: None,
+            default_min_stars: None,
+            default_repo_size: None,
+            default_sort: None,
+            clone_depth: None,
+            clone_mode: CloneMode::Full,
+            clone_submodules: false,
+            clone_layout: CloneLayout::Name,
+            clone_confirm_size_mb: default_clone_confirm_size_mb(),
+            clone_parallelism: default_clone_parallelism(),
+            audit_after_clone: false,
+            pane_command: None,
+            sync_repos: Vec::new(),
+            count_respect_gitignore: true,
+            count_include_vendored: false,
+            count_exclude: Vec::new(),
+            log_level: default_log_level(),
+            linear_mode: false,
+            icons: IconStyle::Emoji,
+            terminal_status: true,
+            language: Language::English,
+            ca_bundle: None,
+            danger_accept_invalid_certs: false,
+            github_app_id: None,
+            github_app_key: None,
+            github_app_installation_id: None,
+            project_license: None,
+            script_timeout_secs: default_script_timeout_secs(),
+            analysis: BTreeMap::new(),
+        }
+    }
+}
+
+/// The commented config.toml `config init` writes: every setting at its default
+pub const DEFAULT_CONFIG: &str = include_str!("../config.toml");
+
+/// Prefix of environment variables that override config.toml keys, e.g. MRKRABZ_VIM_MODE=true
+pub const ENV_PREFIX: &str = "MRKRABZ_";
+
+/// Every setting config.toml accepts
+const KEYS: &[&str] = &[
+    "vim_mode",
+    "keymap",
+    "result_columns",
+    "live_search",
+    "live_search_delay_ms",
+    "infinite_scroll",
+    "search_cache_ttl_secs",
+    "fetch_details",
+    "prefetch_ahead",
+    "default_language",
+    "default_min_stars",
+    "default_repo_size",
+    "default_sort",
+    "clone_depth",
+    "clone_mode",
+    "clone_submodules",
+    "clone_layout",
+    "clone_confirm_size_mb",
+    "clone_parallelism",
+    "audit_after_clone",
+    "pane_command",
+    "sync_repos",
+    "count_respect_gitignore",
+    "count_include_vendored",
+    "count_exclude",
+    "log_level",
+    "linear_mode",
+    "icons",
+    "terminal_status",
+    "language",
+    "ca_bundle",
+    "danger_accept_invalid_certs",
+    "github_app_id",
+    "github_app_key",
+    "github_app_installation_id",
+    "project_license",
+    "script_timeout_secs",
+    "analysis",
+];
+
+/// Size filters default_repo_size (and --repo-size) accept
+const REPO_SIZES: &[&str] = &["small", "medium", "large"];
+
+/// Orders GitHub's repository search accepts for default_sort (and --sort)
+const SORTS: &[&str] = &["stars", "forks", "updated", "help-wanted-issues"];
+
+/// Settings older versions read that are now ignored, with why
+const REMOVED_KEYS: &[(&str, &str)] = &[("filecount_script", "file counting is built in now")];
+
+/// Override config keys from MRKRABZ_<KEY> variables, returning each overridden key and its variable
+/// Values are read as TOML (`true`, `3`, `["*.min.js"]`), falling back to a plain string (`blobless`)
+/// Variables that don't name a setting (e.g. MRKRABZ_TOKEN) are left alone
+fn apply_env(table: &mut toml::Table, vars: impl IntoIterator<Item = (String, String)>) -> Vec<(String, String)> {
+    let mut used = Vec::new();
+    for (name, raw) in vars {
+        let Some(key) = name.strip_prefix(ENV_PREFIX).map(str::to_lowercase) else {
+            continue;
+        };
+        if !KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        let value = toml::from_str::<toml::Table>(&format!("value = {}", raw))
+            .ok()
+            .and_then(|mut parsed| parsed.remove("value"))
+            .unwrap_or(toml::Value::String(raw));
+        table.insert(key.clone(), value);
+        used.push((key, name));
+    }
+    used.sort();
+    used
+}
+
+/// 1-based line in `contents` where `key` is set, if it's there
+fn key_line(contents: &str, key: &str) -> Option<usize> {
+    contents.lines().position(|line| {
+        line.trim_start()
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    })
+    .map(|index| index + 1)
+}
+
+/// The known setting closest to a misspelled one, if any is close
+fn suggest_key(unknown: &str) -> Option<&'static str> {
+    KEYS.iter()
+        .map(|key| (edit_distance(unknown, key), *key))
+        .filter(|(distance, _)| *distance <= 3)
+        .min()
+        .map(|(_, key)| key)
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
 }
 
 impl Config {
-    /// Load configuration from config.toml
-    /// Falls back to defaults if file doesn't exist
+    /// Load configuration from config.toml (see `paths::config_file` for where it's looked for),
+    /// with MRKRABZ_* environment variables layered on top
+    /// Precedence: CLI flags (applied by the caller) > environment > config.toml > defaults
     pub fn load() -> Result<Self> {
-        let config_path = PathBuf::from("config.toml");
+        let config_path = paths::config_file();
 
-        if !config_path.exists() {
-            // Return default config if file doesn't exist
-            return Ok(Config {
-                filecount_script: default_filecount_script(),
-            });
+        // Empty if the file doesn't exist, so every setting uses its default
+        let contents = if config_path.exists() {
+            fs::read_to_string(&config_path).with_context(|| format!("Failed to read {}", config_path.display()))?
+        } else {
+            String::new()
+        };
+        let (config, warnings) = Config::parse(&contents, &config_path.display().to_string(), std::env::vars())?;
+        for warning in warnings {
+            eprintln!("{} {}", "Warning:".yellow(), warning);
         }
+        Ok(config)
+    }
 
-        let contents = fs::read_to_string(&config_path)
-            .context("Failed to read config.toml")?;
+    /// Parse config.toml `contents` (read from `source`) with `vars` overriding it
+    /// Every unknown key and invalid value is reported, each with the line or variable it came from
+    /// Returns the config and warnings about settings that are no longer used
+    fn parse(
+        contents: &str,
+        source: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<(Self, Vec<String>)> {
+        let mut table: toml::Table = toml::from_str(contents).with_context(|| format!("Failed to parse {}", source))?;
+        let origin = |key: &str| match key_line(contents, key) {
+            Some(line) => format!("{} line {}", source, line),
+            None => source.to_string(),
+        };
 
-        let config: Config = toml::from_str(&contents)
-            .context("Failed to parse config.toml")?;
+        let mut warnings = Vec::new();
+        for (key, reason) in REMOVED_KEYS {
+            if table.remove(*key).is_some() {
+                warnings.push(format!("{}: `{}` is no longer used ({}); remove it", origin(key), key, reason));
+            }
+        }
 
-        Ok(config)
+        let overridden = apply_env(&mut table, vars);
+        let origin = |key: &str| match overridden.iter().find(|(overridden, _)| overridden == key) {
+            Some((_, variable)) => variable.clone(),
+            None => origin(key),
+        };
+
+        // Deserialize each setting on its own so every bad one is reported, not just the first
+        let mut problems = Vec::new();
+        for (key, value) in &table {
+            if !KEYS.contains(&key.as_str()) {
+                let hint = suggest_key(key).map_or_else(String::new, |known| format!(" (did you mean `{}`?)", known));
+                problems.push(format!("{}: unknown setting `{}`{}", origin(key), key, hint));
+                continue;
+            }
+            let single = toml::Table::from_iter([(key.clone(), value.clone())]);
+            if let Err(e) = toml::Value::Table(single).try_into::<Config>() {
+                let reason = e.to_string();
+                let reason = reason.lines().next().unwrap_or_default();
+                problems.push(format!("{}: invalid `{}`: {}", origin(key), key, reason));
+            }
+        }
+        if problems.is_empty() {
+            let config: Config = toml::Value::Table(table).try_into()?;
+            problems.extend(config.validate().into_iter().map(|(key, reason)| format!("{}: invalid `{}`: {}", origin(key), key, reason)));
+            if problems.is_empty() {
+                return Ok((config, warnings));
+            }
+        }
+        anyhow::bail!("Invalid configuration:\n  {}", problems.join("\n  "))
+    }
+
+    /// Checks beyond the types: each problem as (setting, reason)
+    fn validate(&self) -> Vec<(&'static str, String)> {
+        let mut problems = Vec::new();
+        if let Some(size) = self.default_repo_size.as_deref().filter(|size| !REPO_SIZES.contains(&size.to_lowercase().as_str())) {
+            problems.push(("default_repo_size", format!("\"{}\" isn't a size (use {})", size, REPO_SIZES.join(", "))));
+        }
+        if let Some(sort) = self.default_sort.as_deref().filter(|sort| !SORTS.contains(sort)) {
+            problems.push(("default_sort", format!("\"{}\" isn't an order (use {})", sort, SORTS.join(", "))));
+        }
+        if self.clone_parallelism == 0 {
+            problems.push(("clone_parallelism", "must be at least 1".to_string()));
+        }
+        if let Err(e) = tracing_subscriber::EnvFilter::try_new(&self.log_level) {
+            problems.push(("log_level", format!("{} (use \"error\", \"warn\", \"info\", \"debug\", \"trace\", or \"off\")", e)));
+        }
+        for glob in &self.count_exclude {
+            if let Err(e) = ignore::overrides::OverrideBuilder::new(".").add(glob) {
+                problems.push(("count_exclude", e.to_string()));
+            }
+        }
+        if let Some(path) = &self.ca_bundle {
+            if let Err(e) = crate::client::load_ca_bundle(path) {
+                problems.push(("ca_bundle", format!("{:#}", e)));
+            }
+        }
+        match (self.github_app_id, &self.github_app_key) {
+            (Some(_), None) => problems.push(("github_app_key", "set it to the app's private key file too".to_string())),
+            (None, Some(_)) => problems.push(("github_app_id", "set it to the app's ID too".to_string())),
+            _ => {}
+        }
+        if let Some(path) = &self.github_app_key {
+            if let Err(e) = crate::client::load_app_key(path) {
+                problems.push(("github_app_key", format!("{:#}", e)));
+            }
+        }
+        if self.github_app_installation_id.is_some() && self.github_app_id.is_none() {
+            problems.push(("github_app_installation_id", "only used with github_app_id".to_string()));
+        }
+        if let Some(license) = &self.project_license {
+            if let Err(e) = crate::licenses::parse_project(license) {
+                problems.push(("project_license", e.to_string()));
+            }
+        }
+        if self.result_columns.is_empty() {
+            problems.push(("result_columns", "list at least one column, e.g. [\"name\"]".to_string()));
+        }
+        for entry in &self.result_columns {
+            if let Err(e) = crate::columns::parse(entry) {
+                problems.push(("result_columns", e.to_string()));
+            }
+        }
+        for entry in &self.sync_repos {
+            if let Err(e) = crate::sync::repo_url(entry) {
+                problems.push(("sync_repos", e.to_string()));
+            }
+        }
+        if self.script_timeout_secs == 0 {
+            problems.push(("script_timeout_secs", "must be at least 1".to_string()));
+        }
+        for (name, profile) in &self.analysis {
+            problems.extend(profiles::validate(name, profile).into_iter().map(|problem| ("analysis", problem)));
+        }
+        problems
     }
 
-    /// Get the filecount script path based on configuration
-    pub fn get_filecount_script_path(&self) -> String {
-        match self.filecount_script.as_str() {
-            "mac_bash" => "mac_linux_bash_filecount.sh".to_string(),
-            "windows" => "windows_filecount.ps1".to_string(),
-            _ => "filecount.sh".to_string(), // Default to mac_zsh
+    /// File count and analysis exclusions from the count_* settings
+    pub fn count_filter(&self) -> CountFilter {
+        CountFilter {
+            respect_gitignore: self.count_respect_gitignore,
+            include_vendored: self.count_include_vendored,
+            exclude: self.count_exclude.clone(),
         }
     }
 }
 
+/// `config` subcommands
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Write a commented config.toml with every setting at its default to the config directory
+    Init {
+        /// Overwrite an existing config.toml
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the effective configuration: config.toml plus MRKRABZ_* overrides and defaults
+    Show,
+}
+
+/// Run a `config` subcommand with the loaded config (or why it failed to load)
+/// `token_set` is whether a GitHub token was given (it's never printed)
+pub fn run(command: &ConfigCommand, config: Result<Config>, token_set: bool) -> Result<()> {
+    match command {
+        ConfigCommand::Init { force } => {
+            let path = paths::user_config_file().context("Couldn't find a config directory (is $HOME set?)")?;
+            if path.exists() && !force {
+                anyhow::bail!("{} already exists (use --force to overwrite it)", path.display());
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, DEFAULT_CONFIG).with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("{} {}", "Wrote".green(), path.display());
+        }
+        ConfigCommand::Show => {
+            let path = paths::config_file();
+            let config = config?;
+            let status = if path.exists() { "" } else { " (not found, using defaults)" };
+            println!("{}", format!("# Config file: {}{}", path.display(), status).dimmed());
+            let overrides: Vec<String> = std::env::vars()
+                .map(|(name, _)| name)
+                .filter(|name| name.starts_with(ENV_PREFIX) && name != "MRKRABZ_TOKEN")
+                .collect();
+            if !overrides.is_empty() {
+                println!("{}", format!("# Environment overrides: {}", overrides.join(", ")).dimmed());
+            }
+            let token = if token_set { "set (redacted)" } else { "not set" };
+            println!("{}", format!("# GitHub token: {}", token).dimmed());
+            println!("{}", config.to_redacted_toml()?);
+        }
+    }
+    Ok(())
+}
+
+impl Config {
+    /// The settings as TOML, with anything that looks like a secret replaced by "<redacted>"
+    fn to_redacted_toml(&self) -> Result<String> {
+        let mut table = toml::Table::try_from(self).context("Failed to serialize the config")?;
+        for (key, value) in table.iter_mut() {
+            let secret = ["token", "secret", "password"].iter().any(|word| key.contains(word));
+            if secret && value.as_str().is_some_and(|value| !value.is_empty()) {
+                *value = toml::Value::String("<redacted>".to_string());
+            }
+        }
+        Ok(toml::to_string(&table)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_default_config() {
-        let config = Config {
-            filecount_script: default_filecount_script(),
-        };
-        assert_eq!(config.filecount_script, "mac_zsh");
-        assert_eq!(config.get_filecount_script_path(), "filecount.sh");
+        let config = Config::default();
+        assert!(!config.vim_mode);
+        assert!(!config.live_search);
+        assert_eq!(config.live_search_delay_ms, 500);
+        assert!(config.infinite_scroll);
+        assert_eq!(config.search_cache_ttl_secs, 300);
+        assert!(config.fetch_details);
+        assert_eq!(config.prefetch_ahead, 2);
+        assert_eq!(config.clone_depth, None);
+        assert!(!config.clone_submodules);
+        assert_eq!(config.clone_parallelism, 3);
+        assert_eq!(config.clone_confirm_size_mb, 500);
+        assert!(!config.audit_after_clone);
+        assert_eq!(config.log_level, "info");
+        assert!(config.sync_repos.is_empty());
     }
 
     #[test]
-    fn test_mac_bash_config() {
-        let config = Config {
-            filecount_script: "mac_bash".to_string(),
-        };
-        assert_eq!(config.get_filecount_script_path(), "mac_linux_bash_filecount.sh");
+    fn test_default_config_file_matches_defaults() {
+        let shipped: Config = toml::from_str(DEFAULT_CONFIG).unwrap();
+        assert_eq!(shipped.to_redacted_toml().unwrap(), Config::default().to_redacted_toml().unwrap());
+        assert!(Config::default().to_redacted_toml().unwrap().contains("clone_layout = \"name\""));
+    }
+
+    fn parse(contents: &str, vars: &[(&str, &str)]) -> Result<(Config, Vec<String>)> {
+        let vars = vars.iter().map(|(name, value)| (name.to_string(), value.to_string()));
+        Config::parse(contents, "config.toml", vars)
     }
 
     #[test]
-    fn test_windows_config() {
-        let config = Config {
-            filecount_script: "windows".to_string(),
-        };
-        assert_eq!(config.get_filecount_script_path(), "windows_filecount.ps1");
+    fn test_old_filecount_script_setting_is_ignored() {
+        let (config, warnings) = parse("vim_mode = true\nfilecount_script = \"windows\"", &[]).unwrap();
+        assert!(config.vim_mode);
+        assert_eq!(
+            warnings,
+            ["config.toml line 2: `filecount_script` is no longer used (file counting is built in now); remove it"]
+        );
     }
 
     #[test]
-    fn test_unknown_config_defaults_to_mac_zsh() {
+    fn test_keys_list_every_setting() {
         let config = Config {
-            filecount_script: "unknown".to_string(),
+            default_language: Some("rust".to_string()),
+            default_min_stars: Some(1),
+            default_repo_size: Some("large".to_string()),
+            default_sort: Some("stars".to_string()),
+            clone_depth: Some(1),
+            pane_command: Some("tmux new-window -c".to_string()),
+            ca_bundle: Some(PathBuf::from("ca.pem")),
+            github_app_id: Some(1),
+            github_app_key: Some(PathBuf::from("app.pem")),
+            github_app_installation_id: Some(2),
+            project_license: Some("MIT".to_string()),
+            analysis: BTreeMap::from([("rust".to_string(), AnalysisProfile { timeout_secs: Some(1), commands: Vec::new() })]),
+            ..Config::default()
         };
-        assert_eq!(config.get_filecount_script_path(), "filecount.sh");
+        let table = toml::Table::try_from(&config).unwrap();
+        let keys: Vec<&str> = table.keys().map(String::as_str).collect();
+        assert_eq!(keys, KEYS);
+    }
+
+    #[test]
+    fn test_invalid_settings_are_all_reported() {
+        let contents = "# Settings\nvim_mod = true\nclone_mode = \"fast\"\nclone_depth = 5\ncolour = 1\n";
+        let error = parse(contents, &[("MRKRABZ_CLONE_PARALLELISM", "many")]).unwrap_err().to_string();
+        assert_eq!(
+            error.lines().collect::<Vec<_>>(),
+            [
+                "Invalid configuration:",
+                "  config.toml line 2: unknown setting `vim_mod` (did you mean `vim_mode`?)",
+                "  config.toml line 3: invalid `clone_mode`: unknown variant `fast`, expected one of `full`, `blobless`, `treeless`",
+                "  config.toml line 5: unknown setting `colour`",
+                "  MRKRABZ_CLONE_PARALLELISM: invalid `clone_parallelism`: invalid type: string \"many\", expected usize",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_value_validation() {
+        let error = parse("clone_parallelism = 0\nsync_repos = [\"nope\"]", &[]).unwrap_err().to_string();
+        assert!(parse("result_columns = [\"name\", \"stars:x\"]", &[]).unwrap_err().to_string().contains(
+            "config.toml line 1: invalid `result_columns`: invalid width in \"stars:x\""
+        ));
+        assert!(error.contains("config.toml line 1: invalid `clone_parallelism`: must be at least 1"));
+        assert!(error.contains("config.toml line 2: invalid `sync_repos`: Invalid sync entry 'nope'"));
+
+        let error = parse("", &[("MRKRABZ_LOG_LEVEL", "loud=[")]).unwrap_err().to_string();
+        assert!(error.contains("MRKRABZ_LOG_LEVEL: invalid `log_level`"));
+
+        assert!(parse("count_exclude = [\"[\"]", &[]).is_err());
+        let error = parse("ca_bundle = \"/nonexistent/ca.pem\"", &[]).unwrap_err().to_string();
+        assert!(error.contains("config.toml line 1: invalid `ca_bundle`: Failed to open /nonexistent/ca.pem"));
+
+        let error = parse("github_app_id = 1
+github_app_installation_id = 2", &[]).unwrap_err().to_string();
+        assert!(error.contains("config.toml: invalid `github_app_key`: set it to the app's private key file too"));
+        let app = "github_app_id = 1\ngithub_app_key = \"tests/fixtures/app_key.pem\"";
+        assert!(parse(app, &[]).is_ok());
+        assert!(parse("github_app_installation_id = 2", &[]).unwrap_err().to_string().contains("only used with github_app_id"));
+        assert!(parse("project_license = \"MIT/Apache-2\"", &[]).is_ok());
+        assert!(parse("project_license = \"MIT OR Proprietary\"", &[]).unwrap_err().to_string().contains(
+            "config.toml line 1: invalid `project_license`: unknown license `Proprietary`"
+        ));
+        let (filters, _) = parse("default_language = \"rust\"\ndefault_repo_size = \"Large\"\ndefault_sort = \"stars\"", &[]).unwrap();
+        assert_eq!(filters.default_repo_size.as_deref(), Some("Large"));
+        let error = parse("default_repo_size = \"huge\"\ndefault_sort = \"best\"", &[]).unwrap_err().to_string();
+        assert!(error.contains("config.toml line 1: invalid `default_repo_size`: \"huge\" isn't a size (use small, medium, large)"));
+        assert!(error.contains("config.toml line 2: invalid `default_sort`: \"best\" isn't an order"));
+        assert!(parse("", &[]).is_ok());
+        assert!(parse("vim_mode = ", &[]).unwrap_err().to_string().starts_with("Failed to parse config.toml"));
+    }
+
+    #[test]
+    fn test_vim_mode_parses_from_toml() {
+        let config: Config = toml::from_str("vim_mode = true").unwrap();
+        assert!(config.vim_mode);
+    }
+
+    #[test]
+    fn test_clone_depth_parses_from_toml() {
+        let config: Config = toml::from_str("clone_depth = 10").unwrap();
+        assert_eq!(config.clone_depth, Some(10));
+    }
+
+    #[test]
+    fn test_clone_mode_parses_from_toml() {
+        let config: Config = toml::from_str("clone_mode = \"treeless\"").unwrap();
+        assert_eq!(config.clone_mode, CloneMode::Treeless);
+        assert_eq!(Config::default().clone_mode, CloneMode::Full);
+    }
+
+    #[test]
+    fn test_clone_parallelism_parses_from_toml() {
+        let config: Config = toml::from_str("clone_parallelism = 8").unwrap();
+        assert_eq!(config.clone_parallelism, 8);
+    }
+
+    #[test]
+    fn test_count_filter_from_toml() {
+        assert_eq!(Config::default().count_filter(), CountFilter::default());
+        let config: Config = toml::from_str(
+            "count_respect_gitignore = false\ncount_include_vendored = true\ncount_exclude = [\"docs/\"]",
+        )
+        .unwrap();
+        let filter = config.count_filter();
+        assert!(!filter.respect_gitignore);
+        assert!(filter.include_vendored);
+        assert_eq!(filter.exclude, ["docs/"]);
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let mut table: toml::Table = toml::from_str("vim_mode = false\nclone_depth = 5\nlog_level = \"warn\"").unwrap();
+        let vars = [
+            ("MRKRABZ_VIM_MODE", "true"),
+            ("MRKRABZ_CLONE_MODE", "blobless"),
+            ("MRKRABZ_CLONE_LAYOUT", "owner/name"),
+            ("MRKRABZ_COUNT_EXCLUDE", "[\"docs/\", \"*.min.js\"]"),
+            ("MRKRABZ_TOKEN", "not-a-setting"),
+            ("HOME", "/home/me"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        let used = apply_env(&mut table, vars);
+        assert_eq!(used.len(), 4); // MRKRABZ_TOKEN and HOME aren't settings
+        assert_eq!(used[0], ("clone_layout".to_string(), "MRKRABZ_CLONE_LAYOUT".to_string()));
+        let config: Config = toml::Value::Table(table).try_into().unwrap();
+        assert!(config.vim_mode);
+        assert_eq!(config.clone_depth, Some(5)); // Not overridden
+        assert_eq!(config.log_level, "warn");
+        assert_eq!(config.clone_mode, CloneMode::Blobless);
+        assert_eq!(config.clone_layout, CloneLayout::OwnerName);
+        assert_eq!(config.count_exclude, ["docs/", "*.min.js"]);
+
+        let mut table = toml::Table::new();
+        apply_env(&mut table, [("MRKRABZ_CLONE_PARALLELISM".to_string(), "many".to_string())]);
+        assert!(toml::Value::Table(table).try_into::<Config>().is_err());
+    }
+
+    #[test]
+    fn test_analysis_profiles() {
+        let contents = "[analysis.rust]\ntimeout_secs = 60\ncommands = [{ name = \"Dependencies\", run = \"cargo tree --depth 1\" }]\n\n[analysis.node]\ncommands = [{ name = \"Outdated\", run = \"npm outdated\" }]\n";
+        let (config, _) = parse(contents, &[]).unwrap();
+        assert_eq!(config.analysis["rust"].timeout_secs, Some(60));
+        assert_eq!(config.analysis["rust"].commands[0].run, "cargo tree --depth 1");
+        assert_eq!(config.analysis["node"].timeout_secs, None);
+        assert_eq!(config.script_timeout_secs, 120);
+
+        let contents = "script_timeout_secs = 30\n[analysis.rust]\ncommands = [{ name = \"Tree\", run = \"cargo tree\", args = [\"--edges\", \"normal\"], timeout_secs = 10 }]";
+        let (config, _) = parse(contents, &[]).unwrap();
+        assert_eq!(config.script_timeout_secs, 30);
+        assert_eq!(config.analysis["rust"].commands[0].args, ["--edges", "normal"]);
+        assert_eq!(config.analysis["rust"].commands[0].timeout_secs, Some(10));
+        assert!(parse("script_timeout_secs = 0", &[]).unwrap_err().to_string().contains("must be at least 1"));
+
+        let error = parse("[analysis.rust]\ncommands = []", &[]).unwrap_err().to_string();
+        assert!(error.contains("config.toml: invalid `analysis`: [analysis.rust] has no commands"));
+        assert!(parse("[analysis.rust]\ncommands = [{ name = \"x\", command = \"ls\" }]", &[]).is_err());
+    }
+
+    #[test]
+    fn test_clone_layout() {
+        let config: Config = toml::from_str("clone_layout = \"owner/name\"").unwrap();
+        assert_eq!(config.clone_layout, CloneLayout::OwnerName);
+        assert_eq!(config.clone_layout.path("foo", "awesome"), PathBuf::from("foo/awesome"));
+        assert_eq!(CloneLayout::Name.path("foo", "awesome"), PathBuf::from("awesome"));
+        assert!(toml::from_str::<Config>("clone_layout = \"flat\"").is_err());
     }
 }
@@ -0,0 +1,217 @@
+use git2::{ErrorClass, ErrorCode};
+use std::fmt;
+
+use crate::i18n::tr;
+
+/// CLI mode exit codes, so scripts can tell outcomes apart (1 is any other error, 2 a usage error)
+pub const EXIT_NO_RESULTS: u8 = 3; // Only with --fail-if-empty
+pub const EXIT_RATE_LIMITED: u8 = 4;
+pub const EXIT_AUTH: u8 = 5;
+
+/// Why a search, clone, or update failed, with enough detail for the TUI to suggest a fix
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Cancelled by the user
+    Cancelled,
+    /// A prefetch was skipped to leave the rest of the rate budget to requests the user asked for
+    Deferred,
+    /// The search API quota is used up; `reset` is when it refills (Unix seconds), if known
+    RateLimited { reset: Option<u64> },
+    /// GitHub asked for credentials: a bad token, or a private or missing repository
+    AuthRequired,
+    /// The repository or resource doesn't exist
+    NotFound,
+    /// GitHub rejected the search query
+    InvalidQuery(String),
+    /// Connection, TLS, or HTTP failure
+    Network(String),
+    /// git (libgit2 or the binary) failed; `stderr` is its last error line
+    GitFailed { stderr: String },
+    /// The `git` binary, needed for partial clones, couldn't be run
+    GitMissing(String),
+    /// GitHub won't list something this large, e.g. the contributors of a very large history
+    TooLarge,
+    /// Anything else
+    Other(String),
+}
+
+impl Error {
+    /// What the user can do about it, shown under the error in the TUI
+    pub fn guidance(&self) -> Option<String> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.guidance_at(now)
+    }
+
+    fn guidance_at(&self, now: u64) -> Option<String> {
+        let hint = match self {
+            Error::RateLimited { reset: Some(reset) } => tr!("hint-rate-limited-retry", seconds = reset.saturating_sub(now)),
+            Error::RateLimited { reset: None } => tr!("hint-rate-limited"),
+            Error::AuthRequired => tr!("hint-auth-required"),
+            Error::NotFound => tr!("hint-not-found"),
+            Error::InvalidQuery(_) => tr!("hint-invalid-query"),
+            Error::Network(message) if message.contains("certificate") => tr!("hint-certificate"),
+            Error::Network(_) => tr!("hint-network"),
+            Error::GitMissing(_) => tr!("hint-git-missing"),
+            Error::Cancelled | Error::Deferred | Error::GitFailed { .. } | Error::TooLarge | Error::Other(_) => return None,
+        };
+        Some(hint)
+    }
+
+    /// Process exit code when a CLI search fails with this error
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::RateLimited { .. } => EXIT_RATE_LIMITED,
+            Error::AuthRequired => EXIT_AUTH,
+            _ => 1,
+        }
+    }
+
+    /// The message followed by its guidance, for toasts and the jobs panel
+    pub fn with_guidance(&self) -> String {
+        match self.guidance() {
+            Some(hint) => format!("{}. {}", self, hint),
+            None => self.to_string(),
+        }
+    }
+
+    /// Classify a GitHub API error response
+    pub(crate) fn from_status(status: u16, message: String) -> Self {
+        match status {
+            401 => Error::AuthRequired,
+            403 if message.to_lowercase().contains("rate limit") => Error::RateLimited { reset: None },
+            403 if message.contains("too large") => Error::TooLarge,
+            429 => Error::RateLimited { reset: None },
+            404 => Error::NotFound,
+            422 => Error::InvalidQuery(message),
+            _ => Error::Other(message),
+        }
+    }
+}
+
+/// Message for an anyhow error, with guidance when it wraps an `Error`
+pub fn describe(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<Error>() {
+        Some(error) => error.with_guidance(),
+        None => format!("{:#}", error),
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Error::Cancelled => tr!("error-cancelled"),
+            Error::Deferred => tr!("error-deferred"),
+            Error::RateLimited { .. } => tr!("error-rate-limited"),
+            Error::AuthRequired => tr!("error-auth-required"),
+            Error::NotFound => tr!("error-not-found"),
+            Error::InvalidQuery(message) => tr!("error-invalid-query", message = message.as_str()),
+            Error::Network(message) => tr!("error-network", message = message.as_str()),
+            Error::GitFailed { stderr } => tr!("error-git-failed", stderr = stderr.as_str()),
+            Error::GitMissing(message) => tr!("error-git-missing", message = message.as_str()),
+            Error::TooLarge => tr!("error-too-large"),
+            Error::Other(message) => return write!(f, "{}", message),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<octocrab::Error> for Error {
+    fn from(error: octocrab::Error) -> Self {
+        match error {
+            octocrab::Error::GitHub { source, .. } => Error::from_status(source.status_code.as_u16(), source.message),
+            // Only the causes: octocrab's own Display appends a backtrace
+            octocrab::Error::Service { source, .. } => Error::Network(causes(source.as_ref())),
+            octocrab::Error::Hyper { source, .. } => Error::Network(causes(&source)),
+            other => Error::Other(other.to_string()),
+        }
+    }
+}
+
+/// An error and its sources, e.g. "client error (Connect): invalid peer certificate: UnknownIssuer"
+fn causes(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(error) = source {
+        message.push_str(&format!(": {}", error));
+        source = error.source();
+    }
+    message
+}
+
+impl From<git2::Error> for Error {
+    fn from(error: git2::Error) -> Self {
+        match (error.code(), error.class()) {
+            // Returned when a callback (our cancel check) aborts the transfer
+            (ErrorCode::User, _) => Error::Cancelled,
+            (ErrorCode::Auth, _) => Error::AuthRequired,
+            (_, ErrorClass::Net | ErrorClass::Http | ErrorClass::Ssl) => Error::Network(error.message().to_string()),
+            _ => Error::GitFailed { stderr: error.message().to_string() },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_git2_error_mapping() {
+        let cancelled = git2::Error::new(ErrorCode::User, ErrorClass::Callback, "stop");
+        assert_eq!(Error::from(cancelled), Error::Cancelled);
+
+        let auth = git2::Error::new(ErrorCode::Auth, ErrorClass::Http, "authentication required");
+        assert_eq!(Error::from(auth), Error::AuthRequired);
+
+        let network = git2::Error::new(ErrorCode::GenericError, ErrorClass::Net, "connection refused");
+        assert_eq!(Error::from(network), Error::Network("connection refused".to_string()));
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let status = |code, message: &str| Error::from_status(code, message.to_string());
+        assert_eq!(status(401, "Bad credentials"), Error::AuthRequired);
+        assert_eq!(status(403, "API rate limit exceeded for 1.2.3.4"), Error::RateLimited { reset: None });
+        assert_eq!(status(429, "Too many requests"), Error::RateLimited { reset: None });
+        assert_eq!(status(403, "Forbidden"), Error::Other("Forbidden".to_string()));
+        let too_large = "The history or contributor list is too large to list contributors for this repository via the API.";
+        assert_eq!(status(403, too_large), Error::TooLarge);
+        assert_eq!(status(404, "Not Found"), Error::NotFound);
+        assert_eq!(status(422, "Validation Failed"), Error::InvalidQuery("Validation Failed".to_string()));
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(Error::RateLimited { reset: Some(1) }.exit_code(), EXIT_RATE_LIMITED);
+        assert_eq!(Error::AuthRequired.exit_code(), EXIT_AUTH);
+        assert_eq!(Error::Network("timed out".to_string()).exit_code(), 1);
+        let codes = [1, 2, EXIT_NO_RESULTS, EXIT_RATE_LIMITED, EXIT_AUTH];
+        assert!(codes.iter().enumerate().all(|(i, code)| !codes[i + 1..].contains(code)));
+    }
+
+    #[test]
+    fn test_guidance() {
+        let limited = Error::RateLimited { reset: Some(1_042) };
+        assert_eq!(
+            limited.guidance_at(1_000).unwrap(),
+            "Retry in 42s, or add a token (-t or GITHUB_TOKEN) for a higher limit"
+        );
+        assert!(Error::AuthRequired.guidance().unwrap().contains("token"));
+        assert!(Error::GitFailed { stderr: "boom".to_string() }.guidance().is_none());
+
+        let network = Error::Network("client error (Connect)".to_string());
+        assert_eq!(
+            network.with_guidance(),
+            "network error: client error (Connect). Check your internet connection and try again"
+        );
+        assert_eq!(describe(&anyhow::Error::new(network.clone())), network.with_guidance());
+        assert_eq!(describe(&anyhow::anyhow!("plain")), "plain");
+
+        let untrusted = Error::Network("client error (Connect): invalid peer certificate: UnknownIssuer".to_string());
+        assert!(untrusted.guidance().unwrap().contains("ca_bundle"));
+    }
+}
@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use octocrab::models::Repository;
+use std::fs;
+use std::path::Path;
+
+use crate::clones;
+use crate::columns;
+
+/// File format of an export, picked by the file's extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Markdown,
+    Json,
+    Csv,
+}
+
+impl Format {
+    /// .json and .csv files get those formats; anything else is markdown
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()).map(str::to_lowercase).as_deref() {
+            Some("json") => Format::Json,
+            Some("csv") => Format::Csv,
+            _ => Format::Markdown,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Format::Markdown => "markdown",
+            Format::Json => "JSON",
+            Format::Csv => "CSV",
+        }
+    }
+}
+
+/// `repos` from a search for `query` (`total` matches) in `format`
+pub fn render(format: Format, query: &str, total: u64, repos: &[Repository]) -> Result<String> {
+    match format {
+        Format::Markdown => Ok(markdown(query, total, repos)),
+        Format::Json => json(repos),
+        Format::Csv => Ok(csv(repos)),
+    }
+}
+
+/// One JSON object per repository, with the fields the other formats have plus forks, language, and size
+fn json(repos: &[Repository]) -> Result<String> {
+    let items: Vec<serde_json::Value> = repos
+        .iter()
+        .map(|repo| {
+            serde_json::json!({
+                "full_name": repo.full_name.as_deref().unwrap_or(&repo.name),
+                "url": repo.html_url.as_ref().map(|url| url.to_string()),
+                "description": repo.description,
+                "stars": repo.stargazers_count.unwrap_or(0),
+                "forks": repo.forks_count.unwrap_or(0),
+                "language": repo.language.as_ref().and_then(|language| language.as_str()),
+                "license": repo.license.as_ref().map(|license| license.spdx_id.as_str()),
+                "size_kb": repo.size.unwrap_or(0),
+                "pushed_at": repo.pushed_at.or(repo.updated_at).map(|date| date.to_rfc3339()),
+            })
+        })
+        .collect();
+    let mut json = serde_json::to_string_pretty(&items).context("Failed to serialize the results")?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// A header row, then one row per repository; exact counts, for spreadsheets
+fn csv(repos: &[Repository]) -> String {
+    let mut csv = String::from("full_name,url,description,stars,forks,language,license,pushed_at\n");
+    for repo in repos {
+        let fields = [
+            repo.full_name.clone().unwrap_or_else(|| repo.name.clone()),
+            repo.html_url.as_ref().map(|url| url.to_string()).unwrap_or_default(),
+            repo.description.clone().unwrap_or_default(),
+            repo.stargazers_count.unwrap_or(0).to_string(),
+            repo.forks_count.unwrap_or(0).to_string(),
+            repo.language.as_ref().and_then(|language| language.as_str()).unwrap_or("").to_string(),
+            repo.license.as_ref().map(|license| license.spdx_id.clone()).unwrap_or_default(),
+            last_push(repo),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// `field` quoted when it holds a comma, quote, or line break (RFC 4180)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Markdown report of `repos` from a search for `query` (`total` matches on GitHub):
+/// a heading, then a table of name and link, stars, license, last push, and description
+pub fn markdown(query: &str, total: u64, repos: &[Repository]) -> String {
+    let mut report = format!("# GitHub repositories: {}\n\n", cell(query));
+    report.push_str(&format!("{} repositories match; {} listed.\n\n", total, repos.len()));
+    report.push_str("| Repository | Stars | License | Last push | Description |\n");
+    report.push_str("|------------|------:|---------|-----------|-------------|\n");
+    for repo in repos {
+        let name = repo.full_name.as_deref().unwrap_or(&repo.name);
+        let link = match &repo.html_url {
+            Some(url) => format!("[{}]({})", cell(name), url),
+            None => cell(name),
+        };
+        report.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            link,
+            clones::format_count(repo.stargazers_count.unwrap_or(0) as u64),
+            cell(&columns::license_label(repo)),
+            last_push(repo),
+            cell(repo.description.as_deref().unwrap_or("")),
+        ));
+    }
+    report
+}
+
+/// Date of the last push (or update, when GitHub doesn't say), e.g. "2024-05-01"
+fn last_push(repo: &Repository) -> String {
+    repo.pushed_at
+        .or(repo.updated_at)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// `text` made safe for a table cell: no pipes or line breaks
+fn cell(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").replace('|', "\\|")
+}
+
+/// Markdown file name for a report on `query`, e.g. "rust-game.md"
+pub fn file_name(query: &str) -> String {
+    let slug: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+    if slug.is_empty() {
+        "results.md".to_string()
+    } else {
+        format!("{}.md", slug.join("-"))
+    }
+}
+
+/// Write an export to `path`, creating its directory if needed
+pub fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock;
+
+    fn repo(description: &str) -> Repository {
+        mock::repo("rust-lang/rust")
+            .description(description)
+            .stars(98000)
+            .pushed_at("2024-05-01T12:00:00Z")
+            .set("license", serde_json::json!({ "key": "mit", "name": "MIT License", "spdx_id": "MIT", "node_id": "", "html_url": null }))
+            .build()
+    }
+
+    #[test]
+    fn test_markdown() {
+        let report = markdown("rust | lang", 642, &[repo("Fast,\nreliable | productive")]);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "# GitHub repositories: rust \\| lang");
+        assert_eq!(lines[2], "642 repositories match; 1 listed.");
+        assert_eq!(
+            lines[6],
+            "| [rust-lang/rust](https://github.com/rust-lang/rust) | 98k | MIT | 2024-05-01 | Fast, reliable \\| productive |"
+        );
+        assert_eq!(lines.len(), 7);
+    }
+
+    #[test]
+    fn test_json_and_csv() {
+        let repos = [repo("Fast, \"reliable\"")];
+        let json: serde_json::Value = serde_json::from_str(&render(Format::Json, "rust", 1, &repos).unwrap()).unwrap();
+        assert_eq!(json[0]["full_name"], "rust-lang/rust");
+        assert_eq!(json[0]["stars"], 98000);
+        assert_eq!(json[0]["license"], "MIT");
+        assert_eq!(json[0]["pushed_at"], "2024-05-01T12:00:00+00:00");
+
+        let csv = render(Format::Csv, "rust", 1, &repos).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "full_name,url,description,stars,forks,language,license,pushed_at");
+        assert_eq!(
+            lines[1],
+            "rust-lang/rust,https://github.com/rust-lang/rust,\"Fast, \"\"reliable\"\"\",98000,0,,MIT,2024-05-01"
+        );
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path(Path::new("out/results.JSON")), Format::Json);
+        assert_eq!(Format::from_path(Path::new("results.csv")), Format::Csv);
+        assert_eq!(Format::from_path(Path::new("report.md")), Format::Markdown);
+        assert_eq!(Format::from_path(Path::new("report")), Format::Markdown);
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name("rust game"), "rust-game.md");
+        assert_eq!(file_name("Web language:Go"), "web-language-go.md");
+        assert_eq!(file_name("  "), "results.md");
+    }
+
+    #[test]
+    fn test_write_creates_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports/rust.md");
+        write(&path, "# report\n").unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "# report\n");
+    }
+}
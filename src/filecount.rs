@@ -0,0 +1,296 @@
+use anyhow::Result;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::clones::format_size;
+use crate::github::GitHub;
+use crate::i18n::tr;
+
+/// Dependency, virtualenv, and build output directories skipped unless `include_vendored` is set
+pub const VENDORED_DIRS: &[&str] = &[
+    "node_modules", ".next", "dist", "build", "target", "venv", ".venv", "env", ".idea", "vendor", "__pycache__",
+];
+
+/// Which files count toward file counts and language analysis
+#[derive(Debug, Clone, PartialEq)]
+pub struct CountFilter {
+    pub respect_gitignore: bool, // Skip files matched by .gitignore
+    pub include_vendored: bool,  // Count VENDORED_DIRS too
+    pub exclude: Vec<String>,    // Extra gitignore-style globs to skip, e.g. "*.min.js" or "docs/"
+}
+
+impl Default for CountFilter {
+    fn default() -> Self {
+        CountFilter { respect_gitignore: true, include_vendored: false, exclude: Vec::new() }
+    }
+}
+
+impl CountFilter {
+    /// Globs excluded from counting (.git is always skipped)
+    pub fn excluded_globs(&self) -> Vec<String> {
+        let vendored = VENDORED_DIRS.iter().filter(|_| !self.include_vendored).map(|dir| dir.to_string());
+        std::iter::once(".git".to_string()).chain(vendored).chain(self.exclude.iter().cloned()).collect()
+    }
+
+    /// Excluded globs as ignore-crate overrides rooted at `root`
+    fn overrides(&self, root: &Path) -> Result<Override> {
+        let mut builder = OverrideBuilder::new(root);
+        for glob in self.excluded_globs() {
+            builder.add(&format!("!{}", glob))?;
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// File count and size for one extension
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ExtensionStats {
+    pub extension: String, // Lowercase, without the dot; "" for files without one
+    pub files: u64,
+    pub size: u64,
+}
+
+/// Files in a repository checkout, grouped by extension (largest count first)
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FileStats {
+    pub total_files: u64,
+    pub total_size: u64,
+    pub extensions: Vec<ExtensionStats>,
+}
+
+/// Walk `root` and count files per extension (symlinks are not followed)
+/// Errors only if an exclude glob is invalid
+pub fn count_files(root: &Path, filter: &CountFilter) -> Result<FileStats> {
+    let walker = WalkBuilder::new(root)
+        .standard_filters(false)
+        .git_ignore(filter.respect_gitignore)
+        .git_exclude(filter.respect_gitignore)
+        .require_git(false)
+        .overrides(filter.overrides(root)?)
+        .build();
+    let files = walker
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_some_and(|file_type| file_type.is_file()))
+        .map(|entry| {
+            let size = entry.metadata().map_or(0, |metadata| metadata.len());
+            (entry.into_path(), size)
+        });
+    Ok(FileStats::from_files(files))
+}
+
+/// Response of `GET /repos/{owner}/{repo}/git/trees/{sha}?recursive=1`
+#[derive(Debug, Deserialize)]
+pub struct Tree {
+    tree: Vec<TreeEntry>,
+    truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    path: String,
+    mode: String,
+    #[serde(rename = "type")]
+    kind: String,
+    size: Option<u64>,
+}
+
+/// Count files from the repository's default-branch tree via the API, without cloning
+/// Returns None when GitHub truncates the tree (too many entries), so the caller can clone instead
+/// The tree lists tracked files only, so .gitignore has nothing left to exclude
+pub async fn count_files_from_api(
+    github: &dyn GitHub,
+    owner: &str,
+    name: &str,
+    filter: &CountFilter,
+) -> Result<Option<FileStats>> {
+    stats_from_tree(github.tree(owner, name).await?, filter)
+}
+
+fn stats_from_tree(tree: Tree, filter: &CountFilter) -> Result<Option<FileStats>> {
+    if tree.truncated {
+        return Ok(None);
+    }
+    let overrides = filter.overrides(Path::new(""))?;
+    // Blobs only (no submodule commits), skipping symlinks (mode 120000) like the directory walk does
+    let files = tree
+        .tree
+        .into_iter()
+        .filter(|entry| entry.kind == "blob" && entry.mode != "120000")
+        .filter(|entry| !is_excluded(&overrides, Path::new(&entry.path)))
+        .map(|entry| (entry.path.into(), entry.size.unwrap_or(0)));
+    Ok(Some(FileStats::from_files(files)))
+}
+
+/// Whether a file or any directory above it matches an exclude glob
+fn is_excluded(overrides: &Override, path: &Path) -> bool {
+    overrides.matched(path, false).is_ignore()
+        || path
+            .ancestors()
+            .skip(1)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .any(|dir| overrides.matched(dir, true).is_ignore())
+}
+
+impl FileStats {
+    /// Group (path, size) pairs by lowercase extension
+    fn from_files(files: impl IntoIterator<Item = (std::path::PathBuf, u64)>) -> Self {
+        let mut by_extension: HashMap<String, (u64, u64)> = HashMap::new();
+        let mut stats = FileStats::default();
+        for (path, size) in files {
+            let extension = path
+                .extension()
+                .map_or_else(String::new, |extension| extension.to_string_lossy().to_lowercase());
+            let counts = by_extension.entry(extension).or_default();
+            counts.0 += 1;
+            counts.1 += size;
+            stats.total_files += 1;
+            stats.total_size += size;
+        }
+
+        stats.extensions = by_extension
+            .into_iter()
+            .map(|(extension, (files, size))| ExtensionStats { extension, files, size })
+            .collect();
+        stats
+            .extensions
+            .sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.extension.cmp(&b.extension)));
+        stats
+    }
+}
+
+/// Report shown in the details panel, one extension per line
+impl fmt::Display for FileStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", tr!("files-by-extension"))?;
+        writeln!(f)?;
+        for stats in &self.extensions {
+            let extension = if stats.extension.is_empty() { tr!("files-no-extension") } else { format!(".{}", stats.extension) };
+            let line = tr!("files-extension", extension = extension, count = stats.files, size = format_size(stats.size));
+            writeln!(f, "  {}", line)?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", tr!("files-total", count = self.total_files, size = format_size(self.total_size)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_count_files_groups_by_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::create_dir_all(root.join(".git/objects")).unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("src/nested/lib.RS"), "pub fn f() {}").unwrap();
+        fs::write(root.join("README.md"), "# hi").unwrap();
+        fs::write(root.join("Makefile"), "all:").unwrap();
+        fs::write(root.join(".git/objects/blob"), "skipped").unwrap();
+        fs::write(root.join("node_modules/pkg/index.js"), "skipped").unwrap();
+
+        let stats = count_files(root, &CountFilter::default()).unwrap();
+        assert_eq!(stats.total_files, 4);
+        assert_eq!(stats.total_size, 12 + 13 + 4 + 4);
+        assert_eq!(stats.extensions[0], ExtensionStats { extension: "rs".to_string(), files: 2, size: 25 });
+        let extensions: Vec<&str> = stats.extensions.iter().map(|stats| stats.extension.as_str()).collect();
+        assert_eq!(extensions, ["rs", "", "md"]);
+    }
+
+    #[test]
+    fn test_stats_from_tree() {
+        let tree: Tree = serde_json::from_str(
+            r#"{
+                "sha": "abc",
+                "truncated": false,
+                "tree": [
+                    {"path": "src", "mode": "040000", "type": "tree", "sha": "1"},
+                    {"path": "src/main.rs", "mode": "100644", "type": "blob", "sha": "2", "size": 120},
+                    {"path": "src/lib.rs", "mode": "100644", "type": "blob", "sha": "3", "size": 80},
+                    {"path": "README.md", "mode": "100644", "type": "blob", "sha": "4", "size": 10},
+                    {"path": "link.md", "mode": "120000", "type": "blob", "sha": "5", "size": 7},
+                    {"path": "vendor/dep", "mode": "160000", "type": "commit", "sha": "6"},
+                    {"path": "web/node_modules/x/index.js", "mode": "100644", "type": "blob", "sha": "7", "size": 5}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let filter = CountFilter::default();
+        let stats = stats_from_tree(tree, &filter).unwrap().unwrap();
+        assert_eq!(stats.total_files, 3);
+        assert_eq!(stats.total_size, 210);
+        assert_eq!(stats.extensions[0], ExtensionStats { extension: "rs".to_string(), files: 2, size: 200 });
+
+        let truncated: Tree = serde_json::from_str(r#"{"tree": [], "truncated": true}"#).unwrap();
+        assert!(stats_from_tree(truncated, &filter).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_count_files_from_api() {
+        let github = crate::github::mock::MockGitHub::new();
+        let stats = count_files_from_api(&github, "rust-lang", "rust", &CountFilter::default()).await.unwrap().unwrap();
+        assert_eq!((stats.total_files, stats.total_size), (3, 9200));
+    }
+
+    #[test]
+    fn test_count_filter_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("debug.log"), "ignored").unwrap();
+        fs::write(root.join("target/debug/app.d"), "vendored").unwrap();
+        fs::write(root.join("docs/guide.md"), "excluded").unwrap();
+        fs::write(root.join("app.min.js"), "excluded").unwrap();
+
+        let count = |filter: &CountFilter| count_files(root, filter).unwrap().total_files;
+        let default = CountFilter::default();
+        assert_eq!(count(&default), 4); // .gitignore, main.rs, docs/guide.md, app.min.js
+
+        let excluding = CountFilter { exclude: vec!["docs/".to_string(), "*.min.js".to_string()], ..default.clone() };
+        assert_eq!(count(&excluding), 2);
+
+        let everything = CountFilter { respect_gitignore: false, include_vendored: true, exclude: Vec::new() };
+        assert_eq!(count(&everything), 6);
+
+        let invalid = CountFilter { exclude: vec!["[".to_string()], ..default };
+        assert!(count_files(root, &invalid).is_err());
+    }
+
+    #[test]
+    fn test_tree_exclusions() {
+        let filter = CountFilter { exclude: vec!["docs/".to_string()], ..Default::default() };
+        let overrides = filter.overrides(Path::new("")).unwrap();
+        assert!(is_excluded(&overrides, Path::new("docs/a/b.md")));
+        assert!(is_excluded(&overrides, Path::new("web/node_modules/x.js")));
+        assert!(!is_excluded(&overrides, Path::new("src/docs.rs")));
+
+        let vendored = CountFilter { include_vendored: true, ..Default::default() };
+        assert!(!is_excluded(&vendored.overrides(Path::new("")).unwrap(), Path::new("node_modules/x.js")));
+    }
+
+    #[test]
+    fn test_report_format() {
+        let stats = FileStats {
+            total_files: 3,
+            total_size: 2048,
+            extensions: vec![
+                ExtensionStats { extension: "rs".to_string(), files: 2, size: 2000 },
+                ExtensionStats { extension: String::new(), files: 1, size: 48 },
+            ],
+        };
+        assert_eq!(
+            stats.to_string(),
+            "Files by extension:\n\n  .rs: 2 files | 2.0 KiB\n  (none): 1 file | 48 B\n\nTotal: 3 files, 2.0 KiB"
+        );
+    }
+}
@@ -0,0 +1,676 @@
+use git2::build::{CheckoutBuilder, RepoBuilder};
+use git2::{ErrorClass, ErrorCode, FetchOptions, RemoteCallbacks};
+
+use crate::error::Error;
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+/// A snapshot of clone progress
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloneProgress {
+    pub phase: String,        // e.g. "Receiving objects"
+    pub percent: u8,
+    pub current: u64,
+    pub total: u64,
+    pub rate: Option<String>, // Transfer rate, e.g. "2.30 MiB/s" (only while receiving)
+}
+
+/// Which objects a clone downloads up front
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloneMode {
+    /// Every commit, tree, and blob
+    #[default]
+    Full,
+    /// Commits and trees only; file contents are fetched on demand (`--filter=blob:none`)
+    Blobless,
+    /// Commits only; trees and blobs are fetched on demand (`--filter=tree:0`)
+    Treeless,
+}
+
+impl CloneMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            CloneMode::Full => "full",
+            CloneMode::Blobless => "blobless",
+            CloneMode::Treeless => "treeless",
+        }
+    }
+
+    /// Partial clone filter passed to git, if any
+    fn filter(self) -> Option<&'static str> {
+        match self {
+            CloneMode::Full => None,
+            CloneMode::Blobless => Some("blob:none"),
+            CloneMode::Treeless => Some("tree:0"),
+        }
+    }
+
+    /// Next mode in the clone prompt's cycle
+    pub fn next(self) -> Self {
+        match self {
+            CloneMode::Full => CloneMode::Blobless,
+            CloneMode::Blobless => CloneMode::Treeless,
+            CloneMode::Treeless => CloneMode::Full,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        self.next().next()
+    }
+}
+
+/// How much of a repository to fetch
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CloneOptions {
+    pub depth: Option<u32>, // Shallow clone with this many commits; None fetches full history
+    pub mode: CloneMode,
+    pub submodules: bool,   // Also clone submodules, recursively
+}
+
+/// Sets the shared cancel flag when the clone future is dropped,
+/// so the blocking libgit2 transfer stops at its next progress callback
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Clone a repository with the embedded git implementation (no `git` binary needed)
+/// Partial (blobless/treeless) clones aren't supported by libgit2 and use the `git` binary instead
+/// `on_progress` is called whenever the percentage or phase changes
+/// Dropping the returned future cancels the transfer and removes the partial clone
+pub async fn clone(
+    url: &str,
+    dest: &Path,
+    options: &CloneOptions,
+    on_progress: impl FnMut(CloneProgress),
+) -> Result<(), Error> {
+    if options.mode.filter().is_some() {
+        return clone_with_cli(url, dest, options, on_progress).await;
+    }
+
+    let url = url.to_string();
+    let dest = dest.to_path_buf();
+    let options = options.clone();
+    run_blocking(on_progress, move |cancelled, tx| clone_blocking(&url, &dest, &options, cancelled, tx)).await
+}
+
+/// What updating an existing clone did
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateOutcome {
+    /// Nothing new on the remote
+    UpToDate,
+    /// The checked-out branch moved forward to its upstream
+    FastForwarded,
+    /// New commits were fetched but the branch was left alone (local changes, diverged, or detached)
+    FetchedOnly,
+}
+
+impl fmt::Display for UpdateOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateOutcome::UpToDate => write!(f, "{}", tr!("update-up-to-date")),
+            UpdateOutcome::FastForwarded => write!(f, "{}", tr!("update-fast-forwarded")),
+            UpdateOutcome::FetchedOnly => write!(f, "{}", tr!("update-fetched-only")),
+        }
+    }
+}
+
+/// Fetch an existing clone's origin and fast-forward the checked-out branch when possible
+/// Partial clones are updated with `git pull --ff-only`, since libgit2 can't fetch into them
+pub async fn update(path: &Path, on_progress: impl FnMut(CloneProgress)) -> Result<UpdateOutcome, Error> {
+    if is_partial_clone(path) {
+        return update_with_cli(path, on_progress).await;
+    }
+
+    let path = path.to_path_buf();
+    run_blocking(on_progress, move |cancelled, tx| update_blocking(&path, cancelled, tx)).await
+}
+
+/// Run blocking libgit2 work on the blocking pool, forwarding its progress to `on_progress`
+/// Dropping the returned future sets the cancel flag passed to `work`
+async fn run_blocking<T: Send + 'static>(
+    mut on_progress: impl FnMut(CloneProgress),
+    work: impl FnOnce(&AtomicBool, mpsc::UnboundedSender<CloneProgress>) -> Result<T, Error> + Send + 'static,
+) -> Result<T, Error> {
+    let cancel = CancelOnDrop(Arc::new(AtomicBool::new(false)));
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    let flag = cancel.0.clone();
+    let task = tokio::task::spawn_blocking(move || work(&flag, tx));
+
+    // Forward progress until the blocking work finishes and drops its sender
+    while let Some(progress) = rx.recv().await {
+        on_progress(progress);
+    }
+
+    task.await.map_err(|e| Error::GitFailed { stderr: e.to_string() })?
+}
+
+/// Remote callbacks that report transfer progress (on phase/percent changes) and abort once cancelled
+fn transfer_callbacks<'a>(
+    cancelled: &'a AtomicBool,
+    tx: mpsc::UnboundedSender<CloneProgress>,
+) -> RemoteCallbacks<'a> {
+    let start = Instant::now();
+    let mut last_sent: Option<(String, u8)> = None;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.transfer_progress(move |stats| {
+        if cancelled.load(Ordering::Relaxed) {
+            return false; // Aborts the fetch with ErrorCode::User
+        }
+        let progress = transfer_progress(
+            stats.received_objects() as u64,
+            stats.total_objects() as u64,
+            stats.indexed_deltas() as u64,
+            stats.total_deltas() as u64,
+            stats.received_bytes() as u64,
+            start.elapsed(),
+        );
+        let key = (progress.phase.clone(), progress.percent);
+        if last_sent.as_ref() != Some(&key) {
+            last_sent = Some(key);
+            let _ = tx.send(progress);
+        }
+        true
+    });
+    callbacks
+}
+
+fn clone_blocking(
+    url: &str,
+    dest: &Path,
+    options: &CloneOptions,
+    cancelled: &AtomicBool,
+    tx: mpsc::UnboundedSender<CloneProgress>,
+) -> Result<(), Error> {
+    let existed = dest.exists();
+
+    let mut fetch = FetchOptions::new();
+    fetch.remote_callbacks(transfer_callbacks(cancelled, tx.clone()));
+    if let Some(depth) = options.depth {
+        fetch.depth(depth as i32);
+    }
+
+    let mut last_percent = None;
+    let mut checkout = CheckoutBuilder::new();
+    checkout.progress(|_path, current, total| {
+        let percent = percent_of(current as u64, total as u64);
+        if last_percent != Some(percent) {
+            last_percent = Some(percent);
+            let _ = tx.send(CloneProgress {
+                phase: "Updating files".to_string(),
+                percent,
+                current: current as u64,
+                total: total as u64,
+                rate: None,
+            });
+        }
+    });
+
+    let result = RepoBuilder::new()
+        .fetch_options(fetch)
+        .with_checkout(checkout)
+        .clone(url, dest)
+        .and_then(|repo| {
+            if options.submodules {
+                update_submodules(&repo, cancelled, &tx)
+            } else {
+                Ok(())
+            }
+        });
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            // Don't leave a half-written clone behind to block the next attempt
+            if !existed {
+                let _ = std::fs::remove_dir_all(dest);
+            }
+            Err(e.into())
+        }
+    }
+}
+
+fn update_blocking(
+    path: &Path,
+    cancelled: &AtomicBool,
+    tx: mpsc::UnboundedSender<CloneProgress>,
+) -> Result<UpdateOutcome, Error> {
+    let repo = git2::Repository::open(path)?;
+    let mut remote = repo.find_remote("origin")?;
+    let mut fetch = FetchOptions::new();
+    fetch.remote_callbacks(transfer_callbacks(cancelled, tx));
+    // Empty refspecs use the remote's configured ones
+    remote.fetch::<&str>(&[], Some(&mut fetch), None)?;
+
+    let head = repo.head()?;
+    if !head.is_branch() {
+        return Ok(UpdateOutcome::FetchedOnly);
+    }
+    let Ok(upstream) = git2::Branch::wrap(head).upstream() else {
+        return Ok(UpdateOutcome::FetchedOnly);
+    };
+    let target = repo.reference_to_annotated_commit(upstream.get())?;
+    let (analysis, _) = repo.merge_analysis(&[&target])?;
+
+    if analysis.is_up_to_date() {
+        Ok(UpdateOutcome::UpToDate)
+    } else if analysis.is_fast_forward() {
+        // Check out the new tree first so local changes block the update instead of being overwritten
+        let commit = repo.find_commit(target.id())?;
+        repo.checkout_tree(commit.as_object(), Some(CheckoutBuilder::new().safe()))?;
+        repo.head()?.set_target(target.id(), "mrkrabz: fast-forward")?;
+        Ok(UpdateOutcome::FastForwarded)
+    } else {
+        Ok(UpdateOutcome::FetchedOnly)
+    }
+}
+
+/// Check whether a clone was made with a partial clone filter (blobless/treeless)
+fn is_partial_clone(path: &Path) -> bool {
+    git2::Repository::open(path)
+        .and_then(|repo| repo.config())
+        .and_then(|config| config.get_string("remote.origin.partialclonefilter"))
+        .is_ok()
+}
+
+/// Clone and check out every submodule of `repo`, recursing into nested ones
+fn update_submodules(
+    repo: &git2::Repository,
+    cancelled: &AtomicBool,
+    tx: &mpsc::UnboundedSender<CloneProgress>,
+) -> Result<(), git2::Error> {
+    let mut submodules = repo.submodules()?;
+    let total = submodules.len() as u64;
+    for (index, submodule) in submodules.iter_mut().enumerate() {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(git2::Error::new(ErrorCode::User, ErrorClass::Callback, "clone cancelled"));
+        }
+        let _ = tx.send(CloneProgress {
+            phase: format!("Submodule {}", submodule.name().unwrap_or("(unnamed)")),
+            percent: percent_of(index as u64, total),
+            current: index as u64,
+            total,
+            rate: None,
+        });
+
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.transfer_progress(|_| !cancelled.load(Ordering::Relaxed));
+        let mut fetch = FetchOptions::new();
+        fetch.remote_callbacks(callbacks);
+        let mut update = git2::SubmoduleUpdateOptions::new();
+        update.fetch(fetch);
+
+        submodule.update(true, Some(&mut update))?;
+        update_submodules(&submodule.open()?, cancelled, tx)?;
+    }
+    Ok(())
+}
+
+/// Removes a partially written clone directory unless disarmed
+/// Covers the CLI path, where dropping the future kills git before it can clean up
+struct RemoveOnDrop(Option<PathBuf>);
+
+impl Drop for RemoveOnDrop {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            let _ = std::fs::remove_dir_all(path);
+        }
+    }
+}
+
+/// Run `git clone --filter=...` for partial clones, parsing its progress output
+async fn clone_with_cli(
+    url: &str,
+    dest: &Path,
+    options: &CloneOptions,
+    on_progress: impl FnMut(CloneProgress),
+) -> Result<(), Error> {
+    let mut command = Command::new("git");
+    command.arg("clone").arg("--progress");
+    if let Some(filter) = options.mode.filter() {
+        command.arg(format!("--filter={}", filter));
+    }
+    if let Some(depth) = options.depth {
+        command.arg("--depth").arg(depth.to_string());
+    }
+    if options.submodules {
+        command.arg("--recurse-submodules");
+        if options.depth.is_some() {
+            command.arg("--shallow-submodules");
+        }
+    }
+
+    let mut cleanup = RemoveOnDrop((!dest.exists()).then(|| dest.to_path_buf()));
+    command.arg(url).arg(dest);
+    run_git(command, on_progress).await?;
+    cleanup.0 = None;
+    Ok(())
+}
+
+/// Run `git pull --ff-only` in a partial clone
+async fn update_with_cli(path: &Path, on_progress: impl FnMut(CloneProgress)) -> Result<UpdateOutcome, Error> {
+    let before = git2::Repository::open(path)?.head()?.target();
+
+    let mut command = Command::new("git");
+    command.arg("-C").arg(path).arg("pull").arg("--ff-only").arg("--progress");
+    run_git(command, on_progress).await?;
+
+    let after = git2::Repository::open(path)?.head()?.target();
+    Ok(if before == after { UpdateOutcome::UpToDate } else { UpdateOutcome::FastForwarded })
+}
+
+/// Run a git command, parsing its progress output
+/// On failure the error is classified from git's last stderr line
+async fn run_git(mut command: Command, mut on_progress: impl FnMut(CloneProgress)) -> Result<(), Error> {
+    let mut child = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| Error::GitMissing(e.to_string()))?;
+
+    // git redraws progress with '\r', so split on both line endings
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let mut buffer = [0u8; 4096];
+    let mut pending = String::new();
+    let mut last_message = String::new();
+    loop {
+        let read = stderr.read(&mut buffer).await.map_err(|e| Error::GitFailed { stderr: e.to_string() })?;
+        if read == 0 {
+            break;
+        }
+        pending.push_str(&String::from_utf8_lossy(&buffer[..read]));
+        while let Some(end) = pending.find(['\r', '\n']) {
+            let line: String = pending.drain(..=end).collect();
+            let line = line.trim();
+            if let Some(progress) = parse_progress_line(line) {
+                on_progress(progress);
+            } else if !line.is_empty() {
+                last_message = line.to_string();
+            }
+        }
+    }
+
+    let status = child.wait().await.map_err(|e| Error::GitFailed { stderr: e.to_string() })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(cli_error(&last_message))
+    }
+}
+
+/// Classify git's last stderr line into a Error
+fn cli_error(message: &str) -> Error {
+    let message = message.trim_start_matches("fatal:").trim();
+    let lower = message.to_lowercase();
+    if lower.contains("not found") || lower.contains("authentication") || lower.contains("username") {
+        Error::AuthRequired
+    } else if lower.contains("could not resolve host") || lower.contains("unable to access") {
+        Error::Network(message.to_string())
+    } else if message.is_empty() {
+        Error::GitFailed { stderr: "git clone failed".to_string() }
+    } else {
+        Error::GitFailed { stderr: message.to_string() }
+    }
+}
+
+/// Parse one progress line from git's stderr, e.g.
+/// "Receiving objects:  45% (450/1000), 1.20 MiB | 2.30 MiB/s"
+/// Returns None for lines without a percentage (remote chatter, errors)
+fn parse_progress_line(line: &str) -> Option<CloneProgress> {
+    let line = line.trim().trim_start_matches("remote:").trim();
+    let (phase, rest) = line.split_once(':')?;
+    let rest = rest.trim();
+
+    let (percent, rest) = rest.split_once('%')?;
+    let percent: u8 = percent.trim().parse().ok()?;
+
+    // "(450/1000)" follows the percentage
+    let counts = rest.trim().strip_prefix('(')?;
+    let (counts, rest) = counts.split_once(')')?;
+    let (current, total) = counts.split_once('/')?;
+
+    let rate = rest
+        .split_once('|')
+        .map(|(_, rate)| rate.trim().trim_end_matches(", done.").trim().to_string())
+        .filter(|rate| !rate.is_empty());
+
+    Some(CloneProgress {
+        phase: phase.trim().to_string(),
+        percent: percent.min(100),
+        current: current.trim().parse().ok()?,
+        total: total.trim().parse().ok()?,
+        rate,
+    })
+}
+
+fn percent_of(current: u64, total: u64) -> u8 {
+    (current * 100).checked_div(total).map_or(0, |percent| percent.min(100) as u8)
+}
+
+/// Build a progress snapshot from libgit2 transfer stats
+/// Objects are received first, then deltas are resolved
+fn transfer_progress(
+    received_objects: u64,
+    total_objects: u64,
+    indexed_deltas: u64,
+    total_deltas: u64,
+    received_bytes: u64,
+    elapsed: Duration,
+) -> CloneProgress {
+    if total_objects > 0 && received_objects == total_objects && total_deltas > 0 {
+        return CloneProgress {
+            phase: "Resolving deltas".to_string(),
+            percent: percent_of(indexed_deltas, total_deltas),
+            current: indexed_deltas,
+            total: total_deltas,
+            rate: None,
+        };
+    }
+
+    CloneProgress {
+        phase: "Receiving objects".to_string(),
+        percent: percent_of(received_objects, total_objects),
+        current: received_objects,
+        total: total_objects,
+        rate: format_rate(received_bytes, elapsed),
+    }
+}
+
+/// Format a transfer rate like git does, e.g. "2.30 MiB/s"
+fn format_rate(bytes: u64, elapsed: Duration) -> Option<String> {
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 || bytes == 0 {
+        return None;
+    }
+    let per_second = bytes as f64 / seconds;
+    Some(if per_second >= 1024.0 * 1024.0 {
+        format!("{:.2} MiB/s", per_second / (1024.0 * 1024.0))
+    } else {
+        format!("{:.2} KiB/s", per_second / 1024.0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Create an empty scratch directory path unique to this test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mrkrabz-git-test-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Create a repository with one committed file
+    fn source_repo(dir: &Path) {
+        let repo = git2::Repository::init(dir).unwrap();
+        std::fs::write(dir.join("README.md"), "hello").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_clone_local_repository() {
+        let source = scratch_dir("source");
+        let dest = scratch_dir("dest");
+        source_repo(&source);
+
+        let url = format!("file://{}", source.display());
+        let mut updates = Vec::new();
+        clone(&url, &dest, &CloneOptions::default(), |progress| updates.push(progress)).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(dest.join("README.md")).unwrap(), "hello");
+        assert!(updates.iter().all(|progress| progress.percent <= 100));
+
+        let _ = std::fs::remove_dir_all(&source);
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[tokio::test]
+    async fn test_clone_with_submodules() {
+        let library = scratch_dir("submodule-library");
+        let source = scratch_dir("submodule-source");
+        let dest = scratch_dir("submodule-dest");
+        source_repo(&library);
+        source_repo(&source);
+        {
+            // Add the library as a submodule of the source repo and commit it
+            let repo = git2::Repository::open(&source).unwrap();
+            let url = format!("file://{}", library.display());
+            let mut submodule = repo.submodule(&url, Path::new("vendor/library"), true).unwrap();
+            submodule.clone(None).unwrap();
+            submodule.add_finalize().unwrap();
+            let mut index = repo.index().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            let signature = git2::Signature::now("test", "test@example.com").unwrap();
+            repo.commit(Some("HEAD"), &signature, &signature, "add submodule", &tree, &[&parent]).unwrap();
+        }
+
+        let options = CloneOptions { submodules: true, ..Default::default() };
+        clone(&format!("file://{}", source.display()), &dest, &options, |_| {}).await.unwrap();
+        assert_eq!(std::fs::read_to_string(dest.join("vendor/library/README.md")).unwrap(), "hello");
+
+        for dir in [&library, &source, &dest] {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_fast_forwards_existing_clone() {
+        let source = scratch_dir("update-source");
+        let dest = scratch_dir("update-dest");
+        source_repo(&source);
+        let url = format!("file://{}", source.display());
+        clone(&url, &dest, &CloneOptions::default(), |_| {}).await.unwrap();
+
+        assert_eq!(update(&dest, |_| {}).await.unwrap(), UpdateOutcome::UpToDate);
+
+        {
+            // New commit on the source changes README.md
+            let repo = git2::Repository::open(&source).unwrap();
+            std::fs::write(source.join("README.md"), "updated").unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("README.md")).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            let signature = git2::Signature::now("test", "test@example.com").unwrap();
+            repo.commit(Some("HEAD"), &signature, &signature, "update", &tree, &[&parent]).unwrap();
+        }
+
+        assert_eq!(update(&dest, |_| {}).await.unwrap(), UpdateOutcome::FastForwarded);
+        assert_eq!(std::fs::read_to_string(dest.join("README.md")).unwrap(), "updated");
+
+        let _ = std::fs::remove_dir_all(&source);
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+
+    #[tokio::test]
+    async fn test_failed_clone_removes_partial_directory() {
+        let dest = scratch_dir("missing-source");
+        let missing = scratch_dir("does-not-exist");
+
+        let result = clone(&format!("file://{}", missing.display()), &dest, &CloneOptions::default(), |_| {}).await;
+        assert!(result.is_err());
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn test_transfer_progress_phases() {
+        let receiving = transfer_progress(450, 1000, 0, 20, 2 * 1024 * 1024, Duration::from_secs(1));
+        assert_eq!(receiving.phase, "Receiving objects");
+        assert_eq!(receiving.percent, 45);
+        assert_eq!(receiving.rate.as_deref(), Some("2.00 MiB/s"));
+
+        let resolving = transfer_progress(1000, 1000, 5, 20, 4096, Duration::from_secs(1));
+        assert_eq!(resolving.phase, "Resolving deltas");
+        assert_eq!(resolving.percent, 25);
+        assert!(resolving.rate.is_none());
+    }
+
+    #[test]
+    fn test_parse_progress_line() {
+        let progress = parse_progress_line("Receiving objects:  45% (450/1000), 1.20 MiB | 2.30 MiB/s").unwrap();
+        assert_eq!(progress.phase, "Receiving objects");
+        assert_eq!(progress.percent, 45);
+        assert_eq!((progress.current, progress.total), (450, 1000));
+        assert_eq!(progress.rate.as_deref(), Some("2.30 MiB/s"));
+
+        let progress = parse_progress_line("remote: Compressing objects:  50% (5/10)").unwrap();
+        assert_eq!(progress.phase, "Compressing objects");
+
+        assert!(parse_progress_line("Cloning into 'repo'...").is_none());
+        assert!(parse_progress_line("fatal: repository not found").is_none());
+    }
+
+    #[test]
+    fn test_cli_error_classification() {
+        assert_eq!(cli_error("fatal: repository 'https://github.com/a/b/' not found"), Error::AuthRequired);
+        assert_eq!(
+            cli_error("fatal: unable to access 'https://github.com/a/b/': Could not resolve host: github.com"),
+            Error::Network("unable to access 'https://github.com/a/b/': Could not resolve host: github.com".to_string())
+        );
+        assert_eq!(cli_error(""), Error::GitFailed { stderr: "git clone failed".to_string() });
+    }
+
+    #[test]
+    fn test_clone_mode_parses_and_cycles() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            mode: CloneMode,
+        }
+        let parsed: Wrapper = toml::from_str("mode = \"blobless\"").unwrap();
+        assert_eq!(parsed.mode, CloneMode::Blobless);
+        assert_eq!(parsed.mode.filter(), Some("blob:none"));
+
+        assert_eq!(CloneMode::Treeless.next(), CloneMode::Full);
+        assert_eq!(CloneMode::Full.previous(), CloneMode::Treeless);
+    }
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(0, Duration::from_secs(1)), None);
+        assert_eq!(format_rate(512 * 1024, Duration::from_secs(1)).as_deref(), Some("512.00 KiB/s"));
+    }
+}
@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use http::header::ACCEPT;
+use http::{HeaderMap, HeaderValue};
+use octocrab::models::Repository;
+use octocrab::Octocrab;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::error::Error;
+use crate::filecount::Tree;
+use crate::maintenance::{self, IssueDates, PullDates};
+use crate::stars;
+use crate::text_match::{self, TextMatches};
+use crate::tui::RateLimitInfo;
+
+#[cfg(test)]
+pub mod mock;
+
+/// Every GitHub API request the app makes, so the run functions can be handed a mock in tests
+#[async_trait]
+pub trait GitHub: Send + Sync {
+    /// One page (1-based) of repositories matching `query`, how many match in all, and why each matched
+    async fn search_repositories(
+        &self,
+        query: &str,
+        sort: Option<&str>,
+        per_page: u8,
+        page: u32,
+    ) -> Result<(Vec<Repository>, u64, TextMatches), Error>;
+
+    /// Remaining search API quota (the rate_limit endpoint itself is free)
+    async fn search_rate_limit(&self) -> Result<RateLimitInfo, Error>;
+
+    /// Login of the user the token belongs to
+    async fn current_user(&self) -> Result<String, Error>;
+
+    /// Every file of owner/name's default branch
+    async fn tree(&self, owner: &str, name: &str) -> Result<Tree, Error>;
+
+    /// Publish dates of owner/name's latest `count` releases, in the order GitHub lists them
+    async fn release_dates(&self, owner: &str, name: &str, count: u8) -> Result<Vec<SystemTime>, Error>;
+
+    /// Number of people who have committed to owner/name
+    async fn contributor_count(&self, owner: &str, name: &str) -> Result<u64, Error>;
+
+    /// The text of owner/name's README
+    async fn readme(&self, owner: &str, name: &str) -> Result<String, Error>;
+
+    /// When each stargazer on one page (1-based, stars::PAGE_SIZE each) of owner/name's list starred
+    /// it, oldest first, and how many pages the list has
+    async fn star_dates(&self, owner: &str, name: &str, page: u32) -> Result<(Vec<SystemTime>, u32), Error>;
+
+    /// owner/name's issues among its latest `count` issues and pull requests, newest first
+    async fn recent_issues(&self, owner: &str, name: &str, count: u8) -> Result<Vec<IssueDates>, Error>;
+
+    /// When someone other than `author` (and not a bot) first commented on issue `number`
+    async fn first_reply(&self, owner: &str, name: &str, number: u64, author: &str) -> Result<Option<SystemTime>, Error>;
+
+    /// owner/name's latest `count` pull requests, open or closed, newest first
+    async fn recent_pulls(&self, owner: &str, name: &str, count: u8) -> Result<Vec<PullDates>, Error>;
+
+    /// Login and number of commits of owner/name's top `count` contributors, most commits first
+    async fn top_contributors(&self, owner: &str, name: &str, count: u8) -> Result<Vec<(String, u64)>, Error>;
+}
+
+/// The client the app shares between the UI and background tasks
+pub type Client = Arc<dyn GitHub>;
+
+/// A page of search results, items left raw so their text matches can be split off
+#[derive(serde::Deserialize)]
+struct SearchPage {
+    #[serde(default)]
+    total_count: u64,
+    items: Vec<serde_json::Value>,
+}
+
+#[async_trait]
+impl GitHub for Octocrab {
+    async fn search_repositories(
+        &self,
+        query: &str,
+        sort: Option<&str>,
+        per_page: u8,
+        page: u32,
+    ) -> Result<(Vec<Repository>, u64, TextMatches), Error> {
+        // Sent by hand rather than with search().repositories() for the text-match media type
+        let mut params = vec![("q", query.to_string()), ("per_page", per_page.to_string()), ("page", page.to_string())];
+        if let Some(sort) = sort {
+            params.push(("sort", sort.to_string()));
+        }
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static(text_match::MEDIA_TYPE));
+        let results: SearchPage = self.get_with_headers("/search/repositories", Some(&params), Some(headers)).await?;
+        let (items, text_matches) = text_match::split_items(results.items).map_err(|e| Error::Other(e.to_string()))?;
+        Ok((items, results.total_count, text_matches))
+    }
+
+    async fn search_rate_limit(&self) -> Result<RateLimitInfo, Error> {
+        let rate = self.ratelimit().get().await?.resources.search;
+        Ok(RateLimitInfo { remaining: rate.remaining, limit: rate.limit, reset: rate.reset })
+    }
+
+    async fn current_user(&self) -> Result<String, Error> {
+        Ok(self.current().user().await?.login)
+    }
+
+    async fn tree(&self, owner: &str, name: &str) -> Result<Tree, Error> {
+        let route = format!("/repos/{}/{}/git/trees/HEAD", owner, name);
+        Ok(self.get(route, Some(&[("recursive", "1")])).await?)
+    }
+
+    async fn release_dates(&self, owner: &str, name: &str, count: u8) -> Result<Vec<SystemTime>, Error> {
+        let releases = self.repos(owner, name).releases().list().per_page(count).send().await?;
+        Ok(releases.items.iter().filter_map(|release| release.published_at).map(Into::into).collect())
+    }
+
+    async fn contributor_count(&self, owner: &str, name: &str) -> Result<u64, Error> {
+        // One contributor per page, so the number of pages is the number of contributors
+        let page = self.repos(owner, name).list_contributors().per_page(1u8).send().await?;
+        Ok(page.number_of_pages().map_or(page.items.len() as u64, u64::from))
+    }
+
+    async fn readme(&self, owner: &str, name: &str) -> Result<String, Error> {
+        let readme = self.repos(owner, name).get_readme().send().await?;
+        readme.decoded_content().ok_or_else(|| Error::Other(format!("{}/{} has an empty README", owner, name)))
+    }
+
+    async fn star_dates(&self, owner: &str, name: &str, page: u32) -> Result<(Vec<SystemTime>, u32), Error> {
+        // list_stargazers asks for the star+json media type, which adds starred_at
+        let stargazers = self.repos(owner, name).list_stargazers().per_page(stars::PAGE_SIZE).page(page).send().await?;
+        let dates = stargazers.items.iter().filter_map(|stargazer| stargazer.starred_at).map(Into::into).collect();
+        // The last page has no link to a last page
+        Ok((dates, stargazers.number_of_pages().unwrap_or(page)))
+    }
+
+    async fn recent_issues(&self, owner: &str, name: &str, count: u8) -> Result<Vec<IssueDates>, Error> {
+        let page = self
+            .issues(owner, name)
+            .list()
+            .state(octocrab::params::State::All)
+            .per_page(count)
+            .send()
+            .await?;
+        Ok(page
+            .items
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .map(|issue| IssueDates {
+                number: issue.number,
+                author: issue.user.login,
+                opened: issue.created_at.into(),
+                closed: issue.closed_at.map(Into::into),
+                comments: issue.comments,
+            })
+            .collect())
+    }
+
+    async fn first_reply(&self, owner: &str, name: &str, number: u64, author: &str) -> Result<Option<SystemTime>, Error> {
+        // Oldest first; a reply past the first hundred comments isn't worth more requests
+        let comments = self.issues(owner, name).list_comments(number).per_page(100u8).send().await?;
+        Ok(comments
+            .items
+            .iter()
+            .find(|comment| maintenance::is_reply(&comment.user.login, author))
+            .map(|comment| comment.created_at.into()))
+    }
+
+    async fn recent_pulls(&self, owner: &str, name: &str, count: u8) -> Result<Vec<PullDates>, Error> {
+        let page = self.pulls(owner, name).list().state(octocrab::params::State::All).per_page(count).send().await?;
+        Ok(page
+            .items
+            .into_iter()
+            .filter_map(|pull| {
+                Some(PullDates {
+                    opened: pull.created_at?.into(),
+                    closed: pull.closed_at.map(Into::into),
+                    merged: pull.merged_at.map(Into::into),
+                })
+            })
+            .collect())
+    }
+
+    async fn top_contributors(&self, owner: &str, name: &str, count: u8) -> Result<Vec<(String, u64)>, Error> {
+        let page = self.repos(owner, name).list_contributors().per_page(count).send().await?;
+        Ok(page.items.into_iter().map(|contributor| (contributor.author.login, u64::from(contributor.contributions))).collect())
+    }
+}
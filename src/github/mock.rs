@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+use octocrab::models::Repository;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::GitHub;
+use crate::error::Error;
+use crate::filecount::Tree;
+use crate::maintenance::{IssueDates, PullDates};
+use crate::text_match::{self, TextMatches};
+use crate::tui::RateLimitInfo;
+
+/// Result pages of the fixture search, in page order
+const SEARCH_PAGES: &[&str] = &[
+    include_str!("../../tests/fixtures/search_page1.json"),
+    include_str!("../../tests/fixtures/search_page2.json"),
+];
+const TREE: &str = include_str!("../../tests/fixtures/tree.json");
+const README: &str = include_str!("../../tests/fixtures/awesome_readme.md");
+
+/// A search the mock was asked for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Search {
+    pub query: String,
+    pub sort: Option<String>,
+    pub per_page: u8,
+    pub page: u32,
+}
+
+/// GitHub as the fixtures in tests/fixtures describe it: every search returns the fixture pages,
+/// and pages after the last one are empty
+pub struct MockGitHub {
+    pages: Vec<(Vec<Repository>, u64, TextMatches)>,
+    failures: HashMap<u32, Error>, // Pages that fail instead, by page number
+    pub rate_limit: RateLimitInfo,
+    pub user: Option<String>, // None when no token is set
+    pub releases: Vec<SystemTime>,
+    pub contributors: Result<u64, Error>, // Error::TooLarge when GitHub won't list them
+    pub stars: Vec<SystemTime>,    // When each stargazer starred, oldest first
+    pub issues: Vec<IssueDates>,   // Recent issues, newest first
+    pub replies: HashMap<u64, SystemTime>, // First reply to each issue that got one, by number
+    pub pulls: Vec<PullDates>,     // Recent pull requests, newest first
+    pub top_contributors: Result<Vec<(String, u64)>, Error>, // Login and commits, most first
+    searches: Mutex<Vec<Search>>, // Every search asked for, in order
+}
+
+impl MockGitHub {
+    pub fn new() -> Self {
+        let pages = SEARCH_PAGES
+            .iter()
+            .map(|page| {
+                let page: serde_json::Value = serde_json::from_str(page).expect("invalid search fixture");
+                let items = serde_json::from_value(page["items"].clone()).expect("invalid search fixture");
+                let (items, text_matches) = text_match::split_items(items).expect("invalid repository in fixture");
+                (items, page["total_count"].as_u64().unwrap_or(0), text_matches)
+            })
+            .collect();
+        Self {
+            pages,
+            failures: HashMap::new(),
+            rate_limit: RateLimitInfo { remaining: 30, limit: 30, reset: 1_700_000_000 },
+            user: None,
+            releases: Vec::new(),
+            contributors: Err(Error::TooLarge),
+            stars: Vec::new(),
+            issues: Vec::new(),
+            replies: HashMap::new(),
+            pulls: Vec::new(),
+            top_contributors: Ok(Vec::new()),
+            searches: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Fail requests for `page` with `error`
+    pub fn failing(mut self, page: u32, error: Error) -> Self {
+        self.failures.insert(page, error);
+        self
+    }
+
+    /// Answer every search with no results
+    pub fn empty(mut self) -> Self {
+        self.pages.clear();
+        self
+    }
+
+    /// The error octocrab's responses turn into for an HTTP `status` with `message`
+    pub fn status(status: u16, message: &str) -> Error {
+        Error::from_status(status, message.to_string())
+    }
+
+    /// Searches asked for so far
+    pub fn searches(&self) -> Vec<Search> {
+        self.searches.lock().unwrap().clone()
+    }
+}
+
+/// A repository named `full_name` (owner/name) with id 1, for tests; set what else they need with
+/// the builder's methods
+pub fn repo(full_name: &str) -> RepoBuilder {
+    let mut fields = serde_json::Map::new();
+    fields.insert("id".to_string(), 1.into());
+    fields.insert("name".to_string(), full_name.rsplit('/').next().unwrap_or(full_name).into());
+    fields.insert("full_name".to_string(), full_name.into());
+    fields.insert("url".to_string(), format!("https://api.github.com/repos/{}", full_name).into());
+    fields.insert("html_url".to_string(), format!("https://github.com/{}", full_name).into());
+    RepoBuilder { fields }
+}
+
+/// Test repository fields, as GitHub's JSON has them; `build` turns them into a `Repository`
+pub struct RepoBuilder {
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl RepoBuilder {
+    pub fn id(self, id: u64) -> Self {
+        self.set("id", id)
+    }
+
+    pub fn description(self, description: &str) -> Self {
+        self.set("description", description)
+    }
+
+    pub fn stars(self, stars: u64) -> Self {
+        self.set("stargazers_count", stars)
+    }
+
+    /// When it was last pushed to, e.g. "2024-05-01T12:00:00Z"
+    pub fn pushed_at(self, time: &str) -> Self {
+        self.set("pushed_at", time)
+    }
+
+    /// Any other field, by its name in GitHub's JSON
+    pub fn set(mut self, field: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.fields.insert(field.to_string(), value.into());
+        self
+    }
+
+    pub fn build(self) -> Repository {
+        serde_json::from_value(self.fields.into()).expect("invalid test repository")
+    }
+}
+
+#[async_trait]
+impl GitHub for MockGitHub {
+    async fn search_repositories(
+        &self,
+        query: &str,
+        sort: Option<&str>,
+        per_page: u8,
+        page: u32,
+    ) -> Result<(Vec<Repository>, u64, TextMatches), Error> {
+        self.searches.lock().unwrap().push(Search {
+            query: query.to_string(),
+            sort: sort.map(str::to_string),
+            per_page,
+            page,
+        });
+        if let Some(error) = self.failures.get(&page) {
+            return Err(error.clone());
+        }
+        let total = self.pages.first().map_or(0, |(_, total, _)| *total);
+        let index = page.checked_sub(1).map_or(usize::MAX, |index| index as usize);
+        Ok(self.pages.get(index).cloned().unwrap_or((Vec::new(), total, TextMatches::new())))
+    }
+
+    async fn search_rate_limit(&self) -> Result<RateLimitInfo, Error> {
+        Ok(self.rate_limit)
+    }
+
+    async fn current_user(&self) -> Result<String, Error> {
+        self.user.clone().ok_or(Error::AuthRequired)
+    }
+
+    async fn tree(&self, _owner: &str, _name: &str) -> Result<Tree, Error> {
+        Ok(serde_json::from_str(TREE).expect("invalid tree fixture"))
+    }
+
+    async fn release_dates(&self, _owner: &str, _name: &str, count: u8) -> Result<Vec<SystemTime>, Error> {
+        Ok(self.releases.iter().take(count as usize).copied().collect())
+    }
+
+    async fn contributor_count(&self, _owner: &str, _name: &str) -> Result<u64, Error> {
+        self.contributors.clone()
+    }
+
+    async fn readme(&self, _owner: &str, _name: &str) -> Result<String, Error> {
+        Ok(README.to_string())
+    }
+
+    async fn star_dates(&self, _owner: &str, _name: &str, page: u32) -> Result<(Vec<SystemTime>, u32), Error> {
+        let size = crate::stars::PAGE_SIZE as usize;
+        let pages = self.stars.len().div_ceil(size).max(1) as u32;
+        let dates = self.stars.iter().skip((page as usize - 1) * size).take(size).copied().collect();
+        Ok((dates, pages))
+    }
+
+    async fn recent_issues(&self, _owner: &str, _name: &str, count: u8) -> Result<Vec<IssueDates>, Error> {
+        Ok(self.issues.iter().take(count as usize).cloned().collect())
+    }
+
+    async fn first_reply(&self, _owner: &str, _name: &str, number: u64, _author: &str) -> Result<Option<SystemTime>, Error> {
+        Ok(self.replies.get(&number).copied())
+    }
+
+    async fn recent_pulls(&self, _owner: &str, _name: &str, count: u8) -> Result<Vec<PullDates>, Error> {
+        Ok(self.pulls.iter().take(count as usize).cloned().collect())
+    }
+
+    async fn top_contributors(&self, _owner: &str, _name: &str, count: u8) -> Result<Vec<(String, u64)>, Error> {
+        Ok(self.top_contributors.clone()?.into_iter().take(count as usize).collect())
+    }
+}
@@ -0,0 +1,145 @@
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+/// Language of the TUI's text and error messages (config: language)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "es")]
+    Spanish,
+}
+
+impl Language {
+    const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// The message catalog, in Fluent syntax
+    fn catalog(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::Spanish => include_str!("../locales/es.ftl"),
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+}
+
+/// The language in effect, set once at startup (stored as its index)
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+/// Show every message in `language` from now on
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+fn language() -> Language {
+    Language::ALL.get(LANGUAGE.load(Ordering::Relaxed) as usize).copied().unwrap_or_default()
+}
+
+/// Each language's catalog, parsed on first use
+fn bundles() -> &'static [FluentBundle<FluentResource>] {
+    static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+    BUNDLES.get_or_init(|| {
+        Language::ALL
+            .iter()
+            .map(|language| {
+                let id: LanguageIdentifier = language.code().parse().expect("invalid language code");
+                let mut bundle = FluentBundle::new_concurrent(vec![id]);
+                // No Unicode isolation marks around placeholders: terminals print them as-is
+                bundle.set_use_isolating(false);
+                let resource = FluentResource::try_new(language.catalog().to_string()).expect("invalid message catalog");
+                bundle.add_resource(resource).expect("duplicate message in catalog");
+                bundle
+            })
+            .collect()
+    })
+}
+
+/// Message `id` in `language` with `args` filled in, falling back to English, then to the id itself
+fn lookup(language: Language, id: &str, args: Option<&FluentArgs>) -> String {
+    [language, Language::English]
+        .iter()
+        .find_map(|language| {
+            let bundle = &bundles()[*language as usize];
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(bundle.format_pattern(pattern, args, &mut errors).into_owned())
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Message `id` in the current language; see `tr!`
+pub fn message(id: &str, args: Option<&FluentArgs>) -> String {
+    lookup(language(), id, args)
+}
+
+/// A message from locales/*.ftl in the current language: `tr!("help-quit")`, or with its
+/// placeholders filled in: `tr!("prompt-clone", count = 3)`
+macro_rules! tr {
+    ($id:expr) => {
+        $crate::i18n::message($id, None)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::message($id, Some(&args))
+    }};
+}
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Message ids defined in a catalog
+    fn ids(catalog: &str) -> Vec<&str> {
+        catalog
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once(" =").map(|(id, _)| id))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_match() {
+        let english = ids(Language::English.catalog());
+        assert!(english.contains(&"help-quit") && english.contains(&"prompt-clone"));
+        for language in Language::ALL {
+            assert_eq!(ids(language.catalog()), english, "{:?} catalog differs from en.ftl", language);
+        }
+        // Parses every catalog
+        assert_eq!(bundles().len(), Language::ALL.len());
+    }
+
+    #[test]
+    fn test_lookup() {
+        let count = |count: u64| FluentArgs::from_iter([("count", count)]);
+        assert_eq!(lookup(Language::English, "help-quit", None), "Quit");
+        assert_eq!(lookup(Language::Spanish, "help-quit", None), "Salir");
+        assert_eq!(lookup(Language::English, "prompt-clone", Some(&count(1))), "Clone repository");
+        assert_eq!(lookup(Language::English, "prompt-clone", Some(&count(3))), "Clone 3 repositories");
+        assert_eq!(lookup(Language::Spanish, "prompt-clone", Some(&count(3))), "Clonar 3 repositorios");
+        assert_eq!(lookup(Language::English, "span-weeks", Some(&count(1))), "~1 week");
+        assert_eq!(lookup(Language::Spanish, "every-days", Some(&count(4))), "cada ~4 días");
+        let seconds = FluentArgs::from_iter([("seconds", 42)]);
+        assert_eq!(
+            lookup(Language::Spanish, "hint-rate-limited-retry", Some(&seconds)),
+            "Reintenta en 42 s, o añade un token (-t o GITHUB_TOKEN) para un límite mayor"
+        );
+        // Unknown ids show as themselves rather than vanishing
+        assert_eq!(lookup(Language::Spanish, "no-such-message", None), "no-such-message");
+
+        assert_eq!(language(), Language::English);
+        assert_eq!(tr!("page-label", pages = 7), "Page (1-7)");
+    }
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Which glyphs decorate the TUI and CLI output (config: icons)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconStyle {
+    /// Emoji and other Unicode symbols
+    #[default]
+    Emoji,
+    /// Plain ASCII stand-ins, for terminals and fonts without emoji
+    Ascii,
+    /// No decorations; markers that carry meaning fall back to ASCII
+    None,
+}
+
+/// The style in effect, set once at startup (stored as its index)
+static STYLE: AtomicU8 = AtomicU8::new(0);
+
+/// Use `style` for every icon from now on
+pub fn set_style(style: IconStyle) {
+    STYLE.store(style as u8, Ordering::Relaxed);
+}
+
+fn style() -> IconStyle {
+    match STYLE.load(Ordering::Relaxed) {
+        1 => IconStyle::Ascii,
+        2 => IconStyle::None,
+        _ => IconStyle::Emoji,
+    }
+}
+
+/// A decorative glyph
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Icon {
+    Search,
+    Offline,
+    Stars,
+    Forks,
+    Language,
+    Package,      // Repository size, clone progress
+    Bookmark,     // Marker: bookmarked
+    Cloned,       // Marker: cloned locally
+    Files,        // Marker: file count done
+    Languages,    // Marker: language analysis done
+    Private,      // Marker: private or internal repository
+    New,          // Marker: new since the search last ran
+    Link,
+    Branch,
+    Disk,
+    Clock,
+    Delete,
+    Sync,
+    User,
+    Welcome,
+    Error,
+    Hint,
+    Queued,
+    Selected,     // List highlight, with its trailing space
+    Marked,       // Marked for a batch action
+    Info,
+    Success,
+    Warning,
+    Failure,
+    Arrow,
+    Separator,
+    Previous,
+    Next,
+    UpDown,       // Key hint
+    LeftRight,    // Key hint
+    BarFilled,
+    BarEmpty,
+}
+
+/// Every icon as (emoji, ascii, none); `none` is only used where the glyph stands alone
+fn table(icon: Icon) -> (&'static str, &'static str, &'static str) {
+    match icon {
+        Icon::Search => ("🔍", ">", ""),
+        Icon::Offline => ("📴", "[offline]", ""),
+        Icon::Stars => ("⭐", "*", ""),
+        Icon::Forks => ("🍴", "Y", ""),
+        Icon::Language => ("💻", "#", ""),
+        Icon::Package => ("📦", "=", ""),
+        Icon::Bookmark => ("🔖", "[B]", "[B]"),
+        Icon::Cloned => ("📂", "[C]", "[C]"),
+        Icon::Files => ("📁", "[F]", "[F]"),
+        Icon::Languages => ("📊", "[L]", "[L]"),
+        Icon::Private => ("🔒", "[P]", "[P]"),
+        Icon::New => ("🆕", "[N]", "[N]"),
+        Icon::Link => ("🔗", "@", ""),
+        Icon::Branch => ("🌿", "br", ""),
+        Icon::Disk => ("💾", "sz", ""),
+        Icon::Clock => ("🕒", "at", ""),
+        Icon::Delete => ("🗑 ", "-", ""),
+        Icon::Sync => ("🔄", ">", ""),
+        Icon::User => ("👤", "@", ""),
+        Icon::Welcome => ("👋", "", ""),
+        Icon::Error => ("❌", "!", ""),
+        Icon::Hint => ("💡", "Hint:", "Hint:"),
+        Icon::Queued => ("⏸", "..", ""),
+        Icon::Selected => ("▶ ", "> ", "> "),
+        Icon::Marked => ("✔", "*", "*"),
+        Icon::Info => ("ℹ", "i", ""),
+        Icon::Success => ("✓", "ok", "ok"),
+        Icon::Warning => ("⚠", "!", ""),
+        Icon::Failure => ("✗", "x", "x"),
+        Icon::Arrow => ("→", "->", "->"),
+        Icon::Separator => ("│", "|", "|"),
+        Icon::Previous => ("◀", "<", "<"),
+        Icon::Next => ("▶", ">", ">"),
+        Icon::UpDown => ("↑↓", "Up/Down", "Up/Down"),
+        Icon::LeftRight => ("←/→", "Left/Right", "Left/Right"),
+        Icon::BarFilled => ("█", "#", "#"),
+        Icon::BarEmpty => ("░", "-", "-"),
+    }
+}
+
+/// Spinner frames shown while an operation is running, per style
+const SPINNER_EMOJI: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_ASCII: &[&str] = &["|", "/", "-", "\\"];
+
+impl Icon {
+    /// The glyph for `style`; empty when the style has none
+    pub fn glyph_for(self, style: IconStyle) -> &'static str {
+        let (emoji, ascii, none) = table(self);
+        match style {
+            IconStyle::Emoji => emoji,
+            IconStyle::Ascii => ascii,
+            IconStyle::None => none,
+        }
+    }
+
+    /// The glyph in the current style
+    pub fn glyph(self) -> &'static str {
+        self.glyph_for(style())
+    }
+
+    /// `text` after the glyph and a space; just `text` when the style has no glyph or is `none`
+    /// (the text says what the glyph would have)
+    pub fn label(self, text: impl fmt::Display) -> String {
+        self.label_for(style(), text)
+    }
+
+    fn label_for(self, style: IconStyle, text: impl fmt::Display) -> String {
+        match self.glyph_for(style) {
+            _ if style == IconStyle::None => text.to_string(),
+            "" => text.to_string(),
+            glyph => format!("{} {}", glyph, text),
+        }
+    }
+}
+
+impl fmt::Display for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.glyph())
+    }
+}
+
+/// Spinner frames in the current style
+pub fn spinner_frames() -> &'static [&'static str] {
+    match style() {
+        IconStyle::Emoji => SPINNER_EMOJI,
+        IconStyle::Ascii | IconStyle::None => SPINNER_ASCII,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_and_none_styles_are_ascii() {
+        let icons = [
+            Icon::Search, Icon::Offline, Icon::Stars, Icon::Forks, Icon::Language, Icon::Package,
+            Icon::Bookmark, Icon::Cloned, Icon::Files, Icon::Languages, Icon::Private, Icon::New, Icon::Link, Icon::Branch,
+            Icon::Disk, Icon::Clock, Icon::Delete, Icon::Sync, Icon::User, Icon::Welcome, Icon::Error,
+            Icon::Hint, Icon::Queued, Icon::Selected, Icon::Marked, Icon::Info, Icon::Success,
+            Icon::Warning, Icon::Failure, Icon::Arrow, Icon::Separator, Icon::Previous, Icon::Next,
+            Icon::UpDown, Icon::LeftRight, Icon::BarFilled, Icon::BarEmpty,
+        ];
+        for icon in icons {
+            assert!(!icon.glyph_for(IconStyle::Emoji).is_empty(), "{:?}", icon);
+            assert!(icon.glyph_for(IconStyle::Ascii).is_ascii(), "{:?}", icon);
+            assert!(icon.glyph_for(IconStyle::None).is_ascii(), "{:?}", icon);
+        }
+        // Markers keep their meaning without decorations
+        assert_eq!(Icon::Bookmark.glyph_for(IconStyle::None), "[B]");
+        assert_eq!(Icon::Stars.glyph_for(IconStyle::None), "");
+        assert!(SPINNER_ASCII.iter().all(|frame| frame.is_ascii()));
+    }
+
+    #[test]
+    fn test_label() {
+        assert_eq!(Icon::Stars.label_for(IconStyle::Emoji, 42), "⭐ 42");
+        assert_eq!(Icon::Stars.label_for(IconStyle::Ascii, 42), "* 42");
+        assert_eq!(Icon::Stars.label_for(IconStyle::None, 42), "42");
+        // The label's text already says what a marker would
+        assert_eq!(Icon::Bookmark.label_for(IconStyle::None, "Bookmarked"), "Bookmarked");
+        assert_eq!(Icon::Welcome.label_for(IconStyle::Ascii, "Hi"), "Hi");
+    }
+
+    #[test]
+    fn test_style_parses_from_config() {
+        let parse = |value: &str| toml::from_str::<toml::Table>(&format!("v = \"{}\"", value)).unwrap()["v"].clone();
+        assert_eq!(parse("ascii").try_into::<IconStyle>().unwrap(), IconStyle::Ascii);
+        assert_eq!(parse("none").try_into::<IconStyle>().unwrap(), IconStyle::None);
+        assert!(parse("unicode").try_into::<IconStyle>().is_err());
+    }
+}
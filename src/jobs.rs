@@ -0,0 +1,444 @@
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Semaphore;
+use tokio::task::AbortHandle;
+
+use octocrab::models::Repository;
+
+use crate::analysis::LanguageStats;
+use crate::audit::Finding;
+use crate::cache;
+use crate::compare::RepoActivity;
+use crate::error::Error;
+use crate::filecount::FileStats;
+use crate::git::{CloneProgress, UpdateOutcome};
+use crate::maintenance::Maintenance;
+use crate::profiles::Section;
+use crate::stars::StarHistory;
+use crate::text_match::TextMatches;
+use crate::tui::RateLimitInfo;
+
+/// Maximum number of file counts (clone + script) running at once; the rest wait in a queue
+pub const MAX_CONCURRENT_COUNTS: usize = 3;
+
+/// Default number of Alt+G clones running at once (config: clone_parallelism)
+pub const DEFAULT_CLONE_PARALLELISM: usize = 3;
+
+/// Finished jobs kept in the jobs panel before the oldest are dropped
+const MAX_FINISHED_JOBS: usize = 50;
+
+pub type JobId = u64;
+
+/// Sends a job's events back to the TUI
+pub type JobSender = UnboundedSender<JobEvent>;
+
+/// Search results, why each matched, whether they came from the cache instead of GitHub, and how
+/// they changed since the search last ran
+pub type SearchResults = (Vec<Repository>, u64, TextMatches, Option<cache::Hit>, Option<Box<cache::Changes>>);
+
+/// Messages sent from background tasks back to the TUI
+#[derive(Debug)]
+pub enum JobEvent {
+    /// A queued job acquired a slot and started
+    Started { id: JobId },
+    /// The first page of a search arrived, with the search quota left afterwards
+    SearchFinished { id: JobId, query: String, result: Result<SearchResults, Error>, rate_limit: Option<RateLimitInfo> },
+    /// A file count finished (Err holds the error message)
+    FileCountFinished { id: JobId, url: String, result: Result<FileStats, String> },
+    /// A language analysis finished (Err holds the error message)
+    AnalysisFinished { id: JobId, url: String, result: Result<Vec<LanguageStats>, String> },
+    /// Progress update from a running clone (or update, with the clone's path for `url`)
+    CloneProgress { id: JobId, url: String, progress: CloneProgress },
+    /// A clone finished; Ok(Some) means an existing clone at `path` was updated instead
+    CloneFinished { id: JobId, url: String, path: String, result: Result<Option<UpdateOutcome>, String> },
+    /// Updating clones from the clones view finished: the updated paths, and a message per failure
+    UpdateFinished { id: JobId, updated: Vec<(String, UpdateOutcome)>, failed: Vec<String> },
+    /// A further page of search results arrived (items, total count); `done` is set on the job's last event
+    PageLoaded { id: JobId, page: u32, done: bool, result: Result<(Vec<Repository>, u64, TextMatches), String> },
+    /// Release and contributor figures for the comparison view arrived
+    ActivityLoaded { id: JobId, url: String, result: Result<RepoActivity, String> },
+    /// A prefetch of those figures was skipped to spare the rate budget; nothing was fetched
+    ActivityDeferred { id: JobId, url: String },
+    /// The repositories an awesome list (owner/name) links to arrived
+    ListLoaded { id: JobId, list: String, result: Result<Vec<Repository>, String> },
+    /// A repository's star history (by URL) arrived
+    StarHistoryLoaded { id: JobId, url: String, result: Result<StarHistory, String> },
+    /// A repository's issue responsiveness (by URL) arrived
+    MaintenanceLoaded { id: JobId, url: String, result: Result<Maintenance, String> },
+    /// An SBOM of a clone was written: where to, and how many components it lists
+    SbomWritten { id: JobId, clone: String, result: Result<(PathBuf, usize), String> },
+    /// A fresh clone's Cargo.lock (clone by URL) was checked against the RustSec advisories
+    AuditFinished { id: JobId, url: String, result: Result<Vec<Finding>, String> },
+    /// The analysis profile commands for a fresh clone (by URL) ran, one section per command
+    ProfilesFinished { id: JobId, url: String, sections: Vec<Section> },
+    /// The signed-in user (None when anonymous or the lookup failed) and search quota for the status bar
+    StatusLoaded { id: JobId, user: Option<String>, rate_limit: Option<RateLimitInfo> },
+}
+
+impl JobEvent {
+    /// The job that sent the event
+    pub fn id(&self) -> JobId {
+        match self {
+            JobEvent::Started { id }
+            | JobEvent::SearchFinished { id, .. }
+            | JobEvent::FileCountFinished { id, .. }
+            | JobEvent::AnalysisFinished { id, .. }
+            | JobEvent::CloneProgress { id, .. }
+            | JobEvent::CloneFinished { id, .. }
+            | JobEvent::UpdateFinished { id, .. }
+            | JobEvent::PageLoaded { id, .. }
+            | JobEvent::ActivityLoaded { id, .. }
+            | JobEvent::ActivityDeferred { id, .. }
+            | JobEvent::ListLoaded { id, .. }
+            | JobEvent::StarHistoryLoaded { id, .. }
+            | JobEvent::MaintenanceLoaded { id, .. }
+            | JobEvent::SbomWritten { id, .. }
+            | JobEvent::AuditFinished { id, .. }
+            | JobEvent::ProfilesFinished { id, .. }
+            | JobEvent::StatusLoaded { id, .. } => *id,
+        }
+    }
+}
+
+/// Progress of a background file count
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CountStatus {
+    Queued,
+    Running(Instant),
+}
+
+/// Progress of a queued Alt+G clone
+#[derive(Debug, Clone, PartialEq)]
+pub enum CloneStatus {
+    Queued,
+    Running(Instant, Option<CloneProgress>),
+}
+
+/// Runs background tasks as jobs: spawns them on tokio, tracks each one's state by id, and
+/// passes the events they send on to the TUI loop
+pub struct JobManager {
+    pub list: JobList,           // Every job started this session, as the jobs panel shows them
+    pub tx: JobSender,           // Handed to every task for its events
+    rx: UnboundedReceiver<JobEvent>,
+    count_slots: Arc<Semaphore>, // File counts and analyses running at once
+    clone_slots: Arc<Semaphore>, // Clones running at once (config: clone_parallelism)
+}
+
+impl JobManager {
+    pub fn new() -> Self {
+        let (tx, rx) = unbounded_channel();
+        Self {
+            list: JobList::default(),
+            tx,
+            rx,
+            count_slots: Arc::new(Semaphore::new(MAX_CONCURRENT_COUNTS)),
+            clone_slots: Arc::new(Semaphore::new(DEFAULT_CLONE_PARALLELISM)),
+        }
+    }
+
+    /// Run at most `parallelism` clones at once from now on
+    pub fn set_clone_parallelism(&mut self, parallelism: usize) {
+        self.clone_slots = Arc::new(Semaphore::new(parallelism.max(1)));
+    }
+
+    /// Slots a job of `kind` waits for, if its kind is limited
+    fn slots(&self, kind: JobKind) -> Option<Arc<Semaphore>> {
+        match kind {
+            JobKind::Clone => Some(self.clone_slots.clone()),
+            JobKind::FileCount | JobKind::Analysis => Some(self.count_slots.clone()),
+            JobKind::Search
+            | JobKind::Update
+            | JobKind::Activity
+            | JobKind::List
+            | JobKind::Stars
+            | JobKind::Maintenance
+            | JobKind::Sbom
+            | JobKind::Audit
+            | JobKind::Profiles
+            | JobKind::Status => None,
+        }
+    }
+
+    /// Start a job of `kind` on `target` (a query, URL, or path) running `task`, which gets the
+    /// job's id and the sender for its events; returns the id
+    /// Clones, file counts, and analyses stay queued until a slot frees up, then send Started
+    pub fn spawn<F>(&mut self, kind: JobKind, target: impl Into<String>, task: impl FnOnce(JobId, JobSender) -> F) -> JobId
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let id = self.list.add(kind, target);
+        let work = task(id, self.tx.clone());
+        let handle = match self.slots(kind) {
+            Some(slots) => {
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let Ok(_permit) = slots.acquire_owned().await else {
+                        return;
+                    };
+                    let _ = tx.send(JobEvent::Started { id });
+                    work.await;
+                })
+            }
+            None => {
+                self.list.start(id);
+                tokio::spawn(work)
+            }
+        };
+        self.list.set_abort(id, handle.abort_handle());
+        id
+    }
+
+    /// The next event a job sent, if one is waiting
+    pub fn next_event(&mut self) -> Option<JobEvent> {
+        self.rx.try_recv().ok()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JobKind {
+    Search,
+    Clone,
+    FileCount,
+    Update,
+    Analysis,
+    Activity,
+    List,
+    Stars,
+    Maintenance,
+    Sbom,
+    Audit,
+    Profiles,
+    Status,
+}
+
+impl JobKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            JobKind::Search => "Search",
+            JobKind::Clone => "Clone",
+            JobKind::FileCount => "File count",
+            JobKind::Update => "Update",
+            JobKind::Analysis => "Analysis",
+            JobKind::Activity => "Activity",
+            JobKind::List => "Awesome list",
+            JobKind::Stars => "Star history",
+            JobKind::Maintenance => "Maintenance",
+            JobKind::Sbom => "SBOM",
+            JobKind::Audit => "Audit",
+            JobKind::Profiles => "Analysis profile",
+            JobKind::Status => "Status bar",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Finished,
+    Failed(String),
+    Cancelled,
+}
+
+impl JobState {
+    pub fn is_active(&self) -> bool {
+        matches!(self, JobState::Queued | JobState::Running)
+    }
+}
+
+/// One entry in the jobs panel
+#[derive(Debug)]
+pub struct JobRecord {
+    pub id: JobId,
+    pub kind: JobKind,
+    pub target: String, // Query or repository URL the job works on
+    pub state: JobState,
+    pub created: Instant,
+    pub started: Option<Instant>,
+    pub finished: Option<Instant>,
+    pub abort: Option<AbortHandle>,
+}
+
+impl JobRecord {
+    /// Time spent running (or waiting, while still queued)
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        let end = self.finished.unwrap_or(now);
+        end.duration_since(self.started.unwrap_or(self.created))
+    }
+}
+
+/// Every job started this session, oldest first
+#[derive(Debug, Default)]
+pub struct JobList {
+    pub records: Vec<JobRecord>,
+    next_id: JobId,
+}
+
+impl JobList {
+    /// Register a new queued job and return its id
+    pub fn add(&mut self, kind: JobKind, target: impl Into<String>) -> JobId {
+        self.next_id += 1;
+        self.records.push(JobRecord {
+            id: self.next_id,
+            kind,
+            target: target.into(),
+            state: JobState::Queued,
+            created: Instant::now(),
+            started: None,
+            finished: None,
+            abort: None,
+        });
+        self.prune();
+        self.next_id
+    }
+
+    pub fn get(&self, id: JobId) -> Option<&JobRecord> {
+        self.records.iter().find(|job| job.id == id)
+    }
+
+    /// The most recent queued or running job of `kind`
+    pub fn active(&self, kind: JobKind) -> Option<&JobRecord> {
+        self.records.iter().rev().find(|job| job.kind == kind && job.state.is_active())
+    }
+
+    fn get_mut(&mut self, id: JobId) -> Option<&mut JobRecord> {
+        self.records.iter_mut().find(|job| job.id == id)
+    }
+
+    /// Attach the handle used to abort a spawned task
+    pub fn set_abort(&mut self, id: JobId, handle: AbortHandle) {
+        if let Some(job) = self.get_mut(id) {
+            job.abort = Some(handle);
+        }
+    }
+
+    pub fn start(&mut self, id: JobId) {
+        if let Some(job) = self.get_mut(id) {
+            if job.state == JobState::Queued {
+                job.state = JobState::Running;
+                job.started = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Mark a job as done; `error` is Some if it failed
+    pub fn finish(&mut self, id: JobId, error: Option<String>) {
+        if let Some(job) = self.get_mut(id) {
+            if !job.state.is_active() {
+                return;
+            }
+            job.state = match error {
+                Some(e) => JobState::Failed(e),
+                None => JobState::Finished,
+            };
+            job.finished = Some(Instant::now());
+            job.abort = None;
+        }
+    }
+
+    /// Cancel an active job, aborting its task if it has one
+    /// Returns the cancelled job's kind and target
+    pub fn cancel(&mut self, id: JobId) -> Option<(JobKind, String)> {
+        let job = self.get_mut(id)?;
+        if !job.state.is_active() {
+            return None;
+        }
+        if let Some(handle) = job.abort.take() {
+            handle.abort();
+        }
+        job.state = JobState::Cancelled;
+        job.finished = Some(Instant::now());
+        Some((job.kind, job.target.clone()))
+    }
+
+    /// Drop the oldest finished jobs beyond the history limit
+    fn prune(&mut self) {
+        let finished = self.records.iter().filter(|job| !job.state.is_active()).count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        self.records.retain(|job| {
+            if excess > 0 && !job.state.is_active() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_lifecycle() {
+        let mut jobs = JobList::default();
+        let id = jobs.add(JobKind::Clone, "https://github.com/owner/repo");
+        assert_eq!(jobs.get(id).unwrap().state, JobState::Queued);
+
+        jobs.start(id);
+        assert_eq!(jobs.get(id).unwrap().state, JobState::Running);
+        assert!(jobs.get(id).unwrap().started.is_some());
+
+        jobs.finish(id, Some("boom".to_string()));
+        assert_eq!(jobs.get(id).unwrap().state, JobState::Failed("boom".to_string()));
+
+        // Finished jobs can't be cancelled or finished again
+        assert!(jobs.cancel(id).is_none());
+        jobs.finish(id, None);
+        assert_eq!(jobs.get(id).unwrap().state, JobState::Failed("boom".to_string()));
+    }
+
+    #[test]
+    fn test_cancel_active_job() {
+        let mut jobs = JobList::default();
+        let id = jobs.add(JobKind::FileCount, "https://github.com/owner/repo");
+
+        let cancelled = jobs.cancel(id);
+        assert_eq!(cancelled, Some((JobKind::FileCount, "https://github.com/owner/repo".to_string())));
+        assert_eq!(jobs.get(id).unwrap().state, JobState::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_manager_queues_limited_jobs() {
+        let mut jobs = JobManager::new();
+        jobs.set_clone_parallelism(1);
+
+        // Searches aren't limited: they run right away
+        let search = jobs.spawn(JobKind::Search, "rust", |_, _| async {});
+        assert_eq!(jobs.list.get(search).unwrap().state, JobState::Running);
+
+        // Clones wait for a slot, and say when they get one
+        let (release, wait) = tokio::sync::oneshot::channel::<()>();
+        let first = jobs.spawn(JobKind::Clone, "https://github.com/owner/first", |_, _| async move {
+            let _ = wait.await;
+        });
+        let second = jobs.spawn(JobKind::Clone, "https://github.com/owner/second", |_, _| async {});
+        assert_eq!(jobs.list.get(first).unwrap().state, JobState::Queued);
+        assert!(matches!(jobs.rx.recv().await, Some(JobEvent::Started { id }) if id == first));
+        assert!(jobs.next_event().is_none());
+
+        release.send(()).unwrap();
+        assert!(matches!(jobs.rx.recv().await, Some(JobEvent::Started { id }) if id == second));
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut jobs = JobList::default();
+        let running = jobs.add(JobKind::Search, "still running");
+        jobs.start(running);
+        for i in 0..(MAX_FINISHED_JOBS + 5) {
+            let id = jobs.add(JobKind::Search, format!("query {}", i));
+            jobs.finish(id, None);
+        }
+        jobs.add(JobKind::Search, "trigger prune");
+
+        let finished = jobs.records.iter().filter(|job| !job.state.is_active()).count();
+        assert_eq!(finished, MAX_FINISHED_JOBS);
+        // Active jobs are never pruned
+        assert!(jobs.get(running).is_some());
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::{bail, Result};
+use octocrab::models::Repository;
+
+/// What a license asks of a project that includes the code
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Terms {
+    /// Keep the notice; usable anywhere
+    Permissive,
+    /// Changes to the code itself stay open; usable as a library anywhere
+    WeakCopyleft,
+    /// The whole project must be under a compatible copyleft license
+    Copyleft,
+}
+
+/// SPDX ids (as GitHub reports them) the checker knows, and their terms
+const LICENSES: &[(&str, Terms)] = &[
+    ("0BSD", Terms::Permissive),
+    ("Apache-2.0", Terms::Permissive),
+    ("BSD-2-Clause", Terms::Permissive),
+    ("BSD-3-Clause", Terms::Permissive),
+    ("BSD-3-Clause-Clear", Terms::Permissive),
+    ("BSL-1.0", Terms::Permissive),
+    ("CC0-1.0", Terms::Permissive),
+    ("ISC", Terms::Permissive),
+    ("MIT", Terms::Permissive),
+    ("MIT-0", Terms::Permissive),
+    ("NCSA", Terms::Permissive),
+    ("PostgreSQL", Terms::Permissive),
+    ("Unlicense", Terms::Permissive),
+    ("UPL-1.0", Terms::Permissive),
+    ("WTFPL", Terms::Permissive),
+    ("Zlib", Terms::Permissive),
+    ("EPL-1.0", Terms::WeakCopyleft),
+    ("EPL-2.0", Terms::WeakCopyleft),
+    ("LGPL-2.1", Terms::WeakCopyleft),
+    ("LGPL-3.0", Terms::WeakCopyleft),
+    ("MPL-2.0", Terms::WeakCopyleft),
+    ("AGPL-3.0", Terms::Copyleft),
+    ("EUPL-1.2", Terms::Copyleft),
+    ("GPL-2.0", Terms::Copyleft),
+    ("GPL-3.0", Terms::Copyleft),
+    ("OSL-3.0", Terms::Copyleft),
+];
+
+/// Copyleft licenses a project may be under to include code under the key license, beyond itself
+const COPYLEFT_UPGRADES: &[(&str, &[&str])] = &[("GPL-3.0", &["AGPL-3.0"])];
+
+/// The known SPDX id `id` stands for, ignoring case, "-only"/"-or-later"/"+" and a missing ".0"
+fn known(id: &str) -> Option<(&'static str, Terms)> {
+    let id = id.trim();
+    let id = ["-only", "-or-later", "+"].iter().fold(id, |id, suffix| id.strip_suffix(suffix).unwrap_or(id));
+    LICENSES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(id) || known.eq_ignore_ascii_case(&format!("{}.0", id)))
+        .copied()
+}
+
+/// The licenses of the project_license setting, e.g. "MIT OR Apache-2.0", "MIT/Apache-2"
+pub fn parse_project(text: &str) -> Result<Vec<&'static str>> {
+    let mut licenses = Vec::new();
+    for id in text.split(['/', ',']).flat_map(|part| part.split(" OR ")).map(str::trim).filter(|id| !id.is_empty()) {
+        match known(id) {
+            Some((id, _)) => licenses.push(id),
+            None => bail!("unknown license `{}` (use SPDX ids like MIT, Apache-2.0, or GPL-3.0)", id),
+        }
+    }
+    if licenses.is_empty() {
+        bail!("name at least one license, e.g. \"MIT OR Apache-2.0\"");
+    }
+    Ok(licenses)
+}
+
+/// Why code under `license` (GitHub's SPDX id) can't go into a project under every one of
+/// `project`'s licenses, or None when it can
+fn license_conflict(license: Option<&str>, project: &[&str]) -> Option<String> {
+    let spdx = match license {
+        None => return Some("no license".to_string()),
+        Some("NOASSERTION") => return Some("custom license".to_string()),
+        Some(spdx) => spdx,
+    };
+    let Some((id, terms)) = known(spdx) else {
+        return Some(format!("{} not recognized", spdx));
+    };
+    let usable_in = |target: &str| match terms {
+        // GPL-2.0 projects can't take Apache-2.0's patent terms
+        Terms::Permissive => !(id == "Apache-2.0" && target == "GPL-2.0"),
+        // EPL's terms clash with the GPL's
+        Terms::WeakCopyleft => !(id.starts_with("EPL") && target.contains("GPL")),
+        Terms::Copyleft => {
+            target == id
+                || COPYLEFT_UPGRADES.iter().any(|(from, to)| *from == id && to.contains(&target))
+        }
+    };
+    let blocked: Vec<&str> = project.iter().copied().filter(|target| !usable_in(target)).collect();
+    (!blocked.is_empty()).then(|| format!("{} can't go into {}", id, blocked.join("/")))
+}
+
+/// Why `repo` can't be used in a project under `project`'s licenses, None when it can (or no
+/// project license is set)
+pub fn conflict(repo: &Repository, project: &[&str]) -> Option<String> {
+    if project.is_empty() {
+        return None;
+    }
+    license_conflict(repo.license.as_ref().map(|license| license.spdx_id.as_str()), project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_project() {
+        assert_eq!(parse_project("MIT OR Apache-2.0").unwrap(), ["MIT", "Apache-2.0"]);
+        assert_eq!(parse_project("mit/Apache-2").unwrap(), ["MIT", "Apache-2.0"]);
+        assert_eq!(parse_project("GPL-3.0-or-later").unwrap(), ["GPL-3.0"]);
+        assert!(parse_project("MIT, Beerware").unwrap_err().to_string().contains("unknown license `Beerware`"));
+        assert!(parse_project(" / ").is_err());
+    }
+
+    #[test]
+    fn test_license_conflict() {
+        let permissive = ["MIT", "Apache-2.0"];
+        assert_eq!(license_conflict(Some("BSD-3-Clause"), &permissive), None);
+        assert_eq!(license_conflict(Some("MPL-2.0"), &permissive), None);
+        assert_eq!(license_conflict(Some("GPL-3.0"), &permissive).as_deref(), Some("GPL-3.0 can't go into MIT/Apache-2.0"));
+        assert_eq!(license_conflict(None, &permissive).as_deref(), Some("no license"));
+        assert_eq!(license_conflict(Some("NOASSERTION"), &permissive).as_deref(), Some("custom license"));
+        assert_eq!(license_conflict(Some("Beerware"), &permissive).as_deref(), Some("Beerware not recognized"));
+
+        assert_eq!(license_conflict(Some("GPL-3.0"), &["AGPL-3.0"]), None);
+        assert_eq!(license_conflict(Some("AGPL-3.0"), &["GPL-3.0"]).as_deref(), Some("AGPL-3.0 can't go into GPL-3.0"));
+        assert_eq!(license_conflict(Some("Apache-2.0"), &["GPL-2.0"]).as_deref(), Some("Apache-2.0 can't go into GPL-2.0"));
+        assert_eq!(license_conflict(Some("Apache-2.0"), &["GPL-3.0"]), None);
+        assert_eq!(license_conflict(Some("EPL-2.0"), &["GPL-3.0"]).as_deref(), Some("EPL-2.0 can't go into GPL-3.0"));
+    }
+}
@@ -0,0 +1,313 @@
+use anyhow::Result;
+use octocrab::models::Repository;
+use std::io::{self, BufRead, Write};
+
+use crate::bookmarks::Bookmarks;
+use crate::cache;
+use crate::clones::{self, CloneLedger};
+use crate::config::Config;
+use crate::git::CloneOptions;
+use crate::github::GitHub;
+use crate::i18n::tr;
+use crate::suggest;
+use crate::{analysis, clone_and_analyze, clone_destination, clone_repository, count_repository_files};
+use crate::{open_in_browser, search_with_cache, Args};
+
+/// What the last line typed asks for
+#[derive(Debug, PartialEq)]
+enum Input {
+    Quit,
+    Nothing,
+    Select(usize), // 1-based result number
+    Action(char),  // o, c, f, a, or b, on the selected result
+    List,          // Read the results again
+    Search(String),
+}
+
+/// Interpret a typed line; single letters are actions only while a result is selected
+fn parse_input(line: &str, selected: bool) -> Input {
+    let line = line.trim();
+    match line {
+        "" => Input::Nothing,
+        "q" | "quit" | "exit" => Input::Quit,
+        "l" | "list" => Input::List,
+        "o" | "c" | "f" | "a" | "b" if selected => Input::Action(line.chars().next().unwrap_or_default()),
+        _ => match line.parse::<usize>() {
+            Ok(number) => Input::Select(number),
+            Err(_) => Input::Search(line.to_string()),
+        },
+    }
+}
+
+/// One result as a single sentence, e.g. "3. rust-lang/rust, 98000 stars, Rust: Empowering everyone..."
+fn result_line(number: usize, repo: &Repository) -> String {
+    let mut line = tr!(
+        "linear-result",
+        number = number,
+        name = full_name(repo),
+        stars = repo.stargazers_count.unwrap_or(0),
+        language = language(repo)
+    );
+    if let Some(description) = repo.description.as_deref().filter(|d| !d.is_empty()) {
+        line.push_str(&format!(": {}", description));
+    }
+    line
+}
+
+/// Everything the details panel shows, one fact per line
+fn details_lines(number: usize, total: usize, repo: &Repository, bookmarks: &Bookmarks, cloned: Option<&str>) -> Vec<String> {
+    let url = repo.html_url.as_ref().map(|url| url.to_string()).unwrap_or_default();
+    let fact = |id: &str, value: String| format!("{}: {}", tr!(id), value);
+    let mut lines = vec![
+        tr!("linear-result-of", number = number, total = total, name = full_name(repo)),
+        fact("details-description", repo.description.clone().unwrap_or_else(|| tr!("linear-no-description"))),
+        fact("details-stars", repo.stargazers_count.unwrap_or(0).to_string()),
+        fact("details-forks", repo.forks_count.unwrap_or(0).to_string()),
+        fact("details-language", language(repo)),
+        fact("details-size", clones::format_size(repo.size.unwrap_or(0) as u64 * 1024)),
+        fact("linear-url", url.clone()),
+    ];
+    if let Some(activity) = crate::tui::activity_label(repo, std::time::SystemTime::now()) {
+        lines.insert(lines.len() - 1, fact("linear-last-activity", activity));
+    }
+    if bookmarks.contains(&url) {
+        lines.push(tr!("details-bookmarked"));
+    }
+    if let Some(path) = cloned {
+        lines.push(tr!("linear-cloned-to", path = path));
+    }
+    lines
+}
+
+fn full_name(repo: &Repository) -> String {
+    repo.full_name.clone().unwrap_or_else(|| tr!("linear-unknown"))
+}
+
+fn language(repo: &Repository) -> String {
+    repo.language.as_ref().and_then(|v| v.as_str()).map_or_else(|| tr!("linear-unknown-language"), str::to_string)
+}
+
+/// What can be typed next: with a result selected, the actions on it, or else a result number
+fn prompt(selected: bool) -> String {
+    if selected {
+        tr!("linear-details-prompt")
+    } else {
+        tr!("linear-results-prompt")
+    }
+}
+
+/// Screen-reader friendly interactive mode: plain lines on stdout, one prompt at a time,
+/// no alternate screen, box drawing, colors, or redraws
+pub async fn run(github: &dyn GitHub, args: &Args, config: &Config) -> Result<()> {
+    let mut bookmarks = Bookmarks::load()?;
+    let search_cache = cache::SharedCache::load();
+    let mut results: Vec<Repository> = Vec::new();
+    let mut selected: Option<usize> = None;
+    let stdin = io::stdin();
+
+    println!("{}", tr!("linear-welcome"));
+    println!("{}", tr!("linear-start"));
+    loop {
+        print!("{}> ", if selected.is_some() { tr!("linear-details") } else { tr!("linear-search") });
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break; // End of input
+        }
+
+        match parse_input(&line, selected.is_some()) {
+            Input::Quit => break,
+            Input::Nothing => println!("{}", prompt(selected.is_some())),
+            Input::List if !results.is_empty() => {
+                selected = None;
+                print_results(&results);
+            }
+            Input::List => println!("{}", tr!("linear-no-results-yet")),
+            Input::Select(number) if (1..=results.len()).contains(&number) => {
+                selected = Some(number - 1);
+                let repo = &results[number - 1];
+                let url = repo.html_url.as_ref().map(|url| url.to_string()).unwrap_or_default();
+                let ledger = CloneLedger::load()?;
+                let cloned = ledger.find(&url).map(|path| path.display().to_string());
+                for detail in details_lines(number, results.len(), repo, &bookmarks, cloned.as_deref()) {
+                    println!("{}", detail);
+                }
+                println!("{}", prompt(true));
+            }
+            Input::Select(number) if results.is_empty() => {
+                search(github, &search_cache, args, &number.to_string(), &mut results).await;
+                selected = None;
+            }
+            Input::Select(number) => println!("{}", tr!("linear-no-such-result", number = number, count = results.len())),
+            Input::Action(action) => {
+                let Some(repo) = selected.and_then(|index| results.get(index)) else {
+                    continue;
+                };
+                act(action, repo, github, config, &mut bookmarks).await?;
+            }
+            Input::Search(query) => {
+                search(github, &search_cache, args, &query, &mut results).await;
+                selected = None;
+            }
+        }
+    }
+    println!("{}", tr!("linear-goodbye"));
+    Ok(())
+}
+
+/// Run a search and read out the results (or why there are none)
+async fn search(github: &dyn GitHub, search_cache: &cache::SharedCache, args: &Args, query: &str, results: &mut Vec<Repository>) {
+    println!("{}", tr!("linear-searching", query = query));
+    match search_with_cache(github, search_cache, query, args, &None).await {
+        Ok((items, total, _, cached, changes)) => {
+            if let Some(cache::Hit::Offline(fetched)) = cached {
+                let age = clones::format_age(fetched, std::time::SystemTime::now());
+                println!("{}", tr!("linear-offline", age = age));
+            }
+            if let Some(changes) = &changes {
+                if let Some(summary) = changes.summary(std::time::SystemTime::now()) {
+                    println!("{}.", summary);
+                }
+                if !changes.removed.is_empty() {
+                    println!("{}", tr!("linear-gone", names = changes.removed.join(", ")));
+                }
+            }
+            *results = items;
+            if results.is_empty() {
+                println!("{}", tr!("linear-none-found"));
+                match suggest::suggest(query) {
+                    Some(suggestion) => println!("{}", tr!("linear-did-you-mean", query = suggestion.query)),
+                    None => println!("{}", tr!("linear-search-again")),
+                }
+            } else {
+                println!("{}", tr!("linear-found", total = total, shown = results.len()));
+                print_results(results);
+            }
+        }
+        Err(e) => {
+            tracing::warn!(query, error = %e, "search failed");
+            println!("{}", tr!("linear-search-failed", error = e.with_guidance()));
+        }
+    }
+}
+
+fn print_results(results: &[Repository]) {
+    for (index, repo) in results.iter().enumerate() {
+        println!("{}", result_line(index + 1, repo));
+    }
+    println!("{}", prompt(false));
+}
+
+/// Carry out a single-letter action on the selected repository, saying what happened
+async fn act(action: char, repo: &Repository, github: &dyn GitHub, config: &Config, bookmarks: &mut Bookmarks) -> Result<()> {
+    let name = full_name(repo);
+    let url = repo.html_url.as_ref().map(|url| url.to_string()).unwrap_or_default();
+    let options = CloneOptions {
+        depth: config.clone_depth.filter(|depth| *depth > 0),
+        mode: config.clone_mode,
+        submodules: config.clone_submodules,
+    };
+    match action {
+        'o' => {
+            open_in_browser(&url);
+            println!("{}", tr!("linear-opened", url = url.as_str()));
+        }
+        'b' => {
+            let bookmarked = bookmarks.toggle(&name, &url);
+            bookmarks.save()?;
+            let message = if bookmarked { "linear-bookmarked" } else { "linear-unbookmarked" };
+            println!("{}", tr!(message, name = name.as_str()));
+        }
+        'c' => {
+            let path = clone_destination(&url, None, false, config.clone_layout)?;
+            println!("{}", tr!("linear-cloning", name = name.as_str(), path = path.display().to_string()));
+            match clone_repository(&url, &path, &options, |_| {}).await {
+                Ok(()) => {
+                    let mut ledger = CloneLedger::load()?;
+                    ledger.record(&url, &path);
+                    ledger.save()?;
+                    println!("{}", tr!("linear-cloned", name = name.as_str()));
+                }
+                Err(e) => println!("{}", tr!("linear-clone-failed", error = crate::error::describe(&e))),
+            }
+        }
+        'f' => {
+            println!("{}", tr!("linear-counting", name = name.as_str()));
+            // Counts only need the latest commit unless clone_depth says otherwise
+            let options = CloneOptions { depth: config.clone_depth.or(Some(1)).filter(|depth| *depth > 0), ..options };
+            match count_repository_files(github, &url, &options, &config.count_filter()).await {
+                Ok(stats) => println!("{}", stats),
+                Err(e) => println!("{}", tr!("linear-count-failed", error = crate::error::describe(&e))),
+            }
+        }
+        'a' => {
+            println!("{}", tr!("linear-analyzing", name = name.as_str()));
+            let options = CloneOptions { depth: Some(1), ..options };
+            match clone_and_analyze(&url, &options, &config.count_filter()).await {
+                Ok(stats) if stats.is_empty() => println!("{}", tr!("linear-no-sources")),
+                Ok(stats) => {
+                    for language in stats.iter().chain([&analysis::total(&stats)]) {
+                        let line = tr!(
+                            "linear-language",
+                            language = language.name.as_str(),
+                            files = language.files,
+                            code = language.code,
+                            comments = language.comments,
+                            blanks = language.blanks
+                        );
+                        println!("{}", line);
+                    }
+                }
+                Err(e) => println!("{}", tr!("linear-analysis-failed", error = crate::error::describe(&e))),
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock;
+
+    fn repo(description: Option<&str>) -> Repository {
+        mock::repo("rust-lang/rust")
+            .set("description", description)
+            .stars(98000)
+            .set("forks_count", 12000)
+            .set("language", "Rust")
+            .set("size", 2048)
+            .build()
+    }
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input("  q\n", false), Input::Quit);
+        assert_eq!(parse_input("\n", true), Input::Nothing);
+        assert_eq!(parse_input("3\n", false), Input::Select(3));
+        assert_eq!(parse_input("c\n", true), Input::Action('c'));
+        // Without a selection a single letter is a search
+        assert_eq!(parse_input("c\n", false), Input::Search("c".to_string()));
+        assert_eq!(parse_input("rust game\n", true), Input::Search("rust game".to_string()));
+        assert_eq!(parse_input("list", true), Input::List);
+    }
+
+    #[test]
+    fn test_result_and_details_text() {
+        assert_eq!(
+            result_line(1, &repo(Some("Empowering everyone"))),
+            "1. rust-lang/rust, 98000 stars, Rust: Empowering everyone"
+        );
+        assert_eq!(result_line(2, &repo(None)), "2. rust-lang/rust, 98000 stars, Rust");
+
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.toggle("rust-lang/rust", "https://github.com/rust-lang/rust");
+        let lines = details_lines(1, 5, &repo(None), &bookmarks, Some("repositories/rust"));
+        assert_eq!(lines[0], "Result 1 of 5: rust-lang/rust");
+        assert!(lines.contains(&"Size: 2.0 MiB".to_string()));
+        assert_eq!(lines[lines.len() - 2..], ["Bookmarked", "Cloned to repositories/rust"]);
+        // Plain text only, nothing for a screen reader to spell out
+        assert!(lines.iter().all(|line| line.is_ascii()));
+    }
+}
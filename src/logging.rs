@@ -0,0 +1,52 @@
+use anyhow::{Context, Result};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+use crate::paths;
+
+/// Directory in the data dir the log files are written to
+const LOG_DIR: &str = "logs";
+
+/// Daily log files kept before the oldest is deleted
+const KEEP_LOG_FILES: usize = 7;
+
+/// Send tracing events to a daily-rotated file in the data dir's `logs/` (never stdout, which the TUI owns)
+/// `level` is an EnvFilter directive such as "info" or "github_search_cli=debug"; RUST_LOG overrides it
+/// `console` also prints events matching its directive to stderr (CLI mode's --verbose / --debug),
+/// colored if `color` is set
+/// Keep the returned guard alive until exit so buffered lines are flushed
+pub fn init(level: &str, console: Option<&str>, color: bool) -> Result<WorkerGuard> {
+    let filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new(level))
+        .with_context(|| format!("Invalid log_level '{}'", level))?;
+    // Created up front: the appender prunes old files before it would create the directory
+    let dir = paths::data_dir().join(LOG_DIR);
+    std::fs::create_dir_all(&dir).context("Failed to create the logs directory")?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("mrkrabz")
+        .filename_suffix("log")
+        .max_log_files(KEEP_LOG_FILES)
+        .build(dir)
+        .context("Failed to open the log file")?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let file = fmt::layer().with_writer(writer).with_ansi(false).with_filter(filter);
+    let console = console.map(|directive| {
+        fmt::layer()
+            .with_writer(std::io::stderr)
+            .without_time()
+            .with_target(false)
+            .with_ansi(color)
+            .with_filter(EnvFilter::new(directive))
+    });
+    tracing_subscriber::registry()
+        .with(file)
+        .with(console)
+        .try_init()
+        .map_err(|e| anyhow::anyhow!("Failed to start logging: {}", e))?;
+    Ok(guard)
+}
@@ -1,17 +1,66 @@
 // TUI module containing the terminal interface logic
 mod tui;
 mod config;
+mod bookmarks;
+mod toast;
+mod jobs;
+mod git;
+mod clones;
+mod repos;
+mod sync;
+mod filecount;
+mod analysis;
+mod logging;
+mod error;
+mod cache;
+mod paths;
+mod client;
+mod icons;
+mod linear;
+mod columns;
+mod export;
+mod compare;
+mod suggest;
+mod qualifiers;
+mod github;
+mod bench;
+mod i18n;
+mod message;
+mod modal;
+mod awesome;
+mod stars;
+mod maintenance;
+mod licenses;
+mod sbom;
+mod audit;
+mod analysis_store;
+mod build_system;
+mod profiles;
+mod text_match;
+mod scheduler;
+mod osc;
+mod pane;
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
-use octocrab::Octocrab;
-use tui::{App, restore_terminal, run_tui, setup_terminal};
+use github::{Client, GitHub};
+use config::CloneLayout;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use toast::ToastLevel;
+use i18n::tr;
+use icons::Icon;
+use message::Action;
+use jobs::SearchResults;
+use tui::{App, RateLimitInfo, restore_terminal, run_tui, setup_terminal};
 
 /// GitHub repository search CLI tool with interactive TUI
 /// Supports both CLI mode (with query args) and TUI mode (interactive)
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, disable_help_subcommand = true)]
 struct Args {
     /// Search query (e.g., "large rust game"). If omitted, starts in interactive mode.
     #[arg()]
@@ -29,6 +78,39 @@ struct Args {
     #[arg(short, long)]
     stars: Option<u32>,
 
+    /// Only match the query words in these fields; repeat for several (e.g. --in name --in topics)
+    #[arg(long = "in", value_enum, value_name = "FIELD")]
+    in_fields: Vec<InField>,
+
+    /// Which repositories to search: public, private, or internal ones (private and internal need a
+    /// token); all of them when unset
+    #[arg(long, value_enum)]
+    visibility: Option<Visibility>,
+
+    /// Filter by minimum forks
+    #[arg(long)]
+    forks: Option<u32>,
+
+    /// Filter by topic; repeat for repositories tagged with all of them (e.g. --topic cli --topic tui)
+    #[arg(long = "topic", value_name = "TOPIC")]
+    topics: Vec<String>,
+
+    /// Filter by license keyword (e.g. "mit", "apache-2.0", "gpl")
+    #[arg(long)]
+    license: Option<String>,
+
+    /// Only archived repositories, or with --archived=false none of them
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    archived: Option<bool>,
+
+    /// Only repositories created on or after DATE (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    created_after: Option<String>,
+
+    /// Only repositories pushed to on or after DATE (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    pushed_after: Option<String>,
+
     /// Filter by repository size: small (<25MB), medium (25-100MB), large (>100MB)
     #[arg(long)]
     repo_size: Option<String>,
@@ -37,51 +119,335 @@ struct Args {
     #[arg(long)]
     sort: Option<String>,
 
-    /// GitHub personal access token (optional, increases rate limit)
-    #[arg(short, long)]
+    /// Only search one user's or organization's repositories: NAME, user:NAME, or org:NAME
+    #[arg(long, value_parser = parse_owner)]
+    owner: Option<String>,
+
+    /// Leave out one user's or organization's repositories: NAME, user:NAME, or org:NAME; repeat for several
+    #[arg(long, value_name = "NAME", value_parser = parse_owner)]
+    exclude_owner: Vec<String>,
+
+    /// Leave out repositories matching a word or phrase; repeat for several (e.g. --not tutorial)
+    #[arg(long = "not", value_name = "TERM")]
+    exclude_terms: Vec<String>,
+
+    /// Send the query to GitHub as written, without the default_* filters of config.toml
+    #[arg(long, conflicts_with_all = [
+        "in_fields", "visibility", "language", "stars", "forks", "topics", "license", "archived", "created_after", "pushed_after", "repo_size", "owner",
+        "exclude_owner", "exclude_terms",
+    ])]
+    raw_query: bool,
+
+    /// GitHub personal access token (optional, increases rate limit); GITHUB_TOKEN is used if unset
+    #[arg(short, long, env = "MRKRABZ_TOKEN", hide_env_values = true)]
     token: Option<String>,
 
     /// Force CLI mode (no interactive TUI)
     #[arg(long)]
     no_tui: bool,
+
+    /// CLI mode: print the composed query, request URLs, timing, and rate limit to stderr
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// CLI mode: like --verbose, plus debug output from every component (HTTP client, git, ...)
+    #[arg(long)]
+    debug: bool,
+
+    /// Interactive mode as plain numbered text with one prompt at a time, for screen readers
+    #[arg(long, visible_alias = "screen-reader")]
+    linear: bool,
+
+    /// CLI mode: print only "owner/name<TAB>URL" per repository, for scripts (errors still go to stderr)
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// CLI mode: exit with code 3 when nothing matches (rate limits exit with 4, auth errors with 5)
+    #[arg(long)]
+    fail_if_empty: bool,
+
+    /// CLI mode: also write the results to PATH: markdown, or JSON/CSV for .json/.csv files
+    #[arg(short, long, value_name = "PATH")]
+    output: Option<PathBuf>,
+
+    /// When to color output; auto colors only a terminal, unless NO_COLOR is set
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// Time each step of a search (query build, API round-trip, parsing, rendering) over RUNS runs
+    #[arg(long, hide = true, value_name = "RUNS", num_args = 0..=1, require_equals = true, default_missing_value = "5")]
+    bench: Option<u32>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// --owner's or --exclude-owner's value as the qualifier it names
+fn parse_owner(value: &str) -> Result<String, String> {
+    qualifiers::owner_scope(value).ok_or_else(|| format!("'{}' isn't a GitHub user or organization name", value))
+}
+
+/// --in values: the repository fields the in: qualifier can limit matches to
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum InField {
+    Name,
+    Description,
+    Readme,
+    Topics,
+}
+
+impl InField {
+    fn qualifier_value(self) -> &'static str {
+        match self {
+            InField::Name => "name",
+            InField::Description => "description",
+            InField::Readme => "readme",
+            InField::Topics => "topics",
+        }
+    }
+}
+
+/// --visibility values
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum Visibility {
+    Public,
+    Private,
+    Internal, // Visible to every member of an enterprise
+    All,
+}
+
+impl Visibility {
+    /// The is: qualifier; none for all, which is what a search returns anyway
+    fn qualifier(self) -> Option<&'static str> {
+        match self {
+            Visibility::Public => Some("is:public"),
+            Visibility::Private => Some("is:private"),
+            Visibility::Internal => Some("is:internal"),
+            Visibility::All => None,
+        }
+    }
+}
+
+/// --created-after and --pushed-after values
+fn parse_date(value: &str) -> Result<String, String> {
+    if suggest::is_date(value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!("'{}' isn't a date (use YYYY-MM-DD, e.g. 2024-01-31)", value))
+    }
+}
+
+/// --color values
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+/// Whether to print colors: --color always/never wins, then NO_COLOR (when set and non-empty),
+/// then whether stdout is a terminal
+fn use_color(choice: ColorChoice, no_color: Option<std::ffi::OsString>, terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => no_color.is_none_or(|value| value.is_empty()) && terminal,
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage previously cloned repositories
+    Repos {
+        #[command(subcommand)]
+        action: repos::ReposCommand,
+    },
+    /// Create or inspect config.toml
+    Config {
+        #[command(subcommand)]
+        action: config::ConfigCommand,
+    },
+    /// Clone or update every repository in sync_repos (config.toml) and/or your bookmarks
+    Sync {
+        /// Also sync bookmarked repositories (used automatically when sync_repos is empty)
+        #[arg(long)]
+        bookmarks: bool,
+    },
 }
 
 /// Main entry point
 /// Parses CLI args and routes to either TUI mode or CLI mode
 #[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+async fn main() -> Result<ExitCode> {
+    let mut args = Args::parse();
+    // Lowest-precedence token source, after --token and MRKRABZ_TOKEN
+    if args.token.is_none() {
+        args.token = std::env::var("GITHUB_TOKEN").ok().filter(|token| !token.is_empty());
+    }
+    let color = use_color(args.color, std::env::var_os("NO_COLOR"), std::io::stdout().is_terminal());
+    colored::control::set_override(color);
 
-    // Build the GitHub API client (octocrab)
-    // If token provided, use it for higher rate limits (5000/hr vs 60/hr)
-    let octocrab = if let Some(token) = &args.token {
-        Octocrab::builder()
-            .personal_token(token.clone())
-            .build()?
-    } else {
-        Octocrab::builder().build()?
+    // Bookmarks, the clones ledger, the search cache, and logs live in the user data directory
+    let copied = paths::prepare_data_dir();
+
+    // `config` subcommands handle an invalid config themselves (`config init --force` fixes it)
+    let config = match (config::Config::load(), &args.command) {
+        (Ok(config), _) => config,
+        (Err(e), Some(Command::Config { action })) => {
+            return config::run(action, Err(e), args.token.is_some()).map(|()| ExitCode::SUCCESS);
+        }
+        (Err(e), _) => return Err(e),
     };
+    icons::set_style(config.icons);
+    i18n::set_language(config.language);
+    apply_default_filters(&mut args, &config);
+
+    // Logs go to a file so they never draw over the TUI; a failure here shouldn't stop the app
+    let _log_guard = match logging::init(&config.log_level, console_log_filter(&args), color) {
+        Ok(guard) => Some(guard),
+        Err(e) => {
+            eprintln!("{} {:#}", "Warning: logging disabled:".yellow(), e);
+            None
+        }
+    };
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "starting");
+    match copied {
+        Ok(copied) if !copied.is_empty() => {
+            tracing::info!(?copied, data_dir = %paths::data_dir().display(), "copied data files from the working directory")
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("{} {:#}", "Warning:".yellow(), e),
+    }
+
+    // Subcommands work on local state and don't need the GitHub client
+    match &args.command {
+        Some(Command::Repos { action }) => return repos::run(action).await.map(|()| ExitCode::SUCCESS),
+        Some(Command::Sync { bookmarks }) => return sync::run(&config, *bookmarks).await.map(|()| ExitCode::SUCCESS),
+        Some(Command::Config { action }) => {
+            return config::run(action, Ok(config), args.token.is_some()).map(|()| ExitCode::SUCCESS);
+        }
+        None => {}
+    }
+
+    // Build the GitHub API client (octocrab)
+    // If token provided, use it for higher rate limits (5000/hr vs 60/hr); a configured GitHub App takes its place
+    if config.danger_accept_invalid_certs {
+        eprintln!("{} danger_accept_invalid_certs is set: GitHub's TLS certificate isn't being checked", "Warning:".yellow());
+        tracing::warn!("TLS certificate verification is disabled");
+    }
+    // Signed out, GitHub only searches public repositories
+    let signed_in = args.token.is_some() || config.github_app_id.is_some();
+    if matches!(args.visibility, Some(Visibility::Private | Visibility::Internal)) && !signed_in {
+        eprintln!("{}", "Error: --visibility private and internal need a token (--token or GITHUB_TOKEN) or a GitHub App".red());
+        return Ok(ExitCode::from(2));
+    }
+    // Every request goes through one scheduler, which keeps them within GitHub's rate limits
+    let rate_limits = Arc::new(scheduler::RateLimits::default());
+    let octocrab = client::github(args.token.as_deref(), &config, rate_limits.clone()).await?;
+    let scheduler = scheduler::Scheduler::new(Arc::new(octocrab), signed_in, rate_limits);
+    let github: Client = Arc::new(scheduler.clone());
+
+    if let Some(runs) = args.bench {
+        return bench::run(github.as_ref(), &args, runs).await;
+    }
 
     // Route to appropriate mode based on args
     if args.query.is_empty() && !args.no_tui {
-        // No query + TUI allowed = Interactive mode
-        run_interactive_mode(octocrab, &args).await?;
+        // No query + TUI allowed = Interactive mode (or its screen-reader friendly linear form)
+        if args.linear || config.linear_mode {
+            linear::run(github.as_ref(), &args, &config).await?;
+        } else {
+            run_interactive_mode(github, scheduler.prefetching(), &args, &config).await?;
+        }
     } else if !args.query.is_empty() {
         // Query provided = CLI mode (print results and exit)
-        run_cli_mode(octocrab, &args).await?;
+        return run_cli_mode(github.as_ref(), &args).await;
     } else {
         // No query + TUI disabled = Error
         println!("{}", "Error: No query provided. Use --help for usage.".red());
+        return Ok(ExitCode::from(2));
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Fill in the filters the command line leaves unset from config.toml's default_* settings
+/// (none with --raw-query)
+fn apply_default_filters(args: &mut Args, config: &config::Config) {
+    if args.raw_query {
+        return;
+    }
+    args.language = args.language.take().or_else(|| config.default_language.clone());
+    args.stars = args.stars.or(config.default_min_stars);
+    args.repo_size = args.repo_size.take().or_else(|| config.default_repo_size.clone());
+    args.sort = args.sort.take().or_else(|| config.default_sort.clone());
+}
+
+/// Filter for --verbose / --debug output on stderr; None while the TUI owns the terminal
+fn console_log_filter(args: &Args) -> Option<&'static str> {
+    let tui = args.command.is_none() && args.query.is_empty() && !args.no_tui;
+    match (args.debug, args.verbose) {
+        _ if tui => None,
+        (true, _) => Some("debug"),
+        // octocrab's debug events carry each request's method and URL
+        (false, true) => Some("github_search_cli=info,octocrab=debug"),
+        (false, false) => None,
+    }
 }
 
 /// Interactive TUI mode
 /// Sets up terminal, runs event loop, handles search/file count/browser actions
-async fn run_interactive_mode(octocrab: Octocrab, args: &Args) -> Result<()> {
+/// `prefetch` makes the same requests as `github`, at the lowest priority
+async fn run_interactive_mode(github: Client, prefetch: Client, args: &Args, config: &config::Config) -> Result<()> {
+    // Loaded before the terminal goes raw, so a corrupt file's error prints normally
+    let bookmarks = bookmarks::Bookmarks::load()?;
+    let clones = clones::CloneLedger::load()?;
+    // Read once; every search job shares it
+    let search_cache = Arc::new(cache::SharedCache::load());
     let mut terminal = setup_terminal()?;
     let mut app = App::new();
+    app.jobs.set_clone_parallelism(config.clone_parallelism);
+    app.vim_mode = config.vim_mode;
+    app.per_page = args.limit.max(1) as u32;
+    app.keymap = config.keymap;
+    app.columns = columns::parse_all(&config.result_columns);
+    app.live_search = config.live_search;
+    app.infinite_scroll = config.infinite_scroll;
+    app.search_cache_ttl = config.search_cache_ttl_secs;
+    app.fetch_details = config.fetch_details;
+    app.prefetch_ahead = config.prefetch_ahead;
+    app.live_search_delay = std::time::Duration::from_millis(config.live_search_delay_ms);
+    app.clone_depth = config.clone_depth;
+    app.clone_mode = config.clone_mode;
+    app.clone_submodules = config.clone_submodules;
+    app.clone_layout = config.clone_layout;
+    app.clone_confirm_size_mb = config.clone_confirm_size_mb;
+    app.audit_after_clone = config.audit_after_clone;
+    app.pane_command = pane::command(config.pane_command.as_deref(), |name| std::env::var(name).ok());
+    app.analysis_profiles = config.analysis.clone();
+    app.script_timeout_secs = config.script_timeout_secs;
+    app.monochrome = !colored::control::SHOULD_COLORIZE.should_colorize();
+    app.terminal_status.enabled = config.terminal_status;
+    app.count_filter = config.count_filter();
+    // Checked when the config loaded
+    app.project_license = config.project_license.as_deref().and_then(|text| licenses::parse_project(text).ok()).unwrap_or_default();
+    app.bookmarks = bookmarks;
+    app.clones = clones;
+    app.analysis_store = analysis_store::AnalysisStore::load();
+    app.cli_filters = cli_filter_labels(args);
+    app.owner_scope = args.owner.clone();
+    app.raw_query = args.raw_query;
+
+    // Fill in the status bar in the background, so the first frame doesn't wait on GitHub
+    let fetch_user = match config.github_app_id {
+        // Installation tokens can't look up a user
+        Some(app_id) => {
+            app.auth_user = Some(format!("app {}", app_id));
+            false
+        }
+        None => args.token.is_some(),
+    };
+    app.jobs.spawn(jobs::JobKind::Status, "github", |id, tx| status_job(github.clone(), id, fetch_user, tx));
 
     // Initialize app with CLI args if provided
     if let Some(ref size) = args.repo_size {
@@ -89,103 +455,288 @@ async fn run_interactive_mode(octocrab: Octocrab, args: &Args) -> Result<()> {
     }
 
     loop {
-        // Run TUI and wait for user action
-        let result = run_tui(&mut terminal, &mut app)?;
-
-        match result {
-            None => {
-                // User pressed Esc or Ctrl+C - quit
-                restore_terminal(&mut terminal)?;
-                break;
-            }
-            Some(action) => {
-                // Handle different action types
-                if action.starts_with("CLONE:") {
-                    // Clone request
-                    let url = action.strip_prefix("CLONE:").unwrap().to_string();
-                    app.cloning = true;
-                    app.clone_status = None;
-
-                    terminal.draw(|f| tui::ui(f, &mut app))?;
-
-                    match clone_repository(&url).await {
-                        Ok(path) => {
-                            app.clone_status = Some(format!("Cloned to {}", path));
-                        }
-                        Err(e) => {
-                            app.clone_status = Some(format!("Clone failed: {}", e));
-                        }
-                    }
-
-                    app.cloning = false;
-                    terminal.draw(|f| tui::ui(f, &mut app))?;
-                } else if action.starts_with("FILECOUNT:") {
-                    // File count request
-                    let url = action.strip_prefix("FILECOUNT:").unwrap().to_string();
-                    app.counting_files = true;
-
-                    terminal.draw(|f| tui::ui(f, &mut app))?;
+        // Run TUI and wait for the next action
+        let Some(action) = run_tui(&mut terminal, &mut app)? else {
+            // User pressed Esc or Ctrl+C - quit
+            app.terminal_status.reset(&mut std::io::stdout())?;
+            restore_terminal(&mut terminal)?;
+            break;
+        };
 
-                    // Clone and count files
-                    match clone_and_count_files(&url).await {
-                        Ok(count) => {
-                            app.file_counts.insert(url.clone(), count);
-                        }
-                        Err(e) => {
-                            app.file_counts.insert(url.clone(), format!("Error: {}", e));
-                        }
+        match action {
+            Action::Clone(urls) => {
+                // Clone request (one or more URLs)
+                // Each clone runs as a background task; at most clone_parallelism run at once
+                let destination = app.clone_destination.take();
+                let batch = urls.len() > 1;
+                for url in &urls {
+                    if app.pending_clones.contains_key(url) {
+                        continue; // Already queued or running
                     }
-                    app.counting_files = false;
-
-                    // Redraw to show the result
-                    terminal.draw(|f| tui::ui(f, &mut app))?;
-                } else if action.starts_with("http") {
-                    // It's a URL - open in browser
-                    open_in_browser(&action);
-                    // Continue running the TUI
-                } else {
-                    // It's a search query
-                    app.searching = true;
-                    app.error_message = None;
-
-                    terminal.draw(|f| tui::ui(f, &mut app))?;
-
-                    // Perform search with app's current size filter
-                    match perform_search_with_filter(&octocrab, &action, args, &app.repo_size_filter).await {
-                        Ok((results, total)) => {
-                            app.set_results(results, total);
-                        }
+                    let path = match clone_destination(url, destination.as_deref(), batch, app.clone_layout) {
+                        Ok(path) => path,
                         Err(e) => {
-                            app.set_error(format!("{}", e));
+                            app.notify(ToastLevel::Error, tr!("toast-clone-failed", url = url.as_str(), error = e.to_string()));
+                            continue;
                         }
+                    };
+                    app.pending_clones.insert(url.to_string(), jobs::CloneStatus::Queued);
+                    let options = app.clone_options.clone();
+                    app.jobs.spawn(jobs::JobKind::Clone, url.as_str(), |id, tx| clone_job(id, url.clone(), path, options, tx));
+                }
+            }
+            Action::FileCount(urls) => {
+                // File count request (one or more URLs)
+                // Each count runs as a background task; results stream back through app.jobs
+                for url in &urls {
+                    if app.pending_counts.contains_key(url) {
+                        continue; // Already queued or running
+                    }
+                    app.pending_counts.insert(url.to_string(), jobs::CountStatus::Queued);
+                    let (options, filter) = (app.clone_options.clone(), app.count_filter.clone());
+                    app.jobs.spawn(jobs::JobKind::FileCount, url.as_str(), |id, tx| {
+                        file_count_job(github.clone(), id, url.clone(), options, filter, tx)
+                    });
+                }
+            }
+            Action::Analyze(urls) => {
+                // Language analysis request (one or more URLs), run like file counts
+                for url in &urls {
+                    if app.pending_analyses.contains_key(url) {
+                        continue; // Already queued or running
                     }
+                    app.pending_analyses.insert(url.to_string(), jobs::CountStatus::Queued);
+                    let (options, filter) = (app.clone_options.clone(), app.count_filter.clone());
+                    app.jobs.spawn(jobs::JobKind::Analysis, url.as_str(), |id, tx| analysis_job(id, url.clone(), options, filter, tx));
                 }
             }
+            Action::Update(paths) => {
+                // Update request from the clones view (local paths), one job for all of them
+                let target = match paths.as_slice() {
+                    [path] => path.display().to_string(),
+                    _ => format!("{} repositories", paths.len()),
+                };
+                app.jobs.spawn(jobs::JobKind::Update, target, |id, tx| update_job(id, paths, tx));
+            }
+            Action::Sbom(path, name) => {
+                // Lockfiles are read off disk, away from the async runtime
+                app.jobs.spawn(jobs::JobKind::Sbom, name.as_str(), |id, tx| sbom_job(id, path, name.clone(), tx));
+            }
+            Action::Audit(url, path) => {
+                app.pending_audits.insert(url.clone());
+                app.jobs.spawn(jobs::JobKind::Audit, url.as_str(), |id, tx| audit_job(id, url.clone(), path, tx));
+            }
+            Action::Profiles(url, path, steps) => {
+                app.pending_profiles.insert(url.clone());
+                app.jobs.spawn(jobs::JobKind::Profiles, url.as_str(), |id, tx| profiles_job(id, url.clone(), path, steps, tx));
+            }
+            Action::Copy(text) => {
+                // Copy to clipboard via the terminal
+                match copy_to_clipboard(&text) {
+                    Ok(()) => app.notify(ToastLevel::Info, tr!("toast-copied", text = text.as_str())),
+                    Err(e) => app.notify(ToastLevel::Error, tr!("toast-copy-failed", error = e.to_string())),
+                }
+            }
+            Action::LoadList(list) => {
+                // The list's README is fetched and its links parsed in the background; a new tab shows them
+                app.jobs.spawn(jobs::JobKind::List, list.as_str(), |id, tx| list_job(github.clone(), id, list.clone(), tx));
+            }
+            Action::StarHistory(url, stars) => {
+                // Up to a dozen pages of stargazers, fetched one after another in the background
+                app.pending_star_history.insert(url.clone());
+                app.jobs.spawn(jobs::JobKind::Stars, url.as_str(), |id, tx| stars_job(github.clone(), id, url.clone(), stars, tx));
+            }
+            Action::Maintenance(url) => {
+                // The latest issues, the first reply to each commented one, the latest PRs, and the top
+                // contributors, in the background
+                app.pending_maintenance.insert(url.clone());
+                app.jobs.spawn(jobs::JobKind::Maintenance, url.as_str(), |id, tx| maintenance_job(github.clone(), id, url.clone(), tx));
+            }
+            Action::Compare(urls) => {
+                // Release and contributor figures for the comparison view, one background job per repo
+                for url in &urls {
+                    if !app.pending_activity.insert(url.to_string()) {
+                        continue; // Already being fetched
+                    }
+                    app.jobs.spawn(jobs::JobKind::Activity, url.as_str(), |id, tx| activity_job(github.clone(), id, url.clone(), tx));
+                }
+            }
+            Action::Prefetch(url) => {
+                // The same figures for a result after the selected one; dropped when the rate budget is low
+                if app.pending_activity.insert(url.clone()) {
+                    app.jobs.spawn(jobs::JobKind::Activity, url.as_str(), |id, tx| activity_job(prefetch.clone(), id, url.clone(), tx));
+                }
+            }
+            Action::Page(page) => {
+                // Go-to-page request: fetch the pages after the loaded ones, up to `page`, in the background
+                match SearchRequest::new(
+                    &app.tab.last_query,
+                    args,
+                    &app.tab.repo_size_filter,
+                    app.owner_scope.as_deref(),
+                    app.pages_loaded() + 1,
+                ) {
+                    Ok(mut request) => {
+                        request.cache_ttl = app.search_cache_ttl;
+                        let target = format!("{} (page {})", app.tab.last_query, page);
+                        let job = app.jobs.spawn(jobs::JobKind::Search, target, |id, tx| {
+                            page_job(github.clone(), search_cache.clone(), id, request, page, tx)
+                        });
+                        app.tab.page_job = Some((job, page));
+                    }
+                    Err(e) => app.set_error(&e),
+                }
+            }
+            Action::OpenUrl(url) => {
+                // Open in browser and keep running the TUI
+                open_in_browser(&url);
+            }
+            Action::OpenPane(path) => {
+                // The multiplexer opens the pane and returns at once; the TUI keeps its own
+                let Some(command) = app.pane_command.clone() else { continue };
+                match pane::open(&command, &path) {
+                    Ok(()) => app.notify(ToastLevel::Info, tr!("toast-pane-opened", path = path.display().to_string())),
+                    Err(e) => app.notify(ToastLevel::Error, tr!("toast-pane-failed", error = format!("{:#}", e))),
+                }
+            }
+            Action::Search(query) => {
+                let cache_ttl = app.search_cache_ttl;
+                start_search(&mut app, &github, &search_cache, args, query, cache_ttl);
+            }
+            // Ctrl+R: the same search, asking GitHub even when its results are cached
+            Action::Reload(query) => start_search(&mut app, &github, &search_cache, args, query, 0),
         }
     }
 
     Ok(())
 }
 
-async fn run_cli_mode(octocrab: Octocrab, args: &Args) -> Result<()> {
+/// Run a search in the active tab in the background; the results panel shows a spinner until the
+/// first page arrives
+/// Results cached less than `cache_ttl` seconds ago are shown without asking GitHub
+fn start_search(app: &mut App, github: &Client, cache: &Arc<cache::SharedCache>, args: &Args, query: String, cache_ttl: u64) {
+    app.begin_search(&query);
+    match SearchRequest::new(&query, args, &app.tab.repo_size_filter, app.owner_scope.as_deref(), 1) {
+        Ok(mut request) => {
+            request.cache_ttl = cache_ttl;
+            let job = app.jobs.spawn(jobs::JobKind::Search, query.as_str(), |id, tx| {
+                search_job(github.clone(), cache.clone(), id, query.clone(), request, tx)
+            });
+            app.tab.search_job = Some(job);
+        }
+        Err(e) => app.set_error(&e),
+    }
+}
+
+/// Query qualifiers from CLI args, as labels for the TUI status bar
+fn cli_filter_labels(args: &Args) -> Vec<String> {
+    let mut labels = filter_qualifiers(args);
+    if let Some(sort_by) = &args.sort {
+        labels.push(format!("sort:{}", sort_by));
+    }
+    labels
+}
+
+/// Fetch the remaining search API quota (the rate_limit endpoint itself is free)
+async fn fetch_search_rate_limit(github: &dyn GitHub) -> Option<RateLimitInfo> {
+    github.search_rate_limit().await.ok()
+}
+
+/// Print the results of a search; the exit code tells scripts how it went (see error::EXIT_*)
+async fn run_cli_mode(github: &dyn GitHub, args: &Args) -> Result<ExitCode> {
     let query = args.query.join(" ");
     
-    println!("{}", format!("🔍 Searching for: {}\n", query).cyan().bold());
+    if !args.quiet {
+        println!("{}", format!("{}\n", Icon::Search.label(format!("Searching for: {}", query))).cyan().bold());
+    }
+
+    let (results, total_count, _, cached, changes) = match search_with_cache(github, &cache::SharedCache::load(), &query, args, &None).await {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red(), e.with_guidance());
+            return Ok(ExitCode::from(e.exit_code()));
+        }
+    };
+    if let Some(cache::Hit::Offline(fetched)) = cached {
+        let age = clones::format_age(fetched, std::time::SystemTime::now());
+        let notice = format!("GitHub is unreachable: showing cached results from {}", age);
+        if args.quiet {
+            eprintln!("{} {}", "Warning:".yellow(), notice);
+        } else {
+            println!("{}\n", Icon::Offline.label(notice).yellow());
+        }
+    }
 
-    let (results, total_count) = perform_search(&octocrab, &query, args).await?;
+    if args.verbose || args.debug {
+        if let Some(rate) = fetch_search_rate_limit(github).await {
+            tracing::info!(remaining = rate.remaining, limit = rate.limit, reset = rate.reset, "search rate limit");
+        }
+    }
 
     // Display results
     if results.is_empty() {
-        println!("{}", "No repositories found.".yellow());
-        return Ok(());
+        if !args.quiet {
+            println!("{}", "No repositories found.".yellow());
+            if let Some(suggestion) = suggest::suggest(&query) {
+                println!("\nDid you mean: {}", suggestion.query.cyan().bold());
+                for change in &suggestion.changes {
+                    println!("  {}", change.dimmed());
+                }
+            }
+        }
+        // Scripts reading --output still get a file, with no rows
+        write_output(args, &query, total_count, &results)?;
+        return Ok(if args.fail_if_empty { ExitCode::from(error::EXIT_NO_RESULTS) } else { ExitCode::SUCCESS });
+    }
+
+    if args.quiet {
+        for repo in &results {
+            println!("{}", quiet_line(repo));
+        }
+    } else {
+        print_results(&results, total_count, changes.as_deref());
+    }
+
+    write_output(args, &query, total_count, &results)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Write the results to --output's file, if given, in the format its extension names
+fn write_output(args: &Args, query: &str, total_count: u64, results: &[octocrab::models::Repository]) -> Result<()> {
+    if let Some(path) = &args.output {
+        let format = export::Format::from_path(path);
+        export::write(path, &export::render(format, query, total_count, results)?)?;
+        if !args.quiet {
+            println!("{}", Icon::Success.label(format!("Wrote the {} export to {}", format.label(), path.display())).green());
+        }
     }
+    Ok(())
+}
+
+/// A repository as `--quiet` prints it: "owner/name<TAB>URL"
+fn quiet_line(repo: &octocrab::models::Repository) -> String {
+    let name = repo.full_name.as_deref().unwrap_or(&repo.name);
+    let url = repo.html_url.as_ref().map(|url| url.to_string()).unwrap_or_default();
+    format!("{}\t{}", name, url)
+}
 
+/// Print results the way CLI mode shows them by default: a numbered entry per repository
+/// with its counts, description, and link
+/// With `changes` since the search last ran, new results are marked and the ones gone are listed
+fn print_results(results: &[octocrab::models::Repository], total_count: u64, changes: Option<&cache::Changes>) {
     println!(
         "{}\n",
         format!("Found {} repositories (showing {})", total_count, results.len())
             .green()
             .bold()
     );
+    if let Some((changes, summary)) = changes.and_then(|changes| Some((changes, changes.summary(std::time::SystemTime::now())?))) {
+        println!("{}", summary.green());
+        for name in &changes.removed {
+            println!("   - {}", name.dimmed());
+        }
+        println!();
+    }
 
     for (i, repo) in results.iter().enumerate() {
         let stars = repo.stargazers_count.unwrap_or(0);
@@ -195,17 +746,24 @@ async fn run_cli_mode(octocrab: Octocrab, args: &Args) -> Result<()> {
             .and_then(|v| v.as_str())
             .unwrap_or("Unknown");
         
+        let visibility = columns::visibility_label(repo).map(|visibility| format!(" {}", Icon::Private.label(visibility)));
+        let new = changes
+            .filter(|changes| changes.added.contains(&repo.id.0))
+            .map(|_| format!(" {}", Icon::New.label("new")));
         println!(
-            "{} {}",
+            "{} {}{}{}",
             format!("{}.", i + 1).cyan(),
-            repo.full_name.as_ref().unwrap().bold()
+            repo.full_name.as_ref().unwrap().bold(),
+            visibility.unwrap_or_default().yellow(),
+            new.unwrap_or_default().green()
         );
         
         println!(
-            "   {} | {} | {}",
-            format!("⭐ {}", stars).yellow(),
-            format!("🍴 {}", forks).green(),
-            format!("💻 {}", language).blue()
+            "   {} | {} | {} | {}",
+            Icon::Stars.label(clones::format_count(stars as u64)).yellow(),
+            Icon::Forks.label(clones::format_count(forks as u64)).green(),
+            Icon::Language.label(language).blue(),
+            Icon::Package.label(clones::format_size(repo.size.unwrap_or(0) as u64 * 1024)).dimmed()
         );
         
         if let Some(desc) = &repo.description {
@@ -215,26 +773,110 @@ async fn run_cli_mode(octocrab: Octocrab, args: &Args) -> Result<()> {
         println!("   {}", repo.html_url.as_ref().unwrap().to_string().cyan().underline());
         println!();
     }
+}
 
-    Ok(())
+/// One page of a search, as sent to GitHub
+#[derive(Debug, Clone)]
+struct SearchRequest {
+    query: String, // Composed query, qualifiers included
+    sort: Option<String>,
+    per_page: u8,
+    page: u32,      // 1-based
+    cache_ttl: u64, // Seconds cached results are used without asking GitHub; 0 always asks
+}
+
+impl SearchRequest {
+    fn new(
+        query: &str,
+        args: &Args,
+        size_filter_override: &Option<String>,
+        owner_scope: Option<&str>,
+        page: u32,
+    ) -> Result<Self, error::Error> {
+        Ok(Self {
+            query: compose_query(query, args, size_filter_override, owner_scope)?,
+            sort: args.sort.clone(),
+            per_page: args.limit,
+            page,
+            cache_ttl: 0,
+        })
+    }
+}
+
+/// Search for the first page of results, caching them; on a network error, fall back to the cached
+/// results for the same search
+async fn search_with_cache(
+    github: &dyn GitHub,
+    cache: &cache::SharedCache,
+    query: &str,
+    args: &Args,
+    size_filter_override: &Option<String>,
+) -> Result<SearchResults, error::Error> {
+    let request = SearchRequest::new(query, args, size_filter_override, args.owner.as_deref(), 1)?;
+    search_page_with_cache(github, cache, &request).await
 }
 
-/// Perform search with an optional size filter override (used by TUI)
-async fn perform_search_with_filter(
-    octocrab: &Octocrab,
+/// Fetch one page of results, caching it; on a network error, fall back to the cached page
+/// A page cached less than `request.cache_ttl` seconds ago is used without asking GitHub
+/// Fresh results are compared with the cached ones they replace
+async fn search_page_with_cache(
+    github: &dyn GitHub,
+    cache: &cache::SharedCache,
+    request: &SearchRequest,
+) -> Result<SearchResults, error::Error> {
+    let key = cache::key(&request.query, request.sort.as_deref(), request.per_page, request.page);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    if request.cache_ttl > 0 {
+        if let Some(hit) = cache.get(&key).filter(|hit| hit.is_fresh(request.cache_ttl, now)) {
+            tracing::info!(key, fetched_at = hit.fetched_at, "using cached results");
+            let fetched = Some(cache::Hit::Fresh(hit.fetched_time()));
+            return Ok((hit.items, hit.total_count, hit.text_matches, fetched, None));
+        }
+    }
+    match perform_search(github, request).await {
+        Ok((items, total, text_matches)) => {
+            let changes = cache.insert(key, total, items.clone(), text_matches.clone(), now).map(Box::new);
+            if let Err(e) = cache.save().await {
+                tracing::warn!(error = %format!("{:#}", e), "failed to save the search cache");
+            }
+            Ok((items, total, text_matches, None, changes))
+        }
+        Err(error::Error::Network(message)) => match cache.get(&key) {
+            Some(hit) => {
+                tracing::info!(key, fetched_at = hit.fetched_at, "offline, using cached results");
+                let offline = Some(cache::Hit::Offline(hit.fetched_time()));
+                Ok((hit.items, hit.total_count, hit.text_matches, offline, None))
+            }
+            None => Err(error::Error::Network(message)),
+        },
+        Err(e) => Err(e),
+    }
+}
+
+/// The query sent to GitHub: the user's terms plus qualifiers from CLI args, the size filter, and
+/// the owner scope (--owner, or the TUI's Alt+U)
+/// The size filter override (from the TUI) takes precedence over --repo-size
+/// With --raw-query the user's terms go out exactly as typed
+fn compose_query(
     query: &str,
     args: &Args,
     size_filter_override: &Option<String>,
-) -> Result<(Vec<octocrab::models::Repository>, u64)> {
-    // Build the search query
+    owner_scope: Option<&str>,
+) -> Result<String, error::Error> {
     let mut search_query = query.to_string();
+    if args.raw_query {
+        return Ok(search_query);
+    }
 
-    if let Some(lang) = &args.language {
-        search_query.push_str(&format!(" language:{}", lang));
+    if let Some(scope) = owner_scope {
+        search_query.push_str(&format!(" {}", scope));
     }
 
-    if let Some(min_stars) = args.stars {
-        search_query.push_str(&format!(" stars:>={}", min_stars));
+    for qualifier in filter_qualifiers(args) {
+        search_query.push_str(&format!(" {}", qualifier));
     }
 
     // Use size filter override if provided (from TUI), otherwise use args
@@ -246,34 +888,99 @@ async fn perform_search_with_filter(
             "medium" => "size:25000..100000",
             "large" => "size:>100000",
             _ => {
-                return Err(anyhow::anyhow!(
-                    "Invalid repo_size '{}'. Use: small, medium, or large",
+                return Err(error::Error::InvalidQuery(format!(
+                    "repo_size '{}' must be small, medium, or large",
                     size_cat
-                ));
+                )));
             }
         };
         search_query.push_str(&format!(" {}", size_filter));
     }
 
-    // Perform the search
-    let mut search = octocrab.search().repositories(&search_query);
+    Ok(search_query)
+}
 
-    if let Some(sort_by) = &args.sort {
-        search = search.sort(sort_by);
+/// The qualifiers the filter flags (--language, --stars, --topic, ...) add to every query, in the
+/// order they're appended; --repo-size is left to compose_query since the TUI can override it
+fn filter_qualifiers(args: &Args) -> Vec<String> {
+    let mut qualifiers = Vec::new();
+    if !args.in_fields.is_empty() {
+        let mut fields: Vec<&str> = Vec::new();
+        for field in args.in_fields.iter().map(|field| field.qualifier_value()) {
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        qualifiers.push(format!("in:{}", fields.join(",")));
     }
-
-    let results = search.per_page(args.limit).send().await?;
-
-    Ok((results.items, results.total_count.unwrap_or(0)))
+    if let Some(lang) = &args.language {
+        qualifiers.push(format!("language:{}", lang));
+    }
+    if let Some(min_stars) = args.stars {
+        qualifiers.push(format!("stars:>={}", min_stars));
+    }
+    qualifiers.extend(args.visibility.and_then(Visibility::qualifier).map(str::to_string));
+    if let Some(min_forks) = args.forks {
+        qualifiers.push(format!("forks:>={}", min_forks));
+    }
+    qualifiers.extend(args.topics.iter().map(|topic| format!("topic:{}", topic)));
+    if let Some(license) = &args.license {
+        qualifiers.push(format!("license:{}", license));
+    }
+    if let Some(archived) = args.archived {
+        qualifiers.push(format!("archived:{}", archived));
+    }
+    if let Some(date) = &args.created_after {
+        qualifiers.push(format!("created:>={}", date));
+    }
+    if let Some(date) = &args.pushed_after {
+        qualifiers.push(format!("pushed:>={}", date));
+    }
+    // Already user:NAME or org:NAME (user: matches organizations too)
+    qualifiers.extend(args.exclude_owner.iter().map(|owner| format!("-{}", owner)));
+    for term in &args.exclude_terms {
+        if term.contains(char::is_whitespace) {
+            qualifiers.push(format!("NOT \"{}\"", term.replace('"', "")));
+        } else {
+            qualifiers.push(format!("NOT {}", term));
+        }
+    }
+    qualifiers
 }
 
-/// Perform search using only Args (used by CLI mode)
+/// Fetch one page of search results
+#[tracing::instrument(skip(github))]
 async fn perform_search(
-    octocrab: &Octocrab,
-    query: &str,
-    args: &Args,
-) -> Result<(Vec<octocrab::models::Repository>, u64)> {
-    perform_search_with_filter(octocrab, query, args, &None).await
+    github: &dyn GitHub,
+    request: &SearchRequest,
+) -> Result<(Vec<octocrab::models::Repository>, u64, text_match::TextMatches), error::Error> {
+    tracing::info!(
+        query = %request.query,
+        sort = ?request.sort,
+        per_page = request.per_page,
+        page = request.page,
+        "searching repositories"
+    );
+    let started = std::time::Instant::now();
+    let search = github.search_repositories(&request.query, request.sort.as_deref(), request.per_page, request.page);
+    let (items, total, text_matches) = match search.await {
+        Ok(results) => results,
+        Err(mut error) => {
+            // Look up when the quota refills so the TUI can say how long to wait
+            if let error::Error::RateLimited { reset } = &mut error {
+                *reset = fetch_search_rate_limit(github).await.map(|rate| rate.reset);
+            }
+            return Err(error);
+        }
+    };
+    tracing::info!(
+        total,
+        returned = items.len(),
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "search finished"
+    );
+
+    Ok((items, total, text_matches))
 }
 
 /// Clones a GitHub repository to the 'repositories' directory
@@ -289,130 +996,346 @@ fn repo_name_from_url(repo_url: &str) -> String {
     }
 }
 
-async fn clone_repository(repo_url: &str) -> Result<String> {
-    use tokio::process::Command;
-    use std::process::Stdio;
-
-    // Create repositories directory in current working directory
-    let repos_dir = std::env::current_dir()?.join("repositories");
-    std::fs::create_dir_all(&repos_dir)?;
+/// Extracts the owner from a GitHub URL, e.g. "https://github.com/owner/repo" -> "owner"
+fn repo_owner_from_url(repo_url: &str) -> String {
+    let mut segments = repo_url.trim_end_matches('/').rsplit('/');
+    segments.next();
+    match segments.next() {
+        Some(owner) if !owner.is_empty() && !owner.contains(':') => owner.to_string(),
+        _ => "unknown".to_string(),
+    }
+}
 
-    let repo_name = repo_name_from_url(repo_url);
-    let clone_path = repos_dir.join(&repo_name);
+/// Where an Alt+G clone goes: the path chosen in the clone prompt, or repositories/ plus the layout path
+/// When several repos are cloned at once the chosen path is their parent directory
+/// Relative paths are resolved against the current working directory
+fn clone_destination(repo_url: &str, chosen: Option<&Path>, batch: bool, layout: CloneLayout) -> Result<PathBuf> {
+    let base = std::env::current_dir()?;
+    let repo_path = layout.path(&repo_owner_from_url(repo_url), &repo_name_from_url(repo_url));
+    Ok(match chosen {
+        Some(path) if batch => base.join(path).join(repo_path),
+        Some(path) => base.join(path),
+        None => base.join(tui::CLONE_DIR).join(repo_path),
+    })
+}
 
-    // Check if directory already exists
+#[tracing::instrument(skip(options, on_progress), fields(dest = %clone_path.display()))]
+async fn clone_repository(
+    repo_url: &str,
+    clone_path: &Path,
+    options: &git::CloneOptions,
+    on_progress: impl FnMut(git::CloneProgress),
+) -> Result<()> {
+    // Existing clones of the same repo are updated by the caller; anything else is left alone
     if clone_path.exists() {
         return Err(anyhow::anyhow!(
-            "Directory '{}' already exists. Please remove it first or choose a different location.",
+            "Directory '{}' already exists and is not a clone of this repository. Please remove it first or choose a different location.",
             clone_path.display()
         ));
     }
 
-    // Clone repository
-    let clone_output = Command::new("git")
-        .arg("clone")
-        .arg(repo_url)
-        .arg(&clone_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .await?;
-
-    if !clone_output.success() {
-        return Err(anyhow::anyhow!("Failed to clone repository"));
+    // Create the parent directory (repositories/ by default)
+    if let Some(parent) = clone_path.parent() {
+        std::fs::create_dir_all(parent)?;
     }
 
-    Ok(clone_path.display().to_string())
+    // Clone repository with the options chosen in the clone prompt, reporting transfer progress
+    git::clone(repo_url, clone_path, options, on_progress).await?;
+
+    Ok(())
+}
+
+/// Counts a GitHub repository's files per extension, returning the report shown in the details panel
+/// Uses the git trees API when possible; clones to a temp directory if the tree is truncated,
+/// the API call fails, or submodules were requested (the tree doesn't include their files)
+async fn count_repository_files(
+    github: &dyn GitHub,
+    repo_url: &str,
+    options: &git::CloneOptions,
+    filter: &filecount::CountFilter,
+) -> Result<filecount::FileStats> {
+    if !options.submodules {
+        let (owner, name) = (repo_owner_from_url(repo_url), repo_name_from_url(repo_url));
+        match filecount::count_files_from_api(github, &owner, &name, filter).await {
+            Ok(Some(stats)) => return Ok(stats),
+            Ok(None) => tracing::info!(url = repo_url, "tree truncated, counting from a clone"),
+            Err(e) => tracing::warn!(url = repo_url, error = %format!("{:#}", e), "trees API failed, counting from a clone"),
+        }
+    }
+    clone_and_count_files(repo_url, options, filter).await
 }
 
-/// Clones a GitHub repository to a temp directory and runs the configured filecount script
-/// Returns the script output as a string
-async fn clone_and_count_files(repo_url: &str) -> Result<String> {
-    use std::process::Stdio;
-    use tokio::process::Command;
+/// Clones a GitHub repository to a temp directory and counts its files per extension
+async fn clone_and_count_files(
+    repo_url: &str,
+    options: &git::CloneOptions,
+    filter: &filecount::CountFilter,
+) -> Result<filecount::FileStats> {
+    let filter = filter.clone();
+    with_temp_clone(repo_url, options, move |path| filecount::count_files(path, &filter)).await?
+}
 
-    // Load configuration to determine which script to use
-    let config = config::Config::load()?;
-    let script_name = config.get_filecount_script_path();
+/// Clones a GitHub repository to a temp directory and counts lines of code per language
+async fn clone_and_analyze(
+    repo_url: &str,
+    options: &git::CloneOptions,
+    filter: &filecount::CountFilter,
+) -> Result<Vec<analysis::LanguageStats>> {
+    let filter = filter.clone();
+    with_temp_clone(repo_url, options, move |path| analysis::analyze(path, &filter)).await
+}
 
-    // Create unique temp directory for this process
-    let temp_dir = std::env::temp_dir().join(format!("github-search-{}", std::process::id()));
-    std::fs::create_dir_all(&temp_dir)?;
+/// Clone into a fresh temp directory, run `inspect` on the checkout, and delete the clone
+async fn with_temp_clone<T: Send + 'static>(
+    repo_url: &str,
+    options: &git::CloneOptions,
+    inspect: impl FnOnce(&Path) -> T + Send + 'static,
+) -> Result<T> {
+    // Unique temp directory for this clone (several may run at once)
+    // Removed when `temp_dir` drops: on success, on any error below, and if the task is aborted
+    let temp_dir = tempfile::Builder::new().prefix("github-search-").tempdir()?;
+    tracing::debug!(url = repo_url, dir = %temp_dir.path().display(), "cloning to temp directory");
 
     let repo_name = repo_name_from_url(repo_url);
-    let clone_path = temp_dir.join(repo_name);
-
-    // Clone repository (shallow clone with depth=1 for speed)
-    let clone_output = Command::new("git")
-        .arg("clone")
-        .arg("--depth")
-        .arg("1")
-        .arg(repo_url)
-        .arg(&clone_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .await?;
-
-    if !clone_output.success() {
-        return Err(anyhow::anyhow!("Failed to clone repository"));
-    }
-
-    // Copy the configured filecount script to temp directory
-    let script_source = std::env::current_dir()?.join(&script_name);
-    let script_dest = clone_path.join(&script_name);
-
-    if !script_source.exists() {
-        std::fs::remove_dir_all(&temp_dir)?;
-        return Err(anyhow::anyhow!("{} not found in project root", script_name));
-    }
-
-    std::fs::copy(&script_source, &script_dest)?;
-
-    // Determine command based on script type
-    let output = if script_name.ends_with(".ps1") {
-        // Windows PowerShell script
-        #[cfg(target_os = "windows")]
-        {
-            Command::new("powershell")
-                .arg("-ExecutionPolicy")
-                .arg("Bypass")
-                .arg("-File")
-                .arg(&script_dest)
-                .current_dir(&clone_path)
-                .output()
-                .await?
+    let clone_path = temp_dir.path().join(repo_name);
+
+    // Clone repository (shallow by default for speed; depth comes from the clone prompt)
+    git::clone(repo_url, &clone_path, options, |_| {}).await?;
+
+    // Walking a large checkout is blocking filesystem work
+    let result = tokio::task::spawn_blocking(move || inspect(&clone_path)).await?;
+
+    // Clean up (reported here, unlike the silent cleanup on drop)
+    temp_dir.close()?;
+
+    Ok(result)
+}
+
+/// Copies text to the system clipboard using the OSC 52 terminal escape sequence
+/// Works over SSH and in most modern terminals without a clipboard crate
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    use std::io::Write;
+
+    let sequence = format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+    let mut stdout = std::io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Standard base64 encoding (RFC 4648, with padding)
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+/// Run the first page of a search, then check the search quota left
+async fn search_job(
+    github: Client,
+    cache: Arc<cache::SharedCache>,
+    id: jobs::JobId,
+    query: String,
+    request: SearchRequest,
+    tx: jobs::JobSender,
+) {
+    let result = search_page_with_cache(github.as_ref(), &cache, &request).await;
+    if let Err(e) = &result {
+        tracing::warn!(query = %query, error = %e, "search failed");
+    }
+    let rate_limit = fetch_search_rate_limit(github.as_ref()).await;
+    let _ = tx.send(jobs::JobEvent::SearchFinished { id, query, result, rate_limit });
+}
+
+/// Clone (or update, if already cloned there) one repository
+async fn clone_job(id: jobs::JobId, url: String, path: PathBuf, options: git::CloneOptions, tx: jobs::JobSender) {
+    let report = |progress| {
+        let _ = tx.send(jobs::JobEvent::CloneProgress { id, url: url.clone(), progress });
+    };
+    // An existing clone of the same repo is updated instead of cloned again
+    let result = if clones::is_clone_of(&path, &url) {
+        git::update(&path, report).await.map(Some).map_err(|e| e.with_guidance())
+    } else {
+        clone_repository(&url, &path, &options, report).await.map(|_| None).map_err(|e| error::describe(&e))
+    };
+    match &result {
+        Ok(outcome) => tracing::info!(url, path = %path.display(), ?outcome, "clone finished"),
+        Err(error) => tracing::warn!(url, path = %path.display(), error, "clone failed"),
+    }
+    let _ = tx.send(jobs::JobEvent::CloneFinished { id, url, path: path.display().to_string(), result });
+}
+
+/// Update local clones from their remotes, one after another
+async fn update_job(id: jobs::JobId, paths: Vec<PathBuf>, tx: jobs::JobSender) {
+    let mut updated = Vec::new();
+    let mut failed = Vec::new();
+    for path in &paths {
+        let report = |progress| {
+            let _ = tx.send(jobs::JobEvent::CloneProgress { id, url: path.display().to_string(), progress });
+        };
+        match git::update(path, report).await {
+            Ok(outcome) => updated.push((path.display().to_string(), outcome)),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "update failed");
+                failed.push(format!("{}: {}", path.display(), e.with_guidance()))
+            }
+        }
+    }
+    let _ = tx.send(jobs::JobEvent::UpdateFinished { id, updated, failed });
+}
+
+/// Write an SBOM of the clone at `path` next to it
+async fn sbom_job(id: jobs::JobId, path: PathBuf, name: String, tx: jobs::JobSender) {
+    let clone = name.clone();
+    let result = tokio::task::spawn_blocking(move || sbom::write(&path, &name))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map_err(|e| format!("{:#}", e));
+    match &result {
+        Ok((path, components)) => tracing::info!(clone, path = %path.display(), components, "SBOM written"),
+        Err(error) => tracing::warn!(clone, error, "writing SBOM failed"),
+    }
+    let _ = tx.send(jobs::JobEvent::SbomWritten { id, clone, result });
+}
+
+/// Check the Cargo.lock of the clone at `path` against the RustSec advisories
+async fn audit_job(id: jobs::JobId, url: String, path: PathBuf, tx: jobs::JobSender) {
+    // The advisory database is a git checkout, fetched and read off the async runtime
+    let result = tokio::task::spawn_blocking(move || audit::audit(&path))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map_err(|e| format!("{:#}", e));
+    match &result {
+        Ok(findings) => tracing::info!(url, advisories = findings.len(), "audit finished"),
+        Err(error) => tracing::warn!(url, error, "audit failed"),
+    }
+    let _ = tx.send(jobs::JobEvent::AuditFinished { id, url, result });
+}
+
+/// Run the analysis profile commands picked for the clone at `path`
+async fn profiles_job(id: jobs::JobId, url: String, path: PathBuf, steps: Vec<profiles::Step>, tx: jobs::JobSender) {
+    let sections = profiles::run(&path, &steps).await;
+    tracing::info!(url, commands = sections.len(), "analysis profile finished");
+    let _ = tx.send(jobs::JobEvent::ProfilesFinished { id, url, sections });
+}
+
+/// Fetch result pages from `request.page` through `last`, one event per page
+/// Stops at the first failure
+async fn page_job(
+    github: Client,
+    cache: Arc<cache::SharedCache>,
+    id: jobs::JobId,
+    mut request: SearchRequest,
+    last: u32,
+    tx: jobs::JobSender,
+) {
+    loop {
+        let result = search_page_with_cache(github.as_ref(), &cache, &request)
+            .await
+            .map(|(items, total, text_matches, _, _)| (items, total, text_matches))
+            .map_err(|e| e.to_string());
+        if let Err(error) = &result {
+            tracing::warn!(page = request.page, error, "loading a page of results failed");
         }
-        #[cfg(not(target_os = "windows"))]
-        {
-            std::fs::remove_dir_all(&temp_dir)?;
-            return Err(anyhow::anyhow!("PowerShell scripts can only be run on Windows"));
+        let done = result.is_err() || request.page >= last;
+        let _ = tx.send(jobs::JobEvent::PageLoaded { id, page: request.page, done, result });
+        if done {
+            break;
         }
-    } else {
-        // Unix shell script
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let mut perms = std::fs::metadata(&script_dest)?.permissions();
-            perms.set_mode(0o755);
-            std::fs::set_permissions(&script_dest, perms)?;
+        request.page += 1;
+    }
+}
+
+/// Fetch the release and contributor figures of one repository for the comparison view
+async fn activity_job(github: Client, id: jobs::JobId, url: String, tx: jobs::JobSender) {
+    let (owner, name) = (repo_owner_from_url(&url), repo_name_from_url(&url));
+    let result = compare::fetch_activity(github.as_ref(), &owner, &name).await;
+    if let Err(e) = &result {
+        if e.downcast_ref::<error::Error>() == Some(&error::Error::Deferred) {
+            tracing::info!(url, "prefetch deferred");
+            let _ = tx.send(jobs::JobEvent::ActivityDeferred { id, url });
+            return;
         }
+    }
+    let result = result.map_err(|e| error::describe(&e));
+    if let Err(error) = &result {
+        tracing::warn!(url, error, "fetching repository activity failed");
+    }
+    let _ = tx.send(jobs::JobEvent::ActivityLoaded { id, url, result });
+}
 
-        Command::new(&format!("./{}", script_name))
-            .current_dir(&clone_path)
-            .output()
-            .await?
-    };
+/// Look up the signed-in user (when `fetch_user`) and the search quota for the status bar
+async fn status_job(github: Client, id: jobs::JobId, fetch_user: bool, tx: jobs::JobSender) {
+    let user = if fetch_user { github.current_user().await.ok() } else { None };
+    let rate_limit = fetch_search_rate_limit(github.as_ref()).await;
+    let _ = tx.send(jobs::JobEvent::StatusLoaded { id, user, rate_limit });
+}
 
-    // Clean up
-    std::fs::remove_dir_all(&temp_dir)?;
+/// Fetch an awesome list's README and the repositories it links to
+async fn list_job(github: Client, id: jobs::JobId, list: String, tx: jobs::JobSender) {
+    let (owner, name) = list.split_once('/').unwrap_or((&list, ""));
+    let result = awesome::fetch_list(github.as_ref(), owner, name).await.map_err(|e| error::describe(&e));
+    match &result {
+        Ok(repos) => tracing::info!(list, repos = repos.len(), "awesome list loaded"),
+        Err(error) => tracing::warn!(list, error, "loading awesome list failed"),
+    }
+    let _ = tx.send(jobs::JobEvent::ListLoaded { id, list, result });
+}
 
-    if output.status.success() {
-        let count = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        Ok(count)
-    } else {
-        Err(anyhow::anyhow!("Failed to count files: {}", String::from_utf8_lossy(&output.stderr)))
+/// Fetch the star history of one repository
+async fn stars_job(github: Client, id: jobs::JobId, url: String, stars: u64, tx: jobs::JobSender) {
+    let (owner, name) = (repo_owner_from_url(&url), repo_name_from_url(&url));
+    let result = stars::fetch_history(github.as_ref(), &owner, &name, stars).await.map_err(|e| error::describe(&e));
+    if let Err(error) = &result {
+        tracing::warn!(url, error, "fetching star history failed");
+    }
+    let _ = tx.send(jobs::JobEvent::StarHistoryLoaded { id, url, result });
+}
+
+/// Measure how one repository is maintained: issue replies, PR merges, and who commits
+async fn maintenance_job(github: Client, id: jobs::JobId, url: String, tx: jobs::JobSender) {
+    let (owner, name) = (repo_owner_from_url(&url), repo_name_from_url(&url));
+    let result = maintenance::fetch_metrics(github.as_ref(), &owner, &name).await.map_err(|e| error::describe(&e));
+    if let Err(error) = &result {
+        tracing::warn!(url, error, "fetching issue metrics failed");
+    }
+    let _ = tx.send(jobs::JobEvent::MaintenanceLoaded { id, url, result });
+}
+
+/// Clone and analyze one repository
+async fn analysis_job(id: jobs::JobId, url: String, options: git::CloneOptions, filter: filecount::CountFilter, tx: jobs::JobSender) {
+    let result = clone_and_analyze(&url, &options, &filter).await.map_err(|e| error::describe(&e));
+    match &result {
+        Ok(stats) => tracing::info!(url, languages = stats.len(), "analysis finished"),
+        Err(error) => tracing::warn!(url, error, "analysis failed"),
+    }
+    let _ = tx.send(jobs::JobEvent::AnalysisFinished { id, url, result });
+}
+
+/// Clone one repository and count its files
+async fn file_count_job(
+    github: Client,
+    id: jobs::JobId,
+    url: String,
+    options: git::CloneOptions,
+    filter: filecount::CountFilter,
+    tx: jobs::JobSender,
+) {
+    let result = count_repository_files(github.as_ref(), &url, &options, &filter).await.map_err(|e| error::describe(&e));
+    if let Err(error) = &result {
+        tracing::warn!(url, error, "file count failed");
     }
+    let _ = tx.send(jobs::JobEvent::FileCountFinished { id, url, result });
 }
 
 /// Opens a URL in the system's default browser
@@ -436,28 +1359,295 @@ fn open_in_browser(url: &str) {
     }
 }
 
+// The end-to-end tests against MockGitHub live here rather than in tests/: the crate is a binary with
+// no library target, so an integration test can't reach run_cli_mode, search_with_cache, or the mock
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use github::mock::{MockGitHub, Search};
+
+    /// A search cache of the test's own, in a scratch directory removed with the `TempDir`
+    fn scratch_cache() -> (tempfile::TempDir, Arc<cache::SharedCache>) {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Arc::new(cache::SharedCache::open(dir.path().join("search_cache.json")));
+        (dir, cache)
+    }
+
     #[test]
-    fn test_url_action_does_not_exit_tui() {
-        // This test verifies the fix for Alt+O behavior.
-        // When a URL action is returned from run_tui(), the main loop
-        // should continue running (not break), allowing the TUI to stay open.
+    fn test_console_log_filter() {
+        let filter = |argv: &[&str]| console_log_filter(&Args::parse_from(argv));
+        assert_eq!(filter(&["gh", "rust"]), None);
+        assert_eq!(filter(&["gh", "-v", "rust"]), Some("github_search_cli=info,octocrab=debug"));
+        assert_eq!(filter(&["gh", "--debug", "--verbose", "rust"]), Some("debug"));
+        // Never while the TUI is running
+        assert_eq!(filter(&["gh", "--verbose"]), None);
+        assert_eq!(filter(&["gh", "--verbose", "--no-tui"]), Some("github_search_cli=info,octocrab=debug"));
+    }
 
-        // The key change is in main.rs lines 142-145:
-        // Before: restore_terminal(), println!(), open_in_browser(), break
-        // After:  open_in_browser() only (no break, so loop continues)
+    #[test]
+    fn test_quiet_line() {
+        let repo = github::mock::repo("rust-lang/rust").build();
+        assert_eq!(quiet_line(&repo), "rust-lang/rust\thttps://github.com/rust-lang/rust");
+        assert!(Args::parse_from(["gh", "-q", "rust"]).quiet);
+    }
 
-        let url = "https://github.com/user/test-repo";
+    #[test]
+    fn test_default_filters_from_config() {
+        let config = config::Config {
+            default_language: Some("rust".to_string()),
+            default_min_stars: Some(50),
+            default_repo_size: Some("large".to_string()),
+            default_sort: Some("stars".to_string()),
+            ..config::Config::default()
+        };
+        let mut args = Args::parse_from(["gh", "-L", "go", "game"]);
+        apply_default_filters(&mut args, &config);
+        assert_eq!(compose_query("game", &args, &None, None).unwrap(), "game language:go stars:>=50 size:>100000");
+        assert_eq!(args.sort.as_deref(), Some("stars"));
+
+        let mut args = Args::parse_from(["gh", "game"]);
+        apply_default_filters(&mut args, &config::Config::default());
+        assert_eq!(compose_query("game", &args, &None, None).unwrap(), "game");
+
+        // --raw-query leaves them out, along with the TUI's size filter and owner scope
+        let mut args = Args::parse_from(["gh", "--raw-query", "game language:c"]);
+        apply_default_filters(&mut args, &config);
+        let query = compose_query("game language:c", &args, &Some("small".to_string()), Some("org:rust-lang")).unwrap();
+        assert_eq!(query, "game language:c");
+        assert!(args.sort.is_none());
+
+        // --raw-query takes no filter flags
+        assert!(Args::parse_from(["gh", "--raw-query", "--sort", "stars", "game"]).raw_query);
+        assert!(Args::try_parse_from(["gh", "--raw-query", "-L", "rust", "game"]).is_err());
+    }
 
-        // Simulate what happens in the main loop when Alt+O returns a URL
-        let action = url.to_string();
+    #[test]
+    fn test_compose_query() {
+        let args = Args::parse_from(["gh", "-L", "rust", "--stars", "100", "--repo-size", "large", "game"]);
+        let query = compose_query("game", &args, &None, None).unwrap();
+        assert_eq!(query, "game language:rust stars:>=100 size:>100000");
+        // The TUI's size filter wins over --repo-size
+        let query = compose_query("game", &args, &Some("small".to_string()), Some("org:rust-lang")).unwrap();
+        assert_eq!(query, "game org:rust-lang language:rust stars:>=100 size:<25000");
+        assert!(compose_query("game", &args, &Some("huge".to_string()), None).is_err());
+
+        let args = Args::parse_from([
+            "gh", "--forks", "10", "--topic", "cli", "--topic", "tui", "--license", "mit", "--archived=false",
+            "--created-after", "2020-01-01", "--pushed-after", "2024-06-01", "game",
+        ]);
+        assert_eq!(
+            compose_query("game", &args, &None, None).unwrap(),
+            "game forks:>=10 topic:cli topic:tui license:mit archived:false created:>=2020-01-01 pushed:>=2024-06-01"
+        );
+        assert_eq!(cli_filter_labels(&args)[..2], ["forks:>=10", "topic:cli"]);
+        assert_eq!(Args::parse_from(["gh", "--archived", "game"]).archived, Some(true));
+
+        let args = Args::parse_from(["gh", "--visibility", "private", "-L", "go", "tools"]);
+        assert_eq!(compose_query("tools", &args, &None, None).unwrap(), "tools language:go is:private");
+        let args = Args::parse_from(["gh", "--visibility", "all", "tools"]);
+        assert_eq!(compose_query("tools", &args, &None, None).unwrap(), "tools");
+
+        let args = Args::parse_from(["gh", "--in", "name", "--in", "topics", "--in", "name", "game"]);
+        assert_eq!(compose_query("game", &args, &None, None).unwrap(), "game in:name,topics");
+        assert!(Args::try_parse_from(["gh", "--in", "title", "game"]).is_err());
+
+        let args = Args::parse_from(["gh", "--exclude-owner", "microsoft", "--not", "tutorial", "--not", "game jam", "game"]);
+        assert_eq!(compose_query("game", &args, &None, None).unwrap(), "game -user:microsoft NOT tutorial NOT \"game jam\"");
+        assert!(Args::try_parse_from(["gh", "--pushed-after", "last week", "game"]).is_err());
+
+        let args = Args::parse_from(["gh", "--owner", "@tokio-rs", "async"]);
+        assert_eq!(args.owner.as_deref(), Some("user:tokio-rs"));
+        assert!(Args::try_parse_from(["gh", "--owner", "repo:a/b"]).is_err());
+        let args = Args::parse_from(["gh", "--exclude-owner", "@microsoft", "--exclude-owner", "org:google", "game"]);
+        assert_eq!(compose_query("game", &args, &None, None).unwrap(), "game -user:microsoft -org:google");
+        // A name with a space or quote would otherwise change the rest of the query
+        assert!(Args::try_parse_from(["gh", "--exclude-owner", "micro soft", "game"]).is_err());
+        assert!(Args::try_parse_from(["gh", "--exclude-owner", "a\"b", "game"]).is_err());
+
+        // Every qualifier added here is one the cheatsheet documents
+        for term in query.split_whitespace().filter(|term| term.contains(':')) {
+            let name = term.split(':').next().unwrap();
+            assert!(qualifiers::find(name).is_some(), "{} missing from qualifiers::QUALIFIERS", name);
+        }
+    }
+
+    #[test]
+    fn test_use_color() {
+        let no_color = || Some(std::ffi::OsString::from("1"));
+        assert!(use_color(ColorChoice::Auto, None, true));
+        assert!(!use_color(ColorChoice::Auto, None, false));
+        assert!(!use_color(ColorChoice::Auto, no_color(), true));
+        // An empty NO_COLOR doesn't count
+        assert!(use_color(ColorChoice::Auto, Some(std::ffi::OsString::new()), true));
+        assert!(use_color(ColorChoice::Always, no_color(), false));
+        assert!(!use_color(ColorChoice::Never, None, true));
+        assert_eq!(Args::parse_from(["gh", "--color=never"]).color, ColorChoice::Never);
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"https://github.com"), "aHR0cHM6Ly9naXRodWIuY29t");
+    }
+
+    #[test]
+    fn test_clone_destination() {
+        let cwd = std::env::current_dir().unwrap();
+        let url = "https://github.com/owner/awesome";
+
+        let destination = |chosen: Option<&str>, batch, layout| {
+            clone_destination(url, chosen.map(Path::new), batch, layout).unwrap()
+        };
+
+        assert_eq!(destination(None, false, CloneLayout::Name), cwd.join("repositories/awesome"));
+        assert_eq!(destination(None, false, CloneLayout::OwnerName), cwd.join("repositories/owner/awesome"));
+        assert_eq!(destination(Some("src/mine"), false, CloneLayout::OwnerName), cwd.join("src/mine"));
+        // Batch clones treat the chosen path as a parent directory
+        assert_eq!(destination(Some("/tmp/clones"), true, CloneLayout::Name), PathBuf::from("/tmp/clones/awesome"));
+        assert_eq!(
+            destination(Some("/tmp/clones"), true, CloneLayout::OwnerName),
+            PathBuf::from("/tmp/clones/owner/awesome")
+        );
+    }
+
+    #[test]
+    fn test_repo_owner_from_url() {
+        assert_eq!(repo_owner_from_url("https://github.com/foo/awesome"), "foo");
+        assert_eq!(repo_owner_from_url("https://github.com/bar/awesome.git/"), "bar");
+        assert_eq!(repo_owner_from_url("awesome"), "unknown");
+    }
 
-        // Verify it's detected as a URL
-        assert!(action.starts_with("http"));
+    #[tokio::test]
+    async fn test_search_request_from_args() {
+        let (_dir, cache) = scratch_cache();
+        let github = MockGitHub::new();
+        let args = Args::parse_from(["gh", "-L", "rust", "--stars", "100", "--sort", "stars", "-l", "2", "game"]);
+        let (results, total, _, cached, _) = search_with_cache(&github, &cache, "game", &args, &Some("small".to_string())).await.unwrap();
+
+        assert_eq!(github.searches(), [Search {
+            query: "game language:rust stars:>=100 size:<25000".to_string(),
+            sort: Some("stars".to_string()),
+            per_page: 2,
+            page: 1,
+        }]);
+        let names: Vec<_> = results.iter().filter_map(|repo| repo.full_name.as_deref()).collect();
+        assert_eq!(names, ["rust-lang/rust", "bevyengine/bevy"]);
+        assert_eq!((total, cached), (3, None));
+    }
+
+    #[tokio::test]
+    async fn test_page_fetch_stops_at_first_failure() {
+        let (_dir, cache) = scratch_cache();
+        let github = Arc::new(MockGitHub::new().failing(3, MockGitHub::status(502, "Bad Gateway")));
+        let args = Args::parse_from(["gh", "-l", "2", "rust"]);
+        let request = SearchRequest::new("rust", &args, &None, None, 2).unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        page_job(github.clone(), cache, 1, request, 5, tx).await;
+
+        let mut events = Vec::new();
+        while let Some(jobs::JobEvent::PageLoaded { page, done, result, .. }) = rx.recv().await {
+            events.push((page, done, result.map(|(items, _, _)| items.len())));
+        }
+        assert_eq!(events, [(2, false, Ok(1)), (3, true, Err("Bad Gateway".to_string()))]);
+        let pages: Vec<u32> = github.searches().iter().map(|search| search.page).collect();
+        assert_eq!(pages, [2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_search_job_reports_results_and_quota() {
+        let (_dir, cache) = scratch_cache();
+        let github: Client = Arc::new(MockGitHub::new());
+        let request = SearchRequest::new("game", &Args::parse_from(["gh"]), &None, None, 1).unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        search_job(github, cache, 7, "game".to_string(), request, tx).await;
+
+        let Some(jobs::JobEvent::SearchFinished { id, query, result, rate_limit }) = rx.recv().await else {
+            panic!("expected the search's results");
+        };
+        assert_eq!((id, query.as_str()), (7, "game"));
+        assert_eq!(result.unwrap().1, 3);
+        assert_eq!(rate_limit.map(|rate| rate.remaining), Some(30));
+    }
+
+    #[tokio::test]
+    async fn test_fresh_cached_results_skip_github() {
+        let (_dir, cache) = scratch_cache();
+        let github = MockGitHub::new();
+        let mut request = SearchRequest::new("ttl", &Args::parse_from(["gh"]), &None, None, 1).unwrap();
+        let (_, _, _, cached, _) = search_page_with_cache(&github, &cache, &request).await.unwrap();
+        assert_eq!(cached, None);
+
+        // Within the TTL the cached page is used; without one (Ctrl+R) GitHub is asked again
+        request.cache_ttl = 300;
+        let (results, total, _, cached, _) = search_page_with_cache(&github, &cache, &request).await.unwrap();
+        assert_eq!((results.len(), total), (2, 3));
+        assert!(matches!(cached, Some(cache::Hit::Fresh(_))));
+        assert_eq!(github.searches().len(), 1);
+        request.cache_ttl = 0;
+        search_page_with_cache(&github, &cache, &request).await.unwrap();
+        assert_eq!(github.searches().len(), 2);
+    }
 
-        // In the actual code at line 142-145, when action.starts_with("http"),
-        // we call open_in_browser() but do NOT break the loop.
-        // This test confirms the URL is properly formatted and would be handled.
+    #[tokio::test]
+    async fn test_search_error_mapping() {
+        let (_dir, cache) = scratch_cache();
+        let search = |github: MockGitHub| {
+            let cache = cache.clone();
+            async move { search_with_cache(&github, &cache, "mapping", &Args::parse_from(["gh"]), &None).await }
+        };
+
+        // Rate limits come with the time the quota refills, from the rate_limit endpoint
+        let limited = MockGitHub::new().failing(1, MockGitHub::status(403, "API rate limit exceeded for 1.2.3.4"));
+        let error = search(limited).await.unwrap_err();
+        assert_eq!(error, error::Error::RateLimited { reset: Some(1_700_000_000) });
+        assert_eq!(error.exit_code(), error::EXIT_RATE_LIMITED);
+
+        let forbidden = MockGitHub::new().failing(1, MockGitHub::status(403, "Resource not accessible"));
+        assert_eq!(search(forbidden).await.unwrap_err(), error::Error::Other("Resource not accessible".to_string()));
+        let invalid = MockGitHub::new().failing(1, MockGitHub::status(422, "Validation Failed"));
+        assert_eq!(search(invalid).await.unwrap_err(), error::Error::InvalidQuery("Validation Failed".to_string()));
+
+        // CLI mode turns them into exit codes
+        let unauthorized = MockGitHub::new().failing(1, MockGitHub::status(401, "Bad credentials"));
+        let code = run_cli_mode(&unauthorized, &Args::parse_from(["gh", "-q", "rust"])).await.unwrap();
+        assert_eq!(code, ExitCode::from(error::EXIT_AUTH));
+
+        // Offline, the last results of the same search come from the cache
+        search(MockGitHub::new()).await.unwrap();
+        let offline = MockGitHub::new().failing(1, error::Error::Network("connection refused".to_string()));
+        let (results, _, text_matches, cached, _) = search(offline).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(matches!(cached, Some(cache::Hit::Offline(_))));
+        // Including why each matched
+        assert_eq!(text_matches[&140683][0].hits, ["game"]);
+        let offline = MockGitHub::new().failing(1, error::Error::Network("connection refused".to_string()));
+        let other_query = search_with_cache(&offline, &cache, "uncached", &Args::parse_from(["gh"]), &None).await;
+        assert!(matches!(other_query, Err(error::Error::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn test_empty_results_still_write_the_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let (csv, json) = (dir.path().join("none.csv"), dir.path().join("none.json"));
+        for path in [&csv, &json] {
+            let path = path.to_str().unwrap();
+            let args = Args::parse_from(["gh", "-q", "--fail-if-empty", "--output", path, "nothing", "matches"]);
+            let code = run_cli_mode(&MockGitHub::new().empty(), &args).await.unwrap();
+            assert_eq!(code, ExitCode::from(error::EXIT_NO_RESULTS));
+        }
+        assert_eq!(std::fs::read_to_string(&csv).unwrap(), "full_name,url,description,stars,forks,language,license,pushed_at\n");
+        assert_eq!(std::fs::read_to_string(&json).unwrap().trim(), "[]");
+    }
+
+    #[test]
+    fn test_url_action_does_not_exit_tui() {
+        // Alt+O hands main's loop an OpenUrl action; the loop opens the browser and goes back
+        // to run_tui rather than breaking out like a quit (None) does
+        let url = "https://github.com/user/test-repo";
+        let action = Action::OpenUrl(url.to_string());
+        assert!(matches!(action, Action::OpenUrl(ref target) if target == url));
     }
 }
@@ -0,0 +1,316 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+use crate::clones;
+use crate::error::Error;
+use crate::github::GitHub;
+use crate::i18n::tr;
+
+/// Latest issues and pull requests listed to measure responsiveness (pull requests are left out)
+const ISSUES_SAMPLED: u8 = 20;
+
+/// Latest pull requests listed to measure how many get merged
+const PULLS_SAMPLED: u8 = 20;
+
+/// Top contributors listed to see how concentrated the commits are (one page, GitHub's maximum)
+const CONTRIBUTORS_SAMPLED: u8 = 100;
+
+/// Contributors the details panel breaks the commits down by; the rest share one row
+pub const CONTRIBUTORS_SHOWN: usize = 5;
+
+/// What the metrics need of one issue
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueDates {
+    pub number: u64,
+    pub author: String, // Login of whoever opened it
+    pub opened: SystemTime,
+    pub closed: Option<SystemTime>,
+    pub comments: u32,
+}
+
+/// What the metrics need of one pull request
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullDates {
+    pub opened: SystemTime,
+    pub closed: Option<SystemTime>, // Merged or not
+    pub merged: Option<SystemTime>,
+}
+
+/// How quickly a repository's maintainers answer and close its recent issues, whether they
+/// merge the pull requests they get, and how many people do the work
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Maintenance {
+    pub issues: usize,                    // Recent issues measured
+    pub first_response: Option<Duration>, // Median time to the first reply, over the issues that got one
+    pub time_to_close: Option<Duration>,  // Median time to close, over the closed issues
+    pub pulls: usize,                     // Recent pull requests measured
+    pub merged: usize,                    // How many of them were merged
+    pub open_pulls: usize,                // How many of them are still open
+    pub merge_time: Option<Duration>,     // Median time from opening to merge, over the merged ones
+    pub contributors: Vec<(String, u64)>, // Commits per top contributor, most first; empty when not listed
+}
+
+/// Fetch owner/name's recent issues, the first reply to each one with comments, its recent pull
+/// requests, and its top contributors (at most ISSUES_SAMPLED + 3 API requests)
+pub async fn fetch_metrics(github: &dyn GitHub, owner: &str, name: &str) -> Result<Maintenance> {
+    let issues = github.recent_issues(owner, name, ISSUES_SAMPLED).await?;
+    let mut responses = Vec::new();
+    for issue in issues.iter().filter(|issue| issue.comments > 0) {
+        if let Some(reply) = github.first_reply(owner, name, issue.number, &issue.author).await? {
+            responses.push(reply.duration_since(issue.opened).unwrap_or_default());
+        }
+    }
+    let closes = issues.iter().filter_map(|issue| issue.closed?.duration_since(issue.opened).ok()).collect();
+
+    let pulls = github.recent_pulls(owner, name, PULLS_SAMPLED).await?;
+    let merges: Vec<Duration> = pulls.iter().filter_map(|pull| pull.merged?.duration_since(pull.opened).ok()).collect();
+
+    // GitHub won't list the contributors of very large histories; the other figures still stand
+    let contributors = match github.top_contributors(owner, name, CONTRIBUTORS_SAMPLED).await {
+        Err(Error::TooLarge) => {
+            tracing::info!(owner, name, "contributor list too large to list");
+            Vec::new()
+        }
+        contributors => contributors?,
+    };
+    Ok(Maintenance {
+        issues: issues.len(),
+        first_response: median(responses),
+        time_to_close: median(closes),
+        pulls: pulls.len(),
+        merged: pulls.iter().filter(|pull| pull.merged.is_some()).count(),
+        open_pulls: pulls.iter().filter(|pull| pull.closed.is_none()).count(),
+        merge_time: median(merges),
+        contributors,
+    })
+}
+
+/// Whether a comment by `login` answers an issue opened by `author`: bots (stale and triage
+/// bots reply within seconds) and the author's own follow-ups don't
+pub fn is_reply(login: &str, author: &str) -> bool {
+    login != author && !login.ends_with("[bot]")
+}
+
+/// The middle one of `durations`, or the mean of the two middle ones; None when empty
+fn median(mut durations: Vec<Duration>) -> Option<Duration> {
+    durations.sort();
+    let middle = durations.len() / 2;
+    match durations.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(durations[middle]),
+        _ => Some((durations[middle - 1] + durations[middle]) / 2),
+    }
+}
+
+/// A rough length of time, e.g. "~3 days"
+fn span_label(span: Duration) -> String {
+    let hours = span.as_secs() / 3600;
+    match hours / 24 {
+        _ if hours == 0 => tr!("span-under-hour"),
+        0..=1 => tr!("span-hours", count = hours),
+        days @ 2..=13 => tr!("span-days", count = days),
+        days @ 14..=59 => tr!("span-weeks", count = days / 7),
+        days @ 60..=729 => tr!("span-months", count = days / 30),
+        days => tr!("span-years", count = days / 365),
+    }
+}
+
+/// The details-panel issue summary, e.g. "first reply ~5 hours, closed in ~2 weeks (median of 20 recent)"
+pub fn issues_label(metrics: &Maintenance) -> String {
+    if metrics.issues == 0 {
+        return tr!("issues-none");
+    }
+    let first_response = metrics.first_response.map_or(tr!("issues-no-replies"), |span| {
+        tr!("issues-first-reply", span = span_label(span))
+    });
+    let time_to_close = metrics.time_to_close.map_or(tr!("issues-none-closed"), |span| {
+        tr!("issues-closed-in", span = span_label(span))
+    });
+    tr!("issues-summary", replies = first_response, closes = time_to_close, count = metrics.issues)
+}
+
+/// The details-panel pull-request summary, e.g. "12 of 20 merged (60%) in ~2 days, 3 still open"
+pub fn pulls_label(metrics: &Maintenance) -> String {
+    if metrics.pulls == 0 {
+        return tr!("pulls-none");
+    }
+    let (merged, count, percent) = (metrics.merged, metrics.pulls, metrics.merged * 100 / metrics.pulls);
+    let label = match metrics.merge_time {
+        Some(span) => tr!("pulls-merged-in", merged = merged, count = count, percent = percent, span = span_label(span)),
+        None => tr!("pulls-merged", merged = merged, count = count, percent = percent),
+    };
+    if metrics.open_pulls > 0 {
+        return tr!("pulls-still-open", pulls = label, open = metrics.open_pulls);
+    }
+    label
+}
+
+/// The bus factor: the fewest top `contributors` who made at least half of their commits, and
+/// those contributors' share of the commits in percent. None without commits
+pub fn bus_factor(contributors: &[(String, u64)]) -> Option<(usize, u64)> {
+    let total: u64 = contributors.iter().map(|(_, commits)| commits).sum();
+    let mut made = 0;
+    for (count, (_, commits)) in contributors.iter().enumerate() {
+        made += commits;
+        if made * 2 >= total && total > 0 {
+            return Some((count + 1, made * 100 / total));
+        }
+    }
+    None
+}
+
+/// The details-panel contributor summary, e.g. "top 2 of 37 contributors made 95% of commits"
+pub fn contributors_label(metrics: &Maintenance) -> String {
+    let Some((top, share)) = bus_factor(&metrics.contributors) else {
+        return tr!("contributors-not-listed");
+    };
+    let count = metrics.contributors.len();
+    let plus = if count >= CONTRIBUTORS_SAMPLED as usize { "+" } else { "" };
+    let listed = format!("{}{}", count, plus);
+    match (top, count) {
+        (1, 1) => tr!("contributors-single"),
+        (1, _) => tr!("contributors-top-one", listed = listed, share = share),
+        _ => tr!("contributors-top", top = top, listed = listed, share = share),
+    }
+}
+
+/// Rows of the details panel's contributor breakdown: login, commits, and share of the commits,
+/// for the top CONTRIBUTORS_SHOWN and then everyone else together
+pub fn contributor_rows(contributors: &[(String, u64)]) -> Vec<String> {
+    let total: u64 = contributors.iter().map(|(_, commits)| commits).sum::<u64>().max(1);
+    let word = tr!("contributors-commits");
+    let row = |login: &str, commits: u64| {
+        format!("  {:<24} {:>6} {} {:>4}%", login, clones::format_count(commits), word, commits * 100 / total)
+    };
+    let mut rows: Vec<String> =
+        contributors.iter().take(CONTRIBUTORS_SHOWN).map(|(login, commits)| row(login, *commits)).collect();
+    let rest = &contributors[contributors.len().min(CONTRIBUTORS_SHOWN)..];
+    if !rest.is_empty() {
+        let others = tr!("contributors-others", count = rest.len());
+        rows.push(row(&others, rest.iter().map(|(_, commits)| commits).sum()));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock::MockGitHub;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn test_median_and_labels() {
+        assert_eq!(median(Vec::new()), None);
+        assert_eq!(median(vec![HOUR * 9, HOUR, HOUR * 4]), Some(HOUR * 4));
+        assert_eq!(median(vec![HOUR * 6, HOUR * 2, HOUR * 100, HOUR]), Some(HOUR * 4));
+
+        assert_eq!(span_label(Duration::from_secs(600)), "under an hour");
+        assert_eq!(span_label(HOUR), "~1 hour");
+        assert_eq!(span_label(HOUR * 30), "~30 hours");
+        assert_eq!(span_label(HOUR * 24 * 5), "~5 days");
+        assert_eq!(span_label(HOUR * 24 * 21), "~3 weeks");
+        assert_eq!(span_label(HOUR * 24 * 800), "~2 years");
+
+        let none = Maintenance {
+            issues: 0,
+            first_response: None,
+            time_to_close: None,
+            pulls: 0,
+            merged: 0,
+            open_pulls: 0,
+            merge_time: None,
+            contributors: Vec::new(),
+        };
+        assert_eq!(issues_label(&none), "no recent issues");
+        assert_eq!(pulls_label(&none), "no recent pull requests");
+        let metrics = Maintenance {
+            issues: 20,
+            first_response: Some(HOUR * 5),
+            pulls: 20,
+            merged: 12,
+            open_pulls: 3,
+            merge_time: Some(HOUR * 50),
+            ..none.clone()
+        };
+        assert_eq!(issues_label(&metrics), "first reply ~5 hours, none closed (median of 20 recent)");
+        assert_eq!(pulls_label(&metrics), "12 of 20 merged (60%) in ~2 days, 3 still open");
+        let ignored = Maintenance { pulls: 4, open_pulls: 1, ..none.clone() };
+        assert_eq!(pulls_label(&ignored), "0 of 4 merged (0%), 1 still open");
+
+        assert!(is_reply("maintainer", "reporter"));
+        assert!(!is_reply("reporter", "reporter"));
+        assert!(!is_reply("github-actions[bot]", "reporter"));
+    }
+
+    #[test]
+    fn test_bus_factor() {
+        let contributors = |commits: &[u64]| -> Vec<(String, u64)> {
+            commits.iter().enumerate().map(|(i, commits)| (format!("dev{}", i), *commits)).collect()
+        };
+        let metrics = |commits: &[u64]| Maintenance {
+            issues: 0,
+            first_response: None,
+            time_to_close: None,
+            pulls: 0,
+            merged: 0,
+            open_pulls: 0,
+            merge_time: None,
+            contributors: contributors(commits),
+        };
+        assert_eq!(bus_factor(&[]), None);
+        assert_eq!(bus_factor(&contributors(&[60, 30, 10])), Some((1, 60)));
+        assert_eq!(bus_factor(&contributors(&[40, 30, 20, 10])), Some((2, 70)));
+        assert_eq!(bus_factor(&contributors(&[50, 50])), Some((1, 50)));
+
+        assert_eq!(contributors_label(&metrics(&[])), "not listed (too many commits, or none)");
+        assert_eq!(contributors_label(&metrics(&[12])), "a single contributor made every commit");
+        assert_eq!(contributors_label(&metrics(&[60, 30, 10])), "the top one of 3 contributors made 60% of commits");
+        assert_eq!(contributors_label(&metrics(&[950, 900, 50, 50, 50])), "top 2 of 5 contributors made 92% of commits");
+        assert_eq!(contributors_label(&metrics(&[1; 100])), "top 50 of 100+ contributors made 50% of commits");
+
+        let rows = contributor_rows(&contributors(&[1200, 500, 100, 100, 50, 30, 20]));
+        assert_eq!(rows.len(), CONTRIBUTORS_SHOWN + 1);
+        assert_eq!(rows[0], format!("  {:<24} {:>6} commits {:>4}%", "dev0", "1.2k", 60));
+        assert_eq!(rows[5], format!("  {:<24} {:>6} commits {:>4}%", "2 others", "50", 2));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_metrics() {
+        let opened = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let issue = |number: u64, comments: u32, closed_after: Option<Duration>| IssueDates {
+            number,
+            author: "reporter".to_string(),
+            opened,
+            closed: closed_after.map(|after| opened + after),
+            comments,
+        };
+        let mut github = MockGitHub::new();
+        github.issues = vec![issue(1, 2, Some(HOUR * 48)), issue(2, 1, None), issue(3, 0, Some(HOUR)), issue(4, 3, None)];
+        // Issue 4's comments are all the author's own
+        github.replies = [(1, opened + HOUR * 2), (2, opened + HOUR * 10)].into();
+        let pull = |closed_after: Option<Duration>, merged: bool| PullDates {
+            opened,
+            closed: closed_after.map(|after| opened + after),
+            merged: closed_after.filter(|_| merged).map(|after| opened + after),
+        };
+        github.pulls = vec![pull(Some(HOUR * 3), true), pull(Some(HOUR), false), pull(None, false), pull(Some(HOUR * 5), true)];
+        github.top_contributors = Ok(vec![("alice".to_string(), 90), ("bob".to_string(), 10)]);
+
+        let metrics = fetch_metrics(&github, "owner", "repo").await.unwrap();
+        assert_eq!(metrics.issues, 4);
+        assert_eq!(metrics.first_response, Some(HOUR * 6));
+        assert_eq!(metrics.time_to_close, Some(HOUR * 49 / 2));
+        assert_eq!((metrics.pulls, metrics.merged, metrics.open_pulls), (4, 2, 1));
+        assert_eq!(metrics.merge_time, Some(HOUR * 4));
+        assert_eq!(bus_factor(&metrics.contributors), Some((1, 90)));
+
+        // Only a history too large to list leaves the contributors out
+        github.top_contributors = Err(Error::TooLarge);
+        assert!(fetch_metrics(&github, "owner", "repo").await.unwrap().contributors.is_empty());
+        github.top_contributors = Err(Error::Network("connection reset".to_string()));
+        let error = fetch_metrics(&github, "owner", "repo").await.unwrap_err();
+        assert_eq!(error.downcast_ref::<Error>(), Some(&Error::Network("connection reset".to_string())));
+    }
+}
@@ -0,0 +1,78 @@
+use crossterm::event::{Event, KeyEvent};
+use std::path::PathBuf;
+
+use crate::jobs::JobEvent;
+use crate::profiles::Step;
+
+/// Everything that changes the TUI's state, fed one at a time to `App::update`
+#[derive(Debug)]
+pub enum AppMessage {
+    /// A key press
+    Key(KeyEvent),
+    /// A frame went by: fire due timers (live search, rate-limit resume, next page)
+    Tick,
+    /// A background task reported back over the jobs channel
+    Job(JobEvent),
+}
+
+impl AppMessage {
+    /// The message for a terminal event, if the TUI reacts to it (resizes just redraw)
+    pub fn from_event(event: Event) -> Option<Self> {
+        match event {
+            Event::Key(key) => Some(AppMessage::Key(key)),
+            _ => None,
+        }
+    }
+}
+
+/// Work `update` hands back to main's loop, which owns the GitHub client and spawns the tasks
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Run a search in the active tab
+    Search(String),
+    /// Run a search in the active tab, asking GitHub even when its results are cached
+    Reload(String),
+    /// Fetch the pages after the loaded ones, up to this one
+    Page(u32),
+    /// Open a URL in the browser
+    OpenUrl(String),
+    /// Copy text to the clipboard
+    Copy(String),
+    /// Clone repos (by URL) to the destination confirmed in the clone prompt
+    Clone(Vec<String>),
+    /// Count the files of repos (by URL)
+    FileCount(Vec<String>),
+    /// Analyze the languages of repos (by URL)
+    Analyze(Vec<String>),
+    /// Fetch release and contributor figures of repos (by URL) for the comparison view or details panel
+    Compare(Vec<String>),
+    /// Fetch the same figures of a repo (by URL) ahead of its selection, at the lowest priority
+    Prefetch(String),
+    /// Update local clones from their remotes
+    Update(Vec<PathBuf>),
+    /// Load the repositories an awesome list (owner/name) links to
+    LoadList(String),
+    /// Fetch the star history of a repo (by URL) that has this many stars
+    StarHistory(String, u64),
+    /// Measure how a repo (by URL) is maintained: issue replies, PR merges, and who commits
+    Maintenance(String),
+    /// Write an SBOM of a local clone (path, owner/name) next to it
+    Sbom(PathBuf, String),
+    /// Check the Cargo.lock of a fresh clone (URL, path) against the RustSec advisories
+    Audit(String, PathBuf),
+    /// Run the analysis profile commands picked for a fresh clone (URL, path) in it
+    Profiles(String, PathBuf, Vec<Step>),
+    /// Open a new terminal pane in a local clone
+    OpenPane(PathBuf),
+}
+
+/// What happens after `App::update` handles a message
+#[derive(Debug, Clone, PartialEq)]
+pub enum Flow {
+    /// Keep going: draw the next frame and wait for the next message
+    Continue,
+    /// Leave the TUI loop so main can carry out the action
+    Run(Action),
+    /// Quit the app
+    Quit,
+}
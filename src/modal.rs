@@ -0,0 +1,277 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
+
+use crate::i18n::tr;
+use crate::icons::Icon;
+
+/// What a modal asks about, so its answer can be acted on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Purpose {
+    GoToPage,       // Alt+P: page number
+    Export,         // Alt+E: file to write
+    OwnerScope,     // Alt+U: user or org every search is limited to
+    AwesomeTopic,   // Alt+L: topic to find awesome lists about
+    DeleteClones,   // d in "my clones": delete the marked/selected clones?
+    CommandPalette, // Ctrl+P: run any shortcut by name
+}
+
+/// A dialog drawn above the main layout
+pub enum Modal {
+    /// A yes/no question: y confirms, any other key cancels
+    Confirm { purpose: Purpose, title: String, message: String },
+    /// A one-line text field: Enter submits, Esc cancels
+    Prompt {
+        purpose: Purpose,
+        title: String,
+        label: String,
+        hint: String,
+        width: u16,
+        input: Input,
+        digits_only: bool, // Other characters are ignored (page numbers)
+    },
+    /// A list to choose from: ↑/↓ select, Enter picks, Esc cancels
+    Picker { purpose: Purpose, title: String, items: Vec<String>, state: ListState },
+}
+
+impl Modal {
+    /// A prompt with `input` prefilled
+    pub fn prompt(purpose: Purpose, title: String, label: String, hint: String, width: u16, input: &str) -> Self {
+        Modal::Prompt { purpose, title, label, hint, width, input: Input::new(input.to_string()), digits_only: false }
+    }
+
+    /// The same prompt, ignoring everything typed but digits
+    pub fn digits_only(mut self) -> Self {
+        if let Modal::Prompt { digits_only, .. } = &mut self {
+            *digits_only = true;
+        }
+        self
+    }
+
+    /// A picker with its first item selected
+    pub fn picker(purpose: Purpose, title: String, items: Vec<String>) -> Self {
+        let mut state = ListState::default();
+        state.select((!items.is_empty()).then_some(0));
+        Modal::Picker { purpose, title, items, state }
+    }
+
+    pub fn purpose(&self) -> Purpose {
+        match self {
+            Modal::Confirm { purpose, .. } | Modal::Prompt { purpose, .. } | Modal::Picker { purpose, .. } => *purpose,
+        }
+    }
+}
+
+/// What a key press did to the top modal
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pending,                    // Still open and waiting
+    Cancelled(Purpose),         // Closed without an answer
+    Confirmed(Purpose),         // y on a Confirm (closed)
+    Submitted(Purpose, String), // Enter on a Prompt (left open: the caller closes it once the text is valid)
+    Picked(Purpose, usize),     // Enter on a Picker, with the item's index (closed)
+}
+
+/// Open modals, bottom to top; only the top one gets key presses
+#[derive(Default)]
+pub struct ModalStack {
+    modals: Vec<Modal>,
+}
+
+impl ModalStack {
+    pub fn push(&mut self, modal: Modal) {
+        self.modals.push(modal);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modals.is_empty()
+    }
+
+    /// Close the topmost modal for `purpose`
+    pub fn close(&mut self, purpose: Purpose) {
+        if let Some(index) = self.modals.iter().rposition(|modal| modal.purpose() == purpose) {
+            self.modals.remove(index);
+        }
+    }
+
+    /// Route a key press to the top modal; None when no modal is open
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Outcome> {
+        let modal = self.modals.last_mut()?;
+        let purpose = modal.purpose();
+        let outcome = match modal {
+            Modal::Confirm { .. } => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => Outcome::Confirmed(purpose),
+                _ => Outcome::Cancelled(purpose),
+            },
+            Modal::Prompt { input, digits_only, .. } => match key.code {
+                KeyCode::Esc => Outcome::Cancelled(purpose),
+                KeyCode::Enter => return Some(Outcome::Submitted(purpose, input.value().to_string())),
+                KeyCode::Char(c) if *digits_only && !c.is_ascii_digit() => Outcome::Pending,
+                _ => {
+                    input.handle_event(&Event::Key(key));
+                    Outcome::Pending
+                }
+            },
+            Modal::Picker { items, state, .. } => match key.code {
+                KeyCode::Esc => Outcome::Cancelled(purpose),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    state.select(Some((state.selected().unwrap_or(0) + 1).min(items.len().saturating_sub(1))));
+                    Outcome::Pending
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    state.select(Some(state.selected().unwrap_or(0).saturating_sub(1)));
+                    Outcome::Pending
+                }
+                KeyCode::Enter => match state.selected() {
+                    Some(index) => Outcome::Picked(purpose, index),
+                    None => Outcome::Cancelled(purpose),
+                },
+                _ => Outcome::Pending,
+            },
+        };
+        if outcome != Outcome::Pending {
+            self.modals.pop();
+        }
+        Some(outcome)
+    }
+
+    /// Draw every open modal over `area`, bottom to top
+    pub fn render(&mut self, f: &mut Frame, area: Rect) {
+        for modal in &mut self.modals {
+            match modal {
+                Modal::Confirm { title, message, .. } => render_confirm(f, area, title, message),
+                Modal::Prompt { title, label, hint, width, input, .. } => {
+                    render_prompt(f, area, title, label, input, hint, *width)
+                }
+                Modal::Picker { title, items, state, .. } => render_picker(f, area, title, items, state),
+            }
+        }
+    }
+}
+
+/// A `width` x `height` rectangle in the middle of `area` (clamped to it)
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect { x: area.x + (area.width - width) / 2, y: area.y + (area.height - height) / 2, width, height }
+}
+
+fn render_confirm(f: &mut Frame, area: Rect, title: &str, message: &str) {
+    let width = Span::raw(message).width().max(Span::raw(title).width()) as u16 + 6;
+    let rect = centered(area, width.max(40), 5);
+    let lines = vec![
+        Line::from(message.to_string()),
+        Line::from(""),
+        Line::from(Span::styled(tr!("confirm-keys"), Style::default().fg(Color::DarkGray))),
+    ];
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", title))
+            .border_style(Style::default().fg(Color::Red)),
+    );
+    f.render_widget(Clear, rect);
+    f.render_widget(widget, rect);
+}
+
+/// A text field labelled `label` with a key `hint` under it, `width` cells wide
+fn render_prompt(f: &mut Frame, area: Rect, title: &str, label: &str, input: &Input, hint: &str, width: u16) {
+    let rect = centered(area, width, 5);
+    let lines = vec![
+        Line::from(vec![Span::styled(label, Style::default().fg(Color::Cyan)), Span::raw(input.value())]),
+        Line::from(""),
+        Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))),
+    ];
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", title))
+            .border_style(Style::default().fg(Color::Green)),
+    );
+    f.render_widget(Clear, rect);
+    f.render_widget(widget, rect);
+
+    let cursor_x = rect.x + 1 + Span::raw(label).width() as u16 + input.visual_cursor() as u16;
+    f.set_cursor_position((cursor_x.min(rect.x + rect.width.saturating_sub(2)), rect.y + 1));
+}
+
+fn render_picker(f: &mut Frame, area: Rect, title: &str, items: &[String], state: &mut ListState) {
+    let width = items.iter().map(|item| Span::raw(item.as_str()).width()).max().unwrap_or(0) as u16 + 8;
+    let rect = centered(area, width.max(Span::raw(title).width() as u16 + 4), items.len() as u16 + 2);
+    let list = List::new(items.iter().map(|item| ListItem::new(item.as_str())))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", title))
+                .title_bottom(format!(" {} ", tr!("picker-keys", updown = Icon::UpDown.to_string())))
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+        .highlight_symbol(Icon::Selected.glyph());
+    f.render_widget(Clear, rect);
+    f.render_stateful_widget(list, rect, state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn confirm() -> Modal {
+        Modal::Confirm { purpose: Purpose::DeleteClones, title: "Delete".to_string(), message: "Delete it?".to_string() }
+    }
+
+    #[test]
+    fn test_top_modal_gets_the_keys() {
+        let mut stack = ModalStack::default();
+        assert_eq!(stack.handle_key(key(KeyCode::Enter)), None);
+
+        stack.push(Modal::prompt(Purpose::Export, "Export".into(), "File: ".into(), String::new(), 40, "a.md"));
+        stack.push(confirm());
+
+        // Any key but y cancels a confirmation, and the prompt under it is back on top
+        assert_eq!(stack.handle_key(key(KeyCode::Char('n'))), Some(Outcome::Cancelled(Purpose::DeleteClones)));
+        assert_eq!(stack.handle_key(key(KeyCode::Backspace)), Some(Outcome::Pending));
+        assert_eq!(stack.handle_key(key(KeyCode::Char('x'))), Some(Outcome::Pending));
+
+        // A submitted prompt stays open until the caller closes it
+        assert_eq!(stack.handle_key(key(KeyCode::Enter)), Some(Outcome::Submitted(Purpose::Export, "a.mx".to_string())));
+        assert!(!stack.is_empty());
+        stack.close(Purpose::Export);
+        assert!(stack.is_empty());
+
+        stack.push(confirm());
+        assert_eq!(stack.handle_key(key(KeyCode::Char('y'))), Some(Outcome::Confirmed(Purpose::DeleteClones)));
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_digits_only_prompt_and_picker() {
+        let mut stack = ModalStack::default();
+        stack.push(Modal::prompt(Purpose::GoToPage, "Page".into(), "Page: ".into(), String::new(), 40, "").digits_only());
+        for c in ['1', 'x', '2'] {
+            stack.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(stack.handle_key(key(KeyCode::Enter)), Some(Outcome::Submitted(Purpose::GoToPage, "12".to_string())));
+        assert_eq!(stack.handle_key(key(KeyCode::Esc)), Some(Outcome::Cancelled(Purpose::GoToPage)));
+
+        let items = vec!["open".to_string(), "clone".to_string(), "jobs".to_string()];
+        stack.push(Modal::picker(Purpose::CommandPalette, "Commands".into(), items));
+        for code in [KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Up] {
+            assert_eq!(stack.handle_key(key(code)), Some(Outcome::Pending));
+        }
+        assert_eq!(stack.handle_key(key(KeyCode::Enter)), Some(Outcome::Picked(Purpose::CommandPalette, 1)));
+        assert!(stack.is_empty());
+    }
+}
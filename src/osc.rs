@@ -0,0 +1,114 @@
+use std::io::{self, Write};
+
+/// Title the terminal shows while no search has run
+const TITLE: &str = "mrkrabz";
+
+/// Taskbar progress terminals like Windows Terminal and ConEmu show (OSC 9;4)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Progress {
+    #[default]
+    None,
+    /// Busy, but how far along isn't known
+    Indeterminate,
+    /// Percent done, 0-100
+    Value(u8),
+}
+
+impl Progress {
+    fn sequence(self) -> String {
+        match self {
+            Progress::None => "\x1b]9;4;0;0\x07".to_string(),
+            Progress::Indeterminate => "\x1b]9;4;3;0\x07".to_string(),
+            Progress::Value(percent) => format!("\x1b]9;4;1;{}\x07", percent.min(100)),
+        }
+    }
+}
+
+/// The terminal title for a search, e.g. "mrkrabz: rust tui"
+pub fn title(query: &str) -> String {
+    let query = query.trim();
+    if query.is_empty() {
+        TITLE.to_string()
+    } else {
+        format!("{}: {}", TITLE, query)
+    }
+}
+
+/// Escape sequence setting the terminal title, with control characters left out of it
+fn title_sequence(title: &str) -> String {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]0;{}\x07", title)
+}
+
+/// Keeps the terminal's title and taskbar progress in step with the app, writing only changes
+#[derive(Debug, Default)]
+pub struct TerminalStatus {
+    pub enabled: bool, // config: terminal_status
+    title: Option<String>,
+    progress: Progress,
+}
+
+impl TerminalStatus {
+    /// Write the sequences for whatever differs from what was last written
+    pub fn update(&mut self, out: &mut impl Write, title: String, progress: Progress) -> io::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.title.as_ref() != Some(&title) {
+            out.write_all(title_sequence(&title).as_bytes())?;
+            self.title = Some(title);
+        }
+        if self.progress != progress {
+            out.write_all(progress.sequence().as_bytes())?;
+            self.progress = progress;
+        }
+        out.flush()
+    }
+
+    /// Clear the progress indicator and put the title back, for when the TUI exits
+    pub fn reset(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if self.progress != Progress::None {
+            out.write_all(Progress::None.sequence().as_bytes())?;
+            self.progress = Progress::None;
+        }
+        if self.title.take().is_some() {
+            out.write_all(title_sequence("").as_bytes())?;
+        }
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title() {
+        assert_eq!(title(""), "mrkrabz");
+        assert_eq!(title(" rust tui "), "mrkrabz: rust tui");
+        assert_eq!(title_sequence("a\x07b\nc"), "\x1b]0;abc\x07");
+    }
+
+    #[test]
+    fn test_update_writes_only_changes() {
+        let mut status = TerminalStatus { enabled: true, ..Default::default() };
+        let mut out = Vec::new();
+        status.update(&mut out, title("rust"), Progress::None).unwrap();
+        assert_eq!(String::from_utf8_lossy(&out), "\x1b]0;mrkrabz: rust\x07");
+
+        out.clear();
+        status.update(&mut out, title("rust"), Progress::Value(42)).unwrap();
+        assert_eq!(String::from_utf8_lossy(&out), "\x1b]9;4;1;42\x07");
+        out.clear();
+        status.update(&mut out, title("rust"), Progress::Value(42)).unwrap();
+        assert!(out.is_empty());
+
+        status.reset(&mut out).unwrap();
+        assert_eq!(String::from_utf8_lossy(&out), "\x1b]9;4;0;0\x07\x1b]0;\x07");
+
+        let mut disabled = TerminalStatus::default();
+        out.clear();
+        disabled.update(&mut out, title("rust"), Progress::Indeterminate).unwrap();
+        assert!(out.is_empty());
+    }
+}
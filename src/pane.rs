@@ -0,0 +1,88 @@
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Terminal multiplexers a clone can be opened next to the TUI in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Multiplexer {
+    Tmux,
+    WezTerm,
+}
+
+impl Multiplexer {
+    /// The multiplexer the TUI runs inside, from the variables each one sets in its panes
+    /// tmux wins when it runs inside WezTerm: its pane is the one the TUI is in
+    pub fn detect(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let set = |name: &str| var(name).is_some_and(|value| !value.is_empty());
+        if set("TMUX") {
+            Some(Multiplexer::Tmux)
+        } else if set("WEZTERM_PANE") {
+            Some(Multiplexer::WezTerm)
+        } else {
+            None
+        }
+    }
+
+    /// Command that opens a pane beside the current one, with its directory appended
+    fn split_command(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "tmux split-window -h -c",
+            Multiplexer::WezTerm => "wezterm cli split-pane --cwd",
+        }
+    }
+}
+
+/// The command that opens a new pane, split on whitespace into the program and its arguments:
+/// `configured` (config: pane_command), or else a split of the multiplexer the TUI runs inside
+pub fn command(configured: Option<&str>, var: impl Fn(&str) -> Option<String>) -> Option<Vec<String>> {
+    let command = match configured {
+        Some(command) => command,
+        None => Multiplexer::detect(var)?.split_command(),
+    };
+    let words: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+    (!words.is_empty()).then_some(words)
+}
+
+/// Run `command` with `dir` as its last argument, so the new pane starts there
+/// Its output is dropped (wezterm prints the new pane's id), apart from stderr on failure
+pub fn open(command: &[String], dir: &Path) -> Result<()> {
+    let (program, args) = command.split_first().context("pane_command is empty")?;
+    let output = Command::new(program)
+        .args(args)
+        .arg(dir)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("couldn't run {}", program))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("{} failed: {}", program, stderr.lines().next().unwrap_or("no output").trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Multiplexer::detect(env(&[])), None);
+        assert_eq!(Multiplexer::detect(env(&[("TMUX", "")])), None);
+        assert_eq!(Multiplexer::detect(env(&[("WEZTERM_PANE", "3")])), Some(Multiplexer::WezTerm));
+        let both = [("TMUX", "/tmp/tmux-1000/default,123,0"), ("WEZTERM_PANE", "3")];
+        assert_eq!(Multiplexer::detect(env(&both)), Some(Multiplexer::Tmux));
+    }
+
+    #[test]
+    fn test_command() {
+        assert_eq!(command(None, env(&[])), None);
+        assert_eq!(command(None, env(&[("WEZTERM_PANE", "3")])).unwrap(), ["wezterm", "cli", "split-pane", "--cwd"]);
+        let configured = command(Some("tmux new-window -c"), env(&[("WEZTERM_PANE", "3")]));
+        assert_eq!(configured.unwrap(), ["tmux", "new-window", "-c"]);
+        assert_eq!(command(Some("  "), env(&[("TMUX", "x")])), None);
+    }
+}
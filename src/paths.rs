@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use directories::{BaseDirs, ProjectDirs};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files older versions kept in the working directory, copied to the data directory on first run
+const LEGACY_DATA_FILES: &[&str] = &["bookmarks.toml", "clones.toml"];
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "mrkrabz")
+}
+
+/// config.toml in the user config directory ($XDG_CONFIG_HOME/mrkrabz on Linux,
+/// ~/Library/Application Support/mrkrabz on macOS, %APPDATA%\mrkrabz\config on Windows)
+/// Falls back to ./config.toml when only that exists, as older versions read it from there
+pub fn config_file() -> PathBuf {
+    choose_config(user_config_file(), PathBuf::from("config.toml"))
+}
+
+/// config.toml in the user config directory, where `config init` writes it
+pub fn user_config_file() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.config_dir().join("config.toml"))
+}
+
+fn choose_config(user: Option<PathBuf>, legacy: PathBuf) -> PathBuf {
+    match user {
+        Some(user) if user.exists() || !legacy.exists() => user,
+        _ => legacy,
+    }
+}
+
+/// Where bookmarks, the clones ledger, the search cache, and logs live
+/// ($XDG_DATA_HOME/mrkrabz on Linux); the working directory if no home directory is known
+pub fn data_dir() -> PathBuf {
+    // Tests get a scratch directory of their own, so they never touch the user's cache or bookmarks
+    if cfg!(test) {
+        let dir = std::env::temp_dir().join(format!("mrkrabz-test-{}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        return dir;
+    }
+    project_dirs().map_or_else(|| PathBuf::from("."), |dirs| dirs.data_dir().to_path_buf())
+}
+
+/// A file in the data directory
+pub fn data_file(name: &str) -> PathBuf {
+    data_dir().join(name)
+}
+
+/// Create the data directory and copy over data files left in the working directory by older versions
+/// Returns the names of the files copied
+pub fn prepare_data_dir() -> Result<Vec<&'static str>> {
+    let dir = data_dir();
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    copy_legacy_files(Path::new("."), &dir)
+}
+
+/// Copy each legacy data file from `from` into `to` unless `to` already has one
+fn copy_legacy_files(from: &Path, to: &Path) -> Result<Vec<&'static str>> {
+    let mut copied = Vec::new();
+    for name in LEGACY_DATA_FILES {
+        let (source, target) = (from.join(name), to.join(name));
+        if source.is_file() && !target.exists() && fs::canonicalize(from)? != fs::canonicalize(to)? {
+            fs::copy(&source, &target).with_context(|| format!("Failed to copy {} to {}", name, to.display()))?;
+            copied.push(*name);
+        }
+    }
+    Ok(copied)
+}
+
+/// Expand a leading "~" to the home directory (the user profile on Windows, where HOME is unset)
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), BaseDirs::new()) {
+        (Some(rest), Some(dirs)) if rest.is_empty() || rest.starts_with(std::path::is_separator) => {
+            dirs.home_dir().join(rest.trim_start_matches(std::path::is_separator))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home() {
+        let home = BaseDirs::new().unwrap().home_dir().to_path_buf();
+        assert_eq!(expand_home("~"), home);
+        assert_eq!(expand_home("~/x"), home.join("x"));
+        assert_eq!(expand_home("~/code"), home.join("code"));
+        assert_eq!(expand_home("~other/code"), PathBuf::from("~other/code"));
+        assert_eq!(expand_home("relative"), PathBuf::from("relative"));
+    }
+
+    #[test]
+    fn test_choose_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let user = dir.path().join("user.toml");
+        let legacy = dir.path().join("legacy.toml");
+
+        // Neither exists: the user config path, so errors and docs point there
+        assert_eq!(choose_config(Some(user.clone()), legacy.clone()), user);
+        // Only the legacy file exists
+        fs::write(&legacy, "").unwrap();
+        assert_eq!(choose_config(Some(user.clone()), legacy.clone()), legacy);
+        // Both: the user config wins
+        fs::write(&user, "").unwrap();
+        assert_eq!(choose_config(Some(user.clone()), legacy.clone()), user);
+        assert_eq!(choose_config(None, legacy.clone()), legacy);
+    }
+
+    #[test]
+    fn test_copy_legacy_files() {
+        let (old, new) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        fs::write(old.path().join("bookmarks.toml"), "old bookmarks").unwrap();
+        fs::write(old.path().join("clones.toml"), "old clones").unwrap();
+        fs::write(new.path().join("clones.toml"), "new clones").unwrap();
+
+        assert_eq!(copy_legacy_files(old.path(), new.path()).unwrap(), ["bookmarks.toml"]);
+        assert_eq!(fs::read_to_string(new.path().join("bookmarks.toml")).unwrap(), "old bookmarks");
+        assert_eq!(fs::read_to_string(new.path().join("clones.toml")).unwrap(), "new clones");
+        // Nothing left to copy, and a directory is never copied onto itself
+        assert!(copy_legacy_files(old.path(), new.path()).unwrap().is_empty());
+        assert!(copy_legacy_files(old.path(), old.path()).unwrap().is_empty());
+    }
+}
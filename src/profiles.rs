@@ -0,0 +1,339 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::paths;
+
+/// Seconds an analysis command may run when nothing sets timeout_secs (config: script_timeout_secs)
+pub const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// Output lines of one command shown in the details panel; the rest are cut off
+pub const MAX_SECTION_LINES: usize = 20;
+
+/// Environment variables analysis commands keep; everything else (tokens, credentials, ...) is dropped
+const PASSED_ENV: &[&str] = &[
+    "HOME", "USER", "LOGNAME", "LANG", "LC_ALL", "TMPDIR", "TEMP", "TMP", "SYSTEMROOT", "USERPROFILE", "CARGO_HOME", "RUSTUP_HOME",
+];
+
+/// Lines of stderr kept in a failed command's error
+const STDERR_LINES: usize = 3;
+
+/// Commands run in a fresh clone whose language matches the profile's name, e.g. [analysis.rust]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnalysisProfile {
+    /// Seconds each command may run before it's killed; script_timeout_secs when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
+    /// Run in order, each one's output shown under its name
+    pub commands: Vec<AnalysisCommand>,
+}
+
+/// One command of a profile: `run` is split on whitespace into the program and its arguments (no shell)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnalysisCommand {
+    pub name: String,
+    pub run: String,
+
+    /// Arguments added after the ones in `run`, each passed as is (so they may contain spaces)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// Overrides the profile's timeout_secs for this command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// A command picked for a clone, ready to run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub section: String, // Heading in the details panel, e.g. "rust: Dependencies"
+    pub run: String,
+    pub args: Vec<String>,
+    pub timeout: Duration,
+}
+
+/// What one step printed (Err holds why it failed)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub name: String,
+    pub result: Result<String, String>,
+}
+
+/// The steps of every profile named after one of `languages` (case-insensitive), in profile order
+/// `languages` are the clone's detected build-system languages ("rust", "node", ...) and GitHub's
+/// primary language, so [analysis.go] works without Go being detected from files
+/// Commands without a timeout of their own or their profile's get `default_timeout_secs`
+pub fn select(profiles: &BTreeMap<String, AnalysisProfile>, languages: &[&str], default_timeout_secs: u64) -> Vec<Step> {
+    profiles
+        .iter()
+        .filter(|(name, _)| languages.iter().any(|language| language.eq_ignore_ascii_case(name)))
+        .flat_map(|(name, profile)| {
+            profile.commands.iter().map(move |command| {
+                let timeout_secs = command.timeout_secs.or(profile.timeout_secs).unwrap_or(default_timeout_secs);
+                Step {
+                    section: format!("{}: {}", name, command.name),
+                    run: command.run.clone(),
+                    args: command.args.clone(),
+                    timeout: Duration::from_secs(timeout_secs),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Problems with a profile, for config validation
+pub fn validate(name: &str, profile: &AnalysisProfile) -> Vec<String> {
+    let mut problems = Vec::new();
+    if profile.commands.is_empty() {
+        problems.push(format!("[analysis.{}] has no commands", name));
+    }
+    if profile.timeout_secs == Some(0) {
+        problems.push(format!("[analysis.{}] timeout_secs must be at least 1", name));
+    }
+    for command in &profile.commands {
+        match command.run.split_whitespace().next().map(program_path) {
+            None => problems.push(format!("[analysis.{}] command `{}` has nothing to run", name, command.name)),
+            Some(Some(Err(e))) => problems.push(format!("[analysis.{}] command `{}`: {}", name, command.name, e)),
+            Some(Some(Ok(path))) if !path.is_file() => {
+                problems.push(format!("[analysis.{}] command `{}`: {} not found", name, command.name, path.display()))
+            }
+            _ => {}
+        }
+        if command.timeout_secs == Some(0) {
+            problems.push(format!("[analysis.{}] command `{}`: timeout_secs must be at least 1", name, command.name));
+        }
+    }
+    problems
+}
+
+/// Run each step in `dir`, one after another
+pub async fn run(dir: &Path, steps: &[Step]) -> Vec<Section> {
+    let mut sections = Vec::new();
+    for step in steps {
+        let result = run_step(dir, step).await;
+        if let Err(error) = &result {
+            tracing::warn!(run = step.run, error, "analysis command failed");
+        }
+        sections.push(Section { name: step.section.clone(), result });
+    }
+    sections
+}
+
+/// Stdout of the step's command; the tail of its stderr when it fails
+/// The program is looked up on PATH (never in the clone), and runs with a minimal environment
+async fn run_step(dir: &Path, step: &Step) -> Result<String, String> {
+    let mut words = step.run.split_whitespace();
+    let program = words.next().ok_or("nothing to run")?;
+    // Compared against canonical PATH entries, e.g. /private/var/... on macOS
+    let clone = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let env = sandbox_env(std::env::vars_os(), &clone);
+    let search_path = env.iter().find(|(name, _)| name == "PATH").map(|(_, value)| value.clone()).unwrap_or_default();
+    let resolved = resolve_program(program, &search_path, &clone)?;
+    let child = Command::new(&resolved)
+        .args(words)
+        .args(&step.args)
+        .current_dir(&clone)
+        .env_clear()
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Dropped when the timeout fires or the job is cancelled
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(step.timeout, child).await {
+        Ok(output) => output.map_err(|e| format!("Failed to run {}: {}", resolved.display(), e))?,
+        // The child is killed as the unfinished future drops
+        Err(_) => return Err(format!("Timed out after {}s (killed)", step.timeout.as_secs())),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lines: Vec<&str> = stderr.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        let tail = lines[lines.len().saturating_sub(STDERR_LINES)..].join(" / ");
+        return Err(format!("{} ({})", output.status, tail));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+/// The environment a command in `clone` gets: the PASSED_ENV variables of `vars`, PATH without
+/// relative entries or directories inside the clone, and NO_COLOR so output has no escape codes
+fn sandbox_env(vars: impl IntoIterator<Item = (OsString, OsString)>, clone: &Path) -> Vec<(OsString, OsString)> {
+    let mut env: Vec<(OsString, OsString)> = Vec::new();
+    for (name, value) in vars {
+        let name_str = name.to_string_lossy();
+        if name_str.eq_ignore_ascii_case("PATH") {
+            let dirs = std::env::split_paths(&value).filter(|dir| dir.is_absolute() && !dir.starts_with(clone));
+            if let Ok(path) = std::env::join_paths(dirs) {
+                env.push((OsString::from("PATH"), path));
+            }
+        } else if PASSED_ENV.iter().any(|passed| passed.eq_ignore_ascii_case(&name_str)) {
+            env.push((name, value));
+        }
+    }
+    env.push((OsString::from("NO_COLOR"), OsString::from("1")));
+    env
+}
+
+/// A program given as a path (`/opt/bin/lint`, `~/scripts/deps.sh`), with "~" expanded; None for a
+/// bare name looked up on PATH
+/// Relative paths (./build.sh) are refused, as they would run a file from the clone
+fn program_path(program: &str) -> Option<Result<PathBuf, String>> {
+    let path = paths::expand_home(program);
+    if path.components().count() == 1 && !path.is_absolute() {
+        return None;
+    }
+    Some(if path.is_absolute() {
+        Ok(path)
+    } else {
+        Err(format!("{} is a relative path; name a program on PATH or give an absolute path", program))
+    })
+}
+
+/// Where `program` lives: its path (see `program_path`), or the first match in `search_path`
+fn resolve_program(program: &str, search_path: &OsStr, clone: &Path) -> Result<PathBuf, String> {
+    if let Some(path) = program_path(program) {
+        let path = path?;
+        if path.starts_with(clone) {
+            return Err(format!("{} is inside the clone", program));
+        }
+        return Ok(path);
+    }
+    let names: Vec<String> = if cfg!(windows) {
+        ["", ".exe", ".cmd", ".bat"].iter().map(|extension| format!("{}{}", program, extension)).collect()
+    } else {
+        vec![program.to_string()]
+    };
+    std::env::split_paths(search_path)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| format!("{} not found on PATH", program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(timeout_secs: Option<u64>, commands: &[(&str, &str)]) -> AnalysisProfile {
+        let commands = commands
+            .iter()
+            .map(|(name, run)| AnalysisCommand { name: name.to_string(), run: run.to_string(), args: Vec::new(), timeout_secs: None })
+            .collect();
+        AnalysisProfile { timeout_secs, commands }
+    }
+
+    #[test]
+    fn test_select() {
+        let mut profiles = BTreeMap::from([
+            ("rust".to_string(), profile(Some(60), &[("Dependencies", "cargo tree --depth 1")])),
+            ("Go".to_string(), profile(None, &[("Modules", "go list -m all"), ("Vet", "go vet ./...")])),
+            ("node".to_string(), profile(Some(5), &[("Outdated", "npm outdated")])),
+        ]);
+        profiles.get_mut("Go").unwrap().commands[1].timeout_secs = Some(10);
+        profiles.get_mut("Go").unwrap().commands[1].args = vec!["-tags".to_string(), "a b".to_string()];
+        let steps = select(&profiles, &["rust", "go"], 120);
+        let sections: Vec<&str> = steps.iter().map(|step| step.section.as_str()).collect();
+        assert_eq!(sections, ["Go: Modules", "Go: Vet", "rust: Dependencies"]);
+        // The command's own timeout, then the profile's, then script_timeout_secs
+        let timeouts: Vec<u64> = steps.iter().map(|step| step.timeout.as_secs()).collect();
+        assert_eq!(timeouts, [120, 10, 60]);
+        assert_eq!(steps[1].args, ["-tags", "a b"]);
+        assert!(select(&profiles, &["python"], 120).is_empty());
+
+        assert!(validate("rust", &profiles["rust"]).is_empty());
+        let mut zero = profile(Some(0), &[("Build", " ")]);
+        zero.commands[0].timeout_secs = Some(0);
+        assert_eq!(
+            validate("c", &zero),
+            [
+                "[analysis.c] timeout_secs must be at least 1",
+                "[analysis.c] command `Build` has nothing to run",
+                "[analysis.c] command `Build`: timeout_secs must be at least 1",
+            ]
+        );
+        assert_eq!(
+            validate("c", &profile(None, &[("Build", "./build.sh"), ("Lint", "/nonexistent/lint --all")])),
+            [
+                "[analysis.c] command `Build`: ./build.sh is a relative path; name a program on PATH or give an absolute path",
+                "[analysis.c] command `Lint`: /nonexistent/lint not found",
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("marker.txt"), "").unwrap();
+        std::fs::write(dir.path().join("with space.txt"), "").unwrap();
+        let step = |run: &str, args: &[&str], timeout: u64| Step {
+            section: run.to_string(),
+            run: run.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            timeout: Duration::from_secs(timeout),
+        };
+        let steps = [
+            step("ls", &[], 5),
+            step("ls missing-file", &[], 5),
+            step("sleep 5", &[], 1),
+            step("no-such-program-x", &[], 5),
+            step("ls -1", &["with space.txt"], 5),
+        ];
+        let sections = run(dir.path(), &steps).await;
+        assert_eq!(sections[0].result, Ok("marker.txt\nwith space.txt".to_string()));
+        assert!(sections[1].result.as_ref().unwrap_err().contains("missing-file"));
+        assert_eq!(sections[2].result, Err("Timed out after 1s (killed)".to_string()));
+        assert_eq!(sections[3].result, Err("no-such-program-x not found on PATH".to_string()));
+        assert_eq!(sections[4].result, Ok("with space.txt".to_string()));
+    }
+
+    #[test]
+    fn test_sandbox_env() {
+        let clone = Path::new("/work/clone");
+        let vars = [
+            ("PATH", "/usr/bin:.:bin:/work/clone/node_modules/.bin:/home/me/.cargo/bin"),
+            ("HOME", "/home/me"),
+            ("GITHUB_TOKEN", "secret"),
+            ("AWS_SECRET_ACCESS_KEY", "secret"),
+        ]
+        .map(|(name, value)| (OsString::from(name), OsString::from(value)));
+        let env = sandbox_env(vars, clone);
+        let names: Vec<&OsStr> = env.iter().map(|(name, _)| name.as_os_str()).collect();
+        assert_eq!(names, ["PATH", "HOME", "NO_COLOR"]);
+        if cfg!(unix) {
+            assert_eq!(env[0].1, "/usr/bin:/home/me/.cargo/bin");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_program() {
+        let dir = tempfile::tempdir().unwrap();
+        let clone = dir.path().join("clone");
+        let bin = dir.path().join("bin");
+        std::fs::create_dir_all(&clone).unwrap();
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(clone.join("tool"), "").unwrap();
+        std::fs::write(bin.join("tool"), "").unwrap();
+        let search_path = std::env::join_paths([&bin]).unwrap();
+
+        assert_eq!(resolve_program("tool", &search_path, &clone), Ok(bin.join("tool")));
+        assert!(resolve_program("./tool", &search_path, &clone).unwrap_err().contains("relative path"));
+        let inside = clone.join("tool").display().to_string();
+        assert!(resolve_program(&inside, &search_path, &clone).unwrap_err().contains("inside the clone"));
+        assert!(resolve_program("missing", &search_path, &clone).is_err());
+
+        // Scripts outside the clone can be named by path, with "~" for the home directory
+        let script = bin.join("tool").display().to_string();
+        assert_eq!(resolve_program(&script, &search_path, &clone), Ok(bin.join("tool")));
+        let home = PathBuf::from(std::env::var_os("HOME").unwrap());
+        assert_eq!(program_path("~/scripts/deps.sh"), Some(Ok(home.join("scripts/deps.sh"))));
+        assert_eq!(program_path("cargo"), None);
+    }
+}
@@ -0,0 +1,113 @@
+/// What a qualifier's value looks like
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueKind {
+    Text,     // A name or keyword
+    Language, // A language name, e.g. rust or jupyter-notebook
+    Number,   // A count, comparison, or range: 100, >100, >=100, 10..50, *..50
+    Date,     // YYYY-MM-DD, with the same comparisons and ranges as numbers
+}
+
+/// A GitHub repository-search qualifier, as the cheatsheet shows it and query suggestions check it
+#[derive(Debug)]
+pub struct Qualifier {
+    pub name: &'static str,
+    pub kind: ValueKind,
+    pub example: &'static str,
+    pub description: &'static str,
+}
+
+const fn qualifier(name: &'static str, kind: ValueKind, example: &'static str, description: &'static str) -> Qualifier {
+    Qualifier { name, kind, example, description }
+}
+
+/// Every qualifier repository search understands, in cheatsheet order
+pub const QUALIFIERS: &[Qualifier] = &[
+    qualifier("in", ValueKind::Text, "in:name", "Match words only in the name, description, readme, or topics"),
+    qualifier("user", ValueKind::Text, "user:octocat", "Repositories owned by a user"),
+    qualifier("org", ValueKind::Text, "org:rust-lang", "Repositories owned by an organization"),
+    qualifier("repo", ValueKind::Text, "repo:rust-lang/rust", "One repository by owner/name"),
+    qualifier("language", ValueKind::Language, "language:rust", "Primary language"),
+    qualifier("topic", ValueKind::Text, "topic:cli", "Tagged with a topic"),
+    qualifier("topics", ValueKind::Number, "topics:>=3", "Number of topics"),
+    qualifier("license", ValueKind::Text, "license:mit", "License keyword, e.g. mit, apache-2.0, gpl"),
+    qualifier("stars", ValueKind::Number, "stars:>1000", "Number of stars"),
+    qualifier("forks", ValueKind::Number, "forks:10..50", "Number of forks"),
+    qualifier("followers", ValueKind::Number, "followers:>=500", "Number of followers"),
+    qualifier("size", ValueKind::Number, "size:<25000", "Size in KB"),
+    qualifier("created", ValueKind::Date, "created:>2024-01-01", "Creation date"),
+    qualifier("pushed", ValueKind::Date, "pushed:>2024-06-01", "Date of the last push"),
+    qualifier("good-first-issues", ValueKind::Number, "good-first-issues:>2", "Open issues labeled good first issue"),
+    qualifier("help-wanted-issues", ValueKind::Number, "help-wanted-issues:>4", "Open issues labeled help wanted"),
+    qualifier("is", ValueKind::Text, "is:public", "public, private, or sponsorable"),
+    qualifier("archived", ValueKind::Text, "archived:false", "Leave out (false) or keep only (true) archived repositories"),
+    qualifier("fork", ValueKind::Text, "fork:true", "Include forks (true) or list only forks (only)"),
+    qualifier("mirror", ValueKind::Text, "mirror:true", "Only mirrors (true) or no mirrors (false)"),
+    qualifier("template", ValueKind::Text, "template:true", "Only template repositories"),
+    qualifier("sort", ValueKind::Text, "sort:updated", "Order by stars, forks, or updated (like --sort)"),
+];
+
+/// Query syntax beyond qualifiers: example, meaning
+pub const SYNTAX: &[(&str, &str)] = &[
+    ("\"game engine\"", "Exact phrase"),
+    ("-language:php", "Leave out matches of a qualifier"),
+    ("NOT tutorial", "Leave out a word"),
+    (">  >=  <  <=", "Comparisons for numbers and dates"),
+    ("10..50  *..50", "Ranges, open-ended with *"),
+];
+
+/// The qualifier called `name` (case-insensitive)
+pub fn find(name: &str) -> Option<&'static Qualifier> {
+    QUALIFIERS.iter().find(|qualifier| qualifier.name.eq_ignore_ascii_case(name))
+}
+
+/// Every qualifier name, in table order
+pub fn names() -> Vec<&'static str> {
+    QUALIFIERS.iter().map(|qualifier| qualifier.name).collect()
+}
+
+/// The qualifier limiting a search to one owner, from `name` (an @ is dropped), `user:name`, or
+/// `org:name`; bare names get user:, which matches organizations too. None if the name isn't a GitHub login
+pub fn owner_scope(text: &str) -> Option<String> {
+    let text = text.trim();
+    let (qualifier, name) = match text.split_once(':') {
+        Some((qualifier, name)) if ["user", "org"].contains(&qualifier.to_ascii_lowercase().as_str()) => {
+            (qualifier.to_ascii_lowercase(), name)
+        }
+        Some(_) => return None,
+        None => ("user".to_string(), text.trim_start_matches('@')),
+    };
+    let login = !name.is_empty()
+        && name.len() <= 39
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    login.then(|| format!("{}:{}", qualifier, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_use_their_qualifier() {
+        for qualifier in QUALIFIERS {
+            let (name, value) = qualifier.example.split_once(':').unwrap();
+            assert_eq!(name, qualifier.name);
+            assert!(!value.is_empty() && !value.contains(' '), "{}", qualifier.example);
+        }
+        let names = names();
+        assert!(names.iter().enumerate().all(|(i, name)| !names[..i].contains(name)), "duplicate qualifier");
+        assert_eq!(find("Stars").map(|qualifier| qualifier.kind), Some(ValueKind::Number));
+        assert!(find("owner").is_none());
+    }
+
+    #[test]
+    fn test_owner_scope() {
+        assert_eq!(owner_scope("rust-lang").as_deref(), Some("user:rust-lang"));
+        assert_eq!(owner_scope(" @octocat ").as_deref(), Some("user:octocat"));
+        assert_eq!(owner_scope("ORG:tokio-rs").as_deref(), Some("org:tokio-rs"));
+        assert_eq!(owner_scope("user:octocat").as_deref(), Some("user:octocat"));
+        for invalid in ["", "org:", "repo:a/b", "rust lang", "-dash", "a/b"] {
+            assert_eq!(owner_scope(invalid), None, "{}", invalid);
+        }
+    }
+}
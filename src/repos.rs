@@ -0,0 +1,166 @@
+use anyhow::Result;
+use clap::Subcommand;
+use colored::*;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::clones::{self, CloneLedger, LocalClone};
+use crate::git;
+use crate::icons::Icon;
+use crate::tui::CLONE_DIR;
+
+/// `repos` subcommands for managing previously cloned repositories
+#[derive(Subcommand, Debug)]
+pub enum ReposCommand {
+    /// List cloned repositories with their branch, size, and last fetch
+    List,
+    /// Fetch and fast-forward a cloned repository (all of them if no name is given)
+    Update {
+        /// Repository name ("owner/name" or "name") or path
+        name: Option<String>,
+    },
+    /// Show disk usage per cloned repository, largest first
+    Du,
+    /// Delete cloned repositories from disk
+    Rm {
+        /// Repository names ("owner/name" or "name") or paths
+        #[arg(required = true)]
+        names: Vec<String>,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Print the local path of a cloned repository (e.g. `cd $(mrkrabz repos path awesome)`)
+    Path {
+        /// Repository name ("owner/name" or "name")
+        name: String,
+    },
+}
+
+/// Run a `repos` subcommand
+pub async fn run(command: &ReposCommand) -> Result<()> {
+    let mut ledger = CloneLedger::load()?;
+    let clones = clones::local_clones(&ledger, Path::new(CLONE_DIR));
+
+    match command {
+        ReposCommand::List => list(&clones),
+        ReposCommand::Update { name: Some(name) } => {
+            update(clones::find_clone(&clones, name)?).await;
+        }
+        ReposCommand::Update { name: None } => {
+            if clones.is_empty() {
+                println!("{}", "No cloned repositories.".yellow());
+            }
+            for clone in &clones {
+                update(clone).await;
+            }
+        }
+        ReposCommand::Du => disk_usage(clones),
+        ReposCommand::Rm { names, yes } => {
+            let mut targets: Vec<&LocalClone> = Vec::new();
+            for name in names {
+                let clone = clones::find_clone(&clones, name)?;
+                if !targets.iter().any(|target| target.path == clone.path) {
+                    targets.push(clone);
+                }
+            }
+            let size: u64 = targets.iter().map(|clone| clone.size).sum();
+            let prompt = match targets.as_slice() {
+                [clone] => format!("Delete {} ({})?", clone.path.display(), clones::format_size(size)),
+                _ => {
+                    for clone in &targets {
+                        println!("   {} ({})", clone.path.display(), clones::format_size(clone.size));
+                    }
+                    format!("Delete these {} repositories ({})?", targets.len(), clones::format_size(size))
+                }
+            };
+            if !yes && !confirm(&prompt)? {
+                println!("{}", "Cancelled.".yellow());
+                return Ok(());
+            }
+            for clone in targets {
+                match std::fs::remove_dir_all(&clone.path) {
+                    Ok(()) => {
+                        ledger.remove_path(&clone.path);
+                        println!("{}", Icon::Delete.label(format!("Deleted {}", clone.path.display())).green());
+                    }
+                    Err(e) => println!("{}", format!("Failed to delete {}: {}", clone.path.display(), e).red()),
+                }
+            }
+            ledger.save()?;
+        }
+        ReposCommand::Path { name } => {
+            println!("{}", clones::find_clone(&clones, name)?.path.display());
+        }
+    }
+
+    Ok(())
+}
+
+fn list(clones: &[LocalClone]) {
+    if clones.is_empty() {
+        println!("{}", "No cloned repositories.".yellow());
+        return;
+    }
+
+    let now = SystemTime::now();
+    for clone in clones {
+        println!("{}", clone.name.bold());
+        println!(
+            "   {} | {} | {}",
+            Icon::Branch.label(clone.branch.as_deref().unwrap_or("(detached)")).green(),
+            Icon::Disk.label(clones::format_size(clone.size)).yellow(),
+            Icon::Clock
+                .label(clone.last_fetch.map_or("never fetched".to_string(), |time| clones::format_age(time, now)))
+                .blue()
+        );
+        println!("   {}", clone.path.display().to_string().dimmed());
+    }
+    println!();
+    let total: u64 = clones.iter().map(|clone| clone.size).sum();
+    println!("{}", format!("{} repositories, {}", clones.len(), clones::format_size(total)).green().bold());
+}
+
+/// Per-repo disk usage, largest first, with each repo's share of the total
+fn disk_usage(mut clones: Vec<LocalClone>) {
+    if clones.is_empty() {
+        println!("{}", "No cloned repositories.".yellow());
+        return;
+    }
+
+    clones::sort_by_size(&mut clones);
+    let total: u64 = clones.iter().map(|clone| clone.size).sum();
+    for clone in &clones {
+        let share = (clone.size * 100).checked_div(total).unwrap_or(0);
+        println!(
+            "{:>10}  {:>3}%  {}  {}",
+            clones::format_size(clone.size).yellow(),
+            share,
+            clone.name.bold(),
+            clone.path.display().to_string().dimmed()
+        );
+    }
+    println!();
+    println!("{}", format!("{} total in {} repositories", clones::format_size(total), clones.len()).green().bold());
+    println!("{}", "Free space with: repos rm <name>...".dimmed());
+}
+
+/// Update one clone, printing the outcome (failures are printed, not returned, so update-all continues)
+async fn update(clone: &LocalClone) {
+    print!("Updating {}... ", clone.name.bold());
+    let _ = io::stdout().flush();
+    match git::update(&clone.path, |_| {}).await {
+        Ok(outcome) => println!("{}", outcome.to_string().green()),
+        Err(e) => println!("{}", e.to_string().red()),
+    }
+}
+
+/// Ask a yes/no question on stdin (default no)
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
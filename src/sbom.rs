@@ -0,0 +1,266 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Package ecosystems whose lockfiles the SBOM reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    PyPI,
+}
+
+impl Ecosystem {
+    /// The ecosystem whose lockfile is named `file`
+    fn of_lockfile(file: &str) -> Option<Self> {
+        match file {
+            "Cargo.lock" => Some(Ecosystem::Cargo),
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(Ecosystem::Npm),
+            "poetry.lock" => Some(Ecosystem::PyPI),
+            _ if file.starts_with("requirements") && file.ends_with(".txt") => Some(Ecosystem::PyPI),
+            _ => None,
+        }
+    }
+
+    /// Package-URL type (https://github.com/package-url/purl-spec)
+    fn purl_type(self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::Npm => "npm",
+            Ecosystem::PyPI => "pypi",
+        }
+    }
+}
+
+/// A package a clone depends on, pinned by one of its lockfiles
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Component {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    pub version: String,
+}
+
+impl Component {
+    fn new(ecosystem: Ecosystem, name: &str, version: &str) -> Self {
+        Component { ecosystem, name: name.to_string(), version: version.to_string() }
+    }
+
+    /// e.g. pkg:cargo/serde@1.0.200, pkg:npm/%40types/node@20.1.0
+    fn purl(&self) -> String {
+        let name = match self.ecosystem {
+            Ecosystem::Npm => self.name.replace('@', "%40"),
+            // PyPI names are case-insensitive, with - _ . all the same
+            Ecosystem::PyPI => self.name.to_lowercase().replace(['_', '.'], "-"),
+            Ecosystem::Cargo => self.name.clone(),
+        };
+        format!("pkg:{}/{}@{}", self.ecosystem.purl_type(), name, self.version)
+    }
+}
+
+/// Where the SBOM of the clone at `clone` goes: next to it, e.g. repositories/serde.cdx.json
+pub fn output_path(clone: &Path) -> PathBuf {
+    let name = clone.file_name().map_or_else(|| "clone".into(), |name| name.to_string_lossy().into_owned());
+    clone.with_file_name(format!("{}.cdx.json", name))
+}
+
+/// Write a CycloneDX SBOM of the clone at `clone` (named `name`) next to it, from every lockfile
+/// in it (gitignored directories such as node_modules left out)
+/// Returns where it went and how many components it lists
+pub fn write(clone: &Path, name: &str) -> Result<(PathBuf, usize)> {
+    let components = components(clone)?;
+    let path = output_path(clone);
+    let bom = cyclonedx(name, &components);
+    fs::write(&path, serde_json::to_string_pretty(&bom)?).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok((path, components.len()))
+}
+
+/// The dependencies every lockfile under `root` pins, each once, sorted
+fn components(root: &Path) -> Result<Vec<Component>> {
+    let mut components = BTreeSet::new();
+    let mut lockfiles = 0;
+    for entry in ignore::WalkBuilder::new(root).build() {
+        let entry = entry?;
+        let file = entry.file_name().to_string_lossy();
+        let Some(ecosystem) = Ecosystem::of_lockfile(&file) else {
+            continue;
+        };
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        let text = fs::read_to_string(entry.path()).with_context(|| format!("Failed to read {}", entry.path().display()))?;
+        let parsed = match (ecosystem, file.as_ref()) {
+            (Ecosystem::Cargo, _) => cargo_lock(&text),
+            (Ecosystem::Npm, _) => package_lock(&text),
+            (Ecosystem::PyPI, "poetry.lock") => poetry_lock(&text),
+            (Ecosystem::PyPI, _) => Ok(requirements(&text)),
+        };
+        components.extend(parsed.with_context(|| format!("Failed to parse {}", entry.path().display()))?);
+        lockfiles += 1;
+    }
+    if lockfiles == 0 {
+        anyhow::bail!("no Cargo.lock, package-lock.json, poetry.lock, or requirements*.txt in {}", root.display());
+    }
+    Ok(components.into_iter().collect())
+}
+
+/// Registry and git dependencies of a Cargo.lock (the workspace's own crates have no source)
+fn cargo_lock(text: &str) -> Result<Vec<Component>> {
+    let lock: toml::Table = toml::from_str(text)?;
+    let packages = lock.get("package").and_then(|packages| packages.as_array()).cloned().unwrap_or_default();
+    Ok(packages
+        .iter()
+        .filter(|package| package.get("source").is_some())
+        .filter_map(|package| {
+            let field = |key: &str| package.get(key).and_then(|value| value.as_str());
+            Some(Component::new(Ecosystem::Cargo, field("name")?, field("version")?))
+        })
+        .collect())
+}
+
+/// Installed packages of a package-lock.json: the "packages" map (lockfile v2 and v3), or the
+/// nested "dependencies" (v1)
+fn package_lock(text: &str) -> Result<Vec<Component>> {
+    let lock: serde_json::Value = serde_json::from_str(text)?;
+    let mut components = Vec::new();
+    if let Some(packages) = lock["packages"].as_object() {
+        for (path, package) in packages {
+            // "" is the project itself; links point into the workspace
+            let Some((_, name)) = path.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if let (Some(version), None) = (package["version"].as_str(), package.get("link")) {
+                components.push(Component::new(Ecosystem::Npm, name, version));
+            }
+        }
+    } else {
+        let mut pending = vec![&lock["dependencies"]];
+        while let Some(dependencies) = pending.pop() {
+            for (name, dependency) in dependencies.as_object().into_iter().flatten() {
+                if let Some(version) = dependency["version"].as_str() {
+                    components.push(Component::new(Ecosystem::Npm, name, version));
+                }
+                pending.push(&dependency["dependencies"]);
+            }
+        }
+    }
+    Ok(components)
+}
+
+/// Packages of a poetry.lock
+fn poetry_lock(text: &str) -> Result<Vec<Component>> {
+    let lock: toml::Table = toml::from_str(text)?;
+    let packages = lock.get("package").and_then(|packages| packages.as_array()).cloned().unwrap_or_default();
+    Ok(packages
+        .iter()
+        .filter_map(|package| {
+            let field = |key: &str| package.get(key).and_then(|value| value.as_str());
+            Some(Component::new(Ecosystem::PyPI, field("name")?, field("version")?))
+        })
+        .collect())
+}
+
+/// Pinned (name==version) requirements; ranges don't say what's installed
+fn requirements(text: &str) -> Vec<Component> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().split(';').next().unwrap_or_default().trim())
+        .filter_map(|line| line.split_once("=="))
+        .map(|(name, version)| {
+            let name = name.split('[').next().unwrap_or(name).trim();
+            Component::new(Ecosystem::PyPI, name, version.trim())
+        })
+        .filter(|component| !component.name.is_empty() && !component.version.is_empty())
+        .collect()
+}
+
+/// A CycloneDX 1.5 document describing `name` and its `components`
+fn cyclonedx(name: &str, components: &[Component]) -> serde_json::Value {
+    let components: Vec<serde_json::Value> = components
+        .iter()
+        .map(|component| {
+            serde_json::json!({
+                "type": "library",
+                "bom-ref": component.purl(),
+                "name": component.name,
+                "version": component.version,
+                "purl": component.purl(),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "tools": {
+                "components": [{ "type": "application", "name": "mrkrabz", "version": env!("CARGO_PKG_VERSION") }]
+            },
+            "component": { "type": "application", "bom-ref": name, "name": name },
+        },
+        "components": components,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lockfiles() {
+        let cargo = "\
+version = 3
+
+[[package]]
+name = \"mytool\"
+version = \"0.1.0\"
+
+[[package]]
+name = \"serde\"
+version = \"1.0.200\"
+source = \"registry+https://github.com/rust-lang/crates.io-index\"
+";
+        assert_eq!(cargo_lock(cargo).unwrap(), [Component::new(Ecosystem::Cargo, "serde", "1.0.200")]);
+
+        let npm_v3 = r#"{"lockfileVersion": 3, "packages": {
+            "": {"name": "app", "version": "1.0.0"},
+            "node_modules/@types/node": {"version": "20.1.0"},
+            "node_modules/a/node_modules/b": {"version": "2.0.0"},
+            "node_modules/local": {"resolved": "packages/local", "link": true}
+        }}"#;
+        let mut npm = package_lock(npm_v3).unwrap();
+        npm.sort();
+        assert_eq!(npm, [Component::new(Ecosystem::Npm, "@types/node", "20.1.0"), Component::new(Ecosystem::Npm, "b", "2.0.0")]);
+        let npm_v1 = r#"{"lockfileVersion": 1, "dependencies": {"a": {"version": "1.0.0", "dependencies": {"b": {"version": "2.0.0"}}}}}"#;
+        assert_eq!(package_lock(npm_v1).unwrap().len(), 2);
+
+        let requirements = requirements("# pinned\nrequests[socks]==2.31.0  # http\nflask>=2\nDjango_Rest==3.0 ; python_version > '3'\n");
+        assert_eq!(requirements, [
+            Component::new(Ecosystem::PyPI, "requests", "2.31.0"),
+            Component::new(Ecosystem::PyPI, "Django_Rest", "3.0"),
+        ]);
+        assert_eq!(requirements[1].purl(), "pkg:pypi/django-rest@3.0");
+        assert_eq!(Component::new(Ecosystem::Npm, "@types/node", "20.1.0").purl(), "pkg:npm/%40types/node@20.1.0");
+
+        let poetry = "[[package]]\nname = \"click\"\nversion = \"8.1.7\"\n";
+        assert_eq!(poetry_lock(poetry).unwrap(), [Component::new(Ecosystem::PyPI, "click", "8.1.7")]);
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let clone = dir.path().join("mytool");
+        fs::create_dir_all(clone.join("web")).unwrap();
+        assert!(write(&clone, "me/mytool").unwrap_err().to_string().starts_with("no Cargo.lock"));
+
+        fs::write(clone.join("requirements.txt"), "requests==2.31.0\n").unwrap();
+        fs::write(clone.join("web/package-lock.json"), r#"{"packages": {"node_modules/left-pad": {"version": "1.3.0"}}}"#).unwrap();
+        let (path, count) = write(&clone, "me/mytool").unwrap();
+        assert_eq!((path, count), (dir.path().join("mytool.cdx.json"), 2));
+
+        let bom: serde_json::Value = serde_json::from_str(&fs::read_to_string(output_path(&clone)).unwrap()).unwrap();
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        assert_eq!(bom["metadata"]["component"]["name"], "me/mytool");
+        let purls: Vec<&str> = bom["components"].as_array().unwrap().iter().map(|c| c["purl"].as_str().unwrap()).collect();
+        assert_eq!(purls, ["pkg:npm/left-pad@1.3.0", "pkg:pypi/requests@2.31.0"]);
+    }
+}
@@ -0,0 +1,329 @@
+use async_trait::async_trait;
+use http::HeaderMap;
+use octocrab::models::Repository;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
+
+use crate::error::Error;
+use crate::filecount::Tree;
+use crate::github::{Client, GitHub};
+use crate::maintenance::{IssueDates, PullDates};
+use crate::text_match::TextMatches;
+use crate::tui::RateLimitInfo;
+
+/// API requests in flight at once
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Of those, how many can be prefetches, so a fetch the user asked for never waits behind them
+const MAX_CONCURRENT_PREFETCHES: usize = 1;
+
+/// Share of a budget kept for requests the user asked for: prefetches stop once less is left
+const PREFETCH_RESERVE: f64 = 0.25;
+
+/// Which of GitHub's rate limits a request counts against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Category {
+    Search, // The search API: 30 requests a minute signed in, 10 signed out
+    Core,   // Everything else: 5,000 requests an hour signed in, 60 signed out
+}
+
+/// How much a request matters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Priority {
+    Normal,   // The user is waiting for it
+    Prefetch, // Speculative: dropped when the budget runs low
+}
+
+/// Requests a category may start within any `window`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Budget {
+    pub limit: usize,
+    pub window: Duration,
+}
+
+impl Budget {
+    /// GitHub's published limits for the search API and everything else
+    pub fn for_github(signed_in: bool) -> [Budget; 2] {
+        let minute = Duration::from_secs(60);
+        let hour = Duration::from_secs(3600);
+        match signed_in {
+            true => [Budget { limit: 30, window: minute }, Budget { limit: 5000, window: hour }],
+            false => [Budget { limit: 10, window: minute }, Budget { limit: 60, window: hour }],
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs())
+}
+
+/// GitHub's own count of each category's budget, from the rate_limit endpoint and the
+/// x-ratelimit-* headers of every response. The scheduler trusts it over its own count, which
+/// misses requests made before the session started or by other apps with the same token
+#[derive(Debug, Default)]
+pub struct RateLimits {
+    reported: Mutex<[Option<RateLimitInfo>; 2]>, // Search, core; `remaining` goes down as requests start
+}
+
+impl RateLimits {
+    /// Take GitHub's figures for `category`. Within one window the lowest count left wins: a
+    /// response can arrive after requests the scheduler has since started
+    pub fn report(&self, category: Category, info: RateLimitInfo) {
+        let mut reported = self.reported.lock().unwrap();
+        let reported = &mut reported[category as usize];
+        let newer = reported.is_none_or(|old| info.reset > old.reset || (info.reset == old.reset && info.remaining < old.remaining));
+        if newer {
+            *reported = Some(info);
+        }
+    }
+
+    /// Take the x-ratelimit-* headers of a response; ones for other APIs (GraphQL, code search) are ignored
+    pub fn observe(&self, headers: &HeaderMap) {
+        let header = |name: &str| headers.get(name)?.to_str().ok();
+        let number = |name: &str| header(name)?.parse::<u64>().ok();
+        let category = match header("x-ratelimit-resource") {
+            Some("search") => Category::Search,
+            Some("core") => Category::Core,
+            _ => return,
+        };
+        if let (Some(remaining), Some(limit), Some(reset)) =
+            (number("x-ratelimit-remaining"), number("x-ratelimit-limit"), number("x-ratelimit-reset"))
+        {
+            self.report(category, RateLimitInfo { remaining: remaining as usize, limit: limit as usize, reset });
+        }
+    }
+}
+
+/// What every copy of a scheduler shares
+struct Shared {
+    budgets: [Budget; 2],                  // Search, core
+    started: Mutex<[VecDeque<Instant>; 2]>, // Start times of recent requests per category, oldest first
+    rate_limits: Arc<RateLimits>,          // What GitHub last reported
+    slots: Arc<Semaphore>,                 // Requests in flight
+    prefetch_slots: Arc<Semaphore>,        // Prefetches in flight
+}
+
+/// Every GitHub request of the session goes through here: it caps how many run at once, keeps each
+/// category within its rate budget (waiting for room rather than getting rejected by GitHub), and
+/// drops prefetches when little budget is left
+#[derive(Clone)]
+pub struct Scheduler {
+    inner: Client,
+    shared: Arc<Shared>,
+    priority: Priority,
+}
+
+impl Scheduler {
+    /// A scheduler for `inner`, whose responses report their rate-limit headers to `rate_limits`
+    pub fn new(inner: Client, signed_in: bool, rate_limits: Arc<RateLimits>) -> Self {
+        Self::with_budgets(inner, Budget::for_github(signed_in), rate_limits)
+    }
+
+    /// A scheduler with these search and core budgets, until GitHub reports its own
+    pub fn with_budgets(inner: Client, budgets: [Budget; 2], rate_limits: Arc<RateLimits>) -> Self {
+        let shared = Shared {
+            budgets,
+            started: Mutex::new([VecDeque::new(), VecDeque::new()]),
+            rate_limits,
+            slots: Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS)),
+            prefetch_slots: Arc::new(Semaphore::new(MAX_CONCURRENT_PREFETCHES)),
+        };
+        Self { inner, shared: Arc::new(shared), priority: Priority::Normal }
+    }
+
+    /// A client on the same queue and budgets whose requests are prefetches
+    pub fn prefetching(&self) -> Client {
+        Arc::new(Self { priority: Priority::Prefetch, ..self.clone() })
+    }
+
+    /// Run `request` once its category has budget left and a slot is free
+    /// A prefetch fails with Deferred instead when the budget is down to its reserve
+    async fn run<T>(&self, category: Category, request: impl Future<Output = Result<T, Error>>) -> Result<T, Error> {
+        let _prefetch_permit = match self.priority {
+            Priority::Prefetch => Some(self.shared.prefetch_slots.clone().acquire_owned().await.map_err(|_| Error::Cancelled)?),
+            Priority::Normal => None,
+        };
+        self.reserve(category).await?;
+        let _permit = self.shared.slots.clone().acquire_owned().await.map_err(|_| Error::Cancelled)?;
+        request.await
+    }
+
+    /// Count a request against `category`'s budget, waiting until the budget has room
+    /// What GitHub reported, while its window lasts, caps what the scheduler's own count allows
+    async fn reserve(&self, category: Category) -> Result<(), Error> {
+        let index = category as usize;
+        let budget = self.shared.budgets[index];
+        loop {
+            let wait = {
+                let now = Instant::now();
+                let mut started = self.shared.started.lock().unwrap();
+                let started = &mut started[index];
+                while started.front().is_some_and(|&time| now.duration_since(time) >= budget.window) {
+                    started.pop_front();
+                }
+                let mut reported = self.shared.rate_limits.reported.lock().unwrap();
+                let reported = &mut reported[index];
+                let unix_now = unix_now();
+                if reported.is_some_and(|info| info.reset <= unix_now) {
+                    *reported = None; // Refilled since
+                }
+                let limit = reported.map_or(budget.limit, |info| info.limit);
+                let counted = limit.saturating_sub(started.len());
+                let (left, wait) = match *reported {
+                    Some(info) if info.remaining < counted => (info.remaining, Duration::from_secs(info.reset - unix_now)),
+                    _ => (counted, started.front().map_or(Duration::ZERO, |&oldest| budget.window.saturating_sub(now.duration_since(oldest)))),
+                };
+                if self.priority == Priority::Prefetch && (left as f64) <= limit as f64 * PREFETCH_RESERVE {
+                    tracing::info!(?category, left, "skipping a prefetch, little rate budget left");
+                    return Err(Error::Deferred);
+                }
+                if left > 0 {
+                    started.push_back(now);
+                    if let Some(info) = reported {
+                        info.remaining = info.remaining.saturating_sub(1);
+                    }
+                    return Ok(());
+                }
+                wait
+            };
+            tracing::info!(?category, wait_ms = wait.as_millis() as u64, "rate budget spent, waiting");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[async_trait]
+impl GitHub for Scheduler {
+    async fn search_repositories(
+        &self,
+        query: &str,
+        sort: Option<&str>,
+        per_page: u8,
+        page: u32,
+    ) -> Result<(Vec<Repository>, u64, TextMatches), Error> {
+        self.run(Category::Search, self.inner.search_repositories(query, sort, per_page, page)).await
+    }
+
+    async fn search_rate_limit(&self) -> Result<RateLimitInfo, Error> {
+        // Free, and asked for when the budget may be spent: not queued
+        let info = self.inner.search_rate_limit().await?;
+        self.shared.rate_limits.report(Category::Search, info);
+        Ok(info)
+    }
+
+    async fn current_user(&self) -> Result<String, Error> {
+        self.run(Category::Core, self.inner.current_user()).await
+    }
+
+    async fn tree(&self, owner: &str, name: &str) -> Result<Tree, Error> {
+        self.run(Category::Core, self.inner.tree(owner, name)).await
+    }
+
+    async fn release_dates(&self, owner: &str, name: &str, count: u8) -> Result<Vec<SystemTime>, Error> {
+        self.run(Category::Core, self.inner.release_dates(owner, name, count)).await
+    }
+
+    async fn contributor_count(&self, owner: &str, name: &str) -> Result<u64, Error> {
+        self.run(Category::Core, self.inner.contributor_count(owner, name)).await
+    }
+
+    async fn readme(&self, owner: &str, name: &str) -> Result<String, Error> {
+        self.run(Category::Core, self.inner.readme(owner, name)).await
+    }
+
+    async fn star_dates(&self, owner: &str, name: &str, page: u32) -> Result<(Vec<SystemTime>, u32), Error> {
+        self.run(Category::Core, self.inner.star_dates(owner, name, page)).await
+    }
+
+    async fn recent_issues(&self, owner: &str, name: &str, count: u8) -> Result<Vec<IssueDates>, Error> {
+        self.run(Category::Core, self.inner.recent_issues(owner, name, count)).await
+    }
+
+    async fn first_reply(&self, owner: &str, name: &str, number: u64, author: &str) -> Result<Option<SystemTime>, Error> {
+        self.run(Category::Core, self.inner.first_reply(owner, name, number, author)).await
+    }
+
+    async fn recent_pulls(&self, owner: &str, name: &str, count: u8) -> Result<Vec<PullDates>, Error> {
+        self.run(Category::Core, self.inner.recent_pulls(owner, name, count)).await
+    }
+
+    async fn top_contributors(&self, owner: &str, name: &str, count: u8) -> Result<Vec<(String, u64)>, Error> {
+        self.run(Category::Core, self.inner.top_contributors(owner, name, count)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock::MockGitHub;
+
+    fn scheduler(search: Budget, core: Budget) -> Scheduler {
+        Scheduler::with_budgets(Arc::new(MockGitHub::new()), [search, core], Arc::default())
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_budget() {
+        let window = Duration::from_millis(300);
+        let github = scheduler(Budget { limit: 2, window }, Budget { limit: 100, window });
+        let started = Instant::now();
+        for _ in 0..2 {
+            github.search_repositories("rust", None, 10, 1).await.unwrap();
+        }
+        assert!(started.elapsed() < window);
+        // The third search waits for the first to leave the window; other categories don't
+        github.readme("owner", "repo").await.unwrap();
+        assert!(started.elapsed() < window);
+        github.search_repositories("rust", None, 10, 1).await.unwrap();
+        assert!(started.elapsed() >= window);
+    }
+
+    #[tokio::test]
+    async fn test_prefetches_keep_out_of_the_reserve() {
+        let window = Duration::from_secs(60);
+        let github = scheduler(Budget { limit: 10, window }, Budget { limit: 4, window });
+        let prefetch = github.prefetching();
+        // Four core requests a minute, one kept back for the user: prefetches stop after three
+        for _ in 0..3 {
+            prefetch.readme("owner", "repo").await.unwrap();
+        }
+        assert!(matches!(prefetch.readme("owner", "repo").await, Err(Error::Deferred)));
+        github.readme("owner", "repo").await.unwrap();
+        // Searches have their own budget
+        prefetch.search_repositories("rust", None, 10, 1).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_budget_follows_what_github_reports() {
+        let window = Duration::from_secs(60);
+        let mut mock = MockGitHub::new();
+        // Two searches left of 30, though this session hasn't searched yet
+        mock.rate_limit = RateLimitInfo { remaining: 2, limit: 30, reset: unix_now() + 60 };
+        let github = Scheduler::with_budgets(Arc::new(mock), [Budget { limit: 30, window }; 2], Arc::default());
+        github.search_rate_limit().await.unwrap();
+        assert!(matches!(github.prefetching().search_repositories("rust", None, 10, 1).await, Err(Error::Deferred)));
+        for _ in 0..2 {
+            github.search_repositories("rust", None, 10, 1).await.unwrap();
+        }
+        let third = tokio::time::timeout(Duration::from_millis(100), github.search_repositories("rust", None, 10, 1));
+        assert!(third.await.is_err(), "waits for the reset");
+
+        // Response headers correct the core budget the same way
+        let mut headers = HeaderMap::new();
+        for (name, value) in [("x-ratelimit-resource", "core"), ("x-ratelimit-remaining", "1"), ("x-ratelimit-limit", "5000")] {
+            headers.insert(name, value.parse().unwrap());
+        }
+        headers.insert("x-ratelimit-reset", (unix_now() + 60).to_string().parse().unwrap());
+        github.shared.rate_limits.observe(&headers);
+        assert!(matches!(github.prefetching().readme("owner", "repo").await, Err(Error::Deferred)));
+        github.readme("owner", "repo").await.unwrap();
+
+        // Figures from a window that has passed are dropped
+        let rate_limits = RateLimits::default();
+        rate_limits.report(Category::Core, RateLimitInfo { remaining: 0, limit: 60, reset: unix_now() - 1 });
+        let github = Scheduler::with_budgets(Arc::new(MockGitHub::new()), [Budget { limit: 30, window }; 2], Arc::new(rate_limits));
+        github.prefetching().readme("owner", "repo").await.unwrap();
+    }
+}
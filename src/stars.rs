@@ -0,0 +1,149 @@
+use anyhow::Result;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    symbols::Marker,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
+    Frame,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::clones;
+use crate::github::GitHub;
+
+/// Stargazers per page of GitHub's list (its maximum)
+pub const PAGE_SIZE: u8 = 100;
+
+/// Pages fetched at most for one chart; more popular repositories are sampled evenly
+const MAX_PAGES: u32 = 12;
+
+/// GitHub lists only the first 40,000 stargazers
+const LAST_LISTED_PAGE: u32 = 400;
+
+/// Rows the chart takes in the details panel, borders included
+pub const CHART_HEIGHT: u16 = 12;
+
+/// Stars over time: when, and how many stars the repository had by then, oldest first
+pub type StarHistory = Vec<(SystemTime, u64)>;
+
+/// Fetch when owner/name got its stars (up to MAX_PAGES requests), ending at its current `stars`
+pub async fn fetch_history(github: &dyn GitHub, owner: &str, name: &str, stars: u64) -> Result<StarHistory> {
+    let (first, pages) = github.star_dates(owner, name, 1).await?;
+    let mut history = points(1, &first);
+    for page in sample_pages(pages.min(LAST_LISTED_PAGE)).into_iter().skip(1) {
+        let (dates, _) = github.star_dates(owner, name, page).await?;
+        history.extend(points(page, &dates));
+    }
+    // Stars past the listed ones (and since the last page was fetched) only show in the total
+    let listed = history.last().map_or(0, |(_, count)| *count);
+    if stars > listed || history.is_empty() {
+        history.push((SystemTime::now(), stars.max(listed)));
+    }
+    Ok(history)
+}
+
+/// The star count after each star on `page`
+fn points(page: u32, dates: &[SystemTime]) -> StarHistory {
+    let before = u64::from(page - 1) * u64::from(PAGE_SIZE);
+    dates.iter().enumerate().map(|(i, date)| (*date, before + i as u64 + 1)).collect()
+}
+
+/// Pages to fetch out of `pages`: every one up to MAX_PAGES, otherwise MAX_PAGES spread evenly,
+/// the first and last included
+fn sample_pages(pages: u32) -> Vec<u32> {
+    if pages <= MAX_PAGES {
+        return (1..=pages.max(1)).collect();
+    }
+    let steps = u64::from(MAX_PAGES - 1);
+    let mut sampled: Vec<u32> =
+        (0..=steps).map(|step| 1 + (step * u64::from(pages - 1) / steps) as u32).collect();
+    sampled.dedup();
+    sampled
+}
+
+/// "2021-03" for a time in March 2021 (UTC)
+fn month_label(time: SystemTime) -> String {
+    let days = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / 86400;
+    // Days since 1970-01-01 to a civil date (Howard Hinnant's algorithm), for years after 1970
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{}-{:02}", year, month)
+}
+
+/// Draw `history` as a line chart of stars over time
+pub fn render_chart(f: &mut Frame, area: Rect, history: &StarHistory) {
+    let (Some((start, _)), Some((end, stars))) = (history.first(), history.last()) else {
+        return;
+    };
+    let days = |time: &SystemTime| time.duration_since(*start).unwrap_or(Duration::ZERO).as_secs_f64() / 86400.0;
+    let data: Vec<(f64, f64)> = history.iter().map(|(time, count)| (days(time), *count as f64)).collect();
+    let span = days(end).max(1.0);
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::Yellow))
+        .data(&data);
+    let label_style = Style::default().fg(Color::Gray);
+    let chart = Chart::new(vec![dataset])
+        .block(Block::default().borders(Borders::ALL).title(" Star history "))
+        .x_axis(
+            Axis::default()
+                .bounds([0.0, span])
+                .labels(vec![Span::styled(month_label(*start), label_style), Span::styled(month_label(*end), label_style)]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, (*stars as f64).max(1.0)])
+                .labels(vec![Span::styled("0", label_style), Span::styled(clones::format_count(*stars), label_style)]),
+        );
+    f.render_widget(chart, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock::MockGitHub;
+
+    #[test]
+    fn test_sample_pages() {
+        assert_eq!(sample_pages(0), [1]);
+        assert_eq!(sample_pages(3), [1, 2, 3]);
+        let sampled = sample_pages(400);
+        assert_eq!(sampled.len(), MAX_PAGES as usize);
+        assert_eq!((sampled[0], sampled[sampled.len() - 1]), (1, 400));
+        assert!(sampled.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_month_label() {
+        assert_eq!(month_label(UNIX_EPOCH), "1970-01");
+        // 2024-02-29T12:00:00Z
+        assert_eq!(month_label(UNIX_EPOCH + Duration::from_secs(1_709_208_000)), "2024-02");
+        // 2021-12-31T23:59:59Z
+        assert_eq!(month_label(UNIX_EPOCH + Duration::from_secs(1_640_995_199)), "2021-12");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_history() {
+        let day = |n: u64| UNIX_EPOCH + Duration::from_secs(1_600_000_000 + n * 86400);
+        let mut github = MockGitHub::new();
+        github.stars = (0..250).map(day).collect();
+        let history = fetch_history(&github, "owner", "repo", 260).await.unwrap();
+        assert_eq!(history.len(), 251);
+        assert_eq!(history[0], (day(0), 1));
+        assert_eq!(history[100], (day(100), 101));
+        assert_eq!(history[249], (day(249), 250));
+        // The total the search reported ends the chart
+        assert_eq!(history[250].1, 260);
+
+        let empty = fetch_history(&MockGitHub::new(), "owner", "repo", 0).await.unwrap();
+        assert_eq!(empty.iter().map(|(_, count)| *count).collect::<Vec<_>>(), [0]);
+    }
+}
@@ -0,0 +1,318 @@
+use octocrab::models::Repository;
+
+use crate::qualifiers::{self, ValueKind};
+
+/// A corrected query for a search that found nothing, and what was changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub query: String,
+    pub changes: Vec<String>, // One per edit, e.g. "language:rsut → language:rust"
+}
+
+/// Topics a "find similar" search uses at most: GitHub needs every one to match
+const SIMILAR_TOPICS: usize = 3;
+
+/// Shorthands that are too far from the qualifier they mean for typo matching
+const QUALIFIER_ALIASES: &[(&str, &str)] = &[("lang", "language"), ("updated", "pushed"), ("lic", "license")];
+
+/// Languages as the language qualifier spells them, for typo matching
+/// GitHub knows many more; a language that isn't close to any of these is left alone
+const LANGUAGES: &[&str] = &[
+    "assembly", "c", "clojure", "coffeescript", "cpp", "crystal", "csharp", "css", "cuda", "dart",
+    "dockerfile", "elixir", "elm", "emacs-lisp", "erlang", "fortran", "fsharp", "gdscript", "go",
+    "groovy", "haskell", "hcl", "html", "java", "javascript", "julia", "jupyter-notebook", "kotlin",
+    "lua", "makefile", "matlab", "nim", "nix", "objective-c", "ocaml", "perl", "php", "powershell",
+    "python", "r", "racket", "ruby", "rust", "scala", "scheme", "shell", "solidity", "sql", "svelte",
+    "swift", "tex", "typescript", "v", "vala", "verilog", "vhdl", "vim-script", "vue", "webassembly",
+    "zig",
+];
+
+/// Spellings GitHub also accepts for a language; never "corrected"
+const LANGUAGE_ALIASES: &[&str] = &["c++", "c#", "f#", "golang", "js", "ts", "py", "rb", "sh", "bash", "node"];
+
+/// A fix for a query that returned no results, if there is one
+/// Typos and malformed qualifiers are fixed first; only a query without any is relaxed
+/// (stars and forks thresholds lowered tenfold), so each suggestion changes one kind of thing
+pub fn suggest(query: &str) -> Option<Suggestion> {
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    let mut changes = Vec::new();
+    let mut fixed = Vec::new();
+    for term in &terms {
+        match fix_term(term) {
+            Some((replacement, change)) => {
+                changes.push(change);
+                fixed.extend(replacement);
+            }
+            None => fixed.push(term.to_string()),
+        }
+    }
+    if changes.is_empty() {
+        fixed.clear();
+        for term in &terms {
+            match relax_term(term) {
+                Some((replacement, change)) => {
+                    changes.push(change);
+                    fixed.extend(replacement);
+                }
+                None => fixed.push(term.to_string()),
+            }
+        }
+    }
+    let query = fixed.join(" ");
+    (!changes.is_empty() && !query.is_empty()).then_some(Suggestion { query, changes })
+}
+
+/// A search for repositories like `repo`: its first topics (or, without any, the words of its name)
+/// and its primary language, e.g. "topic:ecs topic:game-engine language:rust"
+/// None when it has neither topics nor a language to go by
+pub fn similar(repo: &Repository) -> Option<String> {
+    let topics = repo.topics.as_deref().unwrap_or_default();
+    let language = repo.language.as_ref().and_then(|language| language.as_str());
+    if topics.is_empty() && language.is_none() {
+        return None;
+    }
+    let mut terms: Vec<String> = if topics.is_empty() {
+        repo.name.split(['-', '_', '.']).filter(|word| !word.is_empty()).map(str::to_lowercase).collect()
+    } else {
+        topics.iter().take(SIMILAR_TOPICS).map(|topic| format!("topic:{}", topic)).collect()
+    };
+    if let Some(language) = language {
+        terms.push(format!("language:{}", language.to_lowercase().replace(' ', "-")));
+    }
+    Some(terms.join(" "))
+}
+
+/// `term` split into an optional "-" (exclusion), qualifier, and value; None for plain words
+fn qualifier(term: &str) -> Option<(&str, &str, &str)> {
+    let (negation, rest) = match term.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", term),
+    };
+    let (key, value) = rest.split_once(':')?;
+    // URLs and quoted phrases aren't qualifiers
+    if key.is_empty() || value.starts_with("//") || key.contains('"') {
+        return None;
+    }
+    Some((negation, key, value))
+}
+
+/// The corrected term (None to drop it) and a description, when `term` is a qualifier with a typo
+/// or a value GitHub can't use
+fn fix_term(term: &str) -> Option<(Option<String>, String)> {
+    let (negation, key, value) = qualifier(term)?;
+    let lower = key.to_lowercase();
+    let alias = QUALIFIER_ALIASES.iter().find(|(alias, _)| *alias == lower).map(|(_, name)| *name);
+    let known = qualifiers::find(&lower)
+        .or_else(|| alias.and_then(qualifiers::find))
+        .or_else(|| nearest(&lower, &qualifiers::names()).and_then(qualifiers::find));
+    let Some(known) = known else {
+        return Some((None, format!("dropped {} (not a search qualifier)", term)));
+    };
+    if value.is_empty() {
+        return Some((None, format!("dropped {} (no value)", term)));
+    }
+
+    let value = match known.kind {
+        ValueKind::Language => fix_language(value),
+        ValueKind::Number => match fix_range(value, fix_number) {
+            Some(fixed) => fixed,
+            None => return Some((None, format!("dropped {} (not a number or range)", term))),
+        },
+        ValueKind::Date => match fix_range(value, |date| is_date(date).then(|| date.to_string())) {
+            Some(fixed) => fixed,
+            None => return Some((None, format!("dropped {} (dates look like 2024-01-31)", term))),
+        },
+        ValueKind::Text => value.to_string(),
+    };
+    let fixed = format!("{}{}:{}", negation, known.name, value);
+    // Qualifiers are case-insensitive, so a change of case alone isn't a fix
+    (fixed.to_lowercase() != term.to_lowercase()).then(|| (Some(fixed.clone()), format!("{} → {}", term, fixed)))
+}
+
+/// The known language nearest a misspelled `language` value; known values and aliases are kept
+fn fix_language(value: &str) -> String {
+    let lower = value.to_lowercase();
+    if LANGUAGES.contains(&lower.as_str()) || LANGUAGE_ALIASES.contains(&lower.as_str()) || lower.len() < 3 {
+        return value.to_string();
+    }
+    nearest(&lower, LANGUAGES).map_or(value.to_string(), str::to_string)
+}
+
+/// `value` with each bound repaired by `fix`: a bound ("100"), a comparison (">=100"), or a
+/// range ("10..50", "*..50"); None when a bound can't be repaired
+fn fix_range(value: &str, fix: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let bound = |bound: &str| if bound == "*" { Some("*".to_string()) } else { fix(bound) };
+    if let Some((low, high)) = value.split_once("..") {
+        return Some(format!("{}..{}", bound(low)?, bound(high)?));
+    }
+    let operator_len = value.len() - value.trim_start_matches(['>', '<', '=']).len();
+    let (operator, number) = value.split_at(operator_len);
+    if !matches!(operator, "" | ">" | ">=" | "<" | "<=") {
+        return None;
+    }
+    Some(format!("{}{}", operator, fix(number)?))
+}
+
+/// A whole number, allowing "1k"/"2.5k"/"1m" shorthands and thousands separators
+fn fix_number(number: &str) -> Option<String> {
+    let number = number.replace([',', '_'], "").to_lowercase();
+    let (digits, factor) = match number.strip_suffix('k') {
+        Some(digits) => (digits, 1_000.0),
+        None => match number.strip_suffix('m') {
+            Some(digits) => (digits, 1_000_000.0),
+            None => (number.as_str(), 1.0),
+        },
+    };
+    let value: f64 = digits.parse().ok().filter(|value: &f64| value.is_finite() && *value >= 0.0)?;
+    Some(((value * factor).round() as u64).to_string())
+}
+
+/// Whether `date` is YYYY-MM-DD (optionally with a time, as GitHub allows)
+pub fn is_date(date: &str) -> bool {
+    let day = date.split('T').next().unwrap_or_default();
+    let parts: Vec<&str> = day.split('-').collect();
+    matches!(parts.as_slice(), [year, month, day]
+        if year.len() == 4 && month.len() == 2 && day.len() == 2
+            && [year, month, day].iter().all(|part| part.chars().all(|c| c.is_ascii_digit())))
+}
+
+/// `term` with its stars or forks minimum lowered tenfold, or dropped once it's small
+fn relax_term(term: &str) -> Option<(Option<String>, String)> {
+    let (negation, key, value) = qualifier(term)?;
+    let key = key.to_lowercase();
+    if !negation.is_empty() || !matches!(key.as_str(), "stars" | "forks") {
+        return None;
+    }
+    let (operator, minimum, rest) = if let Some((low, high)) = value.split_once("..") {
+        ("", low, format!("..{}", high))
+    } else if let Some(number) = value.strip_prefix(">=") {
+        (">=", number, String::new())
+    } else if let Some(number) = value.strip_prefix('>') {
+        (">", number, String::new())
+    } else {
+        return None; // Exact counts and upper bounds don't narrow a search much
+    };
+    let minimum: u64 = minimum.parse().ok()?;
+    if minimum < 10 {
+        return Some((None, format!("dropped {}", term)));
+    }
+    let relaxed = format!("{}:{}{}{}", key, operator, minimum / 10, rest);
+    Some((Some(relaxed.clone()), format!("{} → {} (fewer stars/forks)", term, relaxed)))
+}
+
+/// The entry of `known` closest to `word` in edit distance, if it's close enough to be a typo
+/// (one edit for words up to four letters, two for longer ones)
+fn nearest<'a>(word: &str, known: &[&'a str]) -> Option<&'a str> {
+    let limit = if word.chars().count() <= 4 { 1 } else { 2 };
+    known
+        .iter()
+        .map(|candidate| (distance(word, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`, counting a swap of neighbours as one edit
+fn distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock;
+
+    fn suggested(query: &str) -> Option<String> {
+        suggest(query).map(|suggestion| suggestion.query)
+    }
+
+    #[test]
+    fn test_language_typos() {
+        let suggestion = suggest("game language:rsut").unwrap();
+        assert_eq!(suggestion.query, "game language:rust");
+        assert_eq!(suggestion.changes, ["language:rsut → language:rust"]);
+        assert_eq!(suggested("web language:javscript").as_deref(), Some("web language:javascript"));
+        assert_eq!(suggested("cli -language:pyhton").as_deref(), Some("cli -language:python"));
+        // Known names, aliases, and languages too far from any known one are left alone
+        assert_eq!(suggested("game language:Rust"), None);
+        assert_eq!(suggested("game language:c++"), None);
+        assert_eq!(suggested("game language:brainfuck"), None);
+    }
+
+    #[test]
+    fn test_malformed_qualifiers() {
+        assert_eq!(suggested("game lang:rust").as_deref(), Some("game language:rust"));
+        assert_eq!(suggested("game langauge:rust").as_deref(), Some("game language:rust"));
+        assert_eq!(suggested("game strs:>100").as_deref(), Some("game stars:>100"));
+        assert_eq!(suggested("game stars:>1k").as_deref(), Some("game stars:>1000"));
+        assert_eq!(suggested("game stars:1,000..5k").as_deref(), Some("game stars:1000..5000"));
+        assert_eq!(suggested("game pushed:>2024-1-5").as_deref(), Some("game"));
+        assert_eq!(suggested("game frobnicate:yes language:").as_deref(), Some("game"));
+
+        let suggestion = suggest("game stars:lots").unwrap();
+        assert_eq!(suggestion.changes, ["dropped stars:lots (not a number or range)"]);
+        // Well-formed qualifiers and URLs are fine
+        let valid = "game stars:>=100 pushed:>2024-01-05 size:*..500 is:public Language:Go";
+        assert!(valid.split_whitespace().all(|term| fix_term(term).is_none()));
+        assert_eq!(suggested("https://github.com"), None);
+    }
+
+    #[test]
+    fn test_relaxing_thresholds() {
+        let suggestion = suggest("game stars:>5000 forks:>=20").unwrap();
+        assert_eq!(suggestion.query, "game stars:>500 forks:>=2");
+        assert_eq!(suggestion.changes.len(), 2);
+        assert_eq!(suggested("game stars:100..200").as_deref(), Some("game stars:10..200"));
+        assert_eq!(suggested("game stars:>5").as_deref(), Some("game"));
+        // Fixes come first; the relaxed query is suggested if the fixed one finds nothing too
+        assert_eq!(suggested("game stars:>5000 lang:rust").as_deref(), Some("game stars:>5000 language:rust"));
+        assert_eq!(suggested("game stars:<10"), None);
+        assert_eq!(suggested("obscure words"), None);
+        // A query that would end up empty isn't a suggestion
+        assert_eq!(suggested("stars:>5"), None);
+    }
+
+    #[test]
+    fn test_similar() {
+        let repo = |topics: serde_json::Value, language: serde_json::Value| -> Repository {
+            mock::repo("me/bevy_ecs-lite").set("topics", topics).set("language", language).build()
+        };
+        let topics = serde_json::json!(["ecs", "game-engine", "gamedev", "bevy"]);
+        assert_eq!(
+            similar(&repo(topics.clone(), "Rust".into())).as_deref(),
+            Some("topic:ecs topic:game-engine topic:gamedev language:rust")
+        );
+        assert_eq!(similar(&repo(topics, serde_json::Value::Null)).as_deref(), Some("topic:ecs topic:game-engine topic:gamedev"));
+        assert_eq!(
+            similar(&repo(serde_json::json!([]), "Jupyter Notebook".into())).as_deref(),
+            Some("bevy ecs lite language:jupyter-notebook")
+        );
+        assert_eq!(similar(&repo(serde_json::Value::Null, serde_json::Value::Null)), None);
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("rust", "rust"), 0);
+        assert_eq!(distance("rsut", "rust"), 1);
+        assert_eq!(distance("pyhton", "python"), 1);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(nearest("go", LANGUAGES), Some("go"));
+        assert_eq!(nearest("zzz", LANGUAGES), None);
+    }
+}
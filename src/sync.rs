@@ -0,0 +1,134 @@
+use anyhow::Result;
+use colored::*;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::bookmarks::Bookmarks;
+use crate::clones::{self, CloneLedger};
+use crate::config::Config;
+use crate::git::{self, CloneOptions, UpdateOutcome};
+use crate::icons::Icon;
+use crate::{clone_destination, clone_repository};
+
+/// Clone or update every repository in the sync list (`sync_repos` in config.toml, plus bookmarks)
+/// Bookmarks are used when `include_bookmarks` is set or no `sync_repos` are configured
+pub async fn run(config: &Config, include_bookmarks: bool) -> Result<()> {
+    let bookmarks = if include_bookmarks || config.sync_repos.is_empty() {
+        Some(Bookmarks::load()?)
+    } else {
+        None
+    };
+    let urls = sync_urls(&config.sync_repos, bookmarks.as_ref())?;
+    if urls.is_empty() {
+        println!("{}", "Nothing to sync. Add repositories to sync_repos in config.toml or bookmark some.".yellow());
+        return Ok(());
+    }
+
+    let options = CloneOptions {
+        depth: config.clone_depth.filter(|depth| *depth > 0),
+        mode: config.clone_mode,
+        submodules: config.clone_submodules,
+    };
+    let slots = Arc::new(Semaphore::new(config.clone_parallelism.max(1)));
+    println!("{}", Icon::Sync.label(format!("Syncing {} repositories...", urls.len())).cyan().bold());
+
+    let mut tasks = JoinSet::new();
+    for url in &urls {
+        let path = clone_destination(url, None, false, config.clone_layout)?;
+        let (url, options, slots) = (url.clone(), options.clone(), slots.clone());
+        tasks.spawn(async move {
+            let _permit = slots.acquire_owned().await;
+            let result = sync_one(&url, &path, &options).await;
+            (url, path, result)
+        });
+    }
+
+    let mut ledger = CloneLedger::load()?;
+    let mut failed = 0;
+    while let Some(joined) = tasks.join_next().await {
+        let (url, path, result) = joined?;
+        match result {
+            Ok(None) => println!("{} {} {} {}", format!("{} cloned ", Icon::Success).green(), url, Icon::Arrow, path.display()),
+            Ok(Some(outcome)) => println!("{} {} ({})", format!("{} updated", Icon::Success).green(), url, outcome),
+            Err(e) => {
+                failed += 1;
+                tracing::warn!(url, error = %format!("{:#}", e), "sync failed");
+                println!("{} {}: {}", format!("{} failed ", Icon::Failure).red(), url, crate::error::describe(&e));
+                continue;
+            }
+        }
+        ledger.record(&url, &path);
+    }
+    ledger.save()?;
+
+    if failed > 0 {
+        anyhow::bail!("{} of {} repositories failed to sync", failed, urls.len());
+    }
+    println!("{}", format!("All {} repositories are up to date.", urls.len()).green().bold());
+    Ok(())
+}
+
+/// Update an existing clone, or clone it if it isn't there yet
+async fn sync_one(url: &str, path: &Path, options: &CloneOptions) -> Result<Option<UpdateOutcome>> {
+    if clones::is_clone_of(path, url) {
+        Ok(Some(git::update(path, |_| {}).await?))
+    } else {
+        clone_repository(url, path, options, |_| {}).await.map(|_| None)
+    }
+}
+
+/// Clone URLs for the configured repos followed by bookmarks, without duplicates
+fn sync_urls(repos: &[String], bookmarks: Option<&Bookmarks>) -> Result<Vec<String>> {
+    let mut urls: Vec<String> = Vec::new();
+    let bookmarked = bookmarks.into_iter().flat_map(|bookmarks| bookmarks.items.iter().map(|b| b.url.as_str()));
+    for entry in repos.iter().map(String::as_str).chain(bookmarked) {
+        let url = repo_url(entry)?;
+        if !urls.iter().any(|existing| existing.eq_ignore_ascii_case(&url)) {
+            urls.push(url);
+        }
+    }
+    Ok(urls)
+}
+
+/// Normalize a sync entry ("owner/name" or a clone URL) to a clone URL
+pub fn repo_url(entry: &str) -> Result<String> {
+    let entry = entry.trim().trim_end_matches('/');
+    if entry.contains("://") || entry.starts_with("git@") {
+        return Ok(entry.trim_end_matches(".git").to_string());
+    }
+    match entry.split('/').collect::<Vec<_>>().as_slice() {
+        [owner, name] if !owner.is_empty() && !name.is_empty() => Ok(format!("https://github.com/{}/{}", owner, name)),
+        _ => Err(anyhow::anyhow!("Invalid sync entry '{}': expected \"owner/name\" or a clone URL", entry)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repo_url() {
+        assert_eq!(repo_url("rust-lang/rust").unwrap(), "https://github.com/rust-lang/rust");
+        assert_eq!(repo_url("https://github.com/foo/bar.git").unwrap(), "https://github.com/foo/bar");
+        assert_eq!(repo_url("https://gitlab.com/foo/bar/").unwrap(), "https://gitlab.com/foo/bar");
+        assert!(repo_url("just-a-name").is_err());
+        assert!(repo_url("a/b/c").is_err());
+    }
+
+    #[test]
+    fn test_sync_urls_merges_config_and_bookmarks() {
+        let mut bookmarks = Bookmarks::default();
+        bookmarks.toggle("foo/bar", "https://github.com/foo/bar");
+        bookmarks.toggle("baz/qux", "https://github.com/baz/qux");
+
+        let repos = vec!["Foo/Bar".to_string(), "rust-lang/rust".to_string()];
+        let urls = sync_urls(&repos, Some(&bookmarks)).unwrap();
+        assert_eq!(
+            urls,
+            ["https://github.com/Foo/Bar", "https://github.com/rust-lang/rust", "https://github.com/baz/qux"]
+        );
+        assert_eq!(sync_urls(&repos, None).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,138 @@
+use octocrab::models::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Accept header that makes the search API say why each result matched
+pub const MEDIA_TYPE: &str = "application/vnd.github.text-match+json";
+
+/// A snippet of one of a result's fields that matched the query
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TextMatch {
+    pub property: String,  // The field: name, description, readme, topics, ...
+    pub fragment: String,  // Whitespace collapsed to single spaces
+    pub hits: Vec<String>, // The words of the query as they appear in the fragment
+}
+
+/// Text matches of a page of results, by repository id
+pub type TextMatches = HashMap<u64, Vec<TextMatch>>;
+
+/// A text match as the search API sends it
+#[derive(Deserialize)]
+struct ApiMatch {
+    #[serde(default)]
+    property: String,
+    #[serde(default)]
+    fragment: String,
+    #[serde(default)]
+    matches: Vec<ApiHit>,
+}
+
+#[derive(Deserialize)]
+struct ApiHit {
+    text: String,
+}
+
+/// The repositories of a search response's items, and the text matches they came with
+pub fn split_items(items: Vec<serde_json::Value>) -> Result<(Vec<Repository>, TextMatches), serde_json::Error> {
+    let mut repos = Vec::with_capacity(items.len());
+    let mut text_matches = TextMatches::new();
+    for mut item in items {
+        let matches = item.as_object_mut().and_then(|item| item.remove("text_matches"));
+        let repo: Repository = serde_json::from_value(item)?;
+        let matches: Vec<ApiMatch> = matches.map(serde_json::from_value).transpose()?.unwrap_or_default();
+        let matches: Vec<TextMatch> = matches
+            .into_iter()
+            .filter(|found| !found.fragment.trim().is_empty())
+            .map(|found| TextMatch {
+                property: found.property,
+                fragment: found.fragment.split_whitespace().collect::<Vec<_>>().join(" "),
+                hits: found.matches.into_iter().map(|hit| hit.text).filter(|text| !text.is_empty()).collect(),
+            })
+            .collect();
+        if !matches.is_empty() {
+            text_matches.insert(repo.id.0, matches);
+        }
+        repos.push(repo);
+    }
+    Ok((repos, text_matches))
+}
+
+/// The fragment as (text, is a hit) pieces, for highlighting
+/// Hits are found again in the fragment (ignoring ASCII case) rather than trusting the API's offsets
+pub fn segments(found: &TextMatch) -> Vec<(&str, bool)> {
+    let fragment = found.fragment.as_str();
+    let lower = fragment.to_ascii_lowercase();
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for hit in &found.hits {
+        let hit = hit.to_ascii_lowercase();
+        ranges.extend(lower.match_indices(hit.as_str()).map(|(start, text)| (start, start + text.len())));
+    }
+    ranges.sort();
+    // Overlapping and touching hits highlight as one
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, stop) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(stop),
+            _ => merged.push((start, stop)),
+        }
+    }
+
+    let mut pieces = Vec::new();
+    let mut end = 0;
+    for (start, stop) in merged {
+        if start > end {
+            pieces.push((&fragment[end..start], false));
+        }
+        pieces.push((&fragment[start..stop], true));
+        end = stop;
+    }
+    if end < fragment.len() {
+        pieces.push((&fragment[end..], false));
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_items() {
+        let items = vec![
+            serde_json::json!({
+                "id": 1,
+                "name": "bevy",
+                "url": "https://api.github.com/repos/bevyengine/bevy",
+                "text_matches": [
+                    {"property": "description", "fragment": "A refreshingly simple\n  data-driven game engine", "matches": [{"text": "game", "indices": [34, 38]}]},
+                    {"property": "readme", "fragment": " ", "matches": []},
+                ],
+            }),
+            serde_json::json!({"id": 2, "name": "other", "url": "https://api.github.com/repos/o/other"}),
+        ];
+        let (repos, matches) = split_items(items).unwrap();
+        assert_eq!(repos.len(), 2);
+        assert_eq!(
+            matches[&1],
+            [TextMatch {
+                property: "description".to_string(),
+                fragment: "A refreshingly simple data-driven game engine".to_string(),
+                hits: vec!["game".to_string()],
+            }]
+        );
+        assert!(!matches.contains_key(&2));
+    }
+
+    #[test]
+    fn test_segments() {
+        let found = TextMatch {
+            property: "description".to_string(),
+            fragment: "Game engine for games".to_string(),
+            hits: vec!["game".to_string(), "engine".to_string(), "game engine".to_string()],
+        };
+        assert_eq!(segments(&found), [("Game engine", true), (" for ", false), ("game", true), ("s", false)]);
+
+        let found = TextMatch { hits: Vec::new(), ..found };
+        assert_eq!(segments(&found), [("Game engine for games", false)]);
+    }
+}
@@ -0,0 +1,170 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+use crate::icons::Icon;
+
+/// How long a toast stays on screen
+const TOAST_TTL: Duration = Duration::from_secs(4);
+/// Errors linger a little longer so they can be read
+const ERROR_TOAST_TTL: Duration = Duration::from_secs(8);
+/// Maximum number of toasts shown at once (oldest are dropped)
+const MAX_TOASTS: usize = 4;
+const TOAST_WIDTH: u16 = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    fn color(self) -> Color {
+        match self {
+            ToastLevel::Info => Color::Cyan,
+            ToastLevel::Success => Color::Green,
+            ToastLevel::Warning => Color::Yellow,
+            ToastLevel::Error => Color::Red,
+        }
+    }
+
+    fn title(self) -> String {
+        let title = match self {
+            ToastLevel::Info => Icon::Info.label("Info"),
+            ToastLevel::Success => Icon::Marked.label("Done"),
+            ToastLevel::Warning => Icon::Warning.label("Warning"),
+            ToastLevel::Error => Icon::Failure.label("Error"),
+        };
+        format!(" {} ", title)
+    }
+}
+
+/// A transient notification shown in the bottom-right corner
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub level: ToastLevel,
+    pub created: Instant,
+}
+
+impl Toast {
+    pub fn new(level: ToastLevel, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level,
+            created: Instant::now(),
+        }
+    }
+
+    fn ttl(&self) -> Duration {
+        if self.level == ToastLevel::Error {
+            ERROR_TOAST_TTL
+        } else {
+            TOAST_TTL
+        }
+    }
+
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.created) >= self.ttl()
+    }
+}
+
+/// Queue of active toasts, newest last
+#[derive(Debug, Default)]
+pub struct Toasts {
+    pub items: Vec<Toast>,
+}
+
+impl Toasts {
+    /// Show a new toast, dropping the oldest one if the stack is full
+    pub fn push(&mut self, level: ToastLevel, message: impl Into<String>) {
+        self.items.push(Toast::new(level, message));
+        if self.items.len() > MAX_TOASTS {
+            self.items.remove(0);
+        }
+    }
+
+    /// Remove toasts whose display time has elapsed
+    pub fn prune(&mut self, now: Instant) {
+        self.items.retain(|toast| !toast.is_expired(now));
+    }
+}
+
+/// Render the toast stack in the bottom-right corner of `area`, newest at the bottom
+pub fn render_toasts(f: &mut Frame, toasts: &Toasts, area: Rect) {
+    let width = TOAST_WIDTH.min(area.width);
+    let inner_width = width.saturating_sub(2).max(1) as usize;
+    let mut bottom = area.y + area.height;
+
+    for toast in toasts.items.iter().rev() {
+        // Wrapped text height plus borders
+        let lines = toast
+            .message
+            .lines()
+            .map(|line| line.chars().count().max(1).div_ceil(inner_width))
+            .sum::<usize>()
+            .max(1);
+        let height = (lines as u16 + 2).min(area.height);
+        if bottom < area.y + height {
+            break;
+        }
+        bottom -= height;
+
+        let rect = Rect {
+            x: area.x + area.width - width,
+            y: bottom,
+            width,
+            height,
+        };
+
+        let color = toast.level.color();
+        let widget = Paragraph::new(toast.message.as_str())
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(toast.level.title())
+                    .border_style(Style::default().fg(color)),
+            )
+            .style(Style::default().fg(color))
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(Clear, rect);
+        f.render_widget(widget, rect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_caps_stack_size() {
+        let mut toasts = Toasts::default();
+        for i in 0..(MAX_TOASTS + 2) {
+            toasts.push(ToastLevel::Info, format!("toast {}", i));
+        }
+        assert_eq!(toasts.items.len(), MAX_TOASTS);
+        assert_eq!(toasts.items[0].message, "toast 2");
+    }
+
+    #[test]
+    fn test_prune_removes_expired_toasts() {
+        let mut toasts = Toasts::default();
+        toasts.push(ToastLevel::Success, "cloned");
+        toasts.push(ToastLevel::Error, "failed");
+
+        // Info/success toasts expire before errors do
+        toasts.prune(Instant::now() + TOAST_TTL);
+        assert_eq!(toasts.items.len(), 1);
+        assert_eq!(toasts.items[0].level, ToastLevel::Error);
+
+        toasts.prune(Instant::now() + ERROR_TOAST_TTL);
+        assert!(toasts.items.is_empty());
+    }
+}
@@ -5,6 +5,7 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use octocrab::models::Repository;
+use rustsec::advisory::Severity;
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     layout::{Alignment, Constraint, Direction, Layout},
@@ -13,75 +14,1040 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame, Terminal,
 };
+use std::cell::RefCell;
+use std::cmp::Reverse;
 use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// How often the UI redraws while idle or busy (drives spinners and toast expiry)
+pub const TICK_RATE: Duration = Duration::from_millis(100);
+
+/// Default directory for Alt+G clones, relative to the working directory
+pub const CLONE_DIR: &str = "repositories";
+
+/// Default size above which Alt+G clones need confirming (config: clone_confirm_size_mb)
+pub const DEFAULT_CLONE_CONFIRM_SIZE_MB: u64 = 500;
+
+/// GitHub's search API returns at most this many results per search, however many match
+pub const SEARCH_RESULT_LIMIT: u64 = 1000;
+
+/// With infinite scroll, the next page starts loading once the selection is this close to the end
+const LOAD_MORE_MARGIN: usize = 10;
+
+/// Widest a tab's query shows in the tab strip, in cells
+const TAB_TITLE_WIDTH: usize = 16;
+
+/// Results list rows built beyond the ones on screen, above and below; the rest aren't built at all
+const LIST_WINDOW_MARGIN: usize = 10;
+
+/// Results gone since a search last ran that its toast names; the rest are counted
+const MAX_NAMED_GONE: usize = 3;
+
+
+use crate::analysis::{self, LanguageStats};
+use crate::analysis_store::{AnalysisStore, Kind as StoredKind, Stored, StoredAnalysis};
+use crate::audit;
+use crate::awesome;
+use crate::bookmarks::Bookmarks;
+use crate::cache;
+use crate::profiles::{self, AnalysisProfile};
+use crate::build_system::{self, BuildInfo};
+use crate::clones::{self, is_clone_of, CloneLedger, LocalClone};
+use crate::columns::{self, Column, ColumnKind};
+use crate::compare::{self, RepoActivity};
+use crate::config::{CloneLayout, Keymap};
+use crate::error::Error;
+use crate::export;
+use crate::licenses;
+use crate::maintenance::{self, Maintenance};
+use crate::qualifiers;
+use crate::stars::{self, StarHistory};
+use crate::suggest::{self, Suggestion};
+use crate::text_match::{self, TextMatches};
+use crate::filecount::{CountFilter, FileStats};
+use crate::i18n::tr;
+use crate::paths::expand_home;
+use crate::icons::{self, Icon};
+use crate::git::{CloneMode, CloneOptions, CloneProgress};
+use crate::jobs::{CloneStatus, CountStatus, JobEvent, JobId, JobKind, JobManager, JobState, SearchResults};
+use crate::message::{AppMessage, Action, Flow};
+use crate::modal::{Modal, ModalStack, Outcome, Purpose};
+use crate::osc::{self, TerminalStatus};
+use crate::toast::{render_toasts, ToastLevel, Toasts};
 
 /// Main application state
 pub struct App {
+    pub tab: SearchTab,                             // The active search tab
+    pub tabs: Vec<SearchTab>,                       // The other tabs, in order (the active one goes at active_tab)
+    pub active_tab: usize,                          // Position of the active tab among all of them
+    pub per_page: u32,                              // Results per page (--limit)
+    pub infinite_scroll: bool,                      // Load the next page near the end of the list (config: infinite_scroll)
+    pub search_cache_ttl: u64,                      // Seconds a search's results are reused (config: search_cache_ttl_secs)
+    pub modals: ModalStack,                         // Dialogs drawn above everything else; the top one gets the keys
+    pub file_counts: HashMap<String, Result<FileStats, String>>, // File counts per repo URL (Err holds the error message)
+    pub pending_counts: HashMap<String, CountStatus>, // Background file counts per repo URL (queued/running)
+    pub pending_clones: HashMap<String, CloneStatus>, // Background Alt+G clones per repo URL (queued/running)
+    pub pending_analyses: HashMap<String, CountStatus>, // Background language analyses per repo URL
+    pub analyses: HashMap<String, Result<Vec<LanguageStats>, String>>, // Finished language analyses per repo URL
+    pub figures_revision: u64,                      // Bumped whenever file_counts or analyses change, so listings are recomputed
+    pub comparison: Option<[Repository; 2]>,        // Comparison overlay (Alt+V): two marked repos side by side
+    pub activity: HashMap<String, Result<RepoActivity, String>>, // Release and contributor figures per repo URL
+    pub pending_activity: HashSet<String>,          // Repo URLs whose figures are being fetched
+    pub fetch_details: bool,                        // Fetch the selected result's figures by itself (config: fetch_details)
+    pub prefetch_ahead: usize,                      // Results after the selected one fetched ahead (config: prefetch_ahead)
+    pub fetched_for_details: HashSet<String>,       // Repo URLs being fetched for the details panel rather than asked for
+    pub deferred_prefetches: HashSet<String>,       // Repo URLs whose prefetch the scheduler skipped: fetched only once selected
+    pub details_paused: bool,                       // A details fetch failed (often the rate limit): none until the next search
+    pub star_history: HashMap<String, Result<StarHistory, String>>, // Star charts per repo URL (Alt+T)
+    pub pending_star_history: HashSet<String>,      // Repo URLs whose star history is being fetched
+    pub maintenance: HashMap<String, Result<Maintenance, String>>, // Issue, PR, and contributor figures per repo URL (Alt+I)
+    pub pending_maintenance: HashSet<String>,     // Repo URLs whose maintenance is being measured
+    pub audits: HashMap<String, Result<Vec<audit::Finding>, String>>, // RustSec advisories against each audited clone, by repo URL
+    pub audits_due: Vec<(String, PathBuf)>,         // Fresh clones (URL, path) waiting for their audit to start
+    pub pending_audits: HashSet<String>,            // Repo URLs whose clone is being audited
+    pub profile_sections: HashMap<String, Vec<profiles::Section>>, // Output of the analysis profile commands, by repo URL
+    pub profiles_due: Vec<(String, PathBuf, Vec<profiles::Step>)>, // Fresh clones (URL, path) and the commands picked for them
+    pub pending_profiles: HashSet<String>,          // Repo URLs whose analysis profile commands are running
+    pub analysis_store: AnalysisStore,              // File counts, analyses, and metrics of earlier sessions (analysis_store.json)
+    pub restored: HashMap<(String, StoredKind), SystemTime>, // Results shown from the store (repo URL, kind): when they were computed
+    pub refresh_due: Vec<Action>,                   // Re-runs queued by a refresh (Alt+N), last one first
+    pub jobs: JobManager,                           // Background searches, clones, counts, ... and their results
+    pub show_jobs: bool,                            // Jobs panel overlay is open
+    pub show_cheatsheet: bool,                      // Search-syntax cheatsheet overlay is open (Alt+H / ?)
+    pub cheatsheet_state: ListState,                // Selected qualifier in the cheatsheet
+    pub clone_progress: Option<(String, CloneProgress)>, // Latest progress of the running clone (repo URL, progress)
+    pub jobs_state: ListState,                      // Selected row in the jobs panel
+    pub details_height: u16,                        // Visible rows in the details panel (for page jumps)
+    pub details_max_scroll: u16,                    // Largest offset that still fills the panel (set while rendering)
+    pub toasts: Toasts,                             // Transient notifications (bottom-right overlay)
+    pub terminal_status: TerminalStatus,            // Title and taskbar progress last set (config: terminal_status)
+    pub vim_mode: bool,                             // Vim-style keybindings enabled (config: vim_mode)
+    pub focus: Focus,                               // Typing in the search box or browsing the results (Tab switches)
+    pub keymap: Keymap,                             // Where focus goes after a search (config: keymap)
+    pub columns: Vec<Column>,                       // Results-list columns (config: result_columns)
+    pub pending_g: bool,                            // First 'g' of a "gg" sequence was pressed
+    pub pending_leader: bool,                       // Leader (Space while browsing) pressed; next key picks the action
+    pub pending_open: bool,                         // Open pressed; next key picks the page (repo, issues, pulls, ...)
+    pub results_height: u16,                        // Visible rows in the results list (for half-page jumps)
+    pub bookmarks: Bookmarks,                       // Bookmarked repositories
+    pub clones: CloneLedger,                        // Repositories cloned with Alt+G (clones.toml)
+    pub cloned_paths: HashMap<String, PathBuf>,     // Local clone path per result URL
+    pub build_info: HashMap<String, BuildInfo>,     // Build systems and toolchains of each local clone, by repo URL
+    pub show_clones: bool,                          // "My clones" overlay is open (Alt+M)
+    pub local_clones: Vec<LocalClone>,              // Clones listed in the overlay
+    pub clones_state: ListState,                    // Selected row in the clones overlay
+    pub clones_marked: HashSet<PathBuf>,            // Clones marked for deletion in the overlay
+    pub clones_by_size: bool,                       // Sort the overlay by disk usage instead of name
+    pub auth_user: Option<String>,                  // Logged-in user when a token is configured
+    pub rate_limit: Option<RateLimitInfo>,          // Remaining search API quota
+    pub cli_filters: Vec<String>,                   // Query qualifiers from CLI args (language, stars, sort)
+    pub project_license: Vec<&'static str>,         // Licenses of the user's project (config: project_license); others are checked against them
+    pub owner_scope: Option<String>,                // user:/org: qualifier added to every search until cleared (Alt+U, --owner)
+    pub raw_query: bool,                            // --raw-query: searches go out as typed, without size or owner filters
+    pub provider: String,                           // Code host the searches go to
+    pub live_search: bool,                          // Search automatically after typing pauses (config: live_search)
+    pub live_search_delay: Duration,                // Debounce delay for live search
+    pub last_edit: Option<Instant>,                 // When the search input last changed (pending live search)
+    pub clone_depth: Option<u32>,                   // Default clone depth (config: clone_depth; 0 = full history)
+    pub clone_mode: CloneMode,                      // Default clone mode (config: clone_mode)
+    pub clone_submodules: bool,                     // Clone submodules by default (config: clone_submodules)
+    pub clone_layout: CloneLayout,                  // repositories/<name> or repositories/<owner>/<name> (config: clone_layout)
+    pub clone_confirm_size_mb: u64,                 // Alt+G asks for a second Enter above this size (0 = never)
+    pub audit_after_clone: bool,                    // Audit the Cargo.lock of fresh clones (config: audit_after_clone)
+    pub pane_command: Option<Vec<String>>,          // Opens a pane in a clone (config: pane_command, or tmux/WezTerm's split)
+    pub analysis_profiles: BTreeMap<String, AnalysisProfile>, // Commands run in fresh clones, per language (config: [analysis.*])
+    pub script_timeout_secs: u64,                   // Time limit of analysis commands that don't set one (config: script_timeout_secs)
+    pub clone_prompt: Option<ClonePrompt>,          // Clone options dialog (open before cloning/counting)
+    pub clone_options: CloneOptions,                // Options confirmed in the last clone prompt
+    pub count_filter: CountFilter,                  // Exclusions for file counts and language analysis
+    pub clone_destination: Option<PathBuf>,         // Destination confirmed in the last Alt+G clone prompt
+    pub monochrome: bool,                           // NO_COLOR / --color=never: draw without colors
+}
+
+/// State of one search tab: its query, filters, results, and selection
+#[derive(Default)]
+pub struct SearchTab {
     pub input: Input,                               // Search input field
     pub results: Vec<Repository>,                   // Search results from GitHub
     pub list_state: ListState,                      // Which result is selected
-    pub searching: bool,                            // Currently performing search
     pub error_message: Option<String>,              // Error to display
+    pub error_hint: Option<String>,                 // What to do about it, e.g. "Retry in 42s"
+    pub cached_at: Option<cache::Hit>,              // Results came from the cache (fresh, or offline), fetched at this time
+    pub changes: Option<cache::Changes>,            // How the first page changed since the search last ran
     pub total_count: Option<u64>,                   // Total results from GitHub
-    pub file_counts: HashMap<String, String>,       // Cached file counts per repo URL
-    pub counting_files: bool,                       // Currently counting files
+    pub page_starts: Vec<usize>,                    // Index in `results` where each loaded page begins
+    pub result_ids: HashSet<u64>,                   // Ids of the repos in `results`, to skip duplicates
+    pub results_query: String,                      // Query `results` came from (a repeat keeps the selection)
+    pub suggestion: Option<Suggestion>,             // Corrected query offered when a search finds nothing (Alt+S runs it)
+    pub search_job: Option<JobId>,                  // The running search, until its first page arrives
+    pub page_job: Option<(JobId, u32)>,             // Background fetch of further pages, and the last page it fetches
+    pub page_target: Option<u32>,                   // Page to select when it arrives (go to page)
+    pub more_pages_paused: bool,                    // A page failed or its fetch was cancelled: no automatic fetches until the next search
     pub details_scroll: u16,                        // Scroll offset for details panel
     pub repo_size_filter: Option<String>,           // Current size filter: small, medium, large, or None
-    pub cloning: bool,                              // Currently cloning a repository
-    pub clone_status: Option<String>,               // Status message from last clone attempt
+    pub filter_input: Input,                        // In-results filter text ('/' in vim mode)
+    pub filtering: bool,                            // Currently typing into the results filter
+    pub marked: HashSet<u64>,                       // Repo ids marked for batch actions
+    pub resume_search: Option<(String, u64)>,       // Rate-limited query to re-run once the quota resets, and when (Unix seconds)
+    pub last_query: String,                         // Most recently submitted query
+    pub collection: Option<String>,                 // Awesome list (owner/name) the results were loaded from, instead of a search
+    pub local_sort: Option<LocalSort>,              // Order the list by a computed figure instead of GitHub's order (Alt+Z)
+    pub text_matches: TextMatches,                  // Why each result matched the search, by repo id
+    pub results_revision: u64,                      // Bumped whenever `results` changes, so the listing is recomputed
+    listing: RefCell<Option<(ListingKey, Listing)>>, // The listing and what it was computed from
+}
+
+/// The results in list order (filtered and sorted), and whether any of them has each analyzed figure
+#[derive(Debug, Clone, Default)]
+pub struct Listing {
+    pub indices: Rc<[usize]>,
+    pub any_files: bool,
+    pub any_loc: bool,
+}
+
+/// What a tab's listing depends on: it's computed again once any of these changes
+#[derive(Debug, PartialEq)]
+struct ListingKey {
+    results: u64,
+    figures: u64,
+    filter: String,
+    sort: Option<LocalSort>,
+}
+
+/// Computed figure the results list can be ordered by, largest first; results without it go last
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LocalSort {
+    Files, // Total files (Alt+F, or Alt+A)
+    Loc,   // Lines of code (Alt+A)
+}
+
+impl LocalSort {
+    fn label(self) -> String {
+        match self {
+            LocalSort::Files => tr!("sort-files"),
+            LocalSort::Loc => tr!("sort-loc"),
+        }
+    }
+}
+
+impl SearchTab {
+    /// Label in the tab strip: the tab's query, or a placeholder before its first search
+    pub fn title(&self) -> String {
+        if self.last_query.is_empty() {
+            tr!("tab-new")
+        } else {
+            self.last_query.clone()
+        }
+    }
+
+    /// The result with the given GitHub URL
+    fn repo_with_url(&self, url: &str) -> Option<&Repository> {
+        self.results.iter().find(|repo| repo.html_url.as_ref().is_some_and(|u| u.as_str() == url))
+    }
+}
+
+/// Where key presses go: the search box or the results list
+/// In vim mode these are insert and normal mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Focus {
+    Typing,   // Letters type into the search box; actions need Alt
+    Browsing, // Plain letters act on the selected result
+}
+
+/// An action bound to Alt+letter, a plain letter while browsing, or a leader chord
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shortcut {
+    Open,        // Alt+O / o
+    Clone,       // Alt+G / c
+    FileCount,   // Alt+F / f
+    Analyze,     // Alt+A / a
+    Bookmark,    // Alt+B / b
+    CopyUrl,     // Alt+Y / y
+    MyClones,    // Alt+M / m
+    Jobs,        // Alt+J / J
+    ClearSearch, // Alt+C (Ctrl+U while typing)
+    Mark,        // Ctrl+Space
+    GoToPage,    // Alt+P / p
+    Export,      // Alt+E / e
+    Compare,     // Alt+V / v
+    Suggestion,  // Alt+S / s
+    Cheatsheet,  // Alt+H / ? / F1
+    Palette,     // Ctrl+P
+    NewTab,      // Ctrl+T
+    NextTab,     // Ctrl+Tab / Ctrl+PageDown
+    PreviousTab, // Ctrl+Shift+Tab / Ctrl+PageUp
+    CloseTab,    // Alt+W
+    OwnerScope,  // Alt+U / u
+    Similar,     // Alt+R / r
+    Awesome,     // Alt+L / l
+    StarHistory, // Alt+T / t
+    Maintenance, // Alt+I / I
+    Refresh,     // Alt+N / n
+    LocalSort,   // Alt+Z / S
+    ExcludeOwner, // Alt+X / x
+    OpenPane,    // Alt+D / d
+    Reload,      // Ctrl+R
+}
+
+/// Second keys of leader chords (Space, then the key) with the hint the popup shows
+const LEADER_KEYS: &[(char, Shortcut, &str)] = &[
+    ('o', Shortcut::Open, "open in browser"),
+    ('c', Shortcut::Clone, "clone"),
+    ('f', Shortcut::FileCount, "count files"),
+    ('a', Shortcut::Analyze, "analyze languages"),
+    ('b', Shortcut::Bookmark, "bookmark"),
+    ('y', Shortcut::CopyUrl, "copy URL"),
+    ('m', Shortcut::MyClones, "my clones"),
+    ('j', Shortcut::Jobs, "jobs"),
+    ('x', Shortcut::ClearSearch, "clear search"),
+    ('p', Shortcut::GoToPage, "go to page"),
+    ('e', Shortcut::Export, "export"),
+    ('v', Shortcut::Compare, "compare two marked"),
+    ('s', Shortcut::Suggestion, "search the suggestion"),
+    ('?', Shortcut::Cheatsheet, "search syntax"),
+    ('t', Shortcut::NewTab, "new tab"),
+    ('w', Shortcut::CloseTab, "close tab"),
+    ('u', Shortcut::OwnerScope, "search one user/org"),
+    ('r', Shortcut::Similar, "find similar"),
+    ('l', Shortcut::Awesome, "awesome lists"),
+    ('h', Shortcut::StarHistory, "star history"),
+    ('i', Shortcut::Maintenance, "maintenance metrics"),
+    ('n', Shortcut::Refresh, "refresh stored results"),
+    ('z', Shortcut::LocalSort, "sort by files / LOC"),
+    ('X', Shortcut::ExcludeOwner, "exclude this owner"),
+    ('d', Shortcut::OpenPane, "open clone in a new pane"),
+    ('R', Shortcut::Reload, "search again, skipping the cache"),
+    (' ', Shortcut::Mark, "mark / unmark"),
+];
+
+/// Pages the open submenu (open, then the key) leads to: key, path under the repo URL, hint
+const OPEN_KEYS: &[(char, &str, &str)] = &[
+    ('o', "", "repository"),
+    ('i', "issues", "issues"),
+    ('p', "pulls", "pull requests"),
+    ('r', "releases", "releases"),
+    ('w', "wiki", "wiki"),
+];
+
+/// URL of the page `key` picks in the open submenu (Enter also opens the repository itself)
+fn deep_link(repo: &Repository, key: &event::KeyEvent) -> Option<String> {
+    let path = match key.code {
+        KeyCode::Enter => "",
+        KeyCode::Char(c) => OPEN_KEYS.iter().find(|(second, _, _)| *second == c)?.1,
+        _ => return None,
+    };
+    let url = repo.html_url.as_ref()?.as_str().trim_end_matches('/');
+    Some(if path.is_empty() { url.to_string() } else { format!("{}/{}", url, path) })
+}
+
+/// The action a leader chord ending in `key` runs, if any
+fn leader_chord(key: &event::KeyEvent) -> Option<Shortcut> {
+    let KeyCode::Char(c) = key.code else {
+        return None;
+    };
+    LEADER_KEYS.iter().find(|(second, _, _)| *second == c).map(|(_, shortcut, _)| *shortcut)
+}
+
+/// Whether a key press starts a leader chord: Space while browsing (typing needs it for queries)
+fn is_leader_key(key: &event::KeyEvent, focus: Focus) -> bool {
+    focus == Focus::Browsing && key.code == KeyCode::Char(' ') && key.modifiers.is_empty()
+}
+
+/// The shortcut a key press triggers, if any
+/// Alt+letter works with either focus, for terminals that pass Alt through;
+/// plain letters only while browsing, so they can still be typed into the search box
+fn shortcut(key: &event::KeyEvent, focus: Focus) -> Option<Shortcut> {
+    if key.code == KeyCode::F(1) {
+        return Some(Shortcut::Cheatsheet);
+    }
+    // Ctrl+Tab doesn't reach the app in every terminal, so Ctrl+PageDown/PageUp switch tabs too
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        match key.code {
+            KeyCode::Char('t') => return Some(Shortcut::NewTab),
+            KeyCode::Char('p') => return Some(Shortcut::Palette),
+            KeyCode::Char('r') => return Some(Shortcut::Reload),
+            KeyCode::Tab | KeyCode::PageDown => return Some(Shortcut::NextTab),
+            KeyCode::BackTab | KeyCode::PageUp => return Some(Shortcut::PreviousTab),
+            _ => {}
+        }
+    }
+    let KeyCode::Char(c) = key.code else {
+        return None;
+    };
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        return match c {
+            'o' => Some(Shortcut::Open),
+            'g' => Some(Shortcut::Clone),
+            'f' => Some(Shortcut::FileCount),
+            'a' => Some(Shortcut::Analyze),
+            'b' => Some(Shortcut::Bookmark),
+            'y' => Some(Shortcut::CopyUrl),
+            'm' => Some(Shortcut::MyClones),
+            'j' => Some(Shortcut::Jobs),
+            'c' => Some(Shortcut::ClearSearch),
+            'p' => Some(Shortcut::GoToPage),
+            'e' => Some(Shortcut::Export),
+            'v' => Some(Shortcut::Compare),
+            's' => Some(Shortcut::Suggestion),
+            'h' => Some(Shortcut::Cheatsheet),
+            'w' => Some(Shortcut::CloseTab),
+            'u' => Some(Shortcut::OwnerScope),
+            'r' => Some(Shortcut::Similar),
+            'l' => Some(Shortcut::Awesome),
+            't' => Some(Shortcut::StarHistory),
+            'i' => Some(Shortcut::Maintenance),
+            'n' => Some(Shortcut::Refresh),
+            'z' => Some(Shortcut::LocalSort),
+            'x' => Some(Shortcut::ExcludeOwner),
+            'd' => Some(Shortcut::OpenPane),
+            _ => None,
+        };
+    }
+    if focus == Focus::Typing || key.modifiers.contains(KeyModifiers::CONTROL) {
+        return None;
+    }
+    match c {
+        'o' => Some(Shortcut::Open),
+        'c' => Some(Shortcut::Clone),
+        'f' => Some(Shortcut::FileCount),
+        'a' => Some(Shortcut::Analyze),
+        'b' => Some(Shortcut::Bookmark),
+        'y' => Some(Shortcut::CopyUrl),
+        'm' => Some(Shortcut::MyClones),
+        'J' => Some(Shortcut::Jobs), // Lowercase j moves down in vim mode
+        'p' => Some(Shortcut::GoToPage),
+        'e' => Some(Shortcut::Export),
+        'v' => Some(Shortcut::Compare),
+        's' => Some(Shortcut::Suggestion),
+        '?' => Some(Shortcut::Cheatsheet),
+        'u' => Some(Shortcut::OwnerScope),
+        'r' => Some(Shortcut::Similar),
+        'l' => Some(Shortcut::Awesome),
+        't' => Some(Shortcut::StarHistory),
+        'I' => Some(Shortcut::Maintenance), // Lowercase i goes back to typing in vim mode
+        'n' => Some(Shortcut::Refresh),
+        'S' => Some(Shortcut::LocalSort),
+        'x' => Some(Shortcut::ExcludeOwner),
+        'd' => Some(Shortcut::OpenPane),
+        _ => None,
+    }
+}
+
+/// What the clone prompt is confirming
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClonePurpose {
+    Clone,     // Alt+G: clone into repositories/
+    FileCount, // Alt+F: temporary clone for the file count
+    Analyze,   // Alt+A: temporary clone for the language analysis
+}
+
+/// Row of the clone prompt that has keyboard focus
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptField {
+    Destination,
+    Depth,
+    Mode,
+    Submodules,
+}
+
+impl PromptField {
+    fn label(self) -> String {
+        match self {
+            PromptField::Destination => tr!("field-destination"),
+            PromptField::Depth => tr!("field-depth"),
+            PromptField::Mode => tr!("field-mode"),
+            PromptField::Submodules => tr!("field-submodules"),
+        }
+    }
+}
+
+/// Dialog asking how to clone before an Alt+G clone, Alt+F file count, or Alt+A analysis
+pub struct ClonePrompt {
+    pub purpose: ClonePurpose,
+    pub urls: Vec<String>,     // Repo URLs the action applies to
+    pub destination: Input,    // Clone path (one repo) or parent directory (several); Alt+G only
+    pub existing: Option<String>, // Path of an existing clone of the repo (confirming there updates it)
+    pub depth: Input,          // Number of commits, or "full"
+    pub mode: CloneMode,       // Full, blobless, or treeless
+    pub submodules: bool,      // Recurse into submodules
+    pub focus: PromptField,    // Row receiving key presses (Tab switches)
+    pub error: Option<String>, // Validation message for invalid input
+    pub size_kb: u64,          // Total size GitHub reports for the repos, in KB
+    pub needs_size_confirm: bool, // Over the size threshold: Enter must be pressed twice
+}
+
+impl ClonePrompt {
+    /// Whether confirming will update an existing clone rather than make a new one
+    pub fn updates_existing(&self) -> bool {
+        self.purpose == ClonePurpose::Clone
+            && self.existing.as_deref() == Some(self.destination.value().trim())
+    }
+
+    /// Rows shown for this prompt, top to bottom (file counts use a temp dir, so no destination)
+    pub fn fields(&self) -> &'static [PromptField] {
+        match self.purpose {
+            ClonePurpose::Clone => &[
+                PromptField::Destination,
+                PromptField::Depth,
+                PromptField::Mode,
+                PromptField::Submodules,
+            ],
+            ClonePurpose::FileCount | ClonePurpose::Analyze => {
+                &[PromptField::Depth, PromptField::Mode, PromptField::Submodules]
+            }
+        }
+    }
+
+    /// Move focus by `delta` rows, wrapping around
+    fn move_focus(&mut self, delta: isize) {
+        let fields = self.fields();
+        let current = fields.iter().position(|&field| field == self.focus).unwrap_or(0);
+        let next = (current as isize + delta).rem_euclid(fields.len() as isize) as usize;
+        self.focus = fields[next];
+    }
+
+    /// Handle a key press other than Enter/Esc
+    pub fn handle_key(&mut self, key: event::KeyEvent) {
+        match (self.focus, key.code) {
+            (_, KeyCode::Tab | KeyCode::Down) => self.move_focus(1),
+            (_, KeyCode::BackTab | KeyCode::Up) => self.move_focus(-1),
+            (PromptField::Mode, KeyCode::Right | KeyCode::Char(' ')) => self.mode = self.mode.next(),
+            (PromptField::Mode, KeyCode::Left) => self.mode = self.mode.previous(),
+            (PromptField::Submodules, KeyCode::Left | KeyCode::Right | KeyCode::Char(' ')) => {
+                self.submodules = !self.submodules;
+            }
+            (PromptField::Mode | PromptField::Submodules, _) => {}
+            (PromptField::Destination, _) => {
+                self.destination.handle_event(&Event::Key(key));
+                self.error = None;
+            }
+            (PromptField::Depth, _) => {
+                self.depth.handle_event(&Event::Key(key));
+                self.error = None;
+            }
+        }
+    }
+}
+
+/// Search API rate-limit snapshot shown in the status bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitInfo {
+    pub remaining: usize,
+    pub limit: usize,
+    pub reset: u64, // Unix timestamp when the quota resets
+}
+
+impl RateLimitInfo {
+    /// Warning to show once a fifth or less of the quota is left
+    pub fn warning(self) -> Option<String> {
+        if self.remaining * 5 > self.limit {
+            return None;
+        }
+        Some(tr!("toast-rate-low", remaining = self.remaining, limit = self.limit, seconds = self.reset.saturating_sub(unix_now())))
+    }
 }
 
 impl App {
     pub fn new() -> Self {
         Self {
-            input: Input::default(),
-            results: Vec::new(),
-            list_state: ListState::default(),
-            searching: false,
-            error_message: None,
-            total_count: None,
+            tab: SearchTab::default(),
+            tabs: Vec::new(),
+            active_tab: 0,
+            per_page: 100,
+            infinite_scroll: true,
+            search_cache_ttl: 300,
+            modals: ModalStack::default(),
             file_counts: HashMap::new(),
-            counting_files: false,
-            details_scroll: 0,
-            repo_size_filter: None,
-            cloning: false,
-            clone_status: None,
+            figures_revision: 0,
+            pending_counts: HashMap::new(),
+            pending_clones: HashMap::new(),
+            pending_analyses: HashMap::new(),
+            analyses: HashMap::new(),
+            comparison: None,
+            activity: HashMap::new(),
+            pending_activity: HashSet::new(),
+            fetch_details: false,
+            prefetch_ahead: 2,
+            fetched_for_details: HashSet::new(),
+            deferred_prefetches: HashSet::new(),
+            details_paused: false,
+            star_history: HashMap::new(),
+            pending_star_history: HashSet::new(),
+            maintenance: HashMap::new(),
+            pending_maintenance: HashSet::new(),
+            audits: HashMap::new(),
+            audits_due: Vec::new(),
+            pending_audits: HashSet::new(),
+            profile_sections: HashMap::new(),
+            profiles_due: Vec::new(),
+            pending_profiles: HashSet::new(),
+            analysis_store: AnalysisStore::default(),
+            restored: HashMap::new(),
+            refresh_due: Vec::new(),
+            jobs: JobManager::new(),
+            show_jobs: false,
+            show_cheatsheet: false,
+            cheatsheet_state: ListState::default(),
+            clone_progress: None,
+            jobs_state: ListState::default(),
+            details_height: 0,
+            details_max_scroll: 0,
+            toasts: Toasts::default(),
+            terminal_status: TerminalStatus::default(),
+            vim_mode: false,
+            focus: Focus::Typing,
+            keymap: Keymap::Alt,
+            columns: columns::defaults(),
+            pending_g: false,
+            pending_leader: false,
+            pending_open: false,
+            results_height: 0,
+            bookmarks: Bookmarks::default(),
+            clones: CloneLedger::default(),
+            cloned_paths: HashMap::new(),
+            build_info: HashMap::new(),
+            show_clones: false,
+            local_clones: Vec::new(),
+            clones_state: ListState::default(),
+            clones_marked: HashSet::new(),
+            clones_by_size: false,
+            auth_user: None,
+            rate_limit: None,
+            cli_filters: Vec::new(),
+            project_license: Vec::new(),
+            owner_scope: None,
+            raw_query: false,
+            provider: "GitHub".to_string(),
+            live_search: false,
+            live_search_delay: Duration::from_millis(500),
+            last_edit: None,
+            clone_depth: None,
+            clone_mode: CloneMode::Full,
+            clone_submodules: false,
+            clone_layout: CloneLayout::Name,
+            clone_confirm_size_mb: DEFAULT_CLONE_CONFIRM_SIZE_MB,
+            audit_after_clone: false,
+            pane_command: None,
+            analysis_profiles: BTreeMap::new(),
+            script_timeout_secs: profiles::DEFAULT_TIMEOUT_SECS,
+            clone_prompt: None,
+            clone_options: CloneOptions::default(),
+            count_filter: CountFilter::default(),
+            clone_destination: None,
+            monochrome: false,
         }
     }
 
-    /// Set the repository size filter
+    /// Set the repository size filter (off with --raw-query)
     pub fn set_size_filter(&mut self, filter: Option<String>) {
-        self.repo_size_filter = filter;
+        if self.raw_query && filter.is_some() {
+            self.notify(ToastLevel::Info, tr!("toast-raw-query"));
+            return;
+        }
+        self.tab.repo_size_filter = filter;
     }
 
-    /// Set search results and automatically select the first item
+    /// Set search results and select the first item
+    /// Repeating the last search (Ctrl+R, another size filter, a cache refresh) keeps the selected
+    /// repository selected, wherever it moved to, and its details scrolled where they were
     pub fn set_results(&mut self, results: Vec<Repository>, total_count: u64) {
-        self.results = results;
-        self.total_count = Some(total_count);
-        if !self.results.is_empty() {
-            self.list_state.select(Some(0)); // Auto-select first result
+        // Pages still loading belong to the previous search
+        if let Some((job, _)) = self.tab.page_job.take() {
+            self.jobs.list.cancel(job);
+        }
+        let repeated = self.tab.results_query == self.tab.last_query;
+        let selected = self.get_selected_repo().map(|repo| repo.id.0).filter(|_| repeated);
+        let scroll = std::mem::take(&mut self.tab.details_scroll);
+        self.tab.results_query = self.tab.last_query.clone();
+        self.tab.results.clear();
+        self.tab.result_ids.clear();
+        self.tab.results_revision += 1;
+        self.add_unique(results);
+        self.tab.total_count = Some(total_count);
+        self.tab.page_starts = vec![0];
+        self.tab.page_target = None;
+        self.tab.more_pages_paused = false;
+        self.details_paused = false;
+        self.deferred_prefetches.clear();
+        self.tab.cached_at = None;
+        self.tab.changes = None;
+        self.tab.text_matches.clear();
+        self.tab.filter_input.reset();
+        self.tab.filtering = false;
+        self.tab.marked.clear();
+        self.tab.suggestion = if self.tab.results.is_empty() { suggest::suggest(&self.tab.results_query) } else { None };
+        if !self.tab.results.is_empty() {
+            // The list may be in local order (Alt+Z), so the row is looked up among the visible ones
+            let row = selected.and_then(|id| self.visible_indices().iter().position(|&i| self.tab.results[i].id.0 == id));
+            if row.is_some() {
+                self.tab.details_scroll = scroll;
+            }
+            self.tab.list_state.select(Some(row.unwrap_or(0))); // Auto-select first result
+        }
+        self.refresh_cloned();
+        self.restore_analyses();
+    }
+
+    /// Add the next page of results after the loaded ones
+    /// Repos already in the list (GitHub sometimes repeats one on adjacent pages) are skipped
+    pub fn append_results(&mut self, results: Vec<Repository>, total_count: u64) {
+        self.tab.page_starts.push(self.tab.results.len());
+        self.add_unique(results);
+        self.tab.total_count = Some(total_count);
+        self.refresh_cloned();
+        self.restore_analyses();
+    }
+
+    /// Append the repos of `results` that aren't in the list yet
+    fn add_unique(&mut self, results: Vec<Repository>) {
+        for repo in results {
+            if self.tab.result_ids.insert(repo.id.0) {
+                self.tab.results.push(repo);
+            }
+        }
+        self.tab.results_revision += 1;
+    }
+
+    /// Pages of the current search in `results`
+    pub fn pages_loaded(&self) -> u32 {
+        self.tab.page_starts.len() as u32
+    }
+
+    /// Pages the current search has (GitHub serves the first SEARCH_RESULT_LIMIT results only)
+    pub fn total_pages(&self) -> u32 {
+        let total = self.tab.total_count.unwrap_or(0).min(SEARCH_RESULT_LIMIT);
+        let pages = total.div_ceil(self.per_page.max(1) as u64) as u32;
+        pages.max(self.pages_loaded())
+    }
+
+    /// Page the selected result is on
+    pub fn current_page(&self) -> u32 {
+        let index = self
+            .tab
+            .list_state
+            .selected()
+            .and_then(|i| self.visible_indices().get(i).copied())
+            .unwrap_or(0);
+        self.tab.page_starts.partition_point(|&start| start <= index).max(1) as u32
+    }
+
+    /// Select the first result of `page`, or return a Page action when it isn't loaded yet
+    pub fn go_to_page(&mut self, page: u32) -> Option<Action> {
+        let pages = self.total_pages();
+        if page == 0 || page > pages {
+            self.notify(ToastLevel::Warning, tr!("toast-no-such-page", page = page, pages = pages));
+            return None;
+        }
+        if page > self.pages_loaded() {
+            // Shown once it arrives; a fetch that stops short of it is replaced
+            self.tab.page_target = Some(page);
+            match self.tab.page_job {
+                Some((_, last)) if page <= last => return None,
+                Some((job, _)) => {
+                    self.tab.page_job = None;
+                    self.jobs.list.cancel(job);
+                }
+                None => {}
+            }
+            return Some(Action::Page(page));
+        }
+        // The filter would hide results, so a page jump shows them all again
+        if !self.tab.filter_input.value().is_empty() {
+            self.clear_filter();
+        }
+        let start = self.tab.page_starts[(page - 1) as usize];
+        self.tab.list_state.select(Some(start.min(self.tab.results.len().saturating_sub(1))));
+        self.reset_details_scroll();
+        None
+    }
+
+    /// Page action for the next page when infinite scroll is on and the selection is near the
+    /// end of the loaded results (not while filtering, which only narrows what's loaded)
+    pub fn next_page_due(&self) -> Option<Action> {
+        if !self.infinite_scroll
+            || self.searching()
+            || self.tab.page_job.is_some()
+            || self.tab.more_pages_paused
+            || !self.tab.filter_input.value().is_empty()
+            || self.pages_loaded() >= self.total_pages()
+        {
+            return None;
+        }
+        let selected = self.tab.list_state.selected()?;
+        (selected + LOAD_MORE_MARGIN >= self.tab.results.len()).then(|| Action::Page(self.pages_loaded() + 1))
+    }
+
+    /// Open the go-to-page dialog (nothing to jump between with a single page)
+    pub fn open_page_prompt(&mut self) {
+        if self.total_pages() > 1 {
+            let label = format!("{}: ", tr!("page-label", pages = self.total_pages()));
+            let prompt = Modal::prompt(Purpose::GoToPage, tr!("page-title"), label, tr!("page-keys"), 40, "");
+            self.modals.push(prompt.digits_only());
+        } else {
+            self.notify(ToastLevel::Info, tr!("toast-one-page"));
+        }
+    }
+
+    /// Jump to the page typed into the go-to-page dialog; keeps it open if that isn't a page number
+    pub fn confirm_page_prompt(&mut self, text: &str) -> Option<Action> {
+        let page = text.trim().parse::<u32>().ok();
+        match page.filter(|page| (1..=self.total_pages()).contains(page)) {
+            Some(page) => {
+                self.modals.close(Purpose::GoToPage);
+                self.go_to_page(page)
+            }
+            None => {
+                self.notify(ToastLevel::Warning, tr!("toast-enter-page", pages = self.total_pages()));
+                None
+            }
+        }
+    }
+
+    /// Find local clones of the current results: ledger entries first, then the clone directory
+    /// (checking both layouts, and that the clone's origin is the same repository)
+    pub fn refresh_cloned(&mut self) {
+        self.cloned_paths = self
+            .tab
+            .results
+            .iter()
+            .filter_map(|repo| {
+                let url = repo.html_url.as_ref()?.to_string();
+                if let Some(path) = self.clones.find(&url) {
+                    return Some((url, path.to_path_buf()));
+                }
+                [CloneLayout::Name, CloneLayout::OwnerName]
+                    .into_iter()
+                    .map(|layout| Path::new(CLONE_DIR).join(layout.path(repo_owner(repo), &repo.name)))
+                    .find(|path| is_clone_of(path, &url))
+                    .map(|path| (url, path))
+            })
+            .collect();
+        // A handful of small files per clone, read once here rather than on every frame
+        self.build_info = self
+            .cloned_paths
+            .iter()
+            .map(|(url, path)| (url.clone(), build_system::detect(path)))
+            .filter(|(_, info)| !info.systems.is_empty())
+            .collect();
+    }
+
+    /// File and line counts computed for `repo`, for the results-list columns
+    /// The file count of Alt+F wins; Alt+A's language table counts files too
+    pub fn analyzed(&self, repo: &Repository) -> columns::Analyzed {
+        let Some(url) = repo.html_url.as_ref().map(|url| url.as_str()) else {
+            return columns::Analyzed::default();
+        };
+        let stats = match self.analyses.get(url) {
+            Some(Ok(stats)) => Some(analysis::total(stats)),
+            _ => None,
+        };
+        columns::Analyzed {
+            files: self
+                .file_counts
+                .get(url)
+                .and_then(|count| count.as_ref().ok())
+                .map(|stats| stats.total_files)
+                .or(stats.as_ref().map(|total| total.files as u64)),
+            loc: stats.map(|total| total.code as u64),
+        }
+    }
+
+    /// Show results stored in earlier sessions for the listed repos not analyzed in this one
+    fn restore_analyses(&mut self) {
+        for repo in &self.tab.results {
+            let (Some(url), Some(entry)) = (repo.html_url.as_ref(), self.analysis_store.get(repo)) else {
+                continue;
+            };
+            let url = url.to_string();
+            if let Some(stored) = entry.files.as_ref().filter(|_| !self.file_counts.contains_key(&url)) {
+                self.file_counts.insert(url.clone(), Ok(stored.value.clone()));
+                self.figures_revision += 1;
+                self.restored.insert((url.clone(), StoredKind::FileCount), stored.computed_time());
+            }
+            if let Some(stored) = entry.languages.as_ref().filter(|_| !self.analyses.contains_key(&url)) {
+                self.analyses.insert(url.clone(), Ok(stored.value.clone()));
+                self.figures_revision += 1;
+                self.restored.insert((url.clone(), StoredKind::Languages), stored.computed_time());
+            }
+            if let Some(stored) = entry.maintenance.as_ref().filter(|_| !self.maintenance.contains_key(&url)) {
+                self.maintenance.insert(url.clone(), Ok(stored.value.clone()));
+                self.restored.insert((url, StoredKind::Maintenance), stored.computed_time());
+            }
+        }
+    }
+
+    /// Keep a fresh result of the listed repo at `url` for later sessions
+    fn store_result(&mut self, url: &str, change: impl FnOnce(&mut StoredAnalysis)) {
+        // The repo may be listed in a tab other than the one now open
+        let Some(repo) = std::iter::once(&self.tab).chain(&self.tabs).find_map(|tab| tab.repo_with_url(url)) else {
+            return;
+        };
+        self.analysis_store.update(repo, change);
+        if let Err(e) = self.analysis_store.save() {
+            self.notify(ToastLevel::Warning, format!("{:#}", e));
         }
-        self.searching = false;
+    }
+
+    /// Compute the selected repo's restored results again: the first now, the others on the next ticks
+    pub fn refresh_restored(&mut self) -> Option<Action> {
+        let url = self.get_selected_repo()?.html_url.as_ref()?.to_string();
+        let mut kinds: Vec<StoredKind> =
+            self.restored.keys().filter(|(restored, _)| *restored == url).map(|(_, kind)| *kind).collect();
+        if kinds.is_empty() {
+            self.notify(ToastLevel::Info, tr!("toast-nothing-to-refresh"));
+            return None;
+        }
+        kinds.sort();
+        for kind in kinds.into_iter().rev() {
+            self.restored.remove(&(url.clone(), kind));
+            self.refresh_due.push(match kind {
+                StoredKind::FileCount => {
+                    self.file_counts.remove(&url);
+                    self.figures_revision += 1;
+                    Action::FileCount(vec![url.clone()])
+                }
+                StoredKind::Languages => {
+                    self.analyses.remove(&url);
+                    self.figures_revision += 1;
+                    Action::Analyze(vec![url.clone()])
+                }
+                StoredKind::Maintenance => {
+                    self.maintenance.remove(&url);
+                    Action::Maintenance(url.clone())
+                }
+            });
+        }
+        self.refresh_due.pop()
+    }
+
+    /// Commands of the analysis profiles matching the clone at `path`: by the languages of its build
+    /// systems, and by the language GitHub reports for the repo
+    fn profile_steps(&self, url: &str, path: &Path) -> Vec<profiles::Step> {
+        if self.analysis_profiles.is_empty() {
+            return Vec::new();
+        }
+        let mut languages: Vec<&str> = build_system::detect(path).systems.iter().map(|system| system.language()).collect();
+        let repo = std::iter::once(&self.tab).chain(&self.tabs).find_map(|tab| tab.repo_with_url(url));
+        languages.extend(repo.and_then(|repo| repo.language.as_ref()?.as_str()));
+        profiles::select(&self.analysis_profiles, &languages, self.script_timeout_secs)
+    }
+
+    /// Show a toast notification
+    pub fn notify(&mut self, level: ToastLevel, message: impl Into<String>) {
+        self.toasts.push(level, message);
     }
 
     /// Set an error message to display
-    pub fn set_error(&mut self, error: String) {
-        self.error_message = Some(error);
-        self.searching = false;
+    pub fn set_error(&mut self, error: &Error) {
+        self.tab.error_message = Some(error.to_string());
+        self.tab.error_hint = error.guidance();
+    }
+
+    /// Indices into `results` that match the in-results filter (all results when no filter is set)
+    pub fn visible_indices(&self) -> Rc<[usize]> {
+        self.listing().indices
+    }
+
+    /// The active tab's listing, computed again only once its results, filter, or sort, or the
+    /// analyzed figures, have changed since it was last asked for (every frame asks)
+    pub fn listing(&self) -> Listing {
+        let mut cached = self.tab.listing.borrow_mut();
+        if let Some((key, listing)) = cached.as_ref() {
+            let current = key.results == self.tab.results_revision
+                && key.figures == self.figures_revision
+                && key.filter == self.tab.filter_input.value()
+                && key.sort == self.tab.local_sort;
+            if current {
+                return listing.clone();
+            }
+        }
+        let indices = self.filtered_indices();
+        // Whether any listed result has figures decides the columns, so they don't come and go while scrolling
+        let (any_files, any_loc) = indices
+            .iter()
+            .map(|&i| self.analyzed(&self.tab.results[i]))
+            .fold((false, false), |(files, loc), figures| (files || figures.files.is_some(), loc || figures.loc.is_some()));
+        let listing = Listing { indices: indices.into(), any_files, any_loc };
+        let key = ListingKey {
+            results: self.tab.results_revision,
+            figures: self.figures_revision,
+            filter: self.tab.filter_input.value().to_string(),
+            sort: self.tab.local_sort,
+        };
+        *cached = Some((key, listing.clone()));
+        listing
+    }
+
+    fn filtered_indices(&self) -> Vec<usize> {
+        let filter = self.tab.filter_input.value().to_lowercase();
+        let mut indices: Vec<usize> = self.tab.results
+            .iter()
+            .enumerate()
+            .filter(|(_, repo)| {
+                if filter.is_empty() {
+                    return true;
+                }
+                let name = repo.full_name.as_deref().unwrap_or(&repo.name).to_lowercase();
+                let description = repo.description.as_deref().unwrap_or("").to_lowercase();
+                name.contains(&filter) || description.contains(&filter)
+            })
+            .map(|(i, _)| i)
+            .collect();
+        if let Some(sort) = self.tab.local_sort {
+            // Stable, so results with the same figure (or none) keep GitHub's order
+            indices.sort_by_cached_key(|&i| {
+                let figures = self.analyzed(&self.tab.results[i]);
+                Reverse(match sort {
+                    LocalSort::Files => figures.files,
+                    LocalSort::Loc => figures.loc,
+                })
+            });
+        }
+        indices
+    }
+
+    /// Order the list by file count, then lines of code, then GitHub's order again (Alt+Z)
+    pub fn cycle_local_sort(&mut self) {
+        let sort = match self.tab.local_sort {
+            None => Some(LocalSort::Files),
+            Some(LocalSort::Files) => Some(LocalSort::Loc),
+            Some(LocalSort::Loc) => None,
+        };
+        self.keep_selection(|app| app.tab.local_sort = sort);
+        let message = match sort {
+            Some(sort) => tr!("toast-sorted-by", sort = sort.label()),
+            None => tr!("toast-github-order"),
+        };
+        self.notify(ToastLevel::Info, message);
+    }
+
+    /// Make a change that may reorder the list, keeping the same repository selected
+    fn keep_selection(&mut self, change: impl FnOnce(&mut Self)) {
+        let selected = self.get_selected_repo().map(|repo| repo.id.0);
+        change(self);
+        let Some(id) = selected else {
+            return;
+        };
+        if let Some(row) = self.visible_indices().iter().position(|&i| self.tab.results[i].id.0 == id) {
+            self.tab.list_state.select(Some(row));
+        }
     }
 
     /// Navigate to the next repository in the list (wraps around)
     pub fn next(&mut self) {
-        if self.results.is_empty() {
+        let len = self.visible_indices().len();
+        if len == 0 {
             return;
         }
-        let i = match self.list_state.selected() {
+        let i = match self.tab.list_state.selected() {
             Some(i) => {
-                if i >= self.results.len() - 1 {
+                if i >= len - 1 {
                     0
                 } else {
                     i + 1
@@ -89,538 +1055,5537 @@ impl App {
             }
             None => 0,
         };
-        self.list_state.select(Some(i));
+        self.tab.list_state.select(Some(i));
     }
 
     /// Navigate to the previous repository in the list (wraps around)
     pub fn previous(&mut self) {
-        if self.results.is_empty() {
+        let len = self.visible_indices().len();
+        if len == 0 {
             return;
         }
-        let i = match self.list_state.selected() {
+        let i = match self.tab.list_state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.results.len() - 1
+                    len - 1
                 } else {
                     i - 1
                 }
             }
             None => 0,
         };
-        self.list_state.select(Some(i));
+        self.tab.list_state.select(Some(i));
+    }
+
+    /// Jump to the first repository in the list (vim "gg")
+    pub fn select_first(&mut self) {
+        if !self.visible_indices().is_empty() {
+            self.tab.list_state.select(Some(0));
+        }
+    }
+
+    /// Jump to the last repository in the list (vim "G")
+    pub fn select_last(&mut self) {
+        let len = self.visible_indices().len();
+        if len > 0 {
+            self.tab.list_state.select(Some(len - 1));
+        }
+    }
+
+    /// Move the selection down by half a page without wrapping (vim Ctrl+d)
+    pub fn half_page_down(&mut self) {
+        let len = self.visible_indices().len();
+        if len == 0 {
+            return;
+        }
+        let step = (self.results_height as usize / 2).max(1);
+        let i = self.tab.list_state.selected().unwrap_or(0);
+        self.tab.list_state.select(Some((i + step).min(len - 1)));
+    }
+
+    /// Move the selection up by half a page without wrapping (vim Ctrl+u)
+    pub fn half_page_up(&mut self) {
+        if self.visible_indices().is_empty() {
+            return;
+        }
+        let step = (self.results_height as usize / 2).max(1);
+        let i = self.tab.list_state.selected().unwrap_or(0);
+        self.tab.list_state.select(Some(i.saturating_sub(step)));
+    }
+
+    /// Re-select the first match after the in-results filter changes
+    pub fn apply_filter(&mut self) {
+        if self.visible_indices().is_empty() {
+            self.tab.list_state.select(None);
+        } else {
+            self.tab.list_state.select(Some(0));
+        }
+        self.reset_details_scroll();
+    }
+
+    /// Clear the in-results filter and leave filter typing mode
+    pub fn clear_filter(&mut self) {
+        self.tab.filter_input.reset();
+        self.tab.filtering = false;
+        self.apply_filter();
     }
 
     /// Get the currently selected repository
     pub fn get_selected_repo(&self) -> Option<&Repository> {
-        self.list_state.selected().and_then(|i| self.results.get(i))
+        self.tab.list_state
+            .selected()
+            .and_then(|i| self.visible_indices().get(i).copied())
+            .and_then(|i| self.tab.results.get(i))
     }
 
-    /// Scroll details panel down
-    pub fn scroll_details_down(&mut self) {
-        self.details_scroll = self.details_scroll.saturating_add(1);
+    /// All active search filters: CLI qualifiers plus the TUI size filter
+    pub fn active_filters(&self) -> Vec<String> {
+        let mut filters = self.cli_filters.clone();
+        if let Some(size) = &self.tab.repo_size_filter {
+            filters.push(format!("size:{}", size));
+        }
+        filters
     }
 
-    /// Scroll details panel up
-    pub fn scroll_details_up(&mut self) {
-        self.details_scroll = self.details_scroll.saturating_sub(1);
+    /// Whether the active tab's search is running
+    pub fn searching(&self) -> bool {
+        self.tab.search_job.is_some()
     }
 
-    /// Reset details scroll when changing repos
-    pub fn reset_details_scroll(&mut self) {
-        self.details_scroll = 0;
+    /// Whether clones from the clones view are being updated
+    pub fn updating(&self) -> bool {
+        self.jobs.list.active(JobKind::Update).is_some()
     }
-}
 
-/// Main TUI event loop
-/// Returns None if user wants to quit, or Some(action) for:
-/// - Search queries (plain text)
-/// - File count requests ("FILECOUNT:url")
-/// - Clone requests ("CLONE:url")
-/// - Browser open requests (http URLs)
-pub fn run_tui<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<Option<String>> {
-    loop {
-        // Render the UI
-        terminal.draw(|f| ui(f, app))?;
+    /// Taskbar progress for the terminal: the clones in flight on average (queued ones at 0%),
+    /// or else the running update
+    pub fn terminal_progress(&self) -> osc::Progress {
+        if !self.pending_clones.is_empty() {
+            let done: usize = self
+                .pending_clones
+                .values()
+                .map(|status| match status {
+                    CloneStatus::Running(_, Some(progress)) => progress.percent as usize,
+                    _ => 0,
+                })
+                .sum();
+            return osc::Progress::Value((done / self.pending_clones.len()) as u8);
+        }
+        match &self.clone_progress {
+            _ if !self.updating() => osc::Progress::None,
+            Some((_, progress)) => osc::Progress::Value(progress.percent),
+            None => osc::Progress::Indeterminate,
+        }
+    }
+
+    /// Set the terminal's title to the active tab's query and its taskbar progress to the clones'
+    fn sync_terminal_status(&mut self, out: &mut impl io::Write) -> io::Result<()> {
+        let (title, progress) = (osc::title(&self.tab.last_query), self.terminal_progress());
+        self.terminal_status.update(out, title, progress)
+    }
+
+    /// Get ready to run a search for `query` in the active tab; a search still running there is
+    /// dropped, since its results would be replaced anyway
+    pub fn begin_search(&mut self, query: &str) {
+        if let Some(job) = self.tab.search_job.take() {
+            self.jobs.list.cancel(job);
+        }
+        self.tab.error_message = None;
+        self.tab.resume_search = None;
+        self.tab.last_query = query.to_string();
+        self.tab.collection = None;
+    }
+
+    /// Cancel the running search, or else the running update, or else every clone, file count,
+    /// and analysis still queued or running (Esc or Ctrl+X); false if none of them runs
+    /// Cancelling aborts a clone's task, which stops its transfer and removes the partial clone
+    fn cancel_running(&mut self) -> bool {
+        let running = self.tab.search_job.or_else(|| self.jobs.list.active(JobKind::Update).map(|job| job.id));
+        if let Some(id) = running {
+            self.cancel_job(id);
+            return true;
+        }
+        let work: Vec<JobId> = self
+            .jobs
+            .list
+            .records
+            .iter()
+            .filter(|job| matches!(job.kind, JobKind::Clone | JobKind::FileCount | JobKind::Analysis) && job.state.is_active())
+            .map(|job| job.id)
+            .collect();
+        for &id in &work {
+            self.cancel_job(id);
+        }
+        !work.is_empty()
+    }
+
+    /// Labels for operations currently in progress
+    pub fn running_jobs(&self) -> Vec<&'static str> {
+        let mut jobs = Vec::new();
+        if self.searching() {
+            jobs.push("searching");
+        }
+        if self.tab.page_job.is_some() {
+            jobs.push("loading results");
+        }
+        if self.updating() || !self.pending_clones.is_empty() {
+            jobs.push("cloning");
+        }
+        if !self.pending_counts.is_empty() {
+            jobs.push("counting files");
+        }
+        if !self.pending_analyses.is_empty() {
+            jobs.push("analyzing");
+        }
+        if !self.pending_activity.is_empty() {
+            jobs.push("loading activity");
+        }
+        jobs
+    }
+
+    /// Handle one message: the single place the TUI's state changes in response to input, the
+    /// clock, or background tasks
+    pub fn update(&mut self, message: AppMessage) -> Flow {
+        match message {
+            AppMessage::Key(key) => handle_key(self, key),
+            AppMessage::Tick => self.tick(),
+            AppMessage::Job(event) => {
+                self.apply_job_event(event);
+                Flow::Continue
+            }
+        }
+    }
+
+    /// Timers checked every frame
+    fn tick(&mut self) -> Flow {
+        // Fire a pending live search once typing has paused
+        if let Some(query) = self.live_search_due(Instant::now()) {
+            return Flow::Run(Action::Search(query));
+        }
+        // Re-run a rate-limited search as soon as the quota resets
+        if let Some(query) = self.resume_due(unix_now()) {
+            return Flow::Run(Action::Search(query));
+        }
+        // Audit fresh clones one at a time as they finish
+        if let Some((url, path)) = self.audits_due.pop() {
+            return Flow::Run(Action::Audit(url, path));
+        }
+        if let Some(action) = self.refresh_due.pop() {
+            return Flow::Run(action);
+        }
+        if let Some((url, path, steps)) = self.profiles_due.pop() {
+            return Flow::Run(Action::Profiles(url, path, steps));
+        }
+        if let Some(action) = self.details_due() {
+            return Flow::Run(action);
+        }
+        // Keep the list going: fetch the next page as the selection nears the end
+        self.next_page_due().map_or(Flow::Continue, Flow::Run)
+    }
+
+    /// Results delivered by background tasks since the last frame, as messages
+    pub fn job_messages(&mut self) -> Vec<AppMessage> {
+        std::iter::from_fn(|| self.jobs.next_event()).map(AppMessage::Job).collect()
+    }
+
+    fn apply_job_event(&mut self, event: JobEvent) {
+        // Ignore late events from cancelled jobs
+        if !self.jobs.list.get(event.id()).is_some_and(|job| job.state.is_active()) {
+            return;
+        }
 
-        // Wait for keyboard input
-        if let Event::Key(key) = event::read()? {
-            // Ctrl+C to quit
-            if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
-                return Ok(None);
-            }
-
-            match key.code {
-                KeyCode::Esc => return Ok(None),
-                KeyCode::Down => {
-                    app.next();
-                    app.reset_details_scroll(); // Reset scroll when changing repos
-                }
-                KeyCode::Up => {
-                    app.previous();
-                    app.reset_details_scroll(); // Reset scroll when changing repos
-                }
-                KeyCode::Left => app.scroll_details_up(),    // Scroll details up
-                KeyCode::Right => app.scroll_details_down(),  // Scroll details down
-                KeyCode::Enter => {
-                    // Otherwise, submit search query if input is not empty
-                    if !app.input.value().is_empty() {
-                        return Ok(Some(app.input.value().to_string()));
+        match event {
+            JobEvent::Started { id } => {
+                self.jobs.list.start(id);
+                let Some(job) = self.jobs.list.get(id) else {
+                    return;
+                };
+                let url = job.target.clone();
+                match job.kind {
+                    JobKind::FileCount => {
+                        self.pending_counts.insert(url, CountStatus::Running(Instant::now()));
+                    }
+                    JobKind::Analysis => {
+                        self.pending_analyses.insert(url, CountStatus::Running(Instant::now()));
+                    }
+                    JobKind::Clone => {
+                        self.pending_clones.insert(url, CloneStatus::Running(Instant::now(), None));
                     }
+                    _ => {}
                 }
-                KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::ALT) => {
-                    // If we have results and something is selected, open it
-                    if !app.results.is_empty() && app.list_state.selected().is_some() {
-                        if let Some(repo) = app.get_selected_repo() {
-                            if let Some(url) = &repo.html_url {
-                                return Ok(Some(url.to_string()));
-                            }
-                        }
+            }
+            JobEvent::SearchFinished { id, query, result, rate_limit } => {
+                self.in_tab_of(id, |app| app.search_finished(id, query, result, rate_limit));
+            }
+            JobEvent::FileCountFinished { id, url, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                self.pending_counts.remove(&url);
+                self.restored.remove(&(url.clone(), StoredKind::FileCount));
+                match &result {
+                    Ok(stats) => {
+                        let stored = Stored { computed_at: unix_now(), value: stats.clone() };
+                        self.store_result(&url, |entry| entry.files = Some(stored));
                     }
+                    Err(e) => self.notify(ToastLevel::Error, tr!("toast-count-failed", url = url.as_str(), error = e.as_str())),
                 }
-                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::ALT) => {
-                    // Alt+C to clear search input
-                    app.input.reset();
+                self.keep_selection(|app| {
+                    app.file_counts.insert(url, result);
+                    app.figures_revision += 1;
+                });
+            }
+            JobEvent::AnalysisFinished { id, url, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                self.pending_analyses.remove(&url);
+                self.restored.remove(&(url.clone(), StoredKind::Languages));
+                match &result {
+                    Ok(stats) => {
+                        let stored = Stored { computed_at: unix_now(), value: stats.clone() };
+                        self.store_result(&url, |entry| entry.languages = Some(stored));
+                    }
+                    Err(e) => self.notify(ToastLevel::Error, tr!("toast-analysis-failed", url = url.as_str(), error = e.as_str())),
+                }
+                self.keep_selection(|app| {
+                    app.analyses.insert(url, result);
+                    app.figures_revision += 1;
+                });
+            }
+            JobEvent::CloneProgress { url, progress, .. } => {
+                if let Some(CloneStatus::Running(_, latest)) = self.pending_clones.get_mut(&url) {
+                    *latest = Some(progress.clone());
                 }
-                KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::ALT) => {
-                    // Trigger file count for selected repo
-                    if !app.results.is_empty() && app.list_state.selected().is_some() {
-                        if let Some(repo) = app.get_selected_repo() {
-                            if let Some(url) = &repo.html_url {
-                                return Ok(Some(format!("FILECOUNT:{}", url)));
+                self.clone_progress = Some((url, progress));
+            }
+            JobEvent::CloneFinished { id, url, path, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                self.pending_clones.remove(&url);
+                if self.clone_progress.as_ref().is_some_and(|(progress_url, _)| *progress_url == url) {
+                    self.clone_progress = None;
+                }
+                match result {
+                    Ok(outcome) => {
+                        // Remember where the repo went so results can show it as cloned
+                        self.clones.record(&url, Path::new(&path));
+                        if let Err(e) = self.clones.save() {
+                            self.notify(ToastLevel::Warning, format!("{:#}", e));
+                        }
+                        self.refresh_cloned();
+                        if self.audit_after_clone && audit::has_lockfile(Path::new(&path)) {
+                            self.audits_due.push((url.clone(), PathBuf::from(&path)));
+                        }
+                        let steps = self.profile_steps(&url, Path::new(&path));
+                        if !steps.is_empty() {
+                            self.profiles_due.push((url.clone(), PathBuf::from(&path), steps));
+                        }
+                        match outcome {
+                            Some(outcome) => self.notify(ToastLevel::Success, tr!("toast-updated", path = path.as_str(), outcome = outcome.to_string())),
+                            None if self.pane_command.is_some() => {
+                                self.notify(ToastLevel::Success, tr!("toast-cloned-pane", path = path.as_str()))
                             }
+                            None => self.notify(ToastLevel::Success, tr!("toast-cloned", path = path.as_str())),
                         }
                     }
+                    Err(e) => self.notify(ToastLevel::Error, tr!("toast-clone-failed", url = url.as_str(), error = e.as_str())),
+                }
+            }
+            JobEvent::UpdateFinished { id, updated, failed } => {
+                self.jobs.list.finish(id, (!failed.is_empty()).then(|| failed.join("; ")));
+                self.clone_progress = None;
+                self.refresh_local_clones();
+                match (updated.as_slice(), failed.first()) {
+                    ([(path, outcome)], None) => self.notify(ToastLevel::Success, tr!("toast-updated", path = path.as_str(), outcome = outcome.to_string())),
+                    (_, None) => self.notify(ToastLevel::Success, tr!("toast-updated-many", count = updated.len())),
+                    (_, Some(_)) => self.notify(
+                        ToastLevel::Error,
+                        tr!("toast-update-failed", failed = failed.len(), total = updated.len() + failed.len(), errors = failed.join("; ")),
+                    ),
+                }
+            }
+            JobEvent::PageLoaded { id, page, done, result } => {
+                self.in_tab_of(id, |app| app.page_loaded(id, page, done, result));
+            }
+            JobEvent::ActivityLoaded { id, url, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                self.pending_activity.remove(&url);
+                let for_details = self.fetched_for_details.remove(&url);
+                match &result {
+                    // The details panel says so; a toast for every result scrolled past would be noise
+                    Err(e) if for_details => {
+                        tracing::info!(url, error = %e, "pausing details fetches");
+                        self.details_paused = true;
+                    }
+                    Err(e) => self.notify(ToastLevel::Error, tr!("toast-activity-failed", url = url.as_str(), error = e.as_str())),
+                    Ok(_) => {}
+                }
+                self.activity.insert(url, result);
+            }
+            JobEvent::ActivityDeferred { id, url } => {
+                // Not a failure: the figures are fetched as usual once the result is selected
+                self.jobs.list.finish(id, None);
+                self.pending_activity.remove(&url);
+                self.fetched_for_details.remove(&url);
+                self.deferred_prefetches.insert(url);
+            }
+            JobEvent::StatusLoaded { id, user, rate_limit } => {
+                self.jobs.list.finish(id, None);
+                if user.is_some() {
+                    self.auth_user = user;
                 }
-                KeyCode::Char('1') => {
-                    // Set size filter to small
-                    app.set_size_filter(Some("small".to_string()));
+                // A search that finished first brought a fresher quota
+                self.rate_limit = self.rate_limit.or(rate_limit);
+            }
+            JobEvent::StarHistoryLoaded { id, url, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                self.pending_star_history.remove(&url);
+                self.star_history.insert(url, result);
+            }
+            JobEvent::SbomWritten { id, clone, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                match result {
+                    Ok((path, components)) => self.notify(
+                        ToastLevel::Success,
+                        tr!("toast-sbom-written", clone = clone.as_str(), components = components, path = path.display().to_string()),
+                    ),
+                    Err(e) => self.notify(ToastLevel::Error, tr!("toast-sbom-failed", clone = clone.as_str(), error = e)),
                 }
-                KeyCode::Char('2') => {
-                    // Set size filter to medium
-                    app.set_size_filter(Some("medium".to_string()));
+            }
+            JobEvent::AuditFinished { id, url, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                self.pending_audits.remove(&url);
+                let name = url.trim_start_matches("https://github.com/");
+                match &result {
+                    Ok(findings) if findings.is_empty() => {
+                        self.notify(ToastLevel::Success, tr!("toast-no-advisories", name = name))
+                    }
+                    Ok(findings) => self.notify(
+                        ToastLevel::Warning,
+                        tr!("toast-advisories", name = name, advisories = audit::label(findings)),
+                    ),
+                    Err(e) => self.notify(ToastLevel::Error, tr!("toast-audit-failed", name = name, error = e.as_str())),
                 }
-                KeyCode::Char('3') => {
-                    // Set size filter to large
-                    app.set_size_filter(Some("large".to_string()));
+                self.audits.insert(url, result);
+            }
+            JobEvent::ProfilesFinished { id, url, sections } => {
+                let failed = sections.iter().filter(|section| section.result.is_err()).count();
+                self.jobs.list.finish(id, (failed > 0).then(|| tr!("job-commands-failed", failed = failed, total = sections.len())));
+                self.pending_profiles.remove(&url);
+                self.profile_sections.insert(url, sections);
+            }
+            JobEvent::MaintenanceLoaded { id, url, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                self.pending_maintenance.remove(&url);
+                self.restored.remove(&(url.clone(), StoredKind::Maintenance));
+                if let Ok(metrics) = &result {
+                    let stored = Stored { computed_at: unix_now(), value: metrics.clone() };
+                    self.store_result(&url, |entry| entry.maintenance = Some(stored));
                 }
-                KeyCode::Char('0') => {
-                    // Clear size filter
-                    app.set_size_filter(None);
+                self.maintenance.insert(url, result);
+            }
+            JobEvent::ListLoaded { id, list, result } => {
+                self.jobs.list.finish(id, result.as_ref().err().cloned());
+                match result {
+                    Ok(repos) if repos.is_empty() => {
+                        self.notify(ToastLevel::Warning, tr!("toast-list-empty", list = list.as_str()))
+                    }
+                    Ok(repos) => self.open_collection(list, repos),
+                    Err(e) => self.notify(ToastLevel::Error, tr!("toast-list-failed", list = list.as_str(), error = e)),
                 }
-                KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::ALT) => {
-                    // Alt+G to clone selected repo
-                    if !app.results.is_empty() && app.list_state.selected().is_some() {
-                        if let Some(repo) = app.get_selected_repo() {
-                            if let Some(url) = &repo.html_url {
-                                return Ok(Some(format!("CLONE:{}", url)));
-                            }
-                        }
+            }
+        }
+    }
+
+    /// The first page of a search arrived, in the tab the search runs in
+    fn search_finished(&mut self, id: JobId, query: String, result: Result<SearchResults, Error>, rate_limit: Option<RateLimitInfo>) {
+        self.jobs.list.finish(id, result.as_ref().err().map(ToString::to_string));
+        if self.tab.search_job == Some(id) {
+            self.tab.search_job = None;
+        }
+        match result {
+            Ok((results, total, text_matches, cached, changes)) => {
+                self.set_results(results, total);
+                self.tab.text_matches = text_matches;
+                self.tab.cached_at = cached;
+                if let Some(cache::Hit::Offline(fetched)) = cached {
+                    let age = clones::format_age(fetched, SystemTime::now());
+                    self.notify(ToastLevel::Warning, tr!("toast-offline", age = age));
+                }
+                // Results gone since the last run aren't in the list, so they're named here
+                if let Some(removed) = changes.as_ref().map(|changes| &changes.removed).filter(|removed| !removed.is_empty()) {
+                    let names = removed.iter().take(MAX_NAMED_GONE).cloned().collect::<Vec<_>>().join(", ");
+                    let more = removed.len().saturating_sub(MAX_NAMED_GONE);
+                    self.notify(ToastLevel::Info, tr!("toast-gone", names = names, more = more));
+                }
+                self.tab.changes = changes.map(|changes| *changes);
+            }
+            Err(e) => {
+                self.set_error(&e);
+                self.schedule_resume(&query, &e, unix_now());
+            }
+        }
+        self.rate_limit = rate_limit.or(self.rate_limit);
+        if let Some(warning) = self.rate_limit.and_then(RateLimitInfo::warning) {
+            self.notify(ToastLevel::Warning, warning);
+        }
+    }
+
+    /// A further page arrived, in the tab that fetches it
+    fn page_loaded(&mut self, id: JobId, page: u32, done: bool, result: Result<(Vec<Repository>, u64, TextMatches), String>) {
+        let error = result.as_ref().err().cloned();
+        match result {
+            // Pages arrive in order; anything else is from a superseded fetch
+            Ok((items, total, text_matches)) if page == self.pages_loaded() + 1 => {
+                self.append_results(items, total);
+                self.tab.text_matches.extend(text_matches);
+                if self.tab.page_target == Some(page) {
+                    self.tab.page_target = None;
+                    self.go_to_page(page);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.tab.more_pages_paused = true;
+                self.tab.page_target = None;
+                self.notify(ToastLevel::Error, tr!("toast-page-failed", page = page, error = e));
+            }
+        }
+        if done {
+            self.jobs.list.finish(id, error);
+            self.tab.page_job = None;
+        }
+    }
+
+    /// Run `apply` with the tab whose search or page fetch is `job` as the active one, so what
+    /// a background tab's search finds lands in that tab; app-wide state that follows the
+    /// active tab's list is kept
+    fn in_tab_of(&mut self, job: JobId, apply: impl FnOnce(&mut Self)) {
+        let owns = |tab: &SearchTab| tab.search_job == Some(job) || tab.page_job.is_some_and(|(id, _)| id == job);
+        let Some(index) = self.tabs.iter().position(owns) else {
+            return apply(self);
+        };
+        std::mem::swap(&mut self.tab, &mut self.tabs[index]);
+        let details_paused = self.details_paused;
+        let deferred = std::mem::take(&mut self.deferred_prefetches);
+        apply(self);
+        std::mem::swap(&mut self.tab, &mut self.tabs[index]);
+        self.details_paused = details_paused;
+        self.deferred_prefetches = deferred;
+        self.refresh_cloned();
+    }
+
+    /// Cancel a queued or running job from the jobs panel
+    pub fn cancel_job(&mut self, id: JobId) {
+        if let Some((kind, target)) = self.jobs.list.cancel(id) {
+            match kind {
+                JobKind::FileCount => {
+                    self.pending_counts.remove(&target);
+                }
+                JobKind::Clone => {
+                    self.pending_clones.remove(&target);
+                }
+                JobKind::Analysis => {
+                    self.pending_analyses.remove(&target);
+                }
+                JobKind::Activity => {
+                    self.pending_activity.remove(&target);
+                    // A cancelled details fetch isn't retried until the next search
+                    if self.fetched_for_details.remove(&target) {
+                        self.details_paused = true;
+                    }
+                }
+                JobKind::Stars => {
+                    self.pending_star_history.remove(&target);
+                }
+                JobKind::Maintenance => {
+                    self.pending_maintenance.remove(&target);
+                }
+                JobKind::Audit => {
+                    self.pending_audits.remove(&target);
+                }
+                JobKind::Profiles => {
+                    self.pending_profiles.remove(&target);
+                }
+                JobKind::Search => self.in_tab_of(id, |app| {
+                    if app.tab.search_job == Some(id) {
+                        app.tab.search_job = None;
+                    } else if app.tab.page_job.is_some_and(|(job, _)| job == id) {
+                        app.tab.page_job = None;
+                        app.tab.page_target = None;
+                        // Cancelling means "stop loading", so infinite scroll doesn't start it again
+                        app.tab.more_pages_paused = true;
                     }
+                }),
+                JobKind::Update => self.clone_progress = None,
+                _ => {}
+            }
+            self.notify(ToastLevel::Warning, tr!("toast-cancelled", job = kind.label().to_lowercase(), target = target));
+        }
+    }
+
+    /// Open the "my clones" overlay, rescanning the clone directory
+    pub fn open_clones_view(&mut self) {
+        self.refresh_local_clones();
+        self.show_clones = true;
+    }
+
+    /// Rescan local clones, keeping the selection in range
+    pub fn refresh_local_clones(&mut self) {
+        self.local_clones = clones::local_clones(&self.clones, Path::new(CLONE_DIR));
+        if self.clones_by_size {
+            clones::sort_by_size(&mut self.local_clones);
+        }
+        let paths: HashSet<&PathBuf> = self.local_clones.iter().map(|clone| &clone.path).collect();
+        self.clones_marked.retain(|path| paths.contains(path));
+        let selected = self.clones_state.selected().unwrap_or(0);
+        self.clones_state
+            .select((!self.local_clones.is_empty()).then(|| selected.min(self.local_clones.len() - 1)));
+    }
+
+    /// Move the clones overlay selection by `delta` rows (clamped)
+    pub fn move_clone_selection(&mut self, delta: isize) {
+        let len = self.local_clones.len();
+        if len == 0 {
+            return;
+        }
+        let row = self.clones_state.selected().unwrap_or(0) as isize + delta;
+        self.clones_state.select(Some(row.clamp(0, len as isize - 1) as usize));
+    }
+
+    pub fn selected_clone(&self) -> Option<&LocalClone> {
+        self.local_clones.get(self.clones_state.selected()?)
+    }
+
+    /// Toggle between name and size ordering, keeping the same clone selected
+    pub fn toggle_clones_sort(&mut self) {
+        let selected = self.selected_clone().map(|clone| clone.path.clone());
+        self.clones_by_size = !self.clones_by_size;
+        self.refresh_local_clones();
+        if let Some(row) = selected.and_then(|path| self.local_clones.iter().position(|clone| clone.path == path)) {
+            self.clones_state.select(Some(row));
+        }
+    }
+
+    /// Mark/unmark the selected clone for deletion
+    pub fn toggle_clone_mark(&mut self) {
+        if let Some(path) = self.selected_clone().map(|clone| clone.path.clone()) {
+            if !self.clones_marked.remove(&path) {
+                self.clones_marked.insert(path);
+            }
+        }
+    }
+
+    /// Clones a delete would remove: the marked ones, or else the selected one
+    pub fn clones_to_delete(&self) -> Vec<&LocalClone> {
+        if self.clones_marked.is_empty() {
+            self.selected_clone().into_iter().collect()
+        } else {
+            self.local_clones.iter().filter(|clone| self.clones_marked.contains(&clone.path)).collect()
+        }
+    }
+
+    /// Ask before deleting the marked (or selected) clones
+    pub fn ask_delete_clones(&mut self) {
+        let targets = self.clones_to_delete();
+        let size: u64 = targets.iter().map(|clone| clone.size).sum();
+        let what = match targets.as_slice() {
+            [clone] => clone.path.display().to_string(),
+            _ => clone_count(targets.len()),
+        };
+        let message = tr!("delete-message", what = what, size = clones::format_size(size));
+        self.modals.push(Modal::Confirm { purpose: Purpose::DeleteClones, title: tr!("delete-title"), message });
+    }
+
+    /// Delete the marked (or selected) clones from disk and forget them
+    /// Returns the number deleted and bytes freed; failures are collected into the error
+    pub fn delete_clones(&mut self) -> Result<(usize, u64)> {
+        let targets: Vec<(PathBuf, u64)> =
+            self.clones_to_delete().iter().map(|clone| (clone.path.clone(), clone.size)).collect();
+        let mut deleted = 0;
+        let mut freed = 0;
+        let mut failed = Vec::new();
+        for (path, size) in targets {
+            match std::fs::remove_dir_all(&path) {
+                Ok(()) => {
+                    self.clones.remove_path(&path);
+                    deleted += 1;
+                    freed += size;
+                }
+                Err(e) => failed.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+        self.clones_marked.clear();
+        let saved = self.clones.save();
+        self.refresh_local_clones();
+        self.refresh_cloned();
+        saved?;
+        if !failed.is_empty() {
+            anyhow::bail!("Deleted {}, failed: {}", deleted, failed.join("; "));
+        }
+        Ok((deleted, freed))
+    }
+
+    /// Move the jobs panel selection by `delta` rows (clamped)
+    pub fn move_job_selection(&mut self, delta: isize) {
+        let len = self.jobs.list.records.len();
+        if len == 0 {
+            return;
+        }
+        // The panel lists newest jobs first, so row 0 is the last record
+        let row = self.jobs_state.selected().unwrap_or(0) as isize + delta;
+        self.jobs_state.select(Some(row.clamp(0, len as isize - 1) as usize));
+    }
+
+    /// Move the cheatsheet selection by `delta` rows, stopping at either end
+    pub fn move_cheatsheet_selection(&mut self, delta: isize) {
+        let row = self.cheatsheet_state.selected().unwrap_or(0) as isize + delta;
+        self.cheatsheet_state.select(Some(row.clamp(0, qualifiers::QUALIFIERS.len() as isize - 1) as usize));
+    }
+
+    /// Add the selected qualifier's example to the end of the search box and go back to typing
+    pub fn insert_cheatsheet_example(&mut self) {
+        let Some(qualifier) = self.cheatsheet_state.selected().and_then(|row| qualifiers::QUALIFIERS.get(row)) else {
+            return;
+        };
+        let query = match self.tab.input.value().trim_end() {
+            "" => qualifier.example.to_string(),
+            query => format!("{} {}", query, qualifier.example),
+        };
+        self.tab.input = Input::new(query);
+        self.show_cheatsheet = false;
+        self.focus = Focus::Typing;
+    }
+
+    /// Job shown at the selected jobs panel row
+    pub fn selected_job_id(&self) -> Option<JobId> {
+        let row = self.jobs_state.selected()?;
+        self.jobs.list.records.iter().rev().nth(row).map(|job| job.id)
+    }
+
+    /// Animated spinner frame plus elapsed time for the running operation, e.g. "⠹ 2.4s"
+    pub fn progress_label(&self) -> String {
+        self.progress_label_at(Instant::now())
+    }
+
+    fn progress_label_at(&self, now: Instant) -> String {
+        let search = self.tab.search_job.and_then(|id| self.jobs.list.get(id));
+        match search.or_else(|| self.jobs.list.active(JobKind::Update)).and_then(|job| job.started) {
+            Some(start) => spinner_label(start, now),
+            None => String::new(),
+        }
+    }
+
+    /// Record a search-input edit so live search can debounce it
+    /// A live search in progress is dropped silently: the edit supersedes it
+    pub fn input_edited(&mut self) {
+        if self.live_search {
+            self.last_edit = Some(Instant::now());
+            if let Some(job) = self.tab.search_job.take() {
+                self.jobs.list.cancel(job);
+            }
+        }
+    }
+
+    /// Query to run once typing has paused for the debounce delay
+    /// Rapid edits coalesce into a single search for the latest text
+    pub fn live_search_due(&mut self, now: Instant) -> Option<String> {
+        let edited = self.last_edit?;
+        if now.duration_since(edited) < self.live_search_delay {
+            return None;
+        }
+        self.last_edit = None;
+
+        let query = self.tab.input.value().trim();
+        if query.is_empty() || query == self.tab.last_query {
+            return None;
+        }
+        Some(query.to_string())
+    }
+
+    /// Re-run `query` when the search quota behind a rate-limit `error` resets (in a minute if GitHub
+    /// didn't say when)
+    pub fn schedule_resume(&mut self, query: &str, error: &Error, now: u64) {
+        if let Error::RateLimited { reset } = error {
+            self.tab.resume_search = Some((query.to_string(), reset.unwrap_or(now + 60)));
+        }
+    }
+
+    /// Seconds until the pending rate-limited query runs again
+    pub fn resume_in(&self, now: u64) -> Option<u64> {
+        // One second past the reset, so GitHub's clock has rolled over too
+        self.tab.resume_search.as_ref().map(|(_, reset)| (reset + 1).saturating_sub(now))
+    }
+
+    /// The rate-limited query, once the quota has reset
+    pub fn resume_due(&mut self, now: u64) -> Option<String> {
+        if self.resume_in(now)? > 0 {
+            return None;
+        }
+        let (query, _) = self.tab.resume_search.take()?;
+        self.notify(ToastLevel::Info, tr!("toast-resuming", query = query.as_str()));
+        Some(query)
+    }
+
+    /// Call off the pending resume; false when there was none
+    pub fn cancel_resume(&mut self) -> bool {
+        if self.tab.resume_search.take().is_none() {
+            return false;
+        }
+        self.notify(ToastLevel::Info, tr!("toast-retry-cancelled"));
+        true
+    }
+
+    /// Toggle the batch-selection marker on the selected repository
+    pub fn toggle_mark(&mut self) {
+        if let Some(id) = self.get_selected_repo().map(|repo| repo.id.0) {
+            if !self.tab.marked.remove(&id) {
+                self.tab.marked.insert(id);
+            }
+        }
+    }
+
+    /// Marked repositories, in result order
+    pub fn marked_repos(&self) -> Vec<&Repository> {
+        self.tab.results
+            .iter()
+            .filter(|repo| self.tab.marked.contains(&repo.id.0))
+            .collect()
+    }
+
+    /// Repositories an action applies to: all marked repos, or the selected one if none are marked
+    pub fn action_targets(&self) -> Vec<&Repository> {
+        if self.tab.marked.is_empty() {
+            self.get_selected_repo().into_iter().collect()
+        } else {
+            self.marked_repos()
+        }
+    }
+
+    /// URLs of the action targets (for the Clone/FileCount/Analyze commands)
+    pub fn action_urls(&self) -> Option<Vec<String>> {
+        let urls: Vec<String> = self
+            .action_targets()
+            .iter()
+            .filter_map(|repo| repo.html_url.as_ref().map(|u| u.to_string()))
+            .collect();
+        (!urls.is_empty()).then_some(urls)
+    }
+
+    /// Open the clone prompt for the action targets, prefilled with the default destination, depth, and mode
+    /// Without a configured depth, clones fetch full history and file counts/analyses fetch 1 commit
+    pub fn open_clone_prompt(&mut self, purpose: ClonePurpose) {
+        let Some(urls) = self.action_urls() else {
+            return;
+        };
+        // One repo gets its own path (its existing clone, if any); several share a parent directory
+        let mut existing = None;
+        let destination = match self.action_targets().as_slice() {
+            [repo] if repo.html_url.as_ref().is_some_and(|url| self.cloned_paths.contains_key(url.as_str())) => {
+                let url = repo.html_url.as_ref().map(|url| url.to_string()).unwrap_or_default();
+                let path = self.cloned_paths[&url].display().to_string();
+                existing = Some(path.clone());
+                path
+            }
+            [repo] => {
+                let path = self.clone_layout.path(repo_owner(repo), &repo.name);
+                format!("{}/{}", CLONE_DIR, path.display())
+            }
+            _ => CLONE_DIR.to_string(),
+        };
+        // GitHub reports repository size in KB
+        let size_kb: u64 = self.action_targets().iter().filter_map(|repo| repo.size).map(u64::from).sum();
+        let needs_size_confirm = purpose == ClonePurpose::Clone
+            && self.clone_confirm_size_mb > 0
+            && size_kb > self.clone_confirm_size_mb * 1024;
+        let depth = match (self.clone_depth, purpose) {
+            (Some(depth), _) => Some(depth).filter(|&d| d > 0),
+            (None, ClonePurpose::Clone) => None,
+            (None, ClonePurpose::FileCount | ClonePurpose::Analyze) => Some(1),
+        };
+        self.clone_prompt = Some(ClonePrompt {
+            purpose,
+            urls,
+            destination: Input::new(destination),
+            existing,
+            depth: Input::new(depth.map_or("full".to_string(), |d| d.to_string())),
+            mode: self.clone_mode,
+            submodules: self.clone_submodules,
+            focus: match purpose {
+                ClonePurpose::Clone => PromptField::Destination,
+                ClonePurpose::FileCount | ClonePurpose::Analyze => PromptField::Depth,
+            },
+            error: None,
+            size_kb,
+            needs_size_confirm,
+        });
+    }
+
+    /// Validate the clone prompt and turn it into a Clone/FileCount/Analyze action
+    /// Keeps the prompt open with an error if the destination or depth is invalid,
+    /// or with a warning the first time Enter is pressed on a clone over the size threshold
+    pub fn confirm_clone_prompt(&mut self) -> Option<Action> {
+        let prompt = self.clone_prompt.as_mut()?;
+        let destination = prompt.destination.value().trim();
+        if prompt.purpose == ClonePurpose::Clone && destination.is_empty() {
+            prompt.error = Some(tr!("prompt-destination-empty"));
+            prompt.focus = PromptField::Destination;
+            return None;
+        }
+        let Some(depth) = parse_depth(prompt.depth.value()) else {
+            prompt.error = Some(tr!("prompt-depth-invalid"));
+            prompt.focus = PromptField::Depth;
+            return None;
+        };
+        // Large clones (e.g. on a metered connection) need Enter twice; updating an existing clone doesn't
+        if prompt.needs_size_confirm && !prompt.updates_existing() {
+            prompt.needs_size_confirm = false;
+            prompt.error = Some(tr!(
+                "prompt-confirm-size",
+                size = clones::format_size(prompt.size_kb * 1024),
+                limit = self.clone_confirm_size_mb
+            ));
+            return None;
+        }
+        let prompt = self.clone_prompt.take()?;
+        if prompt.purpose == ClonePurpose::Clone {
+            self.clone_destination = Some(expand_home(prompt.destination.value().trim()));
+        }
+        self.clone_options = CloneOptions {
+            depth,
+            mode: prompt.mode,
+            submodules: prompt.submodules,
+        };
+        Some(match prompt.purpose {
+            ClonePurpose::Clone => Action::Clone(prompt.urls),
+            ClonePurpose::FileCount => Action::FileCount(prompt.urls),
+            ClonePurpose::Analyze => Action::Analyze(prompt.urls),
+        })
+    }
+
+    /// Carry out a shortcut, returning the action for main's loop if it needs one (copy, compare, ...)
+    pub fn run_shortcut(&mut self, shortcut: Shortcut) -> Option<Action> {
+        match shortcut {
+            Shortcut::Open => self.pending_open = self.get_selected_repo().is_some(),
+            Shortcut::CopyUrl => {
+                return self.get_selected_repo()?.html_url.as_ref().map(|url| Action::Copy(url.to_string()));
+            }
+            Shortcut::Clone => self.open_clone_prompt(ClonePurpose::Clone),
+            Shortcut::FileCount => self.open_clone_prompt(ClonePurpose::FileCount),
+            Shortcut::Analyze => self.open_clone_prompt(ClonePurpose::Analyze),
+            Shortcut::Bookmark => match self.toggle_bookmarks() {
+                Ok(()) => self.notify(ToastLevel::Success, tr!("toast-bookmarks-updated")),
+                Err(e) => self.notify(ToastLevel::Error, format!("{:#}", e)),
+            },
+            Shortcut::MyClones => self.open_clones_view(),
+            Shortcut::Jobs => {
+                self.show_jobs = true;
+                if self.jobs_state.selected().is_none() {
+                    self.jobs_state.select(Some(0));
+                }
+            }
+            Shortcut::ClearSearch => {
+                self.tab.input.reset();
+                self.last_edit = None;
+            }
+            Shortcut::Mark => {
+                self.toggle_mark();
+                self.next();
+                self.reset_details_scroll();
+            }
+            Shortcut::GoToPage => self.open_page_prompt(),
+            Shortcut::Export => self.open_export_prompt(),
+            Shortcut::Compare => return self.open_comparison(),
+            Shortcut::Suggestion => return self.apply_suggestion(),
+            Shortcut::Cheatsheet => {
+                self.show_cheatsheet = true;
+                if self.cheatsheet_state.selected().is_none() {
+                    self.cheatsheet_state.select(Some(0));
                 }
-                _ => {
-                    // Handle text input
-                    app.input.handle_event(&Event::Key(key));
+            }
+            Shortcut::Palette => self.open_palette(),
+            Shortcut::NewTab => self.new_tab(),
+            Shortcut::NextTab => self.switch_tab((self.active_tab + 1) % self.tab_count()),
+            Shortcut::PreviousTab => self.switch_tab((self.active_tab + self.tab_count() - 1) % self.tab_count()),
+            Shortcut::CloseTab => self.close_tab(),
+            Shortcut::OwnerScope => self.open_scope_prompt(),
+            Shortcut::Similar => return self.find_similar(),
+            Shortcut::Awesome => return self.open_awesome(),
+            Shortcut::StarHistory => return self.toggle_star_history(),
+            Shortcut::Maintenance => return self.measure_maintenance(),
+            Shortcut::Refresh => return self.refresh_restored(),
+            Shortcut::LocalSort => self.cycle_local_sort(),
+            Shortcut::ExcludeOwner => return self.exclude_owner(),
+            Shortcut::Reload => return self.reload(),
+            Shortcut::OpenPane => return self.open_pane(),
+        }
+        None
+    }
+
+    /// Open tabs, the active one included
+    pub fn tab_count(&self) -> usize {
+        self.tabs.len() + 1
+    }
+
+    /// Titles of all tabs, in order
+    pub fn tab_titles(&self) -> Vec<String> {
+        let mut titles: Vec<String> = self.tabs.iter().map(SearchTab::title).collect();
+        titles.insert(self.active_tab, self.tab.title());
+        titles
+    }
+
+    /// Open an empty tab after the active one and switch to it
+    pub fn new_tab(&mut self) {
+        self.leave_tab();
+        self.tabs.insert(self.active_tab, std::mem::take(&mut self.tab));
+        self.active_tab += 1;
+        self.focus = Focus::Typing;
+        self.refresh_cloned();
+    }
+
+    /// Make the tab at `index` (among all tabs) the active one
+    pub fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tab_count() {
+            return;
+        }
+        self.leave_tab();
+        self.tabs.insert(self.active_tab, std::mem::take(&mut self.tab));
+        self.tab = self.tabs.remove(index);
+        self.active_tab = index;
+        self.refresh_cloned();
+    }
+
+    /// Close the active tab and switch to the one after it (or before, for the last); the only
+    /// tab stays open
+    pub fn close_tab(&mut self) {
+        if self.tabs.is_empty() {
+            self.notify(ToastLevel::Info, tr!("toast-only-tab"));
+            return;
+        }
+        self.leave_tab();
+        // Nothing is left to show what they find
+        let jobs = [self.tab.search_job.take(), self.tab.page_job.take().map(|(job, _)| job)];
+        for job in jobs.into_iter().flatten() {
+            self.jobs.list.cancel(job);
+        }
+        if self.active_tab == self.tabs.len() {
+            self.active_tab -= 1;
+        }
+        self.tab = self.tabs.remove(self.active_tab);
+        self.refresh_cloned();
+    }
+
+    /// Drop the active tab's pending keystrokes before another tab takes its place; its search
+    /// and page fetch keep running, and fill it in while it's in the background
+    fn leave_tab(&mut self) {
+        self.last_edit = None;
+        self.pending_open = false;
+        self.pending_leader = false;
+        self.pending_g = false;
+    }
+
+    /// Repositories an export writes: the marked ones, or else the listed results (filtered, if a
+    /// filter is set)
+    pub fn export_targets(&self) -> Vec<&Repository> {
+        if self.tab.marked.is_empty() {
+            self.visible_indices().iter().map(|&i| &self.tab.results[i]).collect()
+        } else {
+            self.marked_repos()
+        }
+    }
+
+    /// Open the export dialog, prefilled with a markdown file named after the search
+    pub fn open_export_prompt(&mut self) {
+        if self.export_targets().is_empty() {
+            self.notify(ToastLevel::Info, tr!("toast-nothing-to-export"));
+        } else {
+            let title = tr!("export-title", count = self.export_targets().len());
+            let label = format!("{}: ", tr!("export-label"));
+            let file = export::file_name(&self.tab.results_query);
+            self.modals.push(Modal::prompt(Purpose::Export, title, label, tr!("export-keys"), 72, &file));
+        }
+    }
+
+    /// Write the export targets to the file typed into the export dialog, in the format its
+    /// extension names; keeps the dialog open if the path is empty or the write fails
+    pub fn confirm_export_prompt(&mut self, path: &str) {
+        let path = path.trim();
+        if path.is_empty() {
+            self.notify(ToastLevel::Warning, tr!("toast-enter-file"));
+            return;
+        }
+        let format = export::Format::from_path(Path::new(path));
+        let repos: Vec<Repository> = self.export_targets().into_iter().cloned().collect();
+        let written = export::render(format, &self.tab.results_query, self.tab.total_count.unwrap_or(0), &repos)
+            .and_then(|contents| export::write(&expand_home(path), &contents));
+        match written {
+            Ok(()) => {
+                self.modals.close(Purpose::Export);
+                let message = tr!("toast-exported", count = repos.len(), path = path, format = format.label());
+                self.notify(ToastLevel::Success, message);
+            }
+            Err(e) => self.notify(ToastLevel::Error, format!("{:#}", e)),
+        }
+    }
+
+    /// Open the owner-scope dialog, prefilled with the current scope (off with --raw-query)
+    pub fn open_scope_prompt(&mut self) {
+        if self.raw_query {
+            self.notify(ToastLevel::Info, tr!("toast-raw-query"));
+            return;
+        }
+        let label = format!("{}: ", tr!("scope-label"));
+        let scope = self.owner_scope.clone().unwrap_or_default();
+        self.modals.push(Modal::prompt(Purpose::OwnerScope, tr!("scope-title"), label, tr!("scope-keys"), 72, &scope));
+    }
+
+    /// Limit searches to the owner typed into the scope dialog (empty clears the scope) and re-run
+    /// the last search under it; keeps the dialog open if that isn't a user or org name
+    pub fn confirm_scope_prompt(&mut self, text: &str) -> Option<Action> {
+        let scope = match text.trim() {
+            "" => None,
+            text => match qualifiers::owner_scope(text) {
+                Some(scope) => Some(scope),
+                None => {
+                    self.notify(ToastLevel::Warning, tr!("toast-bad-owner", owner = text));
+                    return None;
                 }
+            },
+        };
+        self.modals.close(Purpose::OwnerScope);
+        if scope == self.owner_scope {
+            return None;
+        }
+        match &scope {
+            Some(scope) => self.notify(ToastLevel::Info, tr!("toast-scope", scope = scope.as_str())),
+            None => self.notify(ToastLevel::Info, tr!("toast-scope-cleared")),
+        }
+        self.owner_scope = scope;
+        let searched = !self.tab.last_query.is_empty() && self.tab.collection.is_none();
+        searched.then(|| Action::Search(self.tab.last_query.clone()))
+    }
+
+    /// Open the command palette: every leader chord, runnable by name
+    pub fn open_palette(&mut self) {
+        let items = LEADER_KEYS
+            .iter()
+            .map(|(key, _, hint)| match key {
+                ' ' => format!("{:<24}Space Space", hint),
+                _ => format!("{:<24}Space {}", hint, key),
+            })
+            .collect();
+        self.modals.push(Modal::picker(Purpose::CommandPalette, tr!("palette-title"), items));
+    }
+
+    /// Act on the answer a dialog gave (cancelling needs nothing: the dialog is already closed)
+    pub fn apply_modal_outcome(&mut self, outcome: Outcome) -> Flow {
+        match outcome {
+            Outcome::Submitted(Purpose::GoToPage, text) => {
+                return self.confirm_page_prompt(&text).map_or(Flow::Continue, Flow::Run);
+            }
+            Outcome::Submitted(Purpose::Export, path) => self.confirm_export_prompt(&path),
+            Outcome::Submitted(Purpose::OwnerScope, text) => {
+                return self.confirm_scope_prompt(&text).map_or(Flow::Continue, Flow::Run);
+            }
+            Outcome::Submitted(Purpose::AwesomeTopic, topic) => {
+                return self.confirm_awesome_prompt(&topic).map_or(Flow::Continue, Flow::Run);
             }
+            Outcome::Confirmed(Purpose::DeleteClones) => match self.delete_clones() {
+                Ok((deleted, freed)) => self.notify(
+                    ToastLevel::Success,
+                    tr!("toast-deleted", clones = clone_count(deleted), size = clones::format_size(freed)),
+                ),
+                Err(e) => self.notify(ToastLevel::Error, tr!("toast-delete-failed", error = format!("{:#}", e))),
+            },
+            Outcome::Picked(Purpose::CommandPalette, index) => {
+                let shortcut = LEADER_KEYS[index].1;
+                return self.run_shortcut(shortcut).map_or(Flow::Continue, Flow::Run);
+            }
+            _ => {}
+        }
+        Flow::Continue
+    }
+
+    /// Put the suggested query into the search box and search for it
+    pub fn apply_suggestion(&mut self) -> Option<Action> {
+        let Some(suggestion) = self.tab.suggestion.take() else {
+            self.notify(ToastLevel::Info, tr!("toast-no-suggestion"));
+            return None;
+        };
+        self.tab.input = Input::new(suggestion.query.clone());
+        self.last_edit = None;
+        if self.keymap == Keymap::Letters {
+            self.focus = Focus::Browsing;
+        }
+        Some(Action::Search(suggestion.query))
+    }
+
+    /// Search for repositories like the selected one (its topics and language) in a new tab
+    pub fn find_similar(&mut self) -> Option<Action> {
+        let repo = self.get_selected_repo()?;
+        let name = repo.full_name.clone().unwrap_or_else(|| repo.name.clone());
+        let Some(query) = suggest::similar(repo) else {
+            self.notify(ToastLevel::Info, tr!("toast-nothing-similar", name = name.as_str()));
+            return None;
+        };
+        self.new_tab();
+        self.tab.input = Input::new(query.clone());
+        if self.keymap == Keymap::Letters {
+            self.focus = Focus::Browsing;
+        }
+        self.notify(ToastLevel::Info, tr!("toast-similar", name = name.as_str()));
+        Some(Action::Search(query))
+    }
+
+    /// Search again without the selected result's owner: the current query plus -user:<owner>
+    pub fn exclude_owner(&mut self) -> Option<Action> {
+        let owner = repo_owner(self.get_selected_repo()?).to_string();
+        if self.tab.last_query.trim().is_empty() || self.tab.collection.is_some() {
+            self.notify(ToastLevel::Info, tr!("toast-exclude-needs-search"));
+            return None;
+        }
+        let exclusion = format!("-user:{}", owner);
+        if self.tab.last_query.split_whitespace().any(|term| term.eq_ignore_ascii_case(&exclusion)) {
+            self.notify(ToastLevel::Info, tr!("toast-already-excluded", owner = owner.as_str()));
+            return None;
+        }
+        let query = format!("{} {}", self.tab.last_query.trim(), exclusion);
+        self.tab.input = Input::new(query.clone());
+        self.notify(ToastLevel::Info, tr!("toast-excluding", owner = owner.as_str()));
+        Some(Action::Search(query))
+    }
+
+    /// Run the tab's search again, asking GitHub even if its results are cached
+    pub fn reload(&mut self) -> Option<Action> {
+        if self.tab.last_query.trim().is_empty() || self.tab.collection.is_some() {
+            self.notify(ToastLevel::Info, tr!("toast-no-search"));
+            return None;
+        }
+        Some(Action::Reload(self.tab.last_query.clone()))
+    }
+
+    /// Load the repositories the selected result links to if it's an awesome list; otherwise ask
+    /// for a topic to find awesome lists about
+    pub fn open_awesome(&mut self) -> Option<Action> {
+        let Some(repo) = self.get_selected_repo().filter(|repo| awesome::is_list(repo)) else {
+            let label = format!("{}: ", tr!("awesome-label"));
+            self.modals.push(Modal::prompt(Purpose::AwesomeTopic, tr!("awesome-title"), label, tr!("awesome-keys"), 60, ""));
+            return None;
+        };
+        let list = repo.full_name.clone().unwrap_or_else(|| format!("{}/{}", repo_owner(repo), repo.name));
+        self.notify(ToastLevel::Info, tr!("toast-loading-list", list = list.as_str()));
+        Some(Action::LoadList(list))
+    }
+
+    /// Search for awesome lists about the topic typed into the dialog, in a new tab
+    pub fn confirm_awesome_prompt(&mut self, topic: &str) -> Option<Action> {
+        let Some(query) = awesome::query(topic) else {
+            self.notify(ToastLevel::Warning, tr!("toast-enter-topic"));
+            return None;
+        };
+        self.modals.close(Purpose::AwesomeTopic);
+        self.new_tab();
+        self.tab.input = Input::new(query.clone());
+        if self.keymap == Keymap::Letters {
+            self.focus = Focus::Browsing;
+        }
+        Some(Action::Search(query))
+    }
+
+    /// Show the repositories an awesome list links to in a new tab, all of them loaded at once
+    pub fn open_collection(&mut self, list: String, repos: Vec<Repository>) {
+        self.new_tab();
+        self.tab.last_query = list.clone();
+        self.tab.collection = Some(list);
+        let count = repos.len();
+        self.set_results(repos, count as u64);
+        self.tab.page_starts = (0..count.max(1)).step_by(self.per_page.max(1) as usize).collect();
+        if self.keymap == Keymap::Letters {
+            self.focus = Focus::Browsing;
+        }
+    }
+
+    /// Fetch the selected repo's star history for the details panel, or hide a chart already shown
+    pub fn toggle_star_history(&mut self) -> Option<Action> {
+        let repo = self.get_selected_repo()?;
+        let url = repo.html_url.as_ref()?.to_string();
+        let stars = repo.stargazers_count.unwrap_or(0) as u64;
+        if self.pending_star_history.contains(&url) {
+            return None;
+        }
+        match self.star_history.remove(&url) {
+            Some(Ok(_)) => None,
+            _ => Some(Action::StarHistory(url, stars)),
+        }
+    }
+
+    /// Measure how the selected repo is maintained (issue replies, pull-request merges, contributor
+    /// concentration), unless that's known or underway (failed measurements are retried)
+    pub fn measure_maintenance(&mut self) -> Option<Action> {
+        let url = self.get_selected_repo()?.html_url.as_ref()?.to_string();
+        if self.pending_maintenance.contains(&url) || matches!(self.maintenance.get(&url), Some(Ok(_))) {
+            return None;
         }
+        Some(Action::Maintenance(url))
+    }
+
+    /// Open the selected result's clone in a new tmux or WezTerm pane (or whatever pane_command runs)
+    pub fn open_pane(&mut self) -> Option<Action> {
+        let url = self.get_selected_repo()?.html_url.as_ref()?.to_string();
+        let Some(path) = self.cloned_paths.get(&url).cloned() else {
+            self.notify(ToastLevel::Info, tr!("toast-not-cloned"));
+            return None;
+        };
+        if self.pane_command.is_none() {
+            self.notify(ToastLevel::Warning, tr!("toast-no-multiplexer"));
+            return None;
+        }
+        Some(Action::OpenPane(path))
+    }
+
+    /// Open the comparison of the two marked repositories, returning a Compare action for
+    /// the figures not fetched yet (failed fetches are retried)
+    pub fn open_comparison(&mut self) -> Option<Action> {
+        let pair: Vec<Repository> = self.marked_repos().into_iter().cloned().collect();
+        let Ok(pair) = <[Repository; 2]>::try_from(pair) else {
+            let message = tr!("toast-mark-two", marked = self.tab.marked.len());
+            self.notify(ToastLevel::Info, message);
+            return None;
+        };
+        let urls: Vec<String> = pair
+            .iter()
+            .filter_map(|repo| repo.html_url.as_ref().map(|url| url.to_string()))
+            .filter(|url| !matches!(self.activity.get(url), Some(Ok(_))) && !self.pending_activity.contains(url))
+            .collect();
+        self.comparison = Some(pair);
+        (!urls.is_empty()).then_some(Action::Compare(urls))
+    }
+
+    /// Release and contributor figures to fetch for the details panel: the selected result's, then
+    /// those of the `prefetch_ahead` results after it (as prefetches), so moving down finds them
+    /// already loaded
+    /// One at a time, so holding an arrow key doesn't start a fetch for every result it passes
+    fn details_due(&mut self) -> Option<Action> {
+        if !self.fetch_details || self.details_paused || !self.pending_activity.is_empty() {
+            return None;
+        }
+        let selected = self.tab.list_state.selected()?;
+        let (offset, url) = self
+            .visible_indices()
+            .iter()
+            .copied()
+            .skip(selected)
+            .take(self.prefetch_ahead + 1)
+            .filter_map(|i| self.tab.results[i].html_url.as_ref().map(ToString::to_string))
+            .enumerate()
+            .find(|(offset, url)| !self.activity.contains_key(url) && (*offset == 0 || !self.deferred_prefetches.contains(url)))?;
+        self.fetched_for_details.insert(url.clone());
+        Some(if offset == 0 { Action::Compare(vec![url]) } else { Action::Prefetch(url) })
+    }
+
+    /// Rows of the open comparison, with whatever figures have arrived
+    pub fn comparison_rows(&self) -> Option<Vec<compare::Row>> {
+        let sides = self.comparison.as_ref()?.each_ref().map(|repo| {
+            let url = repo.html_url.as_ref().map(|url| url.to_string()).unwrap_or_default();
+            compare::Side {
+                repo,
+                activity: self.activity.get(&url),
+                loading: self.pending_activity.contains(&url),
+                analysis: self.analyses.get(&url),
+                license_conflict: licenses::conflict(repo, &self.project_license),
+            }
+        });
+        Some(compare::rows(&sides, SystemTime::now()))
+    }
+
+    /// URL of the selected repository, to open in the browser
+    pub fn selected_url(&self) -> Option<String> {
+        self.get_selected_repo()?.html_url.as_ref().map(|url| url.to_string())
+    }
+
+    /// Switch between typing in the search box and browsing the results
+    pub fn toggle_focus(&mut self) {
+        self.pending_g = false;
+        self.focus = match self.focus {
+            Focus::Typing => Focus::Browsing,
+            Focus::Browsing => Focus::Typing,
+        };
+    }
+
+    /// Toggle bookmarks for the action targets and save them
+    pub fn toggle_bookmarks(&mut self) -> Result<()> {
+        let targets: Vec<(String, String)> = self
+            .action_targets()
+            .iter()
+            .filter_map(|repo| {
+                let url = repo.html_url.as_ref()?.to_string();
+                let name = repo.full_name.clone().unwrap_or_else(|| repo.name.clone());
+                Some((name, url))
+            })
+            .collect();
+        for (name, url) in targets {
+            self.bookmarks.toggle(&name, &url);
+        }
+        self.bookmarks.save()
+    }
+
+    /// Scroll details panel down
+    pub fn scroll_details_down(&mut self) {
+        self.scroll_details_by(1);
+    }
+
+    /// Scroll details panel up
+    pub fn scroll_details_up(&mut self) {
+        self.scroll_details_by(-1);
+    }
+
+    /// Scroll the details panel by a page, keeping one line of overlap
+    pub fn page_details(&mut self, pages: i32) {
+        let page = self.details_height.saturating_sub(1).max(1) as i32;
+        self.scroll_details_by(pages * page);
+    }
+
+    /// Jump to the end of the details
+    pub fn scroll_details_to_end(&mut self) {
+        self.tab.details_scroll = self.details_max_scroll;
+    }
+
+    /// Scroll the details panel by `lines`, never past the end of the content
+    fn scroll_details_by(&mut self, lines: i32) {
+        let scroll = (self.tab.details_scroll as i32 + lines).clamp(0, self.details_max_scroll as i32);
+        self.tab.details_scroll = scroll as u16;
+    }
+
+    /// Reset details scroll when changing repos
+    pub fn reset_details_scroll(&mut self) {
+        self.tab.details_scroll = 0;
+    }
+}
+
+/// Main TUI event loop
+/// Returns None if user wants to quit, or Some(action) for the work only main's loop can do
+/// (searches, background jobs, the browser and clipboard); see `Action`
+pub fn run_tui<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<Option<Action>> {
+    run_tui_with(terminal, app, next_terminal_event)
+}
+
+/// Seconds since the Unix epoch, the clock GitHub's rate-limit resets are given in
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// A countdown in seconds as "42s" or "3m 05s"
+fn format_countdown(seconds: u64) -> String {
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    }
+}
+
+/// The next terminal event, or None when a tick passes without one
+fn next_terminal_event() -> Result<Option<Event>> {
+    if !event::poll(TICK_RATE)? {
+        return Ok(None);
+    }
+    Ok(Some(event::read()?))
+}
+
+/// `run_tui` with its input from `next_event` (None for a tick without input), so tests can script keys
+/// Each pass draws the view, then feeds the messages that arrived to `App::update`: results from
+/// background jobs, a Tick, and the next terminal event
+fn run_tui_with<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    mut next_event: impl FnMut() -> Result<Option<Event>>,
+) -> Result<Option<Action>> {
+    loop {
+        terminal.draw(|f| ui(f, app))?;
+        app.sync_terminal_status(&mut io::stdout())?;
+
+        let mut messages = app.job_messages();
+        messages.push(AppMessage::Tick);
+        for message in messages {
+            match app.update(message) {
+                Flow::Continue => {}
+                Flow::Run(action) => return Ok(Some(action)),
+                Flow::Quit => return Ok(None),
+            }
+        }
+
+        // Wait for keyboard input, waking up every tick to animate and expire toasts
+        if let Some(message) = next_event()?.and_then(AppMessage::from_event) {
+            match app.update(message) {
+                Flow::Continue => {}
+                Flow::Run(action) => return Ok(Some(action)),
+                Flow::Quit => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Update for a key press: the open overlay or dialog gets it first, then pending chords, vim
+/// mode, shortcuts, and finally the results list and search box
+fn handle_key(app: &mut App, key: event::KeyEvent) -> Flow {
+    // Ctrl+C to quit
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        return Flow::Quit;
+    }
+
+    // Dialogs sit above every overlay: the top one takes all keys until it closes
+    if let Some(outcome) = app.modals.handle_key(key) {
+        return app.apply_modal_outcome(outcome);
+    }
+
+    // Jobs panel captures navigation while it's open
+    if app.show_jobs {
+        match key.code {
+            KeyCode::Esc => app.show_jobs = false,
+            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::ALT) => app.show_jobs = false,
+            KeyCode::Char('J') => app.show_jobs = false,
+            KeyCode::Down | KeyCode::Char('j') => app.move_job_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => app.move_job_selection(-1),
+            KeyCode::Char('x') | KeyCode::Delete => {
+                if let Some(id) = app.selected_job_id() {
+                    app.cancel_job(id);
+                }
+            }
+            _ => {}
+        }
+        return Flow::Continue;
+    }
+
+    // Cheatsheet: pick a qualifier to add to the query, or just read
+    if app.show_cheatsheet {
+        match key.code {
+            KeyCode::Esc | KeyCode::F(1) | KeyCode::Char('?') => app.show_cheatsheet = false,
+            KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::ALT) => app.show_cheatsheet = false,
+            KeyCode::Down | KeyCode::Char('j') => app.move_cheatsheet_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => app.move_cheatsheet_selection(-1),
+            KeyCode::Enter => app.insert_cheatsheet_example(),
+            _ => {}
+        }
+        return Flow::Continue;
+    }
+
+    // Comparison overlay: any close key dismisses it, everything else is ignored
+    if app.comparison.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => app.comparison = None,
+            _ => {}
+        }
+        return Flow::Continue;
+    }
+
+    // Clones overlay: navigation, update, SBOM, and delete (with a y/n confirmation)
+    if app.show_clones {
+        match key.code {
+            KeyCode::Esc => app.show_clones = false,
+            KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::ALT) => app.show_clones = false,
+            KeyCode::Down | KeyCode::Char('j') => app.move_clone_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => app.move_clone_selection(-1),
+            KeyCode::Char(' ') => app.toggle_clone_mark(),
+            KeyCode::Char('s') => app.toggle_clones_sort(),
+            KeyCode::Char('d') | KeyCode::Delete if app.selected_clone().is_some() => app.ask_delete_clones(),
+            // One update at a time: two could run git in the same clone
+            KeyCode::Char('u') if !app.updating() => {
+                if let Some(clone) = app.selected_clone() {
+                    return Flow::Run(Action::Update(vec![clone.path.clone()]));
+                }
+            }
+            KeyCode::Char('U') if !app.updating() && !app.local_clones.is_empty() => {
+                let paths = app.local_clones.iter().map(|clone| clone.path.clone()).collect();
+                return Flow::Run(Action::Update(paths));
+            }
+            KeyCode::Char('b') => {
+                if let Some(clone) = app.selected_clone() {
+                    return Flow::Run(Action::Sbom(clone.path.clone(), clone.name.clone()));
+                }
+            }
+            KeyCode::Char('p') if app.pane_command.is_some() => {
+                if let Some(clone) = app.selected_clone() {
+                    return Flow::Run(Action::OpenPane(clone.path.clone()));
+                }
+            }
+            _ => {}
+        }
+        return Flow::Continue;
+    }
+
+    // Clone prompt captures all typing until Enter/Esc
+    if let Some(prompt) = app.clone_prompt.as_mut() {
+        match key.code {
+            KeyCode::Esc => app.clone_prompt = None,
+            KeyCode::Enter => {
+                if let Some(action) = app.confirm_clone_prompt() {
+                    return Flow::Run(action);
+                }
+            }
+            _ => prompt.handle_key(key),
+        }
+        return Flow::Continue;
+    }
+
+    // In-results filter captures all typing until Enter/Esc
+    if app.tab.filtering {
+        match key.code {
+            KeyCode::Enter => app.tab.filtering = false,
+            KeyCode::Esc => app.clear_filter(),
+            _ => {
+                app.tab.filter_input.handle_event(&Event::Key(key));
+                app.apply_filter();
+            }
+        }
+        return Flow::Continue;
+    }
+
+    // Second key of the open submenu: which page of the repo to open; any other key closes it
+    if app.pending_open {
+        app.pending_open = false;
+        if let Some(url) = app.get_selected_repo().and_then(|repo| deep_link(repo, &key)) {
+            return Flow::Run(Action::OpenUrl(url));
+        }
+        return Flow::Continue;
+    }
+
+    // Second key of a leader chord: run its action; any other key drops the chord
+    if app.pending_leader {
+        app.pending_leader = false;
+        if let Some(action) = leader_chord(&key).and_then(|shortcut| app.run_shortcut(shortcut)) {
+            return Flow::Run(action);
+        }
+        return Flow::Continue;
+    }
+    if is_leader_key(&key, app.focus) {
+        app.pending_g = false;
+        app.pending_leader = true;
+        return Flow::Continue;
+    }
+
+    // Esc or Ctrl+X stops the running search, update, or clones and counts, and Esc calls off a
+    // pending automatic retry, before either does anything else; Esc quits only when nothing ran
+    if is_cancel_key(&key) && app.cancel_running() {
+        return Flow::Continue;
+    }
+    if key.code == KeyCode::Esc && app.cancel_resume() {
+        return Flow::Continue;
+    }
+
+    // Tab switches between typing in the search box and browsing the results
+    if key.code == KeyCode::Tab && !key.modifiers.contains(KeyModifiers::CONTROL) {
+        app.toggle_focus();
+        return Flow::Continue;
+    }
+
+    if app.vim_mode {
+        if let Some(action) = handle_vim_key(app, key) {
+            match action {
+                VimAction::Handled => return Flow::Continue,
+                VimAction::Quit => return Flow::Quit,
+                VimAction::Search => {
+                    if !app.tab.input.value().is_empty() {
+                        app.focus = Focus::Browsing;
+                        return Flow::Run(Action::Search(app.tab.input.value().to_string()));
+                    }
+                    return Flow::Continue;
+                }
+            }
+        }
+    }
+
+    if let Some(shortcut) = shortcut(&key, app.focus) {
+        if let Some(action) = app.run_shortcut(shortcut) {
+            return Flow::Run(action);
+        }
+        return Flow::Continue;
+    }
+
+    match key.code {
+        KeyCode::Esc => return Flow::Quit,
+        KeyCode::Down => {
+            app.next();
+            app.reset_details_scroll(); // Reset scroll when changing repos
+        }
+        KeyCode::Up => {
+            app.previous();
+            app.reset_details_scroll(); // Reset scroll when changing repos
+        }
+        KeyCode::Left => app.scroll_details_up(),    // Scroll details up
+        KeyCode::Right => app.scroll_details_down(),  // Scroll details down
+        KeyCode::PageUp => app.page_details(-1),
+        KeyCode::PageDown => app.page_details(1),
+        // Home/End move the cursor in the search box while typing
+        KeyCode::Home if app.focus == Focus::Browsing => app.reset_details_scroll(),
+        KeyCode::End if app.focus == Focus::Browsing => app.scroll_details_to_end(),
+        KeyCode::Enter if app.focus == Focus::Browsing => {
+            // Open the selected result in the browser
+            if let Some(url) = app.selected_url() {
+                return Flow::Run(Action::OpenUrl(url));
+            }
+        }
+        KeyCode::Enter => {
+            // Submit search query if input is not empty
+            if !app.tab.input.value().is_empty() {
+                if app.keymap == Keymap::Letters {
+                    app.focus = Focus::Browsing;
+                }
+                return Flow::Run(Action::Search(app.tab.input.value().to_string()));
+            }
+        }
+        KeyCode::Char(' ') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            // Ctrl+Space to mark/unmark the selected repo for batch actions
+            app.run_shortcut(Shortcut::Mark);
+        }
+        // Digits pick a size filter while browsing; in the search box they're typed
+        KeyCode::Char('1') if app.focus == Focus::Browsing => {
+            // Set size filter to small
+            app.set_size_filter(Some("small".to_string()));
+        }
+        KeyCode::Char('2') if app.focus == Focus::Browsing => {
+            // Set size filter to medium
+            app.set_size_filter(Some("medium".to_string()));
+        }
+        KeyCode::Char('3') if app.focus == Focus::Browsing => {
+            // Set size filter to large
+            app.set_size_filter(Some("large".to_string()));
+        }
+        KeyCode::Char('0') if app.focus == Focus::Browsing => {
+            // Clear size filter
+            app.set_size_filter(None);
+        }
+        _ => {
+            // Typing while browsing goes back to the search box
+            if is_typing_key(&key) {
+                app.focus = Focus::Typing;
+            }
+            // Handle text input
+            if app.tab.input.handle_event(&Event::Key(key)).is_some_and(|change| change.value) {
+                app.input_edited();
+            }
+        }
+    }
+    Flow::Continue
+}
+
+/// Outcome of a key press handled by the vim keymap
+enum VimAction {
+    Handled, // Key consumed, keep looping
+    Quit,    // Esc in normal mode
+    Search,  // Enter in insert mode submits the query
+}
+
+/// Handle a key press in vim mode
+/// Returns None when the key should fall through to the default handler
+/// (shortcuts, text input in insert mode, details scrolling, Enter to open in normal mode)
+fn handle_vim_key(app: &mut App, key: event::KeyEvent) -> Option<VimAction> {
+    if key.modifiers.contains(KeyModifiers::ALT) || shortcut(&key, app.focus).is_some() {
+        app.pending_g = false;
+        return None;
+    }
+
+    if app.focus == Focus::Typing {
+        // Insert mode: type into the search box, Esc switches to normal mode
+        return match key.code {
+            KeyCode::Esc => {
+                app.focus = Focus::Browsing;
+                Some(VimAction::Handled)
+            }
+            KeyCode::Enter => Some(VimAction::Search),
+            _ => None,
+        };
+    }
+
+    // Any key other than a second 'g' cancels a pending "gg"
+    let pending_g = std::mem::take(&mut app.pending_g);
+
+    match key.code {
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.half_page_down();
+            app.reset_details_scroll();
+        }
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.half_page_up();
+            app.reset_details_scroll();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.next();
+            app.reset_details_scroll();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.previous();
+            app.reset_details_scroll();
+        }
+        KeyCode::Char('g') => {
+            if pending_g {
+                app.select_first();
+                app.reset_details_scroll();
+            } else {
+                app.pending_g = true;
+            }
+        }
+        KeyCode::Char('G') => {
+            app.select_last();
+            app.reset_details_scroll();
+        }
+        KeyCode::Char('/') => app.tab.filtering = true,
+        KeyCode::Char('i') => app.focus = Focus::Typing,
+        KeyCode::Esc => {
+            // Esc clears an active filter before it quits
+            if app.tab.filter_input.value().is_empty() {
+                return Some(VimAction::Quit);
+            }
+            app.clear_filter();
+        }
+        // Size filter keys, details scrolling, and Enter keep their default bindings
+        KeyCode::Char('0'..='3')
+        | KeyCode::Left
+        | KeyCode::Right
+        | KeyCode::PageUp
+        | KeyCode::PageDown
+        | KeyCode::Home
+        | KeyCode::End
+        | KeyCode::Enter => return None,
+        // Other plain keys are ignored in normal mode rather than typed into the search box
+        _ => {}
+    }
+
+    Some(VimAction::Handled)
+}
+
+/// Main UI rendering function
+/// Splits screen into 4 sections: Search input, Results list, Details panel, Help bar
+pub fn ui(f: &mut Frame, app: &mut App) {
+    // Define layout: split terminal into 4 vertical sections
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3),  // Search box: fixed 3 lines
+            Constraint::Min(10),    // Results: at least 10 lines, expands with screen
+            Constraint::Min(20),    // Details: at least 20 lines for file count output
+            Constraint::Length(1),  // Status bar: single line
+            Constraint::Length(3),  // Help: fixed 3 lines
+        ])
+        .split(f.area());
+
+    // Search input box (filters and mode are shown in the status bar, except the owner scope)
+    let title = match &app.owner_scope {
+        Some(scope) => format!(" {} {} {} ", Icon::Search.label(tr!("search-title")), Icon::Separator, scope),
+        None => format!(" {} ", Icon::Search.label(tr!("search-title"))),
+    };
+
+    let mut input_block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Cyan));
+    // With more than one tab, the tab strip sits on the right of the search box's top border
+    if app.tab_count() > 1 {
+        let mut tabs = Vec::new();
+        for (index, title) in app.tab_titles().iter().enumerate() {
+            let style = if index == app.active_tab {
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::REVERSED | Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            tabs.push(Span::styled(format!(" {}:{} ", index + 1, columns::truncate(title, TAB_TITLE_WIDTH)), style));
+        }
+        input_block = input_block.title(Line::from(tabs).right_aligned());
+    }
+
+    let input_text = app.tab.input.value();
+    let input_widget = Paragraph::new(input_text)
+        .block(input_block)
+        .style(Style::default().fg(Color::Black));
+
+    f.render_widget(input_widget, chunks[0]);
+
+    // Set cursor position (hidden while browsing the results, typing a filter, or in a dialog)
+    if app.focus == Focus::Typing && !app.tab.filtering && app.modals.is_empty() {
+        let cursor_x = chunks[0].x + app.tab.input.visual_cursor() as u16 + 1;
+        let cursor_y = chunks[0].y + 1;
+        f.set_cursor_position((cursor_x, cursor_y));
+    }
+
+    // Remember how many rows the results list can show (minus borders) for half-page jumps
+    app.results_height = chunks[1].height.saturating_sub(2);
+
+    // Results list
+    if app.searching() {
+        let searching_text = Paragraph::new(tr!("searching", progress = app.progress_label()))
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("results-title"))))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow));
+        f.render_widget(searching_text, chunks[1]);
+    } else if let Some(error) = &app.tab.error_message {
+        let mut lines = vec![Line::from(Span::styled(Icon::Error.label(tr!("error-line", error = error.as_str())), Style::default().fg(Color::Red)))];
+        // A pending automatic retry counts down in place of the static hint
+        let hint = match app.resume_in(unix_now()) {
+            Some(seconds) => Some(tr!("hint-rate-limited-resume", time = format_countdown(seconds))),
+            None => app.tab.error_hint.clone(),
+        };
+        if let Some(hint) = hint {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(Icon::Hint.label(hint), Style::default().fg(Color::Yellow))));
+        }
+        let error_text = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("results-title"))))
+            .wrap(Wrap { trim: true });
+        f.render_widget(error_text, chunks[1]);
+    } else if app.tab.results.is_empty() && app.tab.total_count == Some(0) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                tr!("no-results", query = app.tab.results_query.as_str()),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+        match &app.tab.suggestion {
+            Some(suggestion) => {
+                lines.push(highlight(
+                    tr!("did-you-mean", query = suggestion.query.as_str()),
+                    &suggestion.query,
+                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                ));
+                for change in &suggestion.changes {
+                    lines.push(Line::from(Span::styled(change.clone(), Style::default().fg(Color::Gray))));
+                }
+                lines.push(Line::from(""));
+                let key = if app.focus == Focus::Browsing { "s" } else { "Alt+S" };
+                lines.push(Line::from(tr!("press-to-search", key = key)));
+            }
+            None => lines.push(Line::from(tr!("try-different"))),
+        }
+        let empty = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("results-total", total = 0))))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(empty, chunks[1]);
+    } else if app.tab.results.is_empty() {
+        let welcome_text = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("{} {}", tr!("welcome"), Icon::Welcome).trim_end().to_string(),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(tr!("welcome-type")),
+            Line::from(tr!("welcome-examples")),
+            Line::from(""),
+            Line::from(tr!("welcome-navigate", keys = Icon::UpDown.to_string())),
+        ];
+
+        let welcome = Paragraph::new(welcome_text)
+            .block(Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("results-title"))))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(welcome, chunks[1]);
+    } else {
+        let mut title = match app.tab.total_count {
+            Some(total) if app.tab.collection.is_some() => {
+                let list = app.tab.collection.clone().unwrap_or_default();
+                format!(" {} ", tr!("results-collection", list = list, count = total))
+            }
+            Some(total) if app.total_pages() > 1 => format!(
+                " {} ",
+                tr!(
+                    "results-pages",
+                    page = app.current_page(),
+                    pages = app.total_pages(),
+                    loaded = app.tab.results.len(),
+                    total = total
+                )
+            ),
+            Some(total) => format!(" {} ", tr!("results-total", total = total)),
+            None => format!(" {} ", tr!("results-title")),
+        };
+        match app.tab.cached_at {
+            Some(cache::Hit::Offline(fetched)) => {
+                title.push_str(&format!("{} ", tr!("results-offline", age = clones::format_age(fetched, SystemTime::now()))));
+            }
+            Some(cache::Hit::Fresh(fetched)) => {
+                title.push_str(&format!("{} ", tr!("results-cached", age = clones::format_age(fetched, SystemTime::now()))));
+            }
+            None => {}
+        }
+        if let Some(summary) = app.tab.changes.as_ref().and_then(|changes| changes.summary(SystemTime::now())) {
+            title.push_str(&format!("[{}] ", summary));
+        }
+        if let Some(sort) = app.tab.local_sort {
+            title.push_str(&format!("{} ", tr!("results-sorted-by", sort = sort.label())));
+        }
+        if !app.tab.marked.is_empty() {
+            title.push_str(&format!("{} ", tr!("results-selected", count = app.tab.marked.len())));
+        }
+        if app.tab.filtering || !app.tab.filter_input.value().is_empty() {
+            title.push_str(&format!("/{} ", app.tab.filter_input.value()));
+        }
+
+        let now = SystemTime::now();
+        // Cells left for the columns after the borders, highlight symbol, and mark
+        let row_width = (chunks[1].width as usize).saturating_sub(2 + Span::raw(Icon::Selected.glyph()).width() + 2);
+        let owner_column = app.columns.iter().any(|column| column.kind == ColumnKind::Owner);
+        let listing = app.listing();
+        let visible = &listing.indices;
+
+        // Only the rows on screen, plus a margin, are built: thousands of loaded results draw as fast as a page
+        // Each row is one line, so the scroll offset can be worked out the way the List widget does it
+        let height = chunks[1].height.saturating_sub(2) as usize;
+        let selected = app.tab.list_state.selected().filter(|&selected| selected < visible.len());
+        let mut offset = app.tab.list_state.offset().min(visible.len().saturating_sub(1));
+        if let Some(selected) = selected {
+            // No clamp: with no rows inside the borders (a terminal 8 rows tall) the bounds cross
+            offset = offset.min(selected).max((selected + 1).saturating_sub(height.max(1)));
+        }
+        let start = offset.saturating_sub(LIST_WINDOW_MARGIN);
+        let window = &visible[start..(offset + height + LIST_WINDOW_MARGIN).min(visible.len())];
+
+        let shown_columns = columns::with_analyzed(&app.columns, listing.any_files, listing.any_loc);
+        let items: Vec<ListItem> = window
+            .iter()
+            .map(|&i| &app.tab.results[i])
+            .map(|repo| {
+                let marker = if app.tab.marked.contains(&repo.id.0) { format!("{} ", Icon::Marked) } else { "  ".to_string() };
+                let bookmarked = repo
+                    .html_url
+                    .as_ref()
+                    .is_some_and(|url| app.bookmarks.contains(url.as_str()));
+
+                // Status markers after the columns: private, new since the last run, bookmarked, license clash,
+                // cloned, running jobs
+                let mut line = Line::default();
+                if columns::visibility_label(repo).is_some() {
+                    line.push_span(Span::styled(format!(" {}", Icon::Private), Style::default().fg(Color::Yellow)));
+                }
+                if app.tab.changes.as_ref().is_some_and(|changes| changes.added.contains(&repo.id.0)) {
+                    line.push_span(Span::styled(format!(" {}", Icon::New), Style::default().fg(Color::Green)));
+                }
+                if bookmarked {
+                    line.push_span(Span::styled(format!(" {}", Icon::Bookmark), Style::default().fg(Color::Magenta)));
+                }
+                if licenses::conflict(repo, &app.project_license).is_some() {
+                    line.push_span(Span::styled(format!(" {}", Icon::Warning.label(tr!("row-license"))), Style::default().fg(Color::Red)));
+                }
+                let url = repo.html_url.as_ref().map(|u| u.to_string()).unwrap_or_default();
+                if app.cloned_paths.contains_key(&url) {
+                    line.push_span(Span::styled(format!(" {}", Icon::Cloned), Style::default().fg(Color::Cyan)));
+                }
+                match app.pending_clones.get(&url) {
+                    Some(CloneStatus::Queued) => {
+                        line.push_span(Span::styled(format!(" {}", Icon::Queued.label(tr!("row-clone-queued"))), Style::default().fg(Color::DarkGray)));
+                    }
+                    Some(CloneStatus::Running(_, Some(progress))) => {
+                        line.push_span(Span::styled(
+                            format!(" {}%", Icon::Package.label(progress.percent)),
+                            Style::default().fg(Color::Cyan),
+                        ));
+                    }
+                    Some(CloneStatus::Running(start, None)) => {
+                        line.push_span(Span::styled(
+                            format!(" {} {}", spinner_label(*start, Instant::now()), tr!("row-cloning")),
+                            Style::default().fg(Color::Cyan),
+                        ));
+                    }
+                    None => {}
+                }
+                match app.pending_counts.get(&url) {
+                    Some(CountStatus::Queued) => {
+                        line.push_span(Span::styled(format!(" {}", Icon::Queued.label(tr!("row-queued"))), Style::default().fg(Color::DarkGray)));
+                    }
+                    Some(CountStatus::Running(start)) => {
+                        line.push_span(Span::styled(
+                            format!(" {} {}", spinner_label(*start, Instant::now()), tr!("row-counting")),
+                            Style::default().fg(Color::Yellow),
+                        ));
+                    }
+                    None if app.file_counts.contains_key(&url) => {
+                        line.push_span(Span::styled(format!(" {}", Icon::Files), Style::default().fg(Color::Magenta)));
+                    }
+                    None => {}
+                }
+                match app.pending_analyses.get(&url) {
+                    Some(CountStatus::Queued) => {
+                        line.push_span(Span::styled(format!(" {}", Icon::Queued.label(tr!("row-analysis-queued"))), Style::default().fg(Color::DarkGray)));
+                    }
+                    Some(CountStatus::Running(start)) => {
+                        line.push_span(Span::styled(
+                            format!(" {} {}", spinner_label(*start, Instant::now()), tr!("row-analyzing")),
+                            Style::default().fg(Color::Yellow),
+                        ));
+                    }
+                    None if matches!(app.analyses.get(&url), Some(Ok(_))) => {
+                        line.push_span(Span::styled(format!(" {}", Icon::Languages), Style::default().fg(Color::Magenta)));
+                    }
+                    None => {}
+                }
+
+                let figures = app.analyzed(repo);
+                let cells = shown_columns.iter().map(|column| (*column, column.text(repo, figures, now, owner_column))).collect();
+                let mut row = Line::from(Span::styled(marker, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)));
+                for (index, (column, text)) in columns::fit(cells, row_width.saturating_sub(line.width()), 3).into_iter().enumerate() {
+                    if index > 0 {
+                        row.push_span(Span::raw(" | "));
+                    }
+                    row.push_span(Span::styled(text, column_style(column.kind)));
+                }
+                row.spans.extend(line.spans);
+                ListItem::new(row)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(Icon::Selected.glyph());
+
+        let mut window_state = ListState::default().with_offset(offset - start).with_selected(selected.map(|selected| selected - start));
+        f.render_stateful_widget(list, chunks[1], &mut window_state);
+        *app.tab.list_state.offset_mut() = start + window_state.offset();
+    }
+
+    // Details panel
+    let details_block = Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("details-title")));
+    let mut details_area = chunks[2];
+
+    let details_max_scroll = if let Some(repo) = app.get_selected_repo() {
+        let description = repo.description.clone().unwrap_or_else(|| tr!("details-no-description"));
+        let stars = repo.stargazers_count.unwrap_or(0);
+        let forks = repo.forks_count.unwrap_or(0);
+        let language = repo.language
+            .as_ref()
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| tr!("details-unknown"));
+        let url = repo.html_url.as_ref().map(|u| u.to_string()).unwrap_or_default();
+
+        let mut details_text = vec![
+            Line::from(vec![
+                Span::styled(field("details-description"), Style::default().fg(Color::Gray)),
+                Span::raw(description),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(Icon::Stars.label(field("details-stars")), Style::default().fg(Color::Yellow)),
+                Span::raw(clones::format_count(stars as u64)),
+                Span::raw("  "),
+                Span::styled(Icon::Forks.label(field("details-forks")), Style::default().fg(Color::Green)),
+                Span::raw(clones::format_count(forks as u64)),
+                Span::raw("  "),
+                Span::styled(Icon::Language.label(field("details-language")), Style::default().fg(Color::Blue)),
+                Span::raw(language),
+                Span::raw("  "),
+                Span::styled(Icon::Package.label(field("details-size")), Style::default().fg(Color::Gray)),
+                Span::raw(clones::format_size(repo.size.unwrap_or(0) as u64 * 1024)),
+            ]),
+        ];
+
+        // Results shown from an earlier session say how old they are
+        let restored_note = |kind: StoredKind| {
+            let computed = app.restored.get(&(url.clone(), kind))?;
+            Some(Span::styled(
+                format!("  {}", tr!("details-computed", age = clones::format_age(*computed, SystemTime::now()))),
+                Style::default().fg(Color::DarkGray),
+            ))
+        };
+
+        // Release cadence and contributors, fetched when the result is selected (fetch_details)
+        let releases_label = || Span::styled(Icon::Clock.label(field("details-releases")), Style::default().fg(Color::Cyan));
+        match app.activity.get(&url) {
+            Some(Ok(activity)) => {
+                let mut releases = compare::releases_label(&activity.releases, SystemTime::now());
+                if let Some(count) = activity.contributors {
+                    releases.push_str(&format!(" · {}", tr!("details-contributor-count", count = clones::format_count(count))));
+                }
+                details_text.push(Line::from(vec![releases_label(), Span::raw(releases)]));
+            }
+            Some(Err(_)) => details_text.push(Line::from(vec![
+                releases_label(),
+                Span::styled(tr!("details-unavailable"), Style::default().fg(Color::DarkGray)),
+            ])),
+            None if app.pending_activity.contains(&url) => details_text.push(Line::from(vec![
+                releases_label(),
+                Span::styled(tr!("details-loading"), Style::default().fg(Color::Yellow)),
+            ])),
+            None => {}
+        }
+
+        // Issue and pull-request responsiveness (Alt+I), next to the popularity figures
+        let issue_label = |id: &str| Span::styled(Icon::Clock.label(field(id)), Style::default().fg(Color::Yellow));
+        if app.pending_maintenance.contains(&url) {
+            details_text.push(Line::from(vec![
+                issue_label("details-issues"),
+                Span::styled(tr!("details-loading"), Style::default().fg(Color::Yellow)),
+            ]));
+        } else {
+            match app.maintenance.get(&url) {
+                Some(Ok(metrics)) => {
+                    let mut issues = vec![issue_label("details-issues"), Span::raw(maintenance::issues_label(metrics))];
+                    issues.extend(restored_note(StoredKind::Maintenance));
+                    details_text.push(Line::from(issues));
+                    details_text.push(Line::from(vec![
+                        issue_label("details-pulls"),
+                        Span::raw(maintenance::pulls_label(metrics)),
+                    ]));
+                    // One or two people making most of the commits is a risk worth a color
+                    let concentration = match maintenance::bus_factor(&metrics.contributors) {
+                        Some((1, _)) => Style::default().fg(Color::Red),
+                        Some((2, _)) => Style::default().fg(Color::Yellow),
+                        _ => Style::default(),
+                    };
+                    details_text.push(Line::from(vec![
+                        Span::styled(Icon::User.label(field("details-contributors")), Style::default().fg(Color::Yellow)),
+                        Span::styled(maintenance::contributors_label(metrics), concentration),
+                    ]));
+                }
+                Some(Err(e)) => details_text.push(Line::from(vec![
+                    issue_label("details-issues"),
+                    Span::styled(tr!("error-line", error = e.as_str()), Style::default().fg(Color::Red)),
+                ])),
+                None => {}
+            }
+        }
+        details_text.push(Line::from(""));
+
+        let now = SystemTime::now();
+        let mut times = Vec::new();
+        for (label, time) in [(field("details-pushed"), repo.pushed_at), (field("details-updated"), repo.updated_at)] {
+            if let Some(time) = time {
+                if !times.is_empty() {
+                    times.push(Span::raw("  "));
+                }
+                times.push(Span::styled(Icon::Clock.label(label), Style::default().fg(Color::Gray)));
+                times.push(Span::raw(clones::format_age(time.into(), now)));
+            }
+        }
+        if !times.is_empty() {
+            details_text.push(Line::from(times));
+            details_text.push(Line::from(""));
+        }
+
+        // Why the search matched it: the fragments GitHub found the query in, hits highlighted
+        if let Some(matches) = app.tab.text_matches.get(&repo.id.0) {
+            details_text.push(Line::from(Span::styled(Icon::Search.label(format!("{}:", tr!("details-matched"))), Style::default().fg(Color::Gray))));
+            for found in matches {
+                let mut line = vec![Span::styled(format!("  {}: ", found.property), Style::default().fg(Color::DarkGray))];
+                line.extend(text_match::segments(found).into_iter().map(|(text, hit)| match hit {
+                    true => Span::styled(text, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                    false => Span::raw(text),
+                }));
+                details_text.push(Line::from(line));
+            }
+            details_text.push(Line::from(""));
+        }
+
+        if let Some(visibility) = columns::visibility_label(repo) {
+            details_text.push(Line::from(Span::styled(
+                Icon::Private.label(tr!("details-visibility", visibility = visibility)),
+                Style::default().fg(Color::Yellow),
+            )));
+            details_text.push(Line::from(""));
+        }
+
+        if app.bookmarks.contains(&url) {
+            details_text.push(Line::from(Span::styled(
+                Icon::Bookmark.label(tr!("details-bookmarked")),
+                Style::default().fg(Color::Magenta),
+            )));
+            details_text.push(Line::from(""));
+        }
+
+        if let Some(conflict) = licenses::conflict(repo, &app.project_license) {
+            details_text.push(Line::from(vec![
+                Span::styled(Icon::Warning.label(field("details-license")), Style::default().fg(Color::Red)),
+                Span::raw(tr!("details-license-conflict", conflict = conflict, project = app.project_license.join(" OR "))),
+            ]));
+            details_text.push(Line::from(""));
+        }
+
+        if let Some(path) = app.cloned_paths.get(&url) {
+            details_text.push(Line::from(vec![
+                Span::styled(Icon::Cloned.label(field("details-cloned")), Style::default().fg(Color::Cyan)),
+                Span::raw(path.display().to_string()),
+            ]));
+            if let Some(info) = app.build_info.get(&url) {
+                details_text.push(Line::from(vec![
+                    Span::styled(Icon::Package.label(field("details-build-with")), Style::default().fg(Color::Cyan)),
+                    Span::raw(info.hint()),
+                ]));
+                if !info.toolchains.is_empty() {
+                    details_text.push(Line::from(vec![
+                        Span::styled(Icon::Package.label(field("details-toolchain")), Style::default().fg(Color::Cyan)),
+                        Span::raw(info.toolchains.join(", ")),
+                    ]));
+                }
+            }
+            details_text.push(Line::from(""));
+        }
+
+        // RustSec advisories against the clone's Cargo.lock (audit_after_clone)
+        let advisory_label = || Span::styled(Icon::Warning.label(field("details-advisories")), Style::default().fg(Color::Cyan));
+        if app.pending_audits.contains(&url) {
+            details_text.push(Line::from(vec![
+                advisory_label(),
+                Span::styled(tr!("details-checking"), Style::default().fg(Color::Yellow)),
+            ]));
+            details_text.push(Line::from(""));
+        } else if let Some(audit) = app.audits.get(&url) {
+            match audit {
+                Ok(findings) => {
+                    let color = match audit::worst(findings) {
+                        _ if findings.is_empty() => Color::Green,
+                        Some(Severity::High | Severity::Critical) => Color::Red,
+                        _ => Color::Yellow,
+                    };
+                    details_text.push(Line::from(vec![
+                        advisory_label(),
+                        Span::styled(audit::label(findings), Style::default().fg(color)),
+                    ]));
+                    details_text.extend(audit::finding_rows(findings).into_iter().map(Line::from));
+                }
+                Err(e) => details_text.push(Line::from(vec![
+                    advisory_label(),
+                    Span::styled(tr!("error-line", error = e.as_str()), Style::default().fg(Color::Red)),
+                ])),
+            }
+            details_text.push(Line::from(""));
+        }
+
+        // Output of the analysis profile commands run in the fresh clone ([analysis.*])
+        if app.pending_profiles.contains(&url) {
+            details_text.push(Line::from(vec![
+                Span::styled(Icon::Files.label(field("details-analysis")), Style::default().fg(Color::Magenta)),
+                Span::styled(tr!("details-running"), Style::default().fg(Color::Yellow)),
+            ]));
+            details_text.push(Line::from(""));
+        } else if let Some(sections) = app.profile_sections.get(&url) {
+            for section in sections {
+                details_text.push(Line::from(Span::styled(
+                    Icon::Files.label(format!("{}:", section.name)),
+                    Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+                )));
+                match &section.result {
+                    Ok(output) => {
+                        let lines: Vec<&str> = output.lines().collect();
+                        details_text.extend(lines.iter().take(profiles::MAX_SECTION_LINES).map(|line| Line::from(format!("  {}", line))));
+                        if lines.len() > profiles::MAX_SECTION_LINES {
+                            details_text.push(Line::from(Span::styled(
+                                format!("  {}", tr!("details-more-lines", count = lines.len() - profiles::MAX_SECTION_LINES)),
+                                Style::default().fg(Color::DarkGray),
+                            )));
+                        }
+                    }
+                    Err(e) => details_text.push(Line::from(Span::styled(format!("  {}", tr!("error-line", error = e.as_str())), Style::default().fg(Color::Red)))),
+                }
+                details_text.push(Line::from(""));
+            }
+        }
+
+        // Status of this repo's queued/running Alt+G clone
+        match app.pending_clones.get(&url) {
+            Some(CloneStatus::Queued) => {
+                details_text.push(Line::from(vec![
+                    Span::styled(Icon::Package.label(field("details-clone")), Style::default().fg(Color::Cyan)),
+                    Span::styled(tr!("details-queued"), Style::default().fg(Color::DarkGray)),
+                ]));
+                details_text.push(Line::from(""));
+            }
+            Some(CloneStatus::Running(start, progress)) => {
+                details_text.push(Line::from(vec![
+                    Span::styled(Icon::Package.label(field("details-cloning")), Style::default().fg(Color::Cyan)),
+                    Span::styled(spinner_label(*start, Instant::now()), Style::default().fg(Color::Yellow)),
+                ]));
+                if let Some(progress) = progress {
+                    details_text.push(clone_gauge_line(progress));
+                }
+                details_text.push(Line::from(""));
+            }
+            None => {}
+        }
+
+        // Add file count if available
+        if let Some(status) = app.pending_counts.get(&url) {
+            let text = match status {
+                CountStatus::Queued => tr!("details-queued"),
+                CountStatus::Running(start) => {
+                    tr!("details-cloning-counting", progress = spinner_label(*start, Instant::now()))
+                }
+            };
+            details_text.push(Line::from(vec![
+                Span::styled(Icon::Files.label(field("details-files")), Style::default().fg(Color::Magenta)),
+                Span::styled(text, Style::default().fg(Color::Yellow)),
+            ]));
+        } else if app.updating() {
+            match &app.clone_progress {
+                Some((clone_url, progress)) => {
+                    details_text.push(Line::from(vec![
+                        Span::styled(Icon::Package.label(field("details-cloning")), Style::default().fg(Color::Cyan)),
+                        Span::raw(clone_url.trim_start_matches("https://github.com/").to_string()),
+                        Span::raw(" "),
+                        Span::styled(app.progress_label(), Style::default().fg(Color::Yellow)),
+                    ]));
+                    details_text.push(clone_gauge_line(progress));
+                }
+                None => {
+                    details_text.push(Line::from(vec![
+                        Span::styled(Icon::Package.label(field("details-cloning")), Style::default().fg(Color::Cyan)),
+                        Span::styled(
+                            tr!("details-please-wait", progress = app.progress_label()),
+                            Style::default().fg(Color::Yellow),
+                        ),
+                    ]));
+                }
+            }
+        } else if let Some(count) = app.file_counts.get(&url) {
+            let mut header = vec![Span::styled(
+                Icon::Files.label(format!("{}:", tr!("details-file-count"))),
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            )];
+            header.extend(restored_note(StoredKind::FileCount));
+            details_text.push(Line::from(header));
+            details_text.push(Line::from(""));
+
+            match count {
+                Ok(stats) => details_text.extend(stats.to_string().lines().map(|line| Line::from(line.to_string()))),
+                Err(e) => details_text.push(Line::from(tr!("error-line", error = e.as_str()))),
+            }
+        } else {
+            details_text.push(Line::from(vec![
+                Span::styled(Icon::Files.label(field("details-files")), Style::default().fg(Color::Magenta)),
+                Span::styled(tr!("details-press-count"), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+
+        // Language table from Alt+A
+        match (app.pending_analyses.get(&url), app.analyses.get(&url)) {
+            (Some(status), _) => {
+                let text = match status {
+                    CountStatus::Queued => tr!("details-queued"),
+                    CountStatus::Running(start) => {
+                        tr!("details-cloning-analyzing", progress = spinner_label(*start, Instant::now()))
+                    }
+                };
+                details_text.push(Line::from(""));
+                details_text.push(Line::from(vec![
+                    Span::styled(Icon::Languages.label(field("details-languages")), Style::default().fg(Color::Magenta)),
+                    Span::styled(text, Style::default().fg(Color::Yellow)),
+                ]));
+            }
+            (None, Some(Ok(stats))) => {
+                details_text.push(Line::from(""));
+                let mut header = vec![Span::styled(
+                    Icon::Languages.label(format!("{}:", tr!("details-languages"))),
+                    Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+                )];
+                header.extend(restored_note(StoredKind::Languages));
+                details_text.push(Line::from(header));
+                details_text.extend(language_table(stats));
+            }
+            (None, Some(Err(e))) => {
+                details_text.push(Line::from(""));
+                details_text.push(Line::from(vec![
+                    Span::styled(Icon::Languages.label(field("details-languages")), Style::default().fg(Color::Magenta)),
+                    Span::styled(tr!("error-line", error = e.as_str()), Style::default().fg(Color::Red)),
+                ]));
+            }
+            (None, None) => {}
+        }
+
+        // Who makes the commits (Alt+I): the breakdown behind the contributor summary
+        if let Some(Ok(metrics)) = app.maintenance.get(&url).filter(|_| !app.pending_maintenance.contains(&url)) {
+            if !metrics.contributors.is_empty() {
+                details_text.push(Line::from(""));
+                details_text.push(Line::from(Span::styled(
+                    Icon::User.label(format!("{}:", tr!("details-contributors"))),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                )));
+                details_text.extend(maintenance::contributor_rows(&metrics.contributors).into_iter().map(Line::from));
+            }
+        }
+
+        // Star history (Alt+T): a chart across the bottom of the panel when there's room for one
+        match app.star_history.get(&url) {
+            _ if app.pending_star_history.contains(&url) => {
+                details_text.push(Line::from(""));
+                details_text.push(Line::from(vec![
+                    Span::styled(Icon::Stars.label(field("details-star-history")), Style::default().fg(Color::Yellow)),
+                    Span::styled(tr!("details-loading"), Style::default().fg(Color::Yellow)),
+                ]));
+            }
+            Some(Ok(history)) if details_area.height >= stars::CHART_HEIGHT + 8 => {
+                let [text, chart] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(stars::CHART_HEIGHT)]).areas(details_area);
+                stars::render_chart(f, chart, history);
+                details_area = text;
+            }
+            Some(Err(e)) => {
+                details_text.push(Line::from(""));
+                details_text.push(Line::from(vec![
+                    Span::styled(Icon::Stars.label(field("details-star-history")), Style::default().fg(Color::Yellow)),
+                    Span::styled(tr!("error-line", error = e.as_str()), Style::default().fg(Color::Red)),
+                ]));
+            }
+            _ => {}
+        }
+
+        details_text.push(Line::from(""));
+        details_text.push(Line::from(vec![
+            Span::styled(Icon::Link.label(""), Style::default().fg(Color::Cyan)),
+            Span::styled(url, Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED)),
+        ]));
+
+        // Create scrollable paragraph, clamping the offset to the wrapped content so it can't scroll past the end
+        let details = Paragraph::new(details_text)
+            .block(details_block)
+            .wrap(Wrap { trim: true });
+        let content_height = details.line_count(details_area.width.saturating_sub(2));
+        let max_scroll = content_height.saturating_sub(details_area.height as usize).min(u16::MAX as usize) as u16;
+        f.render_widget(details.scroll((app.tab.details_scroll.min(max_scroll), 0)), details_area);
+        max_scroll
+    } else {
+        let empty = Paragraph::new(tr!("details-empty"))
+            .block(details_block)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(empty, chunks[2]);
+        0
+    };
+    app.details_height = details_area.height.saturating_sub(2);
+    app.details_max_scroll = details_max_scroll;
+    app.tab.details_scroll = app.tab.details_scroll.min(details_max_scroll);
+
+    // Help text: plain letters while browsing, Alt shortcuts while typing
+    let browsing = app.focus == Focus::Browsing;
+    let key = |alt: &'static str, letter: &'static str| if browsing { letter } else { alt };
+    let mut help_spans = vec![
+        Span::styled("Enter", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", if browsing { tr!("help-open") } else { tr!("help-search") })),
+        Span::styled("Tab", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", if browsing { tr!("help-type") } else { tr!("help-browse") })),
+        Span::styled(key("Alt+O", "o"), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-open"))),
+        Span::styled(Icon::UpDown.glyph(), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-navigate"))),
+        Span::styled(key("Alt+F", "f"), Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-count"))),
+        Span::styled(key("Alt+A", "a"), Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-analyze"))),
+        Span::styled(key("Alt+G", "c"), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-clone"))),
+        Span::styled("Ctrl+Space", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-mark"))),
+        Span::styled(key("Alt+B", "b"), Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-bookmark"))),
+        Span::styled(key("Alt+Y", "y"), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-copy-url"))),
+        Span::styled(key("Alt+J", "J"), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-jobs"))),
+        Span::styled(key("Alt+M", "m"), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-my-clones"))),
+        Span::styled("1/2/3/0", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-filter"))),
+        Span::styled(key("Alt+H", "?"), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}  ", tr!("help-syntax"))),
+        Span::styled("Esc", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+        Span::raw(format!(": {}", if app.searching() || app.updating() || app.tab.resume_search.is_some() { tr!("help-cancel") } else { tr!("help-quit") })),
+    ];
+    // Shortcuts that only apply now, each as (key, message id, color)
+    let extras = [
+        (app.vim_mode, "j/k gg/G ^d/^u /", "help-vim", Color::Cyan),
+        (app.tab.suggestion.is_some(), key("Alt+S", "s"), "help-try-suggestion", Color::Cyan),
+        (app.tab.marked.len() == 2, key("Alt+V", "v"), "help-compare", Color::Cyan),
+        (app.total_pages() > 1, key("Alt+P", "p"), "help-page", Color::Cyan),
+        (app.tab_count() > 1, "Ctrl+Tab", "help-tabs", Color::Cyan),
+        (browsing, "Space", "help-more", Color::Yellow),
+    ];
+    for (_, key, id, color) in extras.into_iter().filter(|(shown, ..)| *shown) {
+        help_spans.push(Span::raw("  "));
+        help_spans.push(Span::styled(key, Style::default().fg(color).add_modifier(Modifier::BOLD)));
+        help_spans.push(Span::raw(format!(": {}", tr!(id))));
+    }
+    let help_text = vec![Line::from(help_spans)];
+
+    let help = Paragraph::new(help_text)
+        .block(Block::default().borders(Borders::ALL).title(format!(" {} ", tr!("help-title"))))
+        .alignment(Alignment::Center);
+    f.render_widget(help, chunks[4]);
+
+    render_status_bar(f, app, chunks[3]);
+
+    // Toasts float over the results/details area, above the status bar
+    app.toasts.prune(std::time::Instant::now());
+    let toast_area = ratatui::layout::Rect {
+        height: chunks[3].y.saturating_sub(chunks[0].y + chunks[0].height),
+        y: chunks[0].y + chunks[0].height,
+        ..chunks[1]
+    };
+    render_toasts(f, &app.toasts, toast_area);
+
+    if app.pending_leader {
+        let keys = LEADER_KEYS.iter().map(|(key, _, hint)| (*key, *hint));
+        render_chord_popup(f, toast_area, " Space + ", keys);
+    }
+    if app.pending_open {
+        let keys = OPEN_KEYS.iter().map(|(key, _, hint)| (*key, *hint));
+        render_chord_popup(f, toast_area, " Open ", keys);
+    }
+
+    if let Some(prompt) = &app.clone_prompt {
+        render_clone_prompt(f, prompt);
+    }
+
+    if app.show_clones {
+        render_clones_panel(f, app);
+    }
+
+    if let Some(rows) = app.comparison_rows() {
+        render_comparison(f, app, &rows);
+    }
+
+    if app.show_cheatsheet {
+        render_cheatsheet(f, app);
+    }
+
+    if app.show_jobs {
+        render_jobs_panel(f, app);
+    }
+
+    let area = f.area();
+    app.modals.render(f, area);
+
+    if app.monochrome {
+        strip_colors(f.buffer_mut());
+    }
+}
+
+/// Drop every color from a rendered frame, keeping bold and the like
+/// Highlighted cells (the only ones with a background) are shown reversed instead
+fn strip_colors(buffer: &mut ratatui::buffer::Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        cell.set_fg(Color::Reset).set_bg(Color::Reset);
+    }
+}
+
+/// "My clones" overlay: every local clone with its branch, size, and last fetch
+fn render_clones_panel(f: &mut Frame, app: &mut App) {
+    let area = centered_rect(80, 60, f.area());
+    let now = std::time::SystemTime::now();
+
+    let items: Vec<ListItem> = app
+        .local_clones
+        .iter()
+        .map(|clone| {
+            let fetched = clone.last_fetch.map_or("never fetched".to_string(), |time| clones::format_age(time, now));
+            let mark = if app.clones_marked.contains(&clone.path) { format!("{} ", Icon::Marked) } else { "  ".to_string() };
+            ListItem::new(Line::from(vec![
+                Span::styled(mark, Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:<32}", clone.name), Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    Icon::Branch.label(format!("{:<16}", clone.branch.as_deref().unwrap_or("(detached)"))),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(Icon::Disk.label(format!("{:<11}", clones::format_size(clone.size))), Style::default().fg(Color::Yellow)),
+                Span::styled(Icon::Clock.label(fetched), Style::default().fg(Color::Blue)),
+            ]))
+        })
+        .collect();
+
+    let total: u64 = app.local_clones.iter().map(|clone| clone.size).sum();
+    let sort = if app.clones_by_size { tr!("clones-by-size") } else { tr!("clones-by-name") };
+    let title = format!(
+        " {} ",
+        tr!("clones-title", clones = clone_count(app.local_clones.len()), size = clones::format_size(total), sort = sort)
+    );
+    let list = if items.is_empty() {
+        List::new(vec![ListItem::new(tr!("clones-empty"))])
+    } else {
+        List::new(items)
+    }
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_bottom(format!(" {} select, Space mark, s sort, u update, U update all, b SBOM, d delete, Esc close ", Icon::UpDown))
+            .border_style(Style::default().fg(Color::Cyan)),
+    )
+    .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+    .highlight_symbol(Icon::Selected.glyph());
+
+    f.render_widget(ratatui::widgets::Clear, area);
+    f.render_stateful_widget(list, area, &mut app.clones_state);
+}
+
+/// "1 clone" / "3 clones"
+fn clone_count(count: usize) -> String {
+    tr!("clone-count", count = count)
+}
+
+/// Label of a details-panel field, e.g. "Stars: ", from its message id
+fn field(id: &str) -> String {
+    format!("{}: ", tr!(id))
+}
+
+/// Login of a repository's owner ("unknown" if GitHub didn't send one)
+fn repo_owner(repo: &Repository) -> &str {
+    let from_name = || repo.full_name.as_deref()?.split_once('/').map(|(owner, _)| owner);
+    repo.owner.as_ref().map(|owner| owner.login.as_str()).or_else(from_name).unwrap_or("unknown")
+}
+
+/// Parse a clone depth: a positive number of commits, or "full"/0/empty for full history
+/// Returns None if the input is neither
+fn parse_depth(input: &str) -> Option<Option<u32>> {
+    let input = input.trim();
+    if input.is_empty() || input.eq_ignore_ascii_case("full") {
+        return Some(None);
+    }
+    input.parse::<u32>().ok().map(|depth| Some(depth).filter(|&d| d > 0))
+}
+
+/// Clone options dialog: destination and depth inputs, mode selector, and submodule toggle,
+/// with a hint for the focused row
+fn render_clone_prompt(f: &mut Frame, prompt: &ClonePrompt) {
+    let fields = prompt.fields();
+    let show_size = prompt.purpose == ClonePurpose::Clone && prompt.size_kb > 0;
+    let area = f.area();
+    let width = 72.min(area.width);
+    let height = (fields.len() as u16 + 5 + show_size as u16).min(area.height);
+    let rect = ratatui::layout::Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let count = prompt.urls.len();
+    let title = match prompt.purpose {
+        _ if prompt.updates_existing() => tr!("prompt-update"),
+        ClonePurpose::Clone => tr!("prompt-clone", count = count),
+        ClonePurpose::FileCount => tr!("prompt-count-files", count = count),
+        ClonePurpose::Analyze => tr!("prompt-analyze", count = count),
+    };
+    let hint = match (&prompt.error, prompt.focus) {
+        (Some(error), _) => Span::styled(error.clone(), Style::default().fg(Color::Red)),
+        (None, PromptField::Destination) if prompt.updates_existing() => {
+            Span::styled(tr!("prompt-already-cloned"), Style::default().fg(Color::Yellow))
+        }
+        (None, field) => {
+            let id = match field {
+                PromptField::Destination if count > 1 => "prompt-destination-batch",
+                PromptField::Destination => "prompt-destination",
+                PromptField::Depth => "prompt-depth",
+                PromptField::Mode => match prompt.mode {
+                    CloneMode::Full => "prompt-mode-full",
+                    CloneMode::Blobless => "prompt-mode-blobless",
+                    CloneMode::Treeless => "prompt-mode-treeless",
+                },
+                PromptField::Submodules => "prompt-submodules",
+            };
+            Span::styled(tr!(id), Style::default().fg(Color::DarkGray))
+        }
+    };
+
+    // Labels padded to the longest, so the values line up in any language
+    let size_label = tr!("field-size");
+    let label_width = fields
+        .iter()
+        .map(|field| field.label().chars().count())
+        .chain(show_size.then(|| size_label.chars().count()))
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let padded = |label: String| format!("{:<1$}", format!("{}:", label), label_width);
+
+    let mut lines: Vec<Line> = fields
+        .iter()
+        .map(|&field| {
+            let style = if prompt.focus == field {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Cyan)
+            };
+            let value = match field {
+                PromptField::Destination => prompt.destination.value().to_string(),
+                PromptField::Depth => prompt.depth.value().to_string(),
+                PromptField::Mode => format!("{} {} {}", Icon::Previous, prompt.mode.label(), Icon::Next),
+                PromptField::Submodules if prompt.submodules => format!("[x] {}", tr!("prompt-yes")),
+                PromptField::Submodules => format!("[ ] {}", tr!("prompt-no")),
+            };
+            Line::from(vec![Span::styled(padded(field.label()), style), Span::raw(value)])
+        })
+        .collect();
+    if show_size {
+        let color = if prompt.needs_size_confirm || prompt.error.is_some() { Color::Yellow } else { Color::DarkGray };
+        lines.push(Line::from(vec![
+            Span::styled(padded(size_label), Style::default().fg(Color::Cyan)),
+            Span::styled(
+                tr!("prompt-size", size = clones::format_size(prompt.size_kb * 1024)),
+                Style::default().fg(color),
+            ),
+        ]));
+    }
+    lines.push(Line::from(hint));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        tr!("prompt-keys", updown = Icon::UpDown.to_string(), leftright = Icon::LeftRight.to_string()),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", title))
+            .border_style(Style::default().fg(Color::Green)),
+    );
+    f.render_widget(ratatui::widgets::Clear, rect);
+    f.render_widget(widget, rect);
+
+    // Text cursor on the focused input row
+    let input = match prompt.focus {
+        PromptField::Destination => Some(&prompt.destination),
+        PromptField::Depth => Some(&prompt.depth),
+        PromptField::Mode | PromptField::Submodules => None,
+    };
+    if let Some(input) = input {
+        let row = fields.iter().position(|&field| field == prompt.focus).unwrap_or(0) as u16;
+        let cursor_x = rect.x + 1 + label_width as u16 + input.visual_cursor() as u16;
+        f.set_cursor_position((cursor_x.min(rect.x + rect.width.saturating_sub(2)), rect.y + 1 + row));
+    }
+}
+
+/// `text` as a line with the first occurrence of `part` in `style`, e.g. a query inside a translated sentence
+fn highlight(text: String, part: &str, style: Style) -> Line<'static> {
+    match text.split_once(part) {
+        Some((before, after)) if !part.is_empty() => Line::from(vec![
+            Span::raw(before.to_string()),
+            Span::styled(part.to_string(), style),
+            Span::raw(after.to_string()),
+        ]),
+        _ => Line::from(text),
+    }
+}
+
+/// Centered rectangle taking the given percentage of `area`
+fn centered_rect(percent_x: u16, percent_y: u16, area: ratatui::layout::Rect) -> ratatui::layout::Rect {
+    let width = area.width * percent_x / 100;
+    let height = area.height * percent_y / 100;
+    ratatui::layout::Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Which-key style hint after the leader or open key: every second key and what it does,
+/// in the bottom-right corner of `area`
+fn render_chord_popup<'a>(
+    f: &mut Frame,
+    area: ratatui::layout::Rect,
+    title: &str,
+    keys: impl Iterator<Item = (char, &'a str)>,
+) {
+    let lines: Vec<Line> = keys
+        .map(|(key, hint)| {
+            let key = if key == ' ' { "Space".to_string() } else { key.to_string() };
+            Line::from(vec![
+                Span::styled(format!(" {:<6}", key), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                Span::raw(hint),
+            ])
+        })
+        .collect();
+    let width = 28.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let rect = ratatui::layout::Rect {
+        x: area.x + area.width - width,
+        y: area.y + area.height - height,
+        width,
+        height,
+    };
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title.to_string())
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    f.render_widget(ratatui::widgets::Clear, rect);
+    f.render_widget(popup, rect);
+}
+
+/// Jobs overlay: every search, clone, and file count with its state and elapsed time
+fn render_jobs_panel(f: &mut Frame, app: &mut App) {
+    let area = centered_rect(70, 60, f.area());
+    let now = Instant::now();
+
+    let items: Vec<ListItem> = app
+        .jobs
+        .list
+        .records
+        .iter()
+        .rev()
+        .map(|job| {
+            let (state, color) = match &job.state {
+                JobState::Queued => ("queued".to_string(), Color::DarkGray),
+                JobState::Running => (spinner_label(job.started.unwrap_or(job.created), now), Color::Yellow),
+                JobState::Finished => ("done".to_string(), Color::Green),
+                JobState::Failed(e) => (format!("failed: {}", e), Color::Red),
+                JobState::Cancelled => ("cancelled".to_string(), Color::DarkGray),
+            };
+            let elapsed = if job.state == JobState::Running {
+                String::new() // Spinner label already shows the elapsed time
+            } else {
+                format!(" {:.1}s", job.elapsed(now).as_secs_f64())
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<11}", job.kind.label()), Style::default().fg(Color::Cyan)),
+                Span::raw(job.target.clone()),
+                Span::raw("  "),
+                Span::styled(format!("{}{}", state, elapsed), Style::default().fg(color)),
+            ]))
+        })
+        .collect();
+
+    let list = if items.is_empty() {
+        List::new(vec![ListItem::new(tr!("jobs-empty"))])
+    } else {
+        List::new(items)
+    }
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Jobs ({} select, x cancel, Esc close) ", Icon::UpDown))
+            .border_style(Style::default().fg(Color::Cyan)),
+    )
+    .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+    .highlight_symbol(Icon::Selected.glyph());
+
+    f.render_widget(ratatui::widgets::Clear, area);
+    f.render_stateful_widget(list, area, &mut app.jobs_state);
+}
+
+/// Search-syntax cheatsheet: general syntax on top, then every qualifier with an example
+fn render_cheatsheet(f: &mut Frame, app: &mut App) {
+    let area = centered_rect(80, 70, f.area());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Search syntax ({} select, Enter add to query, Esc close) ", Icon::UpDown))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    f.render_widget(ratatui::widgets::Clear, area);
+    f.render_widget(block, area);
+
+    let syntax_height = qualifiers::SYNTAX.len() as u16 + 1;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(syntax_height), Constraint::Min(0)])
+        .split(inner);
+    let example_width = qualifiers::QUALIFIERS.iter().map(|qualifier| qualifier.example.len()).max().unwrap_or(0) + 2;
+
+    let syntax: Vec<Line> = qualifiers::SYNTAX
+        .iter()
+        .map(|(example, meaning)| {
+            Line::from(vec![
+                Span::styled(format!("  {:<example_width$}", example), Style::default().fg(Color::Yellow)),
+                Span::styled(*meaning, Style::default().fg(Color::Gray)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(syntax), chunks[0]);
+
+    let items: Vec<ListItem> = qualifiers::QUALIFIERS
+        .iter()
+        .map(|qualifier| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<example_width$}", qualifier.example), Style::default().fg(Color::Cyan)),
+                Span::raw(qualifier.description),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+        .highlight_symbol(Icon::Selected.glyph());
+    f.render_stateful_widget(list, chunks[1], &mut app.cheatsheet_state);
+}
+
+/// Comparison overlay: the two marked repositories' figures in aligned columns
+fn render_comparison(f: &mut Frame, app: &App, rows: &[compare::Row]) {
+    let Some(pair) = &app.comparison else {
+        return;
+    };
+    let names = pair.each_ref().map(|repo| repo.full_name.clone().unwrap_or_else(|| repo.name.clone()));
+    let label_width = rows.iter().map(|row| row.label.chars().count()).max().unwrap_or(0) + 2;
+    let first_width = rows
+        .iter()
+        .map(|row| row.values[0].chars().count())
+        .chain([names[0].chars().count()])
+        .max()
+        .unwrap_or(0)
+        + 3;
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw(" ".repeat(label_width)),
+            Span::styled(format!("{:<first_width$}", names[0]), bold.fg(Color::Cyan)),
+            Span::styled(names[1].clone(), bold.fg(Color::Cyan)),
+        ]),
+        Line::from(""),
+    ];
+    for row in rows {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<label_width$}", row.label), Style::default().fg(Color::Gray)),
+            Span::raw(format!("{:<first_width$}", row.values[0])),
+            Span::raw(row.values[1].clone()),
+        ]));
+    }
+
+    let area = centered_rect(70, 50, f.area());
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Compare (Esc close) ")
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(ratatui::widgets::Clear, area);
+    f.render_widget(panel, area);
+}
+
+/// Single-line status bar: auth state, rate limit, filters, provider, and running jobs
+fn render_status_bar(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let separator = Span::styled(format!("  {}  ", Icon::Separator), Style::default().fg(Color::DarkGray));
+
+    let auth = match &app.auth_user {
+        Some(login) => Span::styled(Icon::User.label(login), Style::default().fg(Color::Green)),
+        None => Span::styled(Icon::User.label(tr!("status-anonymous")), Style::default().fg(Color::Gray)),
+    };
+
+    let rate = match app.rate_limit {
+        Some(rate) => {
+            let color = if rate.remaining == 0 {
+                Color::Red
+            } else if rate.remaining * 5 <= rate.limit {
+                Color::Yellow
+            } else {
+                Color::Gray
+            };
+            let text = match app.resume_in(unix_now()) {
+                Some(seconds) => format!("API {}/{} · {}", rate.remaining, rate.limit, tr!("status-resets-in", time = format_countdown(seconds))),
+                None => format!("API {}/{}", rate.remaining, rate.limit),
+            };
+            Span::styled(text, Style::default().fg(color))
+        }
+        None => Span::styled("API ?", Style::default().fg(Color::DarkGray)),
+    };
+
+    let filters = app.active_filters();
+    let filters = if filters.is_empty() {
+        Span::styled(tr!("status-no-filters"), Style::default().fg(Color::DarkGray))
+    } else {
+        Span::styled(filters.join(" "), Style::default().fg(Color::Cyan))
+    };
+
+    let mut spans = vec![
+        auth,
+        separator.clone(),
+        rate,
+        separator.clone(),
+        filters,
+        separator.clone(),
+        Span::styled(app.provider.clone(), Style::default().fg(Color::Gray)),
+    ];
+
+    let mode = match (app.focus, app.vim_mode) {
+        (Focus::Typing, false) => tr!("mode-typing"),
+        (Focus::Browsing, false) => tr!("mode-browsing"),
+        (Focus::Typing, true) => tr!("mode-insert"),
+        (Focus::Browsing, true) => tr!("mode-normal"),
+    };
+    spans.push(separator.clone());
+    spans.push(Span::styled(mode, Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)));
+
+    let jobs = app.running_jobs();
+    if !jobs.is_empty() {
+        spans.push(separator);
+        spans.push(Span::styled(
+            format!("{} {}", app.progress_label(), jobs.join(", ")),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Text progress bar of `width` cells for a percentage, e.g. "██████░░░░"
+fn gauge_bar(percent: u8, width: usize) -> String {
+    let filled = (percent.min(100) as usize * width) / 100;
+    format!("{}{}", Icon::BarFilled.glyph().repeat(filled), Icon::BarEmpty.glyph().repeat(width - filled))
+}
+
+/// Gauge line for clone progress: bar, percentage, phase, object counts, and transfer rate
+/// Per-language rows (files, code, comments, blanks) with a header and a total row
+fn language_table(stats: &[LanguageStats]) -> Vec<Line<'static>> {
+    if stats.is_empty() {
+        return vec![Line::from(Span::styled(format!("  {}", tr!("languages-none")), Style::default().fg(Color::DarkGray)))];
+    }
+    let row = |language: &LanguageStats| {
+        format!(
+            "  {:<16} {:>6} {:>9} {:>9} {:>8}",
+            language.name, language.files, language.code, language.comments, language.blanks
+        )
+    };
+    let header = format!(
+        "  {:<16} {:>6} {:>9} {:>9} {:>8}",
+        tr!("languages-language"),
+        tr!("languages-files"),
+        tr!("languages-code"),
+        tr!("languages-comments"),
+        tr!("languages-blanks")
+    );
+
+    let mut lines = vec![Line::from(Span::styled(header, Style::default().add_modifier(Modifier::BOLD)))];
+    lines.extend(stats.iter().map(|language| Line::from(row(language))));
+    lines.push(Line::from(Span::styled(
+        row(&analysis::total(stats)),
+        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+    )));
+    lines
+}
+
+fn clone_gauge_line(progress: &CloneProgress) -> Line<'static> {
+    let mut spans = vec![
+        Span::styled(gauge_bar(progress.percent, 24), Style::default().fg(Color::Cyan)),
+        Span::styled(format!(" {:>3}% ", progress.percent), Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(format!("{} ({}/{})", progress.phase, progress.current, progress.total)),
+    ];
+    if let Some(rate) = &progress.rate {
+        spans.push(Span::styled(format!("  {}", rate), Style::default().fg(Color::Green)));
+    }
+    Line::from(spans)
+}
+
+/// How a results-list column is drawn
+fn column_style(kind: ColumnKind) -> Style {
+    match kind {
+        ColumnKind::Name => Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD),
+        ColumnKind::Owner => Style::default().fg(Color::Gray),
+        ColumnKind::Stars => Style::default().fg(Color::Yellow),
+        ColumnKind::Forks => Style::default().fg(Color::Green),
+        ColumnKind::Language => Style::default().fg(Color::Blue),
+        ColumnKind::Size => Style::default().fg(Color::Gray),
+        ColumnKind::Pushed => Style::default().fg(Color::DarkGray),
+        ColumnKind::License => Style::default().fg(Color::Cyan),
+        ColumnKind::Files | ColumnKind::Loc => Style::default().fg(Color::Magenta),
+    }
+}
+
+/// When a repository last changed, e.g. "pushed 3 days ago"
+/// Falls back to the last update (metadata included) for repositories without pushes
+pub fn activity_label(repo: &Repository, now: SystemTime) -> Option<String> {
+    match (repo.pushed_at, repo.updated_at) {
+        (Some(pushed), _) => Some(format!("pushed {}", clones::format_age(pushed.into(), now))),
+        (None, Some(updated)) => Some(format!("updated {}", clones::format_age(updated.into(), now))),
+        (None, None) => None,
+    }
+}
+
+/// Spinner frame plus elapsed time since `start`, e.g. "⠹ 2.4s"
+fn spinner_label(start: Instant, now: Instant) -> String {
+    let elapsed = now.duration_since(start);
+    let frames = icons::spinner_frames();
+    let frame = (elapsed.as_millis() / TICK_RATE.as_millis()) as usize % frames.len();
+    format!("{} {:.1}s", frames[frame], elapsed.as_secs_f64())
+}
+
+/// Check whether a key press asks to cancel the running operation (Esc or Ctrl+X)
+fn is_cancel_key(key: &event::KeyEvent) -> bool {
+    key.code == KeyCode::Esc
+        || (key.code == KeyCode::Char('x') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+/// Check whether a key press edits text (no Alt/Ctrl modifiers)
+fn is_typing_key(key: &event::KeyEvent) -> bool {
+    !key.modifiers.intersects(KeyModifiers::ALT | KeyModifiers::CONTROL)
+        && matches!(key.code, KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete)
+}
+
+pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let terminal = Terminal::new(backend)?;
+    Ok(terminal)
+}
+
+pub fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::mock;
+
+    /// A repository fixture with the given id, name, and description
+    fn test_repo(id: u64, full_name: &str, description: &str) -> Repository {
+        mock::repo(full_name).id(id).description(description).build()
+    }
+
+    /// File count results totalling the given number of files
+    fn file_stats(total_files: u64) -> FileStats {
+        FileStats { total_files, total_size: 1024, extensions: Vec::new() }
+    }
+
+    fn key(code: KeyCode) -> event::KeyEvent {
+        event::KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Apply the events background jobs sent so far, as the TUI loop does each frame
+    fn drain_job_events(app: &mut App) {
+        for message in app.job_messages() {
+            app.update(message);
+        }
+    }
+
+    fn app_with_results(count: u64) -> App {
+        let mut app = App::new();
+        let results = (0..count)
+            .map(|i| test_repo(i, &format!("owner/repo{}", i), "a test repo"))
+            .collect();
+        app.set_results(results, count);
+        app
+    }
+
+    #[test]
+    fn test_app_initialization() {
+        let app = App::new();
+        assert!(app.tab.input.value().is_empty());
+        assert!(app.tab.results.is_empty());
+        assert_eq!(app.tab.repo_size_filter, None);
+        assert!(!app.searching());
+        assert!(app.pending_counts.is_empty());
+        assert!(!app.updating());
+    }
+
+    #[test]
+    fn test_set_size_filter() {
+        let mut app = App::new();
+
+        app.set_size_filter(Some("large".to_string()));
+        assert_eq!(app.tab.repo_size_filter, Some("large".to_string()));
+
+        app.set_size_filter(None);
+        assert_eq!(app.tab.repo_size_filter, None);
+    }
+
+    #[test]
+    fn test_digits_are_typed_into_the_query() {
+        let mut app = App::new();
+        app.focus = Focus::Typing;
+        for c in "top 10 2023".chars() {
+            handle_key(&mut app, key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.tab.input.value(), "top 10 2023");
+        assert_eq!(app.tab.repo_size_filter, None);
+
+        // While browsing they set the size filter
+        app.focus = Focus::Browsing;
+        handle_key(&mut app, key(KeyCode::Char('2')));
+        assert_eq!(app.tab.repo_size_filter, Some("medium".to_string()));
+        assert_eq!(app.tab.input.value(), "top 10 2023");
+    }
+
+    #[test]
+    fn test_details_scroll() {
+        let mut app = App::new();
+        app.details_max_scroll = 5;
+        app.details_height = 3;
+
+        assert_eq!(app.tab.details_scroll, 0);
+
+        app.scroll_details_down();
+        assert_eq!(app.tab.details_scroll, 1);
+
+        app.scroll_details_down();
+        assert_eq!(app.tab.details_scroll, 2);
+
+        app.scroll_details_up();
+        assert_eq!(app.tab.details_scroll, 1);
+
+        app.scroll_details_up();
+        assert_eq!(app.tab.details_scroll, 0);
+
+        // Test that it doesn't go below 0
+        app.scroll_details_up();
+        assert_eq!(app.tab.details_scroll, 0);
+
+        // Test reset
+        app.scroll_details_down();
+        app.reset_details_scroll();
+        assert_eq!(app.tab.details_scroll, 0);
+
+        // Pages keep a line of overlap and stop at the end of the content
+        app.page_details(1);
+        assert_eq!(app.tab.details_scroll, 2);
+        app.page_details(5);
+        assert_eq!(app.tab.details_scroll, 5);
+        app.scroll_details_down();
+        assert_eq!(app.tab.details_scroll, 5);
+        app.page_details(-1);
+        assert_eq!(app.tab.details_scroll, 3);
+        app.reset_details_scroll();
+        app.scroll_details_to_end();
+        assert_eq!(app.tab.details_scroll, 5);
+    }
+
+    #[test]
+    fn test_details_scroll_is_clamped_to_content() {
+        let mut app = app_with_results(1);
+        app.tab.details_scroll = 500;
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(100, 60)).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        // The short test repo fits in the panel, so there's nothing to scroll
+        assert_eq!(app.details_max_scroll, 0);
+        assert_eq!(app.tab.details_scroll, 0);
+        assert!(app.details_height > 0);
+
+        // A description that wraps past the bottom scrolls only until its last line shows
+        app.set_results(vec![test_repo(0, "owner/long", &"word ".repeat(1000))], 1);
+        app.tab.details_scroll = 500;
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        assert!(app.details_max_scroll > 0);
+        assert_eq!(app.tab.details_scroll, app.details_max_scroll);
+    }
+
+    #[test]
+    fn test_get_selected_repo_with_no_results() {
+        let mut app = App::new();
+
+        // No selection returns None
+        assert!(app.get_selected_repo().is_none());
+
+        // With selection but no results returns None
+        app.tab.list_state.select(Some(0));
+        assert!(app.get_selected_repo().is_none());
+    }
+
+    #[test]
+    fn test_input_operations() {
+        let mut app = App::new();
+
+        // Test that input starts empty
+        assert!(app.tab.input.value().is_empty());
+
+        // Test Alt+C clear functionality (input.reset())
+        // We can't easily test the actual key handler without mocking terminal input,
+        // but we can test the underlying function
+        app.tab.input = Input::from("test query");
+        assert_eq!(app.tab.input.value(), "test query");
+
+        app.tab.input.reset();
+        assert!(app.tab.input.value().is_empty());
+    }
+
+    #[test]
+    fn test_searching_and_counting_flags() {
+        let mut app = App::new();
+
+        assert!(!app.searching());
+        assert!(app.pending_counts.is_empty());
+        assert!(!app.updating());
+
+        app.tab.search_job = Some(app.jobs.list.add(JobKind::Search, "rust"));
+        assert!(app.searching());
+
+        app.pending_counts.insert("https://github.com/owner/repo".to_string(), CountStatus::Queued);
+        assert!(!app.pending_counts.is_empty());
+
+        let update = app.jobs.list.add(JobKind::Update, "repositories/repo");
+        assert!(app.updating());
+        app.jobs.list.finish(update, None);
+        assert!(!app.updating());
+    }
+
+    #[test]
+    fn test_set_results_auto_selects_first() {
+        let mut app = App::new();
+
+        // set_results auto-selects first item when results is not empty
+        app.set_results(Vec::new(), 0);
+        assert!(app.tab.list_state.selected().is_none());
+
+        // We can't easily create a full Repository here, so we test the logic
+        // The actual functionality is: if results is not empty, select index 0
+    }
+
+    #[test]
+    fn test_navigation_wrapping() {
+        let mut app = App::new();
+
+        // Test that navigation doesn't panic with empty results
+        app.next();
+        assert!(app.tab.list_state.selected().is_none());
+
+        app.previous();
+        assert!(app.tab.list_state.selected().is_none());
+    }
+
+    #[test]
+    fn test_alt_o_key_handler_returns_url() {
+        // This test verifies that when Alt+O is pressed on a selected repo,
+        // the TUI returns the URL (for opening in browser) but does NOT exit.
+        // The actual non-exit behavior is tested in main.rs where the loop continues
+        // instead of breaking when handling a URL action.
+
+        let app = App::new();
+
+        // We can't easily create a mock Repository without serde_json,
+        // but we can verify the logic: when there are results and a selection,
+        // get_selected_repo() should return the repo with its html_url.
+        // The key handler in run_tui() at line 170-178 returns Ok(Some(url))
+        // which is then handled in main.rs at line 142-145 without calling break.
+
+        // Test 1: No results - should not crash
+        assert!(app.get_selected_repo().is_none());
+
+        // Test 2: Results exist and selection is made - selection should work
+        // (Full integration test would require actual Repository objects)
+        assert_eq!(app.tab.results.len(), 0);
+    }
+
+    #[test]
+    fn test_select_first_and_last() {
+        let mut app = app_with_results(5);
+
+        app.select_last();
+        assert_eq!(app.tab.list_state.selected(), Some(4));
+
+        app.select_first();
+        assert_eq!(app.tab.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_half_page_jumps_clamp_without_wrapping() {
+        let mut app = app_with_results(10);
+        app.results_height = 6;
+
+        app.half_page_down();
+        assert_eq!(app.tab.list_state.selected(), Some(3));
+
+        app.half_page_down();
+        app.half_page_down();
+        app.half_page_down();
+        assert_eq!(app.tab.list_state.selected(), Some(9));
+
+        app.half_page_up();
+        assert_eq!(app.tab.list_state.selected(), Some(6));
+
+        app.half_page_up();
+        app.half_page_up();
+        app.half_page_up();
+        assert_eq!(app.tab.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_pages() {
+        let mut app = App::new();
+        app.per_page = 10;
+        let page = |start: u64| (start..start + 10).map(|i| test_repo(i, &format!("owner/repo{}", i), "")).collect();
+        app.set_results(page(0), 1642);
+        // GitHub only serves the first 1000 results
+        assert_eq!(app.total_pages(), 100);
+        assert_eq!(app.current_page(), 1);
+
+        // Pages past the loaded ones are fetched...
+        assert_eq!(app.go_to_page(3), Some(Action::Page(3)));
+        let id = app.jobs.list.add(JobKind::Search, "rust (page 3)");
+        app.tab.page_job = Some((id, 3));
+        assert!(app.running_jobs().contains(&"loading results"));
+        // ...and shown when the target arrives
+        app.jobs.tx.send(JobEvent::PageLoaded { id, page: 2, done: false, result: Ok((page(10), 1642, TextMatches::new())) }).unwrap();
+        drain_job_events(&mut app);
+        assert_eq!((app.pages_loaded(), app.current_page()), (2, 1));
+        app.jobs.tx.send(JobEvent::PageLoaded { id, page: 3, done: true, result: Ok((page(20), 1642, TextMatches::new())) }).unwrap();
+        drain_job_events(&mut app);
+        assert_eq!((app.pages_loaded(), app.current_page()), (3, 3));
+        assert_eq!(app.get_selected_repo().unwrap().id.0, 20);
+        assert!(app.tab.page_job.is_none());
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Finished);
+
+        // Loaded pages are a jump away, and out-of-range ones only warn
+        assert_eq!(app.go_to_page(2), None);
+        assert_eq!(app.current_page(), 2);
+        assert_eq!(app.go_to_page(101), None);
+        assert_eq!(app.current_page(), 2);
+
+        // A new search starts over at one page
+        app.set_results(page(0), 5);
+        assert_eq!((app.pages_loaded(), app.total_pages()), (1, 1));
+    }
+
+    #[test]
+    fn test_duplicate_results_are_skipped() {
+        let mut app = App::new();
+        app.per_page = 3;
+        let repos = |ids: &[u64]| ids.iter().map(|&i| test_repo(i, &format!("owner/repo{}", i), "")).collect();
+        app.tab.last_query = "rust".to_string();
+        app.set_results(repos(&[1, 2, 2, 3]), 9);
+        assert_eq!(app.tab.results.len(), 3);
+
+        // A repo repeated from the previous page isn't added twice, and pages still start where they did
+        app.tab.list_state.select(Some(1));
+        app.append_results(repos(&[3, 4, 5]), 9);
+        let ids: Vec<u64> = app.tab.results.iter().map(|repo| repo.id.0).collect();
+        assert_eq!(ids, [1, 2, 3, 4, 5]);
+        assert_eq!(app.get_selected_repo().unwrap().id.0, 2);
+        app.go_to_page(2);
+        assert_eq!(app.get_selected_repo().unwrap().id.0, 4);
+        assert_eq!(app.current_page(), 2);
+
+        // Repeating the search keeps the same repo selected, even if it moved
+        app.set_results(repos(&[9, 1, 4]), 9);
+        assert_eq!(app.get_selected_repo().unwrap().id.0, 4);
+        // A different search starts at the top
+        app.tab.last_query = "go".to_string();
+        app.set_results(repos(&[9, 1, 4]), 9);
+        assert_eq!(app.tab.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_infinite_scroll() {
+        let mut app = App::new();
+        app.per_page = 30;
+        let repos = (0..30).map(|i| test_repo(i, &format!("owner/repo{}", i), "")).collect();
+        app.set_results(repos, 100);
+        assert_eq!(app.next_page_due(), None, "the top of the list needs nothing more");
+
+        app.tab.list_state.select(Some(20));
+        assert_eq!(app.next_page_due(), Some(Action::Page(2)));
+        // Not while a page is on its way, filtering, or switched off
+        app.tab.page_job = Some((app.jobs.list.add(JobKind::Search, "rust (page 2)"), 2));
+        assert_eq!(app.next_page_due(), None);
+        app.tab.page_job = None;
+        app.tab.filter_input = Input::new("repo2".to_string());
+        assert_eq!(app.next_page_due(), None);
+        app.tab.filter_input.reset();
+        app.infinite_scroll = false;
+        assert_eq!(app.next_page_due(), None);
+        app.infinite_scroll = true;
+
+        // A failed page stops automatic loading until the next search
+        let id = app.jobs.list.add(JobKind::Search, "rust (page 2)");
+        app.tab.page_job = Some((id, 2));
+        app.jobs.tx.send(JobEvent::PageLoaded { id, page: 2, done: true, result: Err("timed out".to_string()) }).unwrap();
+        drain_job_events(&mut app);
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Failed("timed out".to_string()));
+        assert_eq!(app.next_page_due(), None);
+        app.set_results(vec![test_repo(0, "owner/repo0", "")], 100);
+        assert_eq!(app.next_page_due(), Some(Action::Page(2)));
+    }
+
+    #[test]
+    fn test_page_prompt() {
+        let mut app = app_with_results(3);
+        app.open_page_prompt();
+        assert!(app.modals.is_empty(), "one page has nowhere to jump");
+
+        app.per_page = 1;
+        app.tab.page_starts = vec![0, 1, 2];
+        app.run_shortcut(Shortcut::GoToPage);
+        assert_eq!(app.confirm_page_prompt("9"), None);
+        assert!(!app.modals.is_empty());
+        assert_eq!(app.confirm_page_prompt("3"), None);
+        assert!(app.modals.is_empty());
+        assert_eq!(app.current_page(), 3);
+    }
+
+    #[test]
+    fn test_raw_query_turns_off_size_and_owner_filters() {
+        let mut app = App::new();
+        app.raw_query = true;
+        app.set_size_filter(Some("large".to_string()));
+        app.open_scope_prompt();
+        assert!(app.tab.repo_size_filter.is_none() && app.modals.is_empty());
+        assert_eq!(app.toasts.items.len(), 2);
+        assert!(app.toasts.items[0].message.contains("--raw-query"));
+    }
+
+    #[test]
+    fn test_owner_scope_prompt() {
+        let alt = |c| event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+        let mut app = app_with_results(3);
+        app.tab.last_query = "parser".to_string();
+
+        // Not a login: the dialog stays open
+        let mut keys = vec![alt('u')];
+        keys.extend(type_keys("rust lang"));
+        keys.push(key(KeyCode::Enter));
+        let (_, action) = run_keys(&mut app, &keys);
+        assert_eq!(action, None);
+        assert!(!app.modals.is_empty() && app.owner_scope.is_none());
+        app.modals.close(Purpose::OwnerScope);
+
+        // A scope re-runs the last search and is shown in the search box's title
+        let mut keys = vec![alt('u')];
+        keys.extend(type_keys("org:rust-lang"));
+        keys.push(key(KeyCode::Enter));
+        let (rows, action) = run_keys(&mut app, &keys);
+        assert_eq!(action, Some(Action::Search("parser".to_string())));
+        assert_eq!(app.owner_scope.as_deref(), Some("org:rust-lang"));
+        assert!(rows[2].contains("Search GitHub Repositories │ org:rust-lang"), "{}", rows[2]);
+
+        // u while browsing opens it prefilled: resubmitting changes nothing, emptying it searches everywhere again
+        app.focus = Focus::Browsing;
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('u')), key(KeyCode::Enter)]);
+        assert_eq!(action, None);
+        assert!(app.modals.is_empty());
+        assert_eq!(app.owner_scope.as_deref(), Some("org:rust-lang"));
+        app.open_scope_prompt();
+        assert_eq!(app.confirm_scope_prompt(""), Some(Action::Search("parser".to_string())));
+        assert!(app.owner_scope.is_none() && app.modals.is_empty());
+    }
+
+    #[test]
+    fn test_find_similar_opens_a_tab() {
+        let mut app = app_with_results(2);
+        assert_eq!(app.run_shortcut(Shortcut::Similar), None, "no topics or language");
+        assert_eq!(app.tab_count(), 1);
+
+        let mut repo = test_repo(9, "bevyengine/bevy", "");
+        repo.topics = Some(vec!["ecs".to_string(), "game-engine".to_string()]);
+        repo.language = Some("Rust".into());
+        app.set_results(vec![repo], 1);
+        app.focus = Focus::Browsing;
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('r'))]);
+        assert_eq!(action, Some(Action::Search("topic:ecs topic:game-engine language:rust".to_string())));
+        assert_eq!((app.tab_count(), app.active_tab), (2, 1));
+        assert_eq!(app.tab.input.value(), "topic:ecs topic:game-engine language:rust");
+        assert_eq!(app.tabs[0].results.len(), 1, "the original search stays in its tab");
+    }
+
+    #[test]
+    fn test_awesome_lists() {
+        let alt = |c| event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+        let mut app = app_with_results(2);
+
+        // Not on a list: asks for a topic and searches for lists about it in a new tab
+        let mut keys = vec![alt('l')];
+        keys.extend(type_keys("game engine"));
+        keys.push(key(KeyCode::Enter));
+        let (_, action) = run_keys(&mut app, &keys);
+        assert_eq!(action, Some(Action::Search("awesome-game-engine in:name".to_string())));
+        assert_eq!(app.tab_count(), 2);
+
+        // On a list: loads the repositories it links to
+        app.set_results(vec![test_repo(5, "rust-unofficial/awesome-rust", "")], 1);
+        app.focus = Focus::Browsing;
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('l'))]);
+        assert_eq!(action, Some(Action::LoadList("rust-unofficial/awesome-rust".to_string())));
+
+        let list = "rust-unofficial/awesome-rust".to_string();
+        let repos = awesome::linked_repos(include_str!("../tests/fixtures/awesome_readme.md"), &list);
+        let id = app.jobs.list.add(JobKind::List, list.as_str());
+        app.apply_job_event(JobEvent::ListLoaded { id, list: list.clone(), result: Ok(repos) });
+        assert_eq!((app.tab_count(), app.tab.results.len()), (3, 3));
+        assert_eq!(app.tab.collection.as_deref(), Some("rust-unofficial/awesome-rust"));
+        assert_eq!(app.total_pages(), 1);
+        assert_eq!(app.next_page_due(), None);
+        let (rows, _) = run_keys(&mut app, &[]);
+        let screen = rows.join("\n");
+        assert!(screen.contains("rust-unofficial/awesome-rust (3 linked repositories)"), "{}", screen);
+        assert!(screen.contains("bevyengine/bevy"));
+
+        // The scope doesn't re-run a list as a search; a new search replaces it
+        assert_eq!(app.confirm_scope_prompt("org:rust-lang"), None);
+        app.begin_search("ecs");
+        assert!(app.tab.collection.is_none());
+
+        let id = app.jobs.list.add(JobKind::List, "me/awesome-nothing");
+        app.apply_job_event(JobEvent::ListLoaded { id, list: "me/awesome-nothing".to_string(), result: Ok(Vec::new()) });
+        assert_eq!(app.tab_count(), 3);
+    }
+
+    #[test]
+    fn test_star_history_chart() {
+        let alt = |c| event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+        let mut app = app_with_results(1);
+        let url = "https://github.com/owner/repo0".to_string();
+        let (_, action) = run_keys(&mut app, &[alt('t')]);
+        assert_eq!(action, Some(Action::StarHistory(url.clone(), 0)));
+
+        // Loading: one fetch at a time
+        app.pending_star_history.insert(url.clone());
+        let (rows, action) = run_keys(&mut app, &[alt('t')]);
+        assert_eq!(action, None);
+        assert!(panel_text(&rows).iter().any(|row| row.ends_with("Star history: Loading...")), "{}", rows.join("\n"));
+
+        let id = app.jobs.list.add(JobKind::Stars, url.as_str());
+        let history = vec![(SystemTime::UNIX_EPOCH, 1), (SystemTime::UNIX_EPOCH + Duration::from_secs(90 * 86400), 40)];
+        app.apply_job_event(JobEvent::StarHistoryLoaded { id, url: url.clone(), result: Ok(history) });
+        let (rows, _) = run_keys(&mut app, &[]);
+        let screen = rows.join("\n");
+        assert!(screen.contains(" Star history "), "{}", screen);
+        assert!(screen.contains("1970-01") && screen.contains("1970-04"), "{}", screen);
+
+        // Pressed again, hides the chart
+        let (rows, action) = run_keys(&mut app, &[alt('t')]);
+        assert_eq!(action, None);
+        assert!(!rows.join("\n").contains(" Star history "));
+
+        // A failed fetch says why and is retried on the next press
+        let id = app.jobs.list.add(JobKind::Stars, url.as_str());
+        app.apply_job_event(JobEvent::StarHistoryLoaded { id, url: url.clone(), result: Err("rate limited".into()) });
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows.join("\n").contains("Error: rate limited"), "{}", rows.join("\n"));
+        let (_, action) = run_keys(&mut app, &[alt('t')]);
+        assert_eq!(action, Some(Action::StarHistory(url, 0)));
+    }
+
+    #[test]
+    fn test_analysis_results_are_restored() {
+        let mut app = app_with_results(1);
+        app.focus = Focus::Browsing;
+        let url = "https://github.com/owner/repo0".to_string();
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('n'))]);
+        assert_eq!(action, None, "nothing stored yet");
+
+        let id = app.jobs.list.add(JobKind::FileCount, url.as_str());
+        app.apply_job_event(JobEvent::FileCountFinished { id, url: url.clone(), result: Ok(file_stats(42)) });
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(!rows.join("\n").contains("computed"), "fresh results carry no note");
+
+        // The next session shows the stored count and when it was computed
+        let mut next = App::new();
+        next.analysis_store = std::mem::take(&mut app.analysis_store);
+        next.set_results(app.tab.results.clone(), 1);
+        next.focus = Focus::Browsing;
+        assert_eq!(next.file_counts.get(&url), Some(&Ok(file_stats(42))));
+        let (rows, _) = run_keys(&mut next, &[]);
+        assert!(rows.join("\n").contains("File Count:  (computed just now, Alt+N refreshes)"), "{}", rows.join("\n"));
+        assert!(rows.join("\n").contains("Total: 42 files, 1.0 KiB"), "{}", rows.join("\n"));
+
+        // Refreshing counts again
+        let (rows, action) = run_keys(&mut next, &[key(KeyCode::Char('n'))]);
+        assert_eq!(action, Some(Action::FileCount(vec![url])));
+        assert!(!rows.join("\n").contains("Total: 42 files"));
+        assert!(next.restored.is_empty() && next.refresh_due.is_empty());
+    }
+
+    #[test]
+    fn test_private_results_are_marked() {
+        let mut app = app_with_results(2);
+        app.tab.results[0].private = Some(true);
+        app.tab.results[1].visibility = Some("internal".to_string());
+        let (rows, _) = run_keys(&mut app, &[]);
+        let marked = rows.iter().filter(|row| row.contains("owner/repo") && row.contains(&format!(" {}", Icon::Private))).count();
+        assert_eq!(marked, 2, "{}", rows.join("\n"));
+        assert!(rows.join("\n").contains("Private repository"), "{}", rows.join("\n"));
+
+        app.next();
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows.join("\n").contains("Internal repository"), "{}", rows.join("\n"));
+    }
+
+    #[test]
+    fn test_text_matches_in_details() {
+        let mut app = App::new();
+        let bevy = fixture_repos()[1].id.0;
+        let found = text_match::TextMatch {
+            property: "description".to_string(),
+            fragment: "A refreshingly simple data-driven game engine built in Rust".to_string(),
+            hits: vec!["game".to_string()],
+        };
+        app.begin_search("game");
+        let id = app.jobs.list.add(JobKind::Search, "game");
+        app.jobs.list.start(id);
+        app.tab.search_job = Some(id);
+        let result = Ok((fixture_repos(), 3, TextMatches::from([(bevy, vec![found])]), None, None));
+        app.jobs.tx.send(JobEvent::SearchFinished { id, query: "game".to_string(), result, rate_limit: None }).unwrap();
+        drain_job_events(&mut app);
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(!rows.join("\n").contains("Matched:"), "rust-lang/rust came without text matches");
+
+        app.next();
+        let (rows, _) = run_keys(&mut app, &[]);
+        let screen = rows.join("\n");
+        assert!(screen.contains("Matched:"), "{}", screen);
+        assert!(screen.contains("description: A refreshingly simple"), "{}", screen);
+
+        // A new search drops them
+        app.set_results(fixture_repos(), 3);
+        assert!(app.tab.text_matches.is_empty());
+    }
+
+    #[test]
+    fn test_reload_skips_the_cache() {
+        let mut app = app_with_results(2);
+        let ctrl_r = event::KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        let (_, action) = run_keys(&mut app, &[ctrl_r]);
+        assert_eq!(action, None, "no search to refresh yet");
+
+        app.tab.last_query = "game engine".to_string();
+        app.tab.cached_at = Some(cache::Hit::Fresh(SystemTime::now()));
+        let (rows, action) = run_keys(&mut app, &[ctrl_r]);
+        assert_eq!(action, Some(Action::Reload("game engine".to_string())));
+        assert!(rows.join("\n").contains("[cached just now, Ctrl+R refreshes]"), "{}", rows.join("\n"));
+    }
+
+    #[test]
+    fn test_details_prefetch() {
+        let mut app = app_with_results(5);
+        assert_eq!(app.update(AppMessage::Tick), Flow::Continue, "off unless configured");
+        app.fetch_details = true;
+        let url = |n: usize| format!("https://github.com/owner/repo{}", n);
+        // The selected result's figures are asked for like a comparison's, the rest as prefetches
+        let fetch = |n: usize, selected: bool| Flow::Run(if selected { Action::Compare(vec![url(n)]) } else { Action::Prefetch(url(n)) });
+        let load = |app: &mut App, n: usize, selected: bool, result: Result<RepoActivity, String>| {
+            assert_eq!(app.update(AppMessage::Tick), fetch(n, selected));
+            app.pending_activity.insert(url(n));
+            assert_eq!(app.update(AppMessage::Tick), Flow::Continue, "one fetch at a time");
+            let id = app.jobs.list.add(JobKind::Activity, url(n));
+            app.apply_job_event(JobEvent::ActivityLoaded { id, url: url(n), result });
+        };
+        // The selected result first, then the two after it
+        let activity = RepoActivity { releases: Vec::new(), contributors: Some(7) };
+        load(&mut app, 0, true, Ok(activity.clone()));
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows.join("\n").contains("Releases: no releases · 7 contributors"), "{}", rows.join("\n"));
+        load(&mut app, 1, false, Ok(activity.clone()));
+        load(&mut app, 2, false, Ok(activity));
+        assert_eq!(app.update(AppMessage::Tick), Flow::Continue);
+
+        // Moving down reaches further; a failure stops fetching, without a toast, until the next search
+        app.next();
+        load(&mut app, 3, false, Err("API rate limit exceeded".to_string()));
+        assert!(app.toasts.items.is_empty());
+        app.next();
+        assert_eq!(app.update(AppMessage::Tick), Flow::Continue);
+        app.set_results(app.tab.results.clone(), 5);
+        assert_eq!(app.update(AppMessage::Tick), fetch(4, false));
+    }
+
+    #[test]
+    fn test_deferred_prefetch_is_fetched_once_selected() {
+        let mut app = app_with_results(3);
+        app.fetch_details = true;
+        app.activity.insert("https://github.com/owner/repo0".to_string(), Ok(RepoActivity { releases: Vec::new(), contributors: None }));
+        let url = "https://github.com/owner/repo1".to_string();
+        assert_eq!(app.update(AppMessage::Tick), Flow::Run(Action::Prefetch(url.clone())));
+        app.pending_activity.insert(url.clone());
+        let id = app.jobs.list.add(JobKind::Activity, url.clone());
+        app.apply_job_event(JobEvent::ActivityDeferred { id, url: url.clone() });
+
+        // Neither a failure nor a pause: the prefetch moves on, and selecting the result fetches it
+        assert!(!app.details_paused && !app.activity.contains_key(&url));
+        assert!(app.toasts.items.is_empty());
+        assert_eq!(app.update(AppMessage::Tick), Flow::Run(Action::Prefetch("https://github.com/owner/repo2".to_string())));
+        app.next();
+        assert_eq!(app.update(AppMessage::Tick), Flow::Run(Action::Compare(vec![url])));
+    }
+
+    #[test]
+    fn test_large_result_lists_scroll_like_small_ones() {
+        let mut app = app_with_results(3000);
+        app.tab.list_state.select(Some(2500));
+        let (rows, _) = run_keys(&mut app, &[]);
+        let screen = rows.join("\n");
+        let offset = app.tab.list_state.offset();
+        assert!(offset <= 2500 && 2500 - offset < 30, "offset {}", offset);
+        assert!(screen.contains("owner/repo2500") && screen.contains(&format!("owner/repo{} ", offset)), "{}", screen);
+        assert!(!screen.contains("owner/repo2501 "), "{}", screen);
+
+        // Moving up within the view keeps it where it is, past its top scrolls it by one
+        app.tab.list_state.select(Some(offset));
+        run_keys(&mut app, &[]);
+        assert_eq!(app.tab.list_state.offset(), offset);
+        app.tab.list_state.select(Some(offset - 1));
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert_eq!(app.tab.list_state.offset(), offset - 1);
+        assert!(rows.join("\n").contains(&format!("owner/repo{} ", offset - 1)));
+    }
+
+    #[test]
+    fn test_very_short_terminal_draws() {
+        // The results panel has no rows inside its borders at these heights
+        for height in 1..=10 {
+            let mut app = app_with_results(5);
+            app.tab.list_state.select(Some(3));
+            let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(100, height)).unwrap();
+            terminal.draw(|f| ui(f, &mut app)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_repeated_search_keeps_selection() {
+        let mut app = app_with_results(5);
+        app.tab.last_query = "rust".to_string();
+        app.set_results(app.tab.results.clone(), 5);
+        app.tab.list_state.select(Some(3));
+        app.tab.details_scroll = 2;
+
+        // The same search in another order (a new sort, or fresh results): repo3 stays selected
+        let mut reordered = app.tab.results.clone();
+        reordered.reverse();
+        app.set_results(reordered, 5);
+        assert_eq!(app.get_selected_repo().unwrap().name, "repo3");
+        assert_eq!(app.tab.list_state.selected(), Some(1));
+        assert_eq!(app.tab.details_scroll, 2);
+
+        // Gone from the new results: the first is selected, scrolled to the top
+        let without: Vec<Repository> = app.tab.results.iter().filter(|repo| repo.name != "repo3").cloned().collect();
+        app.set_results(without, 4);
+        assert_eq!(app.tab.list_state.selected(), Some(0));
+        assert_eq!(app.tab.details_scroll, 0);
+
+        // Another query starts at the top too
+        app.tab.list_state.select(Some(2));
+        app.tab.details_scroll = 1;
+        app.tab.last_query = "go".to_string();
+        app.set_results(app.tab.results.clone(), 4);
+        assert_eq!((app.tab.list_state.selected(), app.tab.details_scroll), (Some(0), 0));
+    }
+
+    #[test]
+    fn test_exclude_owner() {
+        let mut app = app_with_results(2);
+        app.focus = Focus::Browsing;
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('x'))]);
+        assert_eq!(action, None, "no search to narrow yet");
+
+        app.tab.last_query = "game engine".to_string();
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('x'))]);
+        assert_eq!(action, Some(Action::Search("game engine -user:owner".to_string())));
+        assert_eq!(app.tab.input.value(), "game engine -user:owner");
+
+        app.tab.last_query = "game engine -user:owner".to_string();
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('x'))]);
+        assert_eq!(action, None, "already left out");
+    }
+
+    #[test]
+    fn test_open_pane_in_clone() {
+        let mut app = app_with_results(1);
+        app.focus = Focus::Browsing;
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('d'))]);
+        assert_eq!(action, None);
+        assert!(app.toasts.items.last().unwrap().message.contains("Not cloned yet"));
+
+        let dir = PathBuf::from("repositories/repo0");
+        app.cloned_paths.insert("https://github.com/owner/repo0".to_string(), dir.clone());
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('d'))]);
+        assert_eq!(action, None, "not inside tmux or WezTerm");
+        assert!(app.toasts.items.last().unwrap().message.contains("pane_command"));
+
+        app.pane_command = Some(vec!["tmux".to_string(), "split-window".to_string(), "-c".to_string()]);
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('d'))]);
+        assert_eq!(action, Some(Action::OpenPane(dir)));
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let mut app = app_with_results(1);
+        app.focus = Focus::Browsing;
+        let url = "https://github.com/owner/repo0".to_string();
+        let (_, action) = run_keys(&mut app, &[key(KeyCode::Char('I'))]);
+        assert_eq!(action, Some(Action::Maintenance(url.clone())));
+
+        app.pending_maintenance.insert(url.clone());
+        let (rows, action) = run_keys(&mut app, &[key(KeyCode::Char('I'))]);
+        assert_eq!(action, None, "already being measured");
+        assert!(panel_text(&rows).iter().any(|row| row.ends_with("Issues: Loading...")), "{}", rows.join("\n"));
+
+        let id = app.jobs.list.add(JobKind::Maintenance, url.as_str());
+        let hour = Duration::from_secs(3600);
+        let metrics = Maintenance {
+            issues: 18,
+            first_response: Some(hour * 5),
+            time_to_close: Some(hour * 24 * 21),
+            pulls: 20,
+            merged: 15,
+            open_pulls: 4,
+            merge_time: Some(hour * 30),
+            contributors: vec![("alice".to_string(), 950), ("bob".to_string(), 30), ("carol".to_string(), 20)],
+        };
+        app.apply_job_event(JobEvent::MaintenanceLoaded { id, url, result: Ok(metrics) });
+        let (rows, action) = run_keys(&mut app, &[key(KeyCode::Char('I'))]);
+        assert_eq!(action, None, "already measured");
+        let rows = panel_text(&rows);
+        let stars = rows.iter().position(|row| row.contains("Stars: 0")).unwrap();
+        assert!(rows[stars + 1].ends_with("Issues: first reply ~5 hours, closed in ~3 weeks (median of 18 recent)"), "{:?}", rows);
+        assert!(rows[stars + 2].ends_with("Pull requests: 15 of 20 merged (75%) in ~30 hours, 4 still open"), "{:?}", rows);
+        assert!(rows[stars + 3].ends_with("Contributors: the top one of 3 contributors made 95% of commits"), "{:?}", rows);
+        let breakdown = rows.iter().position(|row| row.ends_with("Contributors:")).unwrap();
+        assert!(rows[breakdown + 1].starts_with("alice") && rows[breakdown + 1].ends_with("950 commits   95%"), "{:?}", rows);
+    }
+
+    #[test]
+    fn test_results_title_shows_page() {
+        let mut app = app_with_results(3);
+        app.per_page = 1;
+        app.tab.page_starts = vec![0, 1, 2];
+        app.tab.total_count = Some(7);
+        app.tab.list_state.select(Some(1));
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(100, 60)).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        let screen: String = terminal.backend().buffer().content.iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Results (page 2/7 · 3 of 7 loaded)"));
+    }
+
+    #[test]
+    fn test_results_filter_narrows_navigation() {
+        let mut app = App::new();
+        app.set_results(
+            vec![
+                test_repo(1, "rust-lang/rust", "Empowering everyone"),
+                test_repo(2, "bevyengine/bevy", "A data-driven game engine"),
+                test_repo(3, "godotengine/godot", "Multi-platform game engine"),
+            ],
+            3,
+        );
+
+        app.tab.filter_input = Input::from("game");
+        app.apply_filter();
+        assert_eq!(*app.visible_indices(), [1, 2]);
+        assert_eq!(app.get_selected_repo().unwrap().name, "bevy");
+
+        app.next();
+        assert_eq!(app.get_selected_repo().unwrap().name, "godot");
+
+        // Wraps within the filtered view, not the full result set
+        app.next();
+        assert_eq!(app.get_selected_repo().unwrap().name, "bevy");
+
+        app.clear_filter();
+        assert_eq!(app.visible_indices().len(), 3);
+        assert_eq!(app.get_selected_repo().unwrap().name, "rust");
+    }
+
+    #[test]
+    fn test_vim_keys_in_normal_mode() {
+        let mut app = app_with_results(5);
+        app.vim_mode = true;
+        app.focus = Focus::Browsing;
+
+        assert!(matches!(handle_vim_key(&mut app, key(KeyCode::Char('j'))), Some(VimAction::Handled)));
+        assert_eq!(app.tab.list_state.selected(), Some(1));
+
+        handle_vim_key(&mut app, key(KeyCode::Char('G')));
+        assert_eq!(app.tab.list_state.selected(), Some(4));
+
+        // A single 'g' waits for the second one
+        handle_vim_key(&mut app, key(KeyCode::Char('g')));
+        assert!(app.pending_g);
+        assert_eq!(app.tab.list_state.selected(), Some(4));
+        handle_vim_key(&mut app, key(KeyCode::Char('g')));
+        assert!(!app.pending_g);
+        assert_eq!(app.tab.list_state.selected(), Some(0));
+
+        handle_vim_key(&mut app, key(KeyCode::Char('k')));
+        assert_eq!(app.tab.list_state.selected(), Some(4));
+
+        // Plain letters are not typed into the search box while browsing
+        handle_vim_key(&mut app, key(KeyCode::Char('x')));
+        assert!(app.tab.input.value().is_empty());
+
+        handle_vim_key(&mut app, key(KeyCode::Char('/')));
+        assert!(app.tab.filtering);
+
+        assert!(matches!(handle_vim_key(&mut app, key(KeyCode::Esc)), Some(VimAction::Quit)));
+    }
+
+    #[test]
+    fn test_vim_insert_mode_passes_typing_through() {
+        let mut app = App::new();
+        app.vim_mode = true;
+
+        // Typing falls through to the search input in insert mode
+        assert!(handle_vim_key(&mut app, key(KeyCode::Char('j'))).is_none());
+        assert!(matches!(handle_vim_key(&mut app, key(KeyCode::Enter)), Some(VimAction::Search)));
+
+        // Esc leaves insert mode instead of quitting
+        assert!(matches!(handle_vim_key(&mut app, key(KeyCode::Esc)), Some(VimAction::Handled)));
+        assert_eq!(app.focus, Focus::Browsing);
+
+        // Shortcut letters fall through to the default handler in normal mode
+        assert!(handle_vim_key(&mut app, key(KeyCode::Char('o'))).is_none());
+
+        handle_vim_key(&mut app, key(KeyCode::Char('i')));
+        assert_eq!(app.focus, Focus::Typing);
+    }
+
+    #[test]
+    fn test_tabs_keep_their_own_search() {
+        let mut app = app_with_results(5);
+        app.tab.last_query = "rust game engine".to_string();
+        app.tab.list_state.select(Some(3));
+        app.set_size_filter(Some("small".to_string()));
+
+        app.new_tab();
+        assert_eq!((app.tab_count(), app.active_tab), (2, 1));
+        assert!(app.tab.results.is_empty() && app.tab.repo_size_filter.is_none());
+        assert_eq!(app.focus, Focus::Typing);
+        app.tab.last_query = "rust ecs".to_string();
+        app.set_results(vec![test_repo(9, "owner/ecs", "an ecs")], 1);
+        assert_eq!(app.tab_titles(), ["rust game engine", "rust ecs"]);
+
+        // Switching back finds the first search as it was left
+        app.run_shortcut(Shortcut::NextTab);
+        assert_eq!(app.active_tab, 0);
+        assert_eq!(app.tab.results.len(), 5);
+        assert_eq!(app.tab.list_state.selected(), Some(3));
+        assert_eq!(app.tab.repo_size_filter.as_deref(), Some("small"));
+
+        // A new tab opens after the active one
+        app.new_tab();
+        assert_eq!(app.tab_titles(), ["rust game engine", "New search", "rust ecs"]);
+        app.run_shortcut(Shortcut::PreviousTab);
+        assert_eq!(app.tab.last_query, "rust game engine");
+
+        app.switch_tab(2);
+        app.close_tab();
+        assert_eq!((app.tab_count(), app.active_tab), (2, 1));
+        assert_eq!(app.tab.title(), "New search");
+        app.close_tab();
+        app.close_tab();
+        assert_eq!(app.tab_count(), 1);
+        assert_eq!(app.tab.results.len(), 5);
+    }
+
+    #[test]
+    fn test_searches_keep_running_in_background_tabs() {
+        let mut app = App::new();
+        app.begin_search("rust");
+        let id = app.jobs.list.add(JobKind::Search, "rust");
+        app.tab.search_job = Some(id);
+
+        app.new_tab();
+        app.tab.last_query = "go".to_string();
+        app.set_results(vec![test_repo(9, "owner/go", "")], 1);
+        assert!(app.jobs.list.get(id).unwrap().state.is_active());
+
+        // The results land in the tab that searched, not the one on screen
+        let result = Ok((vec![test_repo(1, "owner/rust", ""), test_repo(2, "owner/rust2", "")], 250, TextMatches::new(), None, None));
+        app.jobs.tx.send(JobEvent::SearchFinished { id, query: "rust".to_string(), result, rate_limit: None }).unwrap();
+        drain_job_events(&mut app);
+        assert_eq!(app.tab.results.len(), 1);
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Finished);
+
+        // and so do further pages
+        let page = app.jobs.list.add(JobKind::Search, "rust (page 2)");
+        app.tabs[0].page_job = Some((page, 2));
+        let result = Ok((vec![test_repo(3, "owner/rust3", "")], 250, TextMatches::new()));
+        app.jobs.tx.send(JobEvent::PageLoaded { id: page, page: 2, done: true, result }).unwrap();
+        drain_job_events(&mut app);
+        assert_eq!(app.tab.results.len(), 1);
+
+        app.switch_tab(0);
+        assert_eq!(app.tab.results.len(), 3);
+        assert_eq!(app.pages_loaded(), 2);
+        assert!(app.tab.search_job.is_none() && app.tab.page_job.is_none());
+        assert_eq!(app.tab.list_state.selected(), Some(0));
+
+        // Closing a tab stops what it's still fetching
+        let page = app.jobs.list.add(JobKind::Search, "rust (page 3)");
+        app.tab.page_job = Some((page, 3));
+        app.close_tab();
+        assert!(!app.jobs.list.get(page).unwrap().state.is_active());
+        assert_eq!(app.tab.results.len(), 1);
+    }
+
+    #[test]
+    fn test_tab_keys() {
+        let ctrl = |code| event::KeyEvent::new(code, KeyModifiers::CONTROL);
+        for focus in [Focus::Typing, Focus::Browsing] {
+            assert_eq!(shortcut(&ctrl(KeyCode::Char('t')), focus), Some(Shortcut::NewTab));
+            assert_eq!(shortcut(&ctrl(KeyCode::Tab), focus), Some(Shortcut::NextTab));
+            assert_eq!(shortcut(&ctrl(KeyCode::PageUp), focus), Some(Shortcut::PreviousTab));
+            assert_eq!(
+                shortcut(&event::KeyEvent::new(KeyCode::Char('w'), KeyModifiers::ALT), focus),
+                Some(Shortcut::CloseTab)
+            );
+        }
+        // Ctrl+W still deletes a word in the search box
+        assert_eq!(shortcut(&ctrl(KeyCode::Char('w')), Focus::Typing), None);
+    }
+
+    #[test]
+    fn test_shortcuts_depend_on_focus() {
+        let alt = |c| event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+        let shift = |c| event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::SHIFT);
+
+        // Alt shortcuts work with either focus
+        for focus in [Focus::Typing, Focus::Browsing] {
+            assert_eq!(shortcut(&alt('g'), focus), Some(Shortcut::Clone));
+            assert_eq!(shortcut(&alt('c'), focus), Some(Shortcut::ClearSearch));
+        }
+        // Plain letters only act while browsing, so they can still be typed
+        assert_eq!(shortcut(&key(KeyCode::Char('c')), Focus::Typing), None);
+        assert_eq!(shortcut(&key(KeyCode::Char('c')), Focus::Browsing), Some(Shortcut::Clone));
+        assert_eq!(shortcut(&key(KeyCode::Char('o')), Focus::Browsing), Some(Shortcut::Open));
+        assert_eq!(shortcut(&shift('J'), Focus::Browsing), Some(Shortcut::Jobs));
+        assert_eq!(shortcut(&key(KeyCode::Char('j')), Focus::Browsing), None);
+        assert_eq!(shortcut(&event::KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL), Focus::Browsing), None);
+        assert_eq!(shortcut(&key(KeyCode::Enter), Focus::Browsing), None);
+    }
+
+    #[test]
+    fn test_activity_label() {
+        let mut repo = test_repo(1, "owner/repo", "a test repo");
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(activity_label(&repo, now), None);
+
+        repo.updated_at = Some("2021-11-14T22:13:20Z".parse().unwrap());
+        assert_eq!(activity_label(&repo, now).as_deref(), Some("updated 2 years ago"));
+
+        // Pushes are what recency is about, so they win when both are known
+        repo.pushed_at = Some("2023-11-11T22:13:20Z".parse().unwrap());
+        assert_eq!(activity_label(&repo, now).as_deref(), Some("pushed 3 days ago"));
+    }
+
+    #[test]
+    fn test_leader_chords() {
+        assert!(is_leader_key(&key(KeyCode::Char(' ')), Focus::Browsing));
+        // Space is typed into the search box while typing
+        assert!(!is_leader_key(&key(KeyCode::Char(' ')), Focus::Typing));
+        assert!(!is_leader_key(
+            &event::KeyEvent::new(KeyCode::Char(' '), KeyModifiers::CONTROL),
+            Focus::Browsing
+        ));
+
+        assert_eq!(leader_chord(&key(KeyCode::Char('c'))), Some(Shortcut::Clone));
+        assert_eq!(leader_chord(&key(KeyCode::Char('f'))), Some(Shortcut::FileCount));
+        assert_eq!(leader_chord(&key(KeyCode::Char(' '))), Some(Shortcut::Mark));
+        assert_eq!(leader_chord(&key(KeyCode::Char('q'))), None);
+        assert_eq!(leader_chord(&key(KeyCode::Esc)), None);
+
+        // Every chord key is listed once in the popup
+        let keys: HashSet<char> = LEADER_KEYS.iter().map(|(key, _, _)| *key).collect();
+        assert_eq!(keys.len(), LEADER_KEYS.len());
+    }
+
+    #[test]
+    fn test_open_submenu() {
+        let repo = test_repo(1, "rust-lang/rust", "");
+        let link = |code| deep_link(&repo, &key(code));
+        assert_eq!(link(KeyCode::Char('o')), Some("https://github.com/rust-lang/rust".to_string()));
+        assert_eq!(link(KeyCode::Enter), Some("https://github.com/rust-lang/rust".to_string()));
+        assert_eq!(link(KeyCode::Char('i')), Some("https://github.com/rust-lang/rust/issues".to_string()));
+        assert_eq!(link(KeyCode::Char('p')), Some("https://github.com/rust-lang/rust/pulls".to_string()));
+        assert_eq!(link(KeyCode::Char('r')), Some("https://github.com/rust-lang/rust/releases".to_string()));
+        assert_eq!(link(KeyCode::Char('w')), Some("https://github.com/rust-lang/rust/wiki".to_string()));
+        assert_eq!(link(KeyCode::Char('x')), None);
+        assert_eq!(link(KeyCode::Esc), None);
+
+        // Nothing to open without a selection
+        let mut app = App::new();
+        app.run_shortcut(Shortcut::Open);
+        assert!(!app.pending_open);
+    }
+
+    #[test]
+    fn test_export_prompt() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new();
+        app.run_shortcut(Shortcut::Export);
+        assert!(app.modals.is_empty(), "nothing to export yet");
+
+        app.tab.last_query = "rust game".to_string();
+        app.set_results((0..3).map(|i| test_repo(i, &format!("owner/repo{}", i), "")).collect(), 3);
+        app.run_shortcut(Shortcut::Export);
+        let submitted = app.modals.handle_key(key(KeyCode::Enter));
+        assert_eq!(submitted, Some(Outcome::Submitted(Purpose::Export, "rust-game.md".to_string())));
+
+        // Without marks, the listed (filtered) results are exported
+        app.tab.filter_input = Input::new("repo1".to_string());
+        let report = dir.path().join("rust-game.md");
+        app.confirm_export_prompt(&report.display().to_string());
+        assert!(app.modals.is_empty());
+        let markdown = std::fs::read_to_string(&report).unwrap();
+        assert!(markdown.contains("[owner/repo1]"));
+        assert!(!markdown.contains("[owner/repo0]"));
+
+        // Marked repos are, in the format the extension names
+        app.clear_filter();
+        app.tab.marked.extend([0, 2]);
+        let json = dir.path().join("out/marked.json");
+        app.confirm_export_prompt(&json.display().to_string());
+        let exported: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+        let names: Vec<&str> = exported.as_array().unwrap().iter().map(|repo| repo["full_name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["owner/repo0", "owner/repo2"]);
+        assert!(app.toasts.items.last().unwrap().message.contains("Exported 2 repositories"));
+
+        // An empty path keeps the dialog open
+        app.run_shortcut(Shortcut::Export);
+        app.confirm_export_prompt("  ");
+        assert!(!app.modals.is_empty());
+    }
+
+    #[test]
+    fn test_cheatsheet() {
+        let mut app = App::new();
+        app.tab.input = Input::new("game ".to_string());
+        let shortcut_for = |code, modifiers, focus| shortcut(&event::KeyEvent::new(code, modifiers), focus);
+        assert_eq!(shortcut_for(KeyCode::F(1), KeyModifiers::NONE, Focus::Typing), Some(Shortcut::Cheatsheet));
+        assert_eq!(shortcut_for(KeyCode::Char('h'), KeyModifiers::ALT, Focus::Typing), Some(Shortcut::Cheatsheet));
+        assert_eq!(shortcut_for(KeyCode::Char('?'), KeyModifiers::NONE, Focus::Browsing), Some(Shortcut::Cheatsheet));
+        assert_eq!(shortcut_for(KeyCode::Char('?'), KeyModifiers::NONE, Focus::Typing), None);
+
+        app.run_shortcut(Shortcut::Cheatsheet);
+        assert!(app.show_cheatsheet);
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(120, 60)).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        let screen: String = terminal.backend().buffer().content.iter().map(|cell| cell.symbol()).collect();
+        assert!(qualifiers::QUALIFIERS.iter().all(|qualifier| screen.contains(qualifier.example)));
+
+        // Enter adds the selected example to the query
+        app.move_cheatsheet_selection(-5);
+        let stars = qualifiers::QUALIFIERS.iter().position(|qualifier| qualifier.name == "stars").unwrap();
+        app.move_cheatsheet_selection(stars as isize);
+        app.insert_cheatsheet_example();
+        assert_eq!(app.tab.input.value(), "game stars:>1000");
+        assert!(!app.show_cheatsheet);
+        assert_eq!(app.focus, Focus::Typing);
+    }
+
+    #[test]
+    fn test_suggestion() {
+        let mut app = App::new();
+        app.tab.last_query = "game language:rsut".to_string();
+        app.set_results(Vec::new(), 0);
+        assert_eq!(app.tab.suggestion.as_ref().unwrap().query, "game language:rust");
+
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(100, 60)).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        let screen: String = terminal.backend().buffer().content.iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Did you mean game language:rust?"));
+
+        // One key puts it in the search box and searches
+        assert_eq!(app.run_shortcut(Shortcut::Suggestion), Some(Action::Search("game language:rust".to_string())));
+        assert_eq!(app.tab.input.value(), "game language:rust");
+        assert!(app.tab.suggestion.is_none());
+        assert_eq!(app.run_shortcut(Shortcut::Suggestion), None);
+
+        // Results clear it
+        app.tab.last_query = "game language:rsut".to_string();
+        app.set_results(Vec::new(), 0);
+        app.set_results(vec![test_repo(1, "owner/game", "")], 1);
+        assert!(app.tab.suggestion.is_none());
+    }
+
+    #[test]
+    fn test_comparison() {
+        let mut app = app_with_results(3);
+        app.tab.marked.insert(0);
+        assert_eq!(app.run_shortcut(Shortcut::Compare), None);
+        assert!(app.comparison.is_none());
+        assert!(app.toasts.items.last().unwrap().message.contains("(1 marked)"));
+
+        // Two marked: the view opens and asks for both repos' figures
+        app.tab.marked.insert(2);
+        let action = app.run_shortcut(Shortcut::Compare).unwrap();
+        assert_eq!(action, Action::Compare(vec!["https://github.com/owner/repo0".to_string(), "https://github.com/owner/repo2".to_string()]));
+        app.pending_activity.extend(["https://github.com/owner/repo0".to_string(), "https://github.com/owner/repo2".to_string()]);
+        let rows = app.comparison_rows().unwrap();
+        assert_eq!(rows.iter().find(|row| row.label == "Contributors").unwrap().values, ["loading…", "loading…"]);
+
+        let job = app.jobs.list.add(JobKind::Activity, "https://github.com/owner/repo0");
+        app.apply_job_event(JobEvent::ActivityLoaded {
+            id: job,
+            url: "https://github.com/owner/repo0".to_string(),
+            result: Ok(RepoActivity { releases: Vec::new(), contributors: Some(7) }),
+        });
+        let rows = app.comparison_rows().unwrap();
+        assert_eq!(rows.iter().find(|row| row.label == "Contributors").unwrap().values, ["7", "loading…"]);
+        assert_eq!(rows.iter().find(|row| row.label == "Releases").unwrap().values, ["no releases", "loading…"]);
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(100, 60)).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        let screen: String = terminal.backend().buffer().content.iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Contributors   7"));
+
+        // Reopening only fetches what's missing
+        app.comparison = None;
+        app.pending_activity.clear();
+        assert_eq!(app.run_shortcut(Shortcut::Compare), Some(Action::Compare(vec!["https://github.com/owner/repo2".to_string()])));
+    }
+
+    #[test]
+    fn test_license_conflicts_are_flagged() {
+        let mut app = app_with_results(2);
+        let mut mit = test_repo(5, "owner/permissive", "");
+        mit.license = serde_json::from_value(serde_json::json!({
+            "key": "mit", "name": "MIT License", "spdx_id": "MIT", "node_id": "", "html_url": null
+        }))
+        .unwrap();
+        app.tab.results.push(mit);
+        app.tab.results_revision += 1;
+        let flagged = |rows: &[String]| -> Vec<bool> {
+            ["owner/repo0", "owner/repo1", "owner/permissive"]
+                .iter()
+                .map(|name| panel_text(rows).iter().find(|row| row.contains(name)).unwrap().ends_with("license"))
+                .collect()
+        };
+
+        // Nothing is flagged until the project's license is set
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert_eq!(flagged(&rows), [false, false, false]);
+
+        app.project_license = vec!["MIT", "Apache-2.0"];
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert_eq!(flagged(&rows), [true, true, false]);
+        assert!(rows.iter().any(|row| row.contains("License: no license (project_license is MIT OR Apache-2.0)")));
+
+        app.tab.marked.extend([0, 5]);
+        app.run_shortcut(Shortcut::Compare);
+        let rows = app.comparison_rows().unwrap();
+        assert_eq!(rows.iter().find(|row| row.label == "License").unwrap().values, ["incompatible: no license", "MIT"]);
+    }
+
+    #[test]
+    fn test_fresh_clones_are_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.lock"), "version = 3\n").unwrap();
+        let url = "https://github.com/owner/repo0".to_string();
+        let path = dir.path().display().to_string();
+        let mut app = app_with_results(1);
+        let cloned = |app: &mut App| {
+            let id = app.jobs.list.add(JobKind::Clone, url.as_str());
+            app.apply_job_event(JobEvent::CloneFinished { id, url: url.clone(), path: path.clone(), result: Ok(None) });
+        };
+
+        // Only when audit_after_clone is on
+        cloned(&mut app);
+        assert_eq!(app.tick(), Flow::Continue);
+        app.audit_after_clone = true;
+        cloned(&mut app);
+        assert_eq!(app.tick(), Flow::Run(Action::Audit(url.clone(), dir.path().to_path_buf())));
+        assert_eq!(app.tick(), Flow::Continue);
+
+        app.pending_audits.insert(url.clone());
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows.iter().any(|row| row.contains("Advisories: Checking...")));
+
+        let finding = audit::Finding {
+            id: "RUSTSEC-2024-0001".to_string(),
+            package: "foo".to_string(),
+            version: "1.0.0".to_string(),
+            title: "Flaw in foo".to_string(),
+            severity: Some(Severity::Critical),
+        };
+        let id = app.jobs.list.add(JobKind::Audit, url.as_str());
+        app.apply_job_event(JobEvent::AuditFinished { id, url: url.clone(), result: Ok(vec![finding]) });
+        assert!(app.pending_audits.is_empty());
+        assert!(app.toasts.items.last().unwrap().message.contains("owner/repo0 depends on crates with advisories: 1 (1 critical)"));
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows.iter().any(|row| row.contains("Advisories: 1 (1 critical)")));
+        assert!(rows.iter().any(|row| row.contains("RUSTSEC-2024-0001 critical  foo 1.0.0: Flaw in foo")));
+    }
+
+    #[test]
+    fn test_fresh_clones_run_matching_profiles() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        let url = "https://github.com/owner/repo0".to_string();
+        let mut app = app_with_results(1);
+        let command = |name: &str| profiles::AnalysisCommand {
+            name: name.to_string(),
+            run: "ls".to_string(),
+            args: Vec::new(),
+            timeout_secs: None,
+        };
+        app.analysis_profiles = BTreeMap::from([
+            ("node".to_string(), AnalysisProfile { timeout_secs: Some(5), commands: vec![command("Outdated")] }),
+            ("python".to_string(), AnalysisProfile { timeout_secs: None, commands: vec![command("Lint")] }),
+        ]);
+        let id = app.jobs.list.add(JobKind::Clone, url.as_str());
+        let path = dir.path().display().to_string();
+        app.apply_job_event(JobEvent::CloneFinished { id, url: url.clone(), path, result: Ok(None) });
+        match app.tick() {
+            Flow::Run(Action::Profiles(profiled, path, steps)) => {
+                assert_eq!((profiled.as_str(), path.as_path()), (url.as_str(), dir.path()));
+                let sections: Vec<&str> = steps.iter().map(|step| step.section.as_str()).collect();
+                assert_eq!(sections, ["node: Outdated"]);
+            }
+            other => panic!("expected the node profile to run, got {:?}", other),
+        }
+
+        let id = app.jobs.list.add(JobKind::Profiles, url.as_str());
+        let sections = vec![
+            profiles::Section { name: "node: Outdated".to_string(), result: Ok("left-pad 1.0.0 1.3.0".to_string()) },
+            profiles::Section { name: "node: Audit".to_string(), result: Err("Timed out after 5s".to_string()) },
+        ];
+        app.apply_job_event(JobEvent::ProfilesFinished { id, url: url.clone(), sections });
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows.iter().any(|row| row.contains("node: Outdated:")));
+        assert!(rows.iter().any(|row| row.contains("left-pad 1.0.0 1.3.0")));
+        assert!(rows.iter().any(|row| row.contains("Error: Timed out after 5s")));
+    }
+
+    #[test]
+    fn test_run_shortcut() {
+        let mut app = app_with_results(2);
+        // Open asks which page first (see test_open_submenu)
+        assert_eq!(app.run_shortcut(Shortcut::Open), None);
+        assert!(app.pending_open);
+        assert_eq!(app.selected_url(), Some("https://github.com/owner/repo0".to_string()));
+        assert_eq!(app.run_shortcut(Shortcut::CopyUrl), Some(Action::Copy("https://github.com/owner/repo0".to_string())));
+
+        assert_eq!(app.run_shortcut(Shortcut::Jobs), None);
+        assert!(app.show_jobs);
+
+        app.tab.input = Input::new("rust".to_string());
+        app.run_shortcut(Shortcut::ClearSearch);
+        assert!(app.tab.input.value().is_empty());
+
+        app.toggle_focus();
+        assert_eq!(app.focus, Focus::Browsing);
+        app.toggle_focus();
+        assert_eq!(app.focus, Focus::Typing);
+    }
+
+    #[test]
+    fn test_toggle_mark_and_action_targets() {
+        let mut app = app_with_results(3);
+
+        // Without marks, actions apply to the selected repo
+        assert_eq!(app.action_urls(), Some(vec!["https://github.com/owner/repo0".to_string()]));
+
+        app.toggle_mark();
+        app.tab.list_state.select(Some(2));
+        app.toggle_mark();
+        assert_eq!(app.tab.marked.len(), 2);
+        assert_eq!(
+            app.action_urls(),
+            Some(vec!["https://github.com/owner/repo0".to_string(), "https://github.com/owner/repo2".to_string()])
+        );
+
+        // Toggling again unmarks
+        app.toggle_mark();
+        assert_eq!(app.marked_repos().len(), 1);
+        assert_eq!(app.marked_repos()[0].name, "repo0");
+    }
+
+    #[test]
+    fn test_new_results_clear_marks() {
+        let mut app = app_with_results(3);
+        app.toggle_mark();
+        assert!(!app.tab.marked.is_empty());
+
+        app.set_results(Vec::new(), 0);
+        assert!(app.tab.marked.is_empty());
+        assert!(app.action_urls().is_none());
+    }
+
+    #[test]
+    fn test_active_filters_combine_cli_and_size() {
+        let mut app = App::new();
+        assert!(app.active_filters().is_empty());
+
+        app.cli_filters = vec!["language:rust".to_string()];
+        app.set_size_filter(Some("large".to_string()));
+        assert_eq!(app.active_filters(), vec!["language:rust", "size:large"]);
+    }
+
+    #[test]
+    fn test_running_jobs_reflect_flags() {
+        let mut app = App::new();
+        assert!(app.running_jobs().is_empty());
+
+        app.tab.search_job = Some(app.jobs.list.add(JobKind::Search, "rust"));
+        app.pending_counts.insert("https://github.com/owner/repo".to_string(), CountStatus::Queued);
+        assert_eq!(app.running_jobs(), vec!["searching", "counting files"]);
+    }
+
+    #[test]
+    fn test_progress_label_animates_with_elapsed_time() {
+        let mut app = App::new();
+        assert_eq!(app.progress_label(), "");
+
+        // Counted from when the running search started
+        let id = app.jobs.list.add(JobKind::Search, "rust");
+        app.jobs.list.start(id);
+        app.tab.search_job = Some(id);
+        let start = app.jobs.list.get(id).unwrap().started.unwrap();
+        assert_eq!(app.progress_label_at(start), "⠋ 0.0s");
+        assert_eq!(app.progress_label_at(start + Duration::from_millis(250)), "⠹ 0.2s");
+        assert_eq!(app.progress_label_at(start + Duration::from_millis(1500)), "⠴ 1.5s");
+    }
+
+    #[test]
+    fn test_status_loads_in_the_background() {
+        let mut app = App::new();
+        assert!(app.auth_user.is_none() && app.rate_limit.is_none());
+        let quota = Some(RateLimitInfo { remaining: 30, limit: 30, reset: 0 });
+        let id = app.jobs.list.add(JobKind::Status, "github");
+        app.apply_job_event(JobEvent::StatusLoaded { id, user: Some("octocat".to_string()), rate_limit: quota });
+        assert_eq!(app.auth_user.as_deref(), Some("octocat"));
+        assert_eq!(app.rate_limit, quota);
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Finished);
+
+        // A search that finished first keeps its fresher quota, and a failed lookup keeps the user
+        let id = app.jobs.list.add(JobKind::Status, "github");
+        let stale = Some(RateLimitInfo { remaining: 10, limit: 30, reset: 0 });
+        app.apply_job_event(JobEvent::StatusLoaded { id, user: None, rate_limit: stale });
+        assert_eq!(app.rate_limit, quota);
+        assert_eq!(app.auth_user.as_deref(), Some("octocat"));
+    }
+
+    #[test]
+    fn test_rate_limit_warning_threshold() {
+        let plenty = RateLimitInfo { remaining: 25, limit: 30, reset: 0 };
+        assert!(plenty.warning().is_none());
+
+        let low = RateLimitInfo { remaining: 6, limit: 30, reset: 0 };
+        assert!(low.warning().unwrap().contains("6 of 30"));
+    }
+
+    #[tokio::test]
+    async fn test_esc_cancels_clones_before_quitting() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        /// Sets its flag when dropped, like the clone's CancelOnDrop
+        struct Dropped(Arc<AtomicBool>);
+        impl Drop for Dropped {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let mut app = app_with_results(3);
+        app.focus = Focus::Browsing;
+        let url = "https://github.com/owner/repo0".to_string();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = Dropped(dropped.clone());
+        let id = app.jobs.spawn(JobKind::Clone, url.as_str(), |_, _| async move {
+            let _guard = guard;
+            std::future::pending::<()>().await;
+        });
+        app.pending_clones.insert(url.clone(), CloneStatus::Running(Instant::now(), None));
+        let count = app.jobs.list.add(JobKind::FileCount, "https://github.com/owner/repo1");
+        app.pending_counts.insert("https://github.com/owner/repo1".to_string(), CountStatus::Queued);
+
+        assert_eq!(handle_key(&mut app, key(KeyCode::Esc)), Flow::Continue);
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Cancelled);
+        assert_eq!(app.jobs.list.get(count).unwrap().state, JobState::Cancelled);
+        assert!(app.pending_clones.is_empty() && app.pending_counts.is_empty());
+        // The aborted task drops the clone future, which stops the transfer
+        tokio::task::yield_now().await;
+        for _ in 0..100 {
+            if dropped.load(Ordering::Relaxed) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(dropped.load(Ordering::Relaxed));
+
+        // With nothing left to cancel, Esc quits
+        assert_eq!(handle_key(&mut app, key(KeyCode::Esc)), Flow::Quit);
+    }
+
+    #[test]
+    fn test_cancel_keys() {
+        assert!(is_cancel_key(&key(KeyCode::Esc)));
+        assert!(is_cancel_key(&event::KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL)));
+        assert!(!is_cancel_key(&key(KeyCode::Char('x'))));
+        assert!(!is_cancel_key(&key(KeyCode::Enter)));
+    }
+
+    #[test]
+    fn test_live_search_debounces_and_coalesces() {
+        let mut app = App::new();
+        app.live_search = true;
+        let start = Instant::now();
+
+        // Nothing pending without an edit
+        assert!(app.live_search_due(start).is_none());
+
+        app.tab.input = Input::from("rust gam");
+        app.last_edit = Some(start);
+        app.tab.input = Input::from("rust game");
+        app.last_edit = Some(start + Duration::from_millis(200));
+
+        // Still within the debounce window of the latest edit
+        assert!(app.live_search_due(start + Duration::from_millis(600)).is_none());
+
+        // Only the latest text is searched, once
+        assert_eq!(app.live_search_due(start + Duration::from_millis(700)), Some("rust game".to_string()));
+        assert!(app.live_search_due(start + Duration::from_millis(800)).is_none());
+    }
+
+    #[test]
+    fn test_live_search_skips_repeated_query() {
+        let mut app = App::new();
+        app.live_search = true;
+        app.tab.input = Input::from("rust game");
+        app.tab.last_query = "rust game".to_string();
+        app.input_edited();
+
+        assert!(app.live_search_due(Instant::now() + Duration::from_secs(1)).is_none());
+    }
+
+    #[test]
+    fn test_update_messages() {
+        let mut app = app_with_results(3);
+        assert_eq!(app.update(AppMessage::Key(key(KeyCode::Down))), Flow::Continue);
+        assert_eq!(app.tab.list_state.selected(), Some(1));
+        let ctrl_c = event::KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(app.update(AppMessage::Key(ctrl_c)), Flow::Quit);
+
+        // Ticks fire due timers as actions for main's loop
+        assert_eq!(app.update(AppMessage::Tick), Flow::Continue);
+        app.live_search = true;
+        app.tab.input = Input::from("rust ecs");
+        app.last_edit = Some(Instant::now() - Duration::from_secs(1));
+        assert_eq!(app.update(AppMessage::Tick), Flow::Run(Action::Search("rust ecs".to_string())));
+
+        // Results from background jobs arrive as messages too
+        let url = "https://github.com/owner/repo0".to_string();
+        let id = app.jobs.list.add(JobKind::FileCount, url.clone());
+        app.jobs.tx.send(JobEvent::FileCountFinished { id, url: url.clone(), result: Ok(file_stats(42)) }).unwrap();
+        let messages = app.job_messages();
+        assert!(matches!(messages.as_slice(), [AppMessage::Job(JobEvent::FileCountFinished { .. })]));
+        for message in messages {
+            assert_eq!(app.update(message), Flow::Continue);
+        }
+        assert_eq!(app.file_counts.get(&url), Some(&Ok(file_stats(42))));
+    }
+
+    #[test]
+    fn test_input_edited_only_in_live_mode() {
+        let mut app = App::new();
+        app.input_edited();
+        assert!(app.last_edit.is_none());
+
+        app.live_search = true;
+        app.input_edited();
+        assert!(app.last_edit.is_some());
+    }
+
+    #[test]
+    fn test_resume_after_rate_limit() {
+        let mut app = App::new();
+        let now = 1_700_000_000;
+
+        // Only rate-limit errors are retried
+        app.schedule_resume("rust", &Error::NotFound, now);
+        assert!(app.tab.resume_search.is_none());
+
+        app.schedule_resume("rust", &Error::RateLimited { reset: None }, now);
+        assert_eq!(app.resume_in(now), Some(61));
+
+        app.schedule_resume("rust game", &Error::RateLimited { reset: Some(now + 42) }, now);
+        assert_eq!(app.resume_in(now), Some(43));
+        assert!(app.resume_due(now + 42).is_none());
+        assert_eq!(app.resume_due(now + 43), Some("rust game".to_string()));
+        assert!(app.tab.resume_search.is_none());
+        assert!(app.resume_due(now + 44).is_none());
+
+        app.schedule_resume("rust", &Error::RateLimited { reset: Some(now) }, now);
+        assert!(app.cancel_resume());
+        assert!(!app.cancel_resume());
+        assert!(app.resume_due(now + 60).is_none());
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(0), "0s");
+        assert_eq!(format_countdown(42), "42s");
+        assert_eq!(format_countdown(185), "3m 05s");
+    }
+
+    #[test]
+    fn test_job_events_update_pending_and_results() {
+        let mut app = App::new();
+        let url = "https://github.com/owner/repo".to_string();
+        app.pending_counts.insert(url.clone(), CountStatus::Queued);
+        let id = app.jobs.list.add(JobKind::FileCount, url.clone());
+
+        app.jobs.tx.send(JobEvent::Started { id }).unwrap();
+        drain_job_events(&mut app);
+        assert!(matches!(app.pending_counts.get(&url), Some(CountStatus::Running(_))));
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Running);
+
+        app.jobs
+            .tx
+            .send(JobEvent::FileCountFinished { id, url: url.clone(), result: Ok(file_stats(42)) })
+            .unwrap();
+        drain_job_events(&mut app);
+        assert!(app.pending_counts.is_empty());
+        assert_eq!(app.file_counts.get(&url), Some(&Ok(file_stats(42))));
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Finished);
+    }
+
+    #[test]
+    fn test_failed_job_records_error_and_toasts() {
+        let mut app = App::new();
+        let url = "https://github.com/owner/repo".to_string();
+        app.pending_counts.insert(url.clone(), CountStatus::Queued);
+        let id = app.jobs.list.add(JobKind::FileCount, url.clone());
+
+        app.jobs
+            .tx
+            .send(JobEvent::FileCountFinished { id, url: url.clone(), result: Err("clone failed".to_string()) })
+            .unwrap();
+        drain_job_events(&mut app);
+
+        assert_eq!(app.file_counts.get(&url), Some(&Err("clone failed".to_string())));
+        assert_eq!(app.toasts.items.len(), 1);
+    }
+
+    #[test]
+    fn test_cancel_job_clears_pending_count_and_ignores_late_events() {
+        let mut app = App::new();
+        let url = "https://github.com/owner/repo".to_string();
+        app.pending_counts.insert(url.clone(), CountStatus::Queued);
+        let id = app.jobs.list.add(JobKind::FileCount, url.clone());
+
+        app.show_jobs = true;
+        app.jobs_state.select(Some(0));
+        assert_eq!(app.selected_job_id(), Some(id));
+
+        app.cancel_job(id);
+        assert!(app.pending_counts.is_empty());
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Cancelled);
+
+        // A result that was already in flight doesn't resurrect the job
+        app.jobs
+            .tx
+            .send(JobEvent::FileCountFinished { id, url: url.clone(), result: Ok(file_stats(42)) })
+            .unwrap();
+        drain_job_events(&mut app);
+        assert!(!app.file_counts.contains_key(&url));
+    }
+
+    #[test]
+    fn test_jobs_panel_lists_newest_first() {
+        let mut app = App::new();
+        let first = app.jobs.list.add(JobKind::Search, "first");
+        let second = app.jobs.list.add(JobKind::Search, "second");
+
+        app.jobs_state.select(Some(0));
+        assert_eq!(app.selected_job_id(), Some(second));
+
+        app.move_job_selection(1);
+        assert_eq!(app.selected_job_id(), Some(first));
+
+        // Clamped at the end of the list
+        app.move_job_selection(1);
+        assert_eq!(app.selected_job_id(), Some(first));
+    }
+
+    #[test]
+    fn test_gauge_bar() {
+        assert_eq!(gauge_bar(0, 10), "░░░░░░░░░░");
+        assert_eq!(gauge_bar(45, 10), "████░░░░░░");
+        assert_eq!(gauge_bar(100, 10), "██████████");
+        assert_eq!(gauge_bar(150, 4), "████");
+    }
+
+    #[test]
+    fn test_clone_progress_event_updates_app() {
+        let mut app = App::new();
+        let url = "https://github.com/owner/repo".to_string();
+        let id = app.jobs.list.add(JobKind::Clone, url.clone());
+        app.jobs.list.start(id);
+
+        let progress = CloneProgress {
+            phase: "Receiving objects".to_string(),
+            percent: 45,
+            current: 450,
+            total: 1000,
+            rate: Some("2.30 MiB/s".to_string()),
+        };
+        app.jobs.tx.send(JobEvent::CloneProgress { id, url: url.clone(), progress: progress.clone() }).unwrap();
+        drain_job_events(&mut app);
+
+        assert_eq!(app.clone_progress, Some((url, progress)));
+    }
+
+    #[test]
+    fn test_search_runs_as_a_job() {
+        let mut app = App::new();
+        let start = |app: &mut App, query: &str| {
+            app.begin_search(query);
+            let id = app.jobs.list.add(JobKind::Search, query);
+            app.jobs.list.start(id);
+            app.tab.search_job = Some(id);
+            id
+        };
+        let finished = |id, result| JobEvent::SearchFinished { id, query: "rust".to_string(), result, rate_limit: None };
+
+        // Keys still reach the app while it runs; Esc cancels it instead of quitting
+        let id = start(&mut app, "rust");
+        assert!(app.searching());
+        assert_eq!(handle_key(&mut app, key(KeyCode::Esc)), Flow::Continue);
+        assert!(!app.searching());
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Cancelled);
+        // and whatever it finds afterwards is dropped
+        app.jobs.tx.send(finished(id, Ok((vec![test_repo(1, "owner/late", "")], 1, TextMatches::new(), None, None)))).unwrap();
+        drain_job_events(&mut app);
+        assert!(app.tab.results.is_empty());
+
+        // A new search replaces the one still running
+        let replaced = start(&mut app, "rus");
+        let id = start(&mut app, "rust");
+        assert_eq!(app.jobs.list.get(replaced).unwrap().state, JobState::Cancelled);
+
+        let rate_limit = Some(RateLimitInfo { remaining: 2, limit: 30, reset: 0 });
+        let result = Ok((vec![test_repo(1, "owner/repo1", "")], 1, TextMatches::new(), None, None));
+        app.jobs.tx.send(JobEvent::SearchFinished { id, query: "rust".to_string(), result, rate_limit }).unwrap();
+        drain_job_events(&mut app);
+        assert!(!app.searching());
+        assert_eq!(app.tab.results.len(), 1);
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Finished);
+        assert_eq!(app.rate_limit, rate_limit);
+        assert!(app.toasts.items.last().unwrap().message.contains("2 of 30"));
+
+        // A rate-limited one shows the error and retries once the quota resets
+        let id = start(&mut app, "rust");
+        app.jobs.tx.send(finished(id, Err(Error::RateLimited { reset: Some(unix_now() + 30) }))).unwrap();
+        drain_job_events(&mut app);
+        assert!(app.tab.error_message.is_some() && app.tab.resume_search.is_some());
+    }
+
+    #[test]
+    fn test_search_changes_since_last_run() {
+        let mut app = App::new();
+        app.begin_search("rust");
+        let id = app.jobs.list.add(JobKind::Search, "rust");
+        app.jobs.list.start(id);
+        app.tab.search_job = Some(id);
+        let changes = cache::Changes {
+            since: SystemTime::now() - Duration::from_secs(3 * 86400),
+            added: HashSet::from([2]),
+            removed: (1..=5).map(|n| format!("owner/gone{}", n)).collect(),
+        };
+        let repos = vec![test_repo(1, "owner/kept", ""), test_repo(2, "owner/new", "")];
+        let result = Ok((repos, 2, TextMatches::new(), None, Some(Box::new(changes))));
+        app.jobs.tx.send(JobEvent::SearchFinished { id, query: "rust".to_string(), result, rate_limit: None }).unwrap();
+        drain_job_events(&mut app);
+        assert!(app.tab.changes.as_ref().unwrap().added.contains(&2));
+        let toast = &app.toasts.items.last().unwrap().message;
+        assert_eq!(toast, "No longer found: owner/gone1, owner/gone2, owner/gone3 and 2 more");
+
+        // Results without a previous run to compare with replace them
+        app.set_results(vec![test_repo(3, "owner/other", "")], 1);
+        assert!(app.tab.changes.is_none());
+    }
+
+    #[test]
+    fn test_analysis_events_store_results() {
+        let mut app = App::new();
+        let url = "https://github.com/owner/repo".to_string();
+        let id = app.jobs.list.add(JobKind::Analysis, url.clone());
+        app.pending_analyses.insert(url.clone(), CountStatus::Queued);
+
+        app.jobs.tx.send(JobEvent::Started { id }).unwrap();
+        drain_job_events(&mut app);
+        assert!(matches!(app.pending_analyses.get(&url), Some(CountStatus::Running(_))));
+
+        let stats = vec![LanguageStats { name: "Rust".to_string(), files: 2, code: 40, comments: 5, blanks: 3 }];
+        app.jobs.tx.send(JobEvent::AnalysisFinished { id, url: url.clone(), result: Ok(stats.clone()) }).unwrap();
+        drain_job_events(&mut app);
+        assert!(app.pending_analyses.is_empty());
+        assert_eq!(app.analyses.get(&url), Some(&Ok(stats.clone())));
+        assert_eq!(app.jobs.list.get(id).unwrap().state, JobState::Finished);
+
+        let table = language_table(&stats);
+        assert_eq!(table.len(), 3); // Header, Rust, total
+        assert_eq!(table[1].to_string(), "  Rust                  2        40         5        3");
     }
-}
 
-/// Main UI rendering function
-/// Splits screen into 4 sections: Search input, Results list, Details panel, Help bar
-pub fn ui(f: &mut Frame, app: &mut App) {
-    // Define layout: split terminal into 4 vertical sections
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .margin(2)
-        .constraints([
-            Constraint::Length(3),  // Search box: fixed 3 lines
-            Constraint::Min(10),    // Results: at least 10 lines, expands with screen
-            Constraint::Min(20),    // Details: at least 20 lines for file count output
-            Constraint::Length(3),  // Help: fixed 3 lines
-        ])
-        .split(f.area());
+    #[test]
+    fn test_results_for_background_tabs_are_stored() {
+        let mut app = app_with_results(1);
+        let url = "https://github.com/owner/repo0".to_string();
+        let id = app.jobs.list.add(JobKind::FileCount, url.clone());
+        app.new_tab();
 
-    // Search input box with size filter indicator
-    let title = if let Some(filter) = &app.repo_size_filter {
-        format!(" 🔍 Search GitHub Repositories [Filter: {}] ", filter)
-    } else {
-        " 🔍 Search GitHub Repositories ".to_string()
-    };
+        app.jobs.tx.send(JobEvent::FileCountFinished { id, url: url.clone(), result: Ok(file_stats(42)) }).unwrap();
+        drain_job_events(&mut app);
+        let stored = app.analysis_store.get(&app.tabs[0].results[0]).and_then(|entry| entry.files.as_ref());
+        assert_eq!(stored.map(|stored| &stored.value), Some(&file_stats(42)));
+    }
 
-    let input_block = Block::default()
-        .borders(Borders::ALL)
-        .title(title)
-        .border_style(Style::default().fg(Color::Cyan));
+    #[test]
+    fn test_listing_is_recomputed_only_on_change() {
+        let mut app = app_with_results(3);
+        let first = app.listing();
+        assert!(Rc::ptr_eq(&first.indices, &app.listing().indices), "nothing changed");
+        assert!(!first.any_files);
 
-    let input_text = app.input.value();
-    let input_widget = Paragraph::new(input_text)
-        .block(input_block)
-        .style(Style::default().fg(Color::Black));
+        app.tab.filter_input = Input::new("repo1".to_string());
+        assert_eq!(*app.visible_indices(), [1]);
+        app.tab.filter_input.reset();
 
-    f.render_widget(input_widget, chunks[0]);
+        let url = "https://github.com/owner/repo0".to_string();
+        let id = app.jobs.list.add(JobKind::FileCount, url.clone());
+        app.apply_job_event(JobEvent::FileCountFinished { id, url, result: Ok(file_stats(4)) });
+        assert!(app.listing().any_files);
 
-    // Set cursor position
-    let cursor_x = chunks[0].x + app.input.visual_cursor() as u16 + 1;
-    let cursor_y = chunks[0].y + 1;
-    f.set_cursor_position((cursor_x, cursor_y));
+        app.append_results(vec![test_repo(3, "owner/repo3", "")], 4);
+        assert_eq!(app.visible_indices().len(), 4);
+    }
 
-    // Results list
-    if app.searching {
-        let searching_text = Paragraph::new("Searching... ⏳")
-            .block(Block::default().borders(Borders::ALL).title(" Results "))
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::Yellow));
-        f.render_widget(searching_text, chunks[1]);
-    } else if let Some(error) = &app.error_message {
-        let error_text = Paragraph::new(format!("❌ Error: {}", error))
-            .block(Block::default().borders(Borders::ALL).title(" Results "))
-            .style(Style::default().fg(Color::Red))
-            .wrap(Wrap { trim: true });
-        f.render_widget(error_text, chunks[1]);
-    } else if app.results.is_empty() {
-        let welcome_text = vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                "Welcome to Mr Krabz GitHub Search! 👋",
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from("Type a search query and press Enter to search."),
-            Line::from("Examples: 'rust game', 'web framework', 'machine learning'"),
-            Line::from(""),
-            Line::from("Use ↑/↓ to navigate results, Enter to open in browser."),
-        ];
+    #[test]
+    fn test_analyzed_figures_become_columns() {
+        let mut app = app_with_results(2);
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(!rows.join("\n").contains("LOC"));
 
-        let welcome = Paragraph::new(welcome_text)
-            .block(Block::default().borders(Borders::ALL).title(" Results "))
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(Color::Gray));
-        f.render_widget(welcome, chunks[1]);
-    } else {
-        let title = if let Some(total) = app.total_count {
-            format!(" Results ({} total) ", total)
-        } else {
-            " Results ".to_string()
-        };
+        // Files come from Alt+F's report, or else from the language table
+        let first = "https://github.com/owner/repo0".to_string();
+        let stats = vec![LanguageStats { name: "Rust".to_string(), files: 7, code: 42000, comments: 5, blanks: 3 }];
+        app.analyses.insert(first.clone(), Ok(stats));
+        app.figures_revision += 1;
+        let (rows, _) = run_keys(&mut app, &[]);
+        let row = |rows: &[String], name: &str| rows.iter().find(|row| row.contains(name)).cloned().unwrap();
+        assert!(row(&rows, "owner/repo0").contains("| 7 files | 42k LOC"), "{}", rows.join("\n"));
+        assert!(row(&rows, "owner/repo1").contains("| - | -"), "{}", rows.join("\n"));
 
-        let items: Vec<ListItem> = app
-            .results
-            .iter()
-            .map(|repo| {
-                let name = repo.full_name.as_ref().map(|s| s.as_str()).unwrap_or("Unknown");
-                let stars = repo.stargazers_count.unwrap_or(0);
-                let language = repo.language
-                    .as_ref()
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown");
-                let size = repo.size.unwrap_or(0);
-
-                let line = Line::from(vec![
-                    Span::styled(name, Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)),
-                    Span::raw(" | "),
-                    Span::styled(format!("⭐ {}", stars), Style::default().fg(Color::Yellow)),
-                    Span::raw(" | "),
-                    Span::styled(language, Style::default().fg(Color::Blue)),
-                    Span::styled(format!("📦 {}", size), Style::default().fg(Color::Gray))
-                ]);
-
-                ListItem::new(line)
-            })
-            .collect();
+        app.file_counts.insert(first, Ok(file_stats(9)));
+        app.figures_revision += 1;
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(row(&rows, "owner/repo0").contains("| 9 files | 42k LOC"), "{}", rows.join("\n"));
+    }
 
-        let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title(title))
-            .highlight_style(
-                Style::default()
-                    .bg(Color::DarkGray)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .highlight_symbol("▶ ");
+    #[test]
+    fn test_local_sort_by_analyzed_figures() {
+        let mut app = app_with_results(3);
+        app.focus = Focus::Browsing;
+        let url = |i: usize| format!("https://github.com/owner/repo{}", i);
+        let stats = |code: usize| vec![LanguageStats { name: "Rust".to_string(), files: 1, code, comments: 0, blanks: 0 }];
+        app.analyses.insert(url(1), Ok(stats(10)));
+        app.analyses.insert(url(2), Ok(stats(500)));
+        app.file_counts.insert(url(1), Ok(file_stats(90)));
+        app.figures_revision += 1;
+        app.tab.list_state.select(Some(0));
+        let order = |app: &App| app.visible_indices().to_vec();
+        let alt = |c| event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
 
-        f.render_stateful_widget(list, chunks[1], &mut app.list_state);
+        let (rows, _) = run_keys(&mut app, &[key(KeyCode::Char('S'))]);
+        assert_eq!(app.tab.local_sort, Some(LocalSort::Files));
+        assert_eq!(order(&app), [1, 2, 0], "not analyzed goes last");
+        assert!(rows.join("\n").contains("Results (3 total) [by file count]"), "{}", rows.join("\n"));
+        assert_eq!(app.get_selected_repo().unwrap().name, "repo0", "the selection follows its repo");
+
+        run_keys(&mut app, &[alt('z')]);
+        assert_eq!(order(&app), [2, 1, 0]);
+
+        // New figures re-sort the list as they arrive
+        let id = app.jobs.list.add(JobKind::Analysis, url(0));
+        app.apply_job_event(JobEvent::AnalysisFinished { id, url: url(0), result: Ok(stats(9000)) });
+        assert_eq!(order(&app), [0, 2, 1]);
+        assert_eq!(app.get_selected_repo().unwrap().name, "repo0");
+
+        run_keys(&mut app, &[alt('z')]);
+        assert_eq!(app.tab.local_sort, None);
+        assert_eq!(order(&app), [0, 1, 2]);
     }
 
-    // Details panel
-    let details_block = Block::default().borders(Borders::ALL).title(" Details ");
+    #[test]
+    fn test_queued_clones_track_status_per_repo() {
+        let mut app = App::new();
+        let first = "https://github.com/owner/first".to_string();
+        let second = "https://github.com/owner/second".to_string();
+        let first_id = app.jobs.list.add(JobKind::Clone, first.clone());
+        let second_id = app.jobs.list.add(JobKind::Clone, second.clone());
+        app.pending_clones.insert(first.clone(), CloneStatus::Queued);
+        app.pending_clones.insert(second.clone(), CloneStatus::Queued);
 
-    if let Some(repo) = app.get_selected_repo() {
-        let description = repo.description.as_deref().unwrap_or("No description");
-        let stars = repo.stargazers_count.unwrap_or(0);
-        let forks = repo.forks_count.unwrap_or(0);
-        let language = repo.language
-            .as_ref()
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown");
-        let url = repo.html_url.as_ref().map(|u| u.to_string()).unwrap_or_default();
+        app.jobs.tx.send(JobEvent::Started { id: first_id }).unwrap();
+        drain_job_events(&mut app);
+        assert!(matches!(app.pending_clones.get(&first), Some(CloneStatus::Running(_, None))));
+        assert_eq!(app.pending_clones.get(&second), Some(&CloneStatus::Queued));
+        assert_eq!(app.running_jobs(), vec!["cloning"]);
 
-        let mut details_text = vec![
-            Line::from(vec![
-                Span::styled("Description: ", Style::default().fg(Color::Gray)),
-                Span::raw(description),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("⭐ Stars: ", Style::default().fg(Color::Yellow)),
-                Span::raw(format!("{}", stars)),
-                Span::raw("  "),
-                Span::styled("🍴 Forks: ", Style::default().fg(Color::Green)),
-                Span::raw(format!("{}", forks)),
-                Span::raw("  "),
-                Span::styled("💻 Language: ", Style::default().fg(Color::Blue)),
-                Span::raw(language),
-            ]),
-            Line::from(""),
-        ];
+        app.jobs
+            .tx
+            .send(JobEvent::CloneFinished {
+                id: first_id,
+                url: first.clone(),
+                path: "repositories/first".to_string(),
+                result: Err("network error".to_string()),
+            })
+            .unwrap();
+        drain_job_events(&mut app);
+        assert!(!app.pending_clones.contains_key(&first));
+        assert_eq!(app.jobs.list.get(first_id).unwrap().state, JobState::Failed("network error".to_string()));
 
-        // Show clone status if available
-        if let Some(status) = &app.clone_status {
-            details_text.push(Line::from(vec![
-                Span::styled("📦 Clone: ", Style::default().fg(Color::Cyan)),
-                Span::styled(status, Style::default().fg(Color::Green)),
-            ]));
-            details_text.push(Line::from(""));
-        }
+        // Cancelling a queued clone drops its status line
+        app.cancel_job(second_id);
+        assert!(app.pending_clones.is_empty());
+        assert!(app.running_jobs().is_empty());
+    }
 
-        // Add file count if available
-        if app.counting_files {
-            details_text.push(Line::from(vec![
-                Span::styled("📁 Files: ", Style::default().fg(Color::Magenta)),
-                Span::styled("Cloning and counting...", Style::default().fg(Color::Yellow)),
-            ]));
-        } else if app.cloning {
-            details_text.push(Line::from(vec![
-                Span::styled("📦 Cloning: ", Style::default().fg(Color::Cyan)),
-                Span::styled("Please wait...", Style::default().fg(Color::Yellow)),
-            ]));
-        } else if let Some(count) = app.file_counts.get(&url) {
-            details_text.push(Line::from(vec![
-                Span::styled("📁 File Count:", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
-            ]));
-            details_text.push(Line::from(""));
+    #[test]
+    fn test_terminal_progress_follows_clones() {
+        let mut app = App::new();
+        assert_eq!(app.terminal_progress(), osc::Progress::None);
 
-            // Split the output into lines and add each as a separate Line
-            for line in count.lines() {
-                details_text.push(Line::from(Span::raw(line)));
-            }
-        } else {
-            details_text.push(Line::from(vec![
-                Span::styled("📁 Files: ", Style::default().fg(Color::Magenta)),
-                Span::styled("Press 'f' to count", Style::default().fg(Color::DarkGray)),
-            ]));
-        }
+        let progress = CloneProgress { phase: "Receiving objects".to_string(), percent: 80, current: 8, total: 10, rate: None };
+        app.pending_clones.insert("https://github.com/owner/first".to_string(), CloneStatus::Running(Instant::now(), Some(progress)));
+        app.pending_clones.insert("https://github.com/owner/second".to_string(), CloneStatus::Queued);
+        assert_eq!(app.terminal_progress(), osc::Progress::Value(40));
 
-        details_text.push(Line::from(""));
-        details_text.push(Line::from(vec![
-            Span::styled("🔗 ", Style::default().fg(Color::Cyan)),
-            Span::styled(url, Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED)),
-        ]));
+        app.pending_clones.clear();
+        let id = app.jobs.list.add(JobKind::Update, "owner/first");
+        app.jobs.list.start(id);
+        assert_eq!(app.terminal_progress(), osc::Progress::Indeterminate);
 
-        // Create scrollable paragraph with scroll offset
-        let details = Paragraph::new(details_text)
-            .block(details_block)
-            .wrap(Wrap { trim: true })
-            .scroll((app.details_scroll, 0)); // Apply vertical scroll
-        f.render_widget(details, chunks[2]);
-    } else {
-        let empty = Paragraph::new("Select a repository to see details")
-            .block(details_block)
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center);
-        f.render_widget(empty, chunks[2]);
+        app.terminal_status.enabled = true;
+        app.tab.last_query = "rust tui".to_string();
+        let mut out = Vec::new();
+        app.sync_terminal_status(&mut out).unwrap();
+        assert_eq!(String::from_utf8_lossy(&out), "\x1b]0;mrkrabz: rust tui\x07\x1b]9;4;3;0\x07");
     }
 
-    // Help text
-    let help_text = vec![
-        Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-            Span::raw(": Search  "),
-            Span::styled("Alt+O", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-            Span::raw(": Open  "),
-            Span::styled("↑↓", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-            Span::raw(": Navigate  "),
-            Span::styled("Alt+F", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
-            Span::raw(": Count  "),
-            Span::styled("Alt+G", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-            Span::raw(": Clone  "),
-            Span::styled("1/2/3/0", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-            Span::raw(": Filter  "),
-            Span::styled("Esc", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
-            Span::raw(": Quit"),
-        ]),
-    ];
+    #[test]
+    fn test_parse_depth() {
+        assert_eq!(parse_depth("full"), Some(None));
+        assert_eq!(parse_depth(" "), Some(None));
+        assert_eq!(parse_depth("0"), Some(None));
+        assert_eq!(parse_depth("50"), Some(Some(50)));
+        assert_eq!(parse_depth("-1"), None);
+        assert_eq!(parse_depth("shallow"), None);
+    }
 
-    let help = Paragraph::new(help_text)
-        .block(Block::default().borders(Borders::ALL).title(" Help "))
-        .alignment(Alignment::Center);
-    f.render_widget(help, chunks[3]);
-}
+    #[test]
+    fn test_clone_prompt_defaults_per_purpose() {
+        let mut app = app_with_results(1);
 
-pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let terminal = Terminal::new(backend)?;
-    Ok(terminal)
-}
+        app.open_clone_prompt(ClonePurpose::Clone);
+        assert_eq!(app.clone_prompt.as_ref().unwrap().depth.value(), "full");
 
-pub fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
-    Ok(())
-}
+        app.open_clone_prompt(ClonePurpose::FileCount);
+        assert_eq!(app.clone_prompt.as_ref().unwrap().depth.value(), "1");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // A configured depth applies to both
+        app.clone_depth = Some(20);
+        app.open_clone_prompt(ClonePurpose::Clone);
+        assert_eq!(app.clone_prompt.as_ref().unwrap().depth.value(), "20");
+        app.clone_depth = Some(0);
+        app.open_clone_prompt(ClonePurpose::FileCount);
+        assert_eq!(app.clone_prompt.as_ref().unwrap().depth.value(), "full");
+    }
 
     #[test]
-    fn test_app_initialization() {
-        let app = App::new();
-        assert!(app.input.value().is_empty());
-        assert!(app.results.is_empty());
-        assert_eq!(app.repo_size_filter, None);
-        assert!(!app.searching);
-        assert!(!app.counting_files);
-        assert!(!app.cloning);
+    fn test_confirm_clone_prompt() {
+        let mut app = app_with_results(1);
+        let url = app.action_urls().unwrap();
+
+        app.open_clone_prompt(ClonePurpose::Clone);
+        app.clone_prompt.as_mut().unwrap().depth = Input::new("abc".to_string());
+        assert_eq!(app.confirm_clone_prompt(), None);
+        assert!(app.clone_prompt.as_ref().unwrap().error.is_some());
+
+        app.clone_prompt.as_mut().unwrap().depth = Input::new("5".to_string());
+        assert_eq!(app.confirm_clone_prompt(), Some(Action::Clone(url)));
+        assert!(app.clone_prompt.is_none());
+        assert_eq!(app.clone_options, CloneOptions { depth: Some(5), ..Default::default() });
     }
 
     #[test]
-    fn test_set_size_filter() {
+    fn test_search_error_keeps_guidance() {
         let mut app = App::new();
+        app.set_error(&Error::Network("client error (Connect)".to_string()));
+        assert_eq!(app.tab.error_message.as_deref(), Some("network error: client error (Connect)"));
+        assert_eq!(app.tab.error_hint.as_deref(), Some("Check your internet connection and try again"));
 
-        app.set_size_filter(Some("large".to_string()));
-        assert_eq!(app.repo_size_filter, Some("large".to_string()));
-
-        app.set_size_filter(None);
-        assert_eq!(app.repo_size_filter, None);
+        app.set_error(&Error::Other("boom".to_string()));
+        assert!(app.tab.error_hint.is_none());
     }
 
     #[test]
-    fn test_details_scroll() {
+    fn test_large_clone_needs_second_enter() {
         let mut app = App::new();
+        let mut big = test_repo(1, "owner/big", "huge");
+        big.size = Some(600 * 1024); // 600 MB
+        app.set_results(vec![big], 1);
 
-        assert_eq!(app.details_scroll, 0);
+        app.open_clone_prompt(ClonePurpose::Clone);
+        assert_eq!(app.confirm_clone_prompt(), None);
+        let error = app.clone_prompt.as_ref().unwrap().error.clone().unwrap();
+        assert_eq!(error, "~600.0 MiB is over 500 MB: press Enter again to clone");
+        assert!(matches!(app.confirm_clone_prompt(), Some(Action::Clone(_))));
 
-        app.scroll_details_down();
-        assert_eq!(app.details_scroll, 1);
+        // File counts and a raised (or disabled) threshold don't ask
+        app.open_clone_prompt(ClonePurpose::FileCount);
+        assert!(app.confirm_clone_prompt().is_some());
+        app.clone_confirm_size_mb = 0;
+        app.open_clone_prompt(ClonePurpose::Clone);
+        assert!(app.confirm_clone_prompt().is_some());
+    }
 
-        app.scroll_details_down();
-        assert_eq!(app.details_scroll, 2);
+    #[test]
+    fn test_clone_prompt_mode_selection() {
+        let mut app = app_with_results(1);
+        app.clone_mode = CloneMode::Blobless;
+        app.open_clone_prompt(ClonePurpose::FileCount);
 
-        app.scroll_details_up();
-        assert_eq!(app.details_scroll, 1);
+        let prompt = app.clone_prompt.as_mut().unwrap();
+        assert_eq!(prompt.mode, CloneMode::Blobless);
 
-        app.scroll_details_up();
-        assert_eq!(app.details_scroll, 0);
+        // Arrow keys only change the mode once it has focus
+        prompt.handle_key(key(KeyCode::Right));
+        assert_eq!(prompt.mode, CloneMode::Blobless);
+        prompt.handle_key(key(KeyCode::Tab));
+        assert_eq!(prompt.focus, PromptField::Mode);
+        prompt.handle_key(key(KeyCode::Right));
+        assert_eq!(prompt.mode, CloneMode::Treeless);
+        prompt.handle_key(key(KeyCode::Char('x')));
+        assert_eq!(prompt.depth.value(), "1");
 
-        // Test that it doesn't go below 0
-        app.scroll_details_up();
-        assert_eq!(app.details_scroll, 0);
+        app.confirm_clone_prompt().unwrap();
+        assert_eq!(
+            app.clone_options,
+            CloneOptions { depth: Some(1), mode: CloneMode::Treeless, submodules: false }
+        );
+    }
 
-        // Test reset
-        app.scroll_details_down();
-        app.reset_details_scroll();
-        assert_eq!(app.details_scroll, 0);
+    #[test]
+    fn test_clone_prompt_destination() {
+        let mut app = app_with_results(2);
+        app.open_clone_prompt(ClonePurpose::Clone);
+        let prompt = app.clone_prompt.as_mut().unwrap();
+        assert_eq!(prompt.focus, PromptField::Destination);
+        let default = prompt.destination.value().to_string();
+        assert!(default.starts_with("repositories/"));
+
+        // Clearing the path is rejected
+        prompt.destination = Input::default();
+        assert_eq!(app.confirm_clone_prompt(), None);
+
+        app.clone_prompt.as_mut().unwrap().destination = Input::new("elsewhere/renamed".to_string());
+        assert!(app.confirm_clone_prompt().is_some());
+        assert_eq!(app.clone_destination, Some(PathBuf::from("elsewhere/renamed")));
+
+        // Several targets share a parent directory; file counts have no destination row
+        app.tab.marked = app.tab.results.iter().map(|repo| repo.id.0).collect();
+        app.open_clone_prompt(ClonePurpose::Clone);
+        assert_eq!(app.clone_prompt.as_ref().unwrap().destination.value(), CLONE_DIR);
+        app.open_clone_prompt(ClonePurpose::FileCount);
+        assert!(!app.clone_prompt.as_ref().unwrap().fields().contains(&PromptField::Destination));
     }
 
     #[test]
-    fn test_get_selected_repo_with_no_results() {
+    fn test_results_show_ledger_clones() {
+        let dir = std::env::temp_dir().join(format!("mrkrabz-tui-cloned-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+
         let mut app = App::new();
+        let repos = vec![test_repo(1, "owner/cloned", "a"), test_repo(2, "owner/other", "b")];
+        let url = repos[0].html_url.as_ref().unwrap().to_string();
+        app.clones.record(&url, &dir);
+        app.set_results(repos, 2);
 
-        // No selection returns None
-        assert!(app.get_selected_repo().is_none());
+        assert_eq!(app.cloned_paths.get(&url), Some(&dir));
+        assert_eq!(app.cloned_paths.len(), 1);
+        assert!(app.build_info.is_empty());
 
-        // With selection but no results returns None
-        app.list_state.select(Some(0));
-        assert!(app.get_selected_repo().is_none());
+        // Build hints come from the clone's files
+        std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"cloned\"\nrust-version = \"1.74\"\n").unwrap();
+        app.refresh_cloned();
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows.iter().any(|row| row.contains("Build with: cargo build")));
+        assert!(rows.iter().any(|row| row.contains("Toolchain: Rust >=1.74")));
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 
     #[test]
-    fn test_input_operations() {
+    fn test_clone_prompt_offers_update_of_existing_clone() {
+        let mut app = app_with_results(1);
+        let url = app.action_urls().unwrap().remove(0);
+        app.cloned_paths.insert(url, PathBuf::from("elsewhere/repo0"));
+
+        app.open_clone_prompt(ClonePurpose::Clone);
+        let prompt = app.clone_prompt.as_mut().unwrap();
+        assert_eq!(prompt.destination.value(), "elsewhere/repo0");
+        assert!(prompt.updates_existing());
+
+        // Choosing another path makes it a fresh clone again
+        prompt.destination = Input::new("repositories/copy".to_string());
+        assert!(!prompt.updates_existing());
+    }
+
+    #[test]
+    fn test_clones_view_selection() {
+        let dir = std::env::temp_dir().join(format!("mrkrabz-tui-clones-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+
         let mut app = App::new();
+        app.clones.record("https://github.com/owner/listed", &dir);
+        app.open_clones_view();
+        assert!(app.show_clones);
+        assert_eq!(app.clones_state.selected(), Some(0));
 
-        // Test that input starts empty
-        assert!(app.input.value().is_empty());
+        let row = app.local_clones.iter().position(|clone| clone.path == dir).unwrap();
+        app.clones_state.select(Some(row));
+        assert_eq!(app.selected_clone().unwrap().path, dir);
 
-        // Test Alt+C clear functionality (input.reset())
-        // We can't easily test the actual key handler without mocking terminal input,
-        // but we can test the underlying function
-        app.input = Input::from("test query");
-        assert_eq!(app.input.value(), "test query");
+        app.move_clone_selection(1000);
+        assert_eq!(app.clones_state.selected(), Some(app.local_clones.len() - 1));
+        app.move_clone_selection(-1000);
+        assert_eq!(app.clones_state.selected(), Some(0));
+
+        // Nothing marked: delete targets the selection; marks take over once set
+        app.clones_state.select(Some(row));
+        assert_eq!(app.clones_to_delete().len(), 1);
+        app.toggle_clone_mark();
+        assert!(app.clones_marked.contains(&dir));
+        assert_eq!(app.clones_to_delete()[0].path, dir);
+        app.toggle_clones_sort();
+        assert!(app.clones_by_size);
+        assert_eq!(app.selected_clone().unwrap().path, dir);
+        app.toggle_clone_mark();
+        assert!(app.clones_marked.is_empty());
 
-        app.input.reset();
-        assert!(app.input.value().is_empty());
+        // b writes an SBOM of the selection in the background; the result comes back as a toast
+        let flow = handle_key(&mut app, key(KeyCode::Char('b')));
+        assert_eq!(flow, Flow::Run(Action::Sbom(dir.clone(), app.selected_clone().unwrap().name.clone())));
+        let id = app.jobs.list.add(JobKind::Sbom, "owner/listed");
+        app.apply_job_event(JobEvent::SbomWritten { id, clone: "owner/listed".into(), result: Err("no Cargo.lock".into()) });
+        assert!(app.toasts.items.last().unwrap().message.contains("SBOM of owner/listed failed: no Cargo.lock"));
+
+        // d asks first; anything but y keeps the clone and the overlay
+        app.clones_state.select(Some(app.local_clones.iter().position(|clone| clone.path == dir).unwrap()));
+        handle_key(&mut app, key(KeyCode::Char('d')));
+        assert!(!app.modals.is_empty());
+        handle_key(&mut app, key(KeyCode::Char('n')));
+        assert!(app.modals.is_empty() && app.show_clones && dir.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
-    fn test_searching_and_counting_flags() {
-        let mut app = App::new();
+    fn test_clone_prompt_submodules_toggle() {
+        let mut app = app_with_results(1);
+        app.clone_submodules = true;
+        app.open_clone_prompt(ClonePurpose::Clone);
+
+        let prompt = app.clone_prompt.as_mut().unwrap();
+        assert!(prompt.submodules);
+        prompt.handle_key(key(KeyCode::BackTab));
+        assert_eq!(prompt.focus, PromptField::Submodules);
+        prompt.handle_key(key(KeyCode::Tab));
+        assert_eq!(prompt.focus, PromptField::Destination);
+        prompt.handle_key(key(KeyCode::BackTab));
+        prompt.handle_key(key(KeyCode::Char(' ')));
+
+        app.confirm_clone_prompt().unwrap();
+        assert!(!app.clone_options.submodules);
+    }
+
+    #[test]
+    fn test_strip_colors_keeps_highlight_visible() {
+        let mut buffer = ratatui::buffer::Buffer::empty(ratatui::layout::Rect::new(0, 0, 2, 1));
+        buffer[(0, 0)].set_fg(Color::Yellow).modifier.insert(Modifier::BOLD);
+        buffer[(1, 0)].set_bg(Color::DarkGray);
+        strip_colors(&mut buffer);
+
+        assert!(buffer.content.iter().all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+        assert_eq!(buffer[(0, 0)].modifier, Modifier::BOLD);
+        assert_eq!(buffer[(1, 0)].modifier, Modifier::REVERSED);
+    }
+
+    /// Drive `run_tui` on a 100x45 test terminal: press `keys` in order, then Ctrl+C
+    /// Returns the rows of the screen it leaves behind, and what run_tui returned
+    fn run_keys(app: &mut App, keys: &[event::KeyEvent]) -> (Vec<String>, Option<Action>) {
+        let terminal = || Terminal::new(ratatui::backend::TestBackend::new(100, 45)).unwrap();
+        let quit = event::KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let mut script = keys.iter().copied().chain([quit]);
+        let action = run_tui_with(&mut terminal(), app, || Ok(script.next().map(Event::Key))).unwrap();
+        // Drawn again from scratch: TestBackend keeps stale halves of wide characters between frames
+        let mut terminal = terminal();
+        terminal.draw(|f| ui(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows = buffer
+            .content
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>().trim_end().to_string())
+            .collect();
+        (rows, action)
+    }
+
+    /// Key presses that type `text`
+    #[test]
+    fn test_command_palette() {
+        let mut app = app_with_results(3);
+        let ctrl_p = event::KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        let (rows, _) = run_keys(&mut app, &[ctrl_p, key(KeyCode::Down)]);
+        let screen = rows.join("\n");
+        assert!(screen.contains("Commands") && screen.contains("Space o"), "{}", screen);
+        assert!(!app.modals.is_empty());
 
-        assert!(!app.searching);
-        assert!(!app.counting_files);
-        assert!(!app.cloning);
+        // Enter runs the picked chord's shortcut and closes the palette: "jobs" opens the jobs panel
+        let jobs = LEADER_KEYS.iter().position(|(key, _, _)| *key == 'j').unwrap();
+        let mut keys = vec![key(KeyCode::Up), key(KeyCode::Up)];
+        keys.extend(std::iter::repeat_n(key(KeyCode::Down), jobs));
+        keys.push(key(KeyCode::Enter));
+        run_keys(&mut app, &keys);
+        assert!(app.modals.is_empty() && app.show_jobs);
 
-        app.searching = true;
-        assert!(app.searching);
+        // Esc closes it without running anything, and keys go back to the app
+        let mut app = app_with_results(3);
+        let (_, action) = run_keys(&mut app, &[ctrl_p, key(KeyCode::Esc), key(KeyCode::Down)]);
+        assert_eq!(action, None);
+        assert!(app.modals.is_empty());
+        assert_eq!(app.tab.list_state.selected(), Some(1));
+    }
+
+    fn type_keys(text: &str) -> Vec<event::KeyEvent> {
+        text.chars().map(|c| key(KeyCode::Char(c))).collect()
+    }
+
+    /// The text inside the panel borders on screen `rows`, trimmed
+    fn panel_text(rows: &[String]) -> Vec<&str> {
+        rows.iter().map(|row| row.trim().trim_matches('│').trim()).collect()
+    }
 
-        app.counting_files = true;
-        assert!(app.counting_files);
+    /// The repositories of the first page of the fixture search
+    fn fixture_repos() -> Vec<Repository> {
+        let page: serde_json::Value = serde_json::from_str(include_str!("../tests/fixtures/search_page1.json")).unwrap();
+        serde_json::from_value(page["items"].clone()).unwrap()
+    }
 
-        app.cloning = true;
-        assert!(app.cloning);
+    #[test]
+    fn test_snapshot_welcome() {
+        let (rows, action) = run_keys(&mut App::new(), &[]);
+        assert_eq!(action, None);
+        assert!(rows[2].contains("Search GitHub Repositories"));
+        assert!(rows[5].contains("┌ Results ─"));
+        assert_eq!(panel_text(&rows[6..13]), [
+            "",
+            "Welcome to Mr Krabz GitHub Search! 👋",
+            "",
+            "Type a search query and press Enter to search.",
+            "Examples: 'rust game', 'web framework', 'machine learning'",
+            "",
+            "Use ↑↓ to navigate results, Enter to open in browser.",
+        ]);
+        assert_eq!(panel_text(&rows[20..21]), ["Select a repository to see details"]);
+        assert_eq!(rows[39].trim(), "👤  anonymous  │  API ?  │  no filters  │  GitHub  │  TYPING");
+        assert!(rows[41].contains("Enter: Search  Tab: Browse"));
     }
 
     #[test]
-    fn test_set_results_auto_selects_first() {
+    fn test_snapshot_results() {
         let mut app = App::new();
+        let mut keys = type_keys("rust");
+        keys.push(key(KeyCode::Enter));
+        let (rows, action) = run_keys(&mut app, &keys);
+        assert_eq!(action, Some(Action::Search("rust".to_string())));
+        assert_eq!(panel_text(&rows[3..4]), ["rust"]);
 
-        // set_results auto-selects first item when results is not empty
-        app.set_results(Vec::new(), 0);
-        assert!(app.list_state.selected().is_none());
+        // What main does with the query's results
+        app.set_results(fixture_repos(), 3);
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows[5].contains("┌ Results (3 total) ─"));
+        let list = panel_text(&rows[6..9]);
+        // Ages depend on today's date, so only what comes before them is compared
+        assert!(list[0].starts_with("▶   rust-lang/rust | ⭐  98k | Rust | 📦  245.3 MiB | pushed "), "{}", list[0]);
+        assert!(list[1].starts_with("bevyengine/bevy | ⭐  36k | Rust | 📦  96.5 MiB | pushed "), "{}", list[1]);
+        assert_eq!(list[2], "");
+    }
 
-        // We can't easily create a full Repository here, so we test the logic
-        // The actual functionality is: if results is not empty, select index 0
+    #[test]
+    fn test_snapshot_error() {
+        let mut app = App::new();
+        let mut keys = type_keys("rust");
+        keys.push(key(KeyCode::Enter));
+        run_keys(&mut app, &keys);
+
+        // What main does when the search fails
+        app.set_error(&Error::RateLimited { reset: None });
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(rows[5].contains("┌ Results ─"));
+        assert_eq!(panel_text(&rows[6..10]), [
+            "❌  Error: GitHub API rate limit exceeded",
+            "",
+            "💡  Wait a minute and retry, or add a token (-t or GITHUB_TOKEN) for a higher limit",
+            "",
+        ]);
+        assert_eq!(panel_text(&rows[20..21]), ["Select a repository to see details"]);
     }
 
     #[test]
-    fn test_navigation_wrapping() {
+    fn test_snapshot_rate_limit_resume() {
         let mut app = App::new();
+        app.rate_limit = Some(RateLimitInfo { remaining: 0, limit: 10, reset: 0 });
+        let error = Error::RateLimited { reset: Some(unix_now() + 90) };
+        app.set_error(&error);
+        app.schedule_resume("rust", &error, unix_now());
 
-        // Test that navigation doesn't panic with empty results
-        app.next();
-        assert!(app.list_state.selected().is_none());
+        let (rows, action) = run_keys(&mut app, &[]);
+        assert_eq!(action, None);
+        let hint = &panel_text(&rows[8..9])[0];
+        assert!(hint.starts_with("💡  Searching again in 1m 3"), "{}", hint);
+        assert!(hint.contains("(Esc to cancel)"));
+        assert!(rows.iter().any(|row| row.contains("API 0/10 · resets in 1m 3")));
 
-        app.previous();
-        assert!(app.list_state.selected().is_none());
+        // Esc calls off the retry instead of quitting
+        let (rows, action) = run_keys(&mut app, &[key(KeyCode::Esc)]);
+        assert_eq!(action, None);
+        assert!(app.tab.resume_search.is_none());
+        assert!(panel_text(&rows[8..9])[0].starts_with("💡  Retry in "));
+
+        // Once the quota has reset, the query runs again by itself
+        app.schedule_resume("rust", &Error::RateLimited { reset: Some(unix_now() - 5) }, unix_now());
+        let (_, action) = run_keys(&mut app, &[]);
+        assert_eq!(action, Some(Action::Search("rust".to_string())));
     }
 
     #[test]
-    fn test_alt_o_key_handler_returns_url() {
-        // This test verifies that when Alt+O is pressed on a selected repo,
-        // the TUI returns the URL (for opening in browser) but does NOT exit.
-        // The actual non-exit behavior is tested in main.rs where the loop continues
-        // instead of breaking when handling a URL action.
+    fn test_snapshot_tabs() {
+        let mut app = App::new();
+        app.tab.last_query = "rust game engine".to_string();
+        app.set_results(fixture_repos(), 3);
+        let (rows, _) = run_keys(&mut app, &[]);
+        assert!(!rows[2].contains("1:"), "{}", rows[2]);
 
-        let app = App::new();
+        let ctrl = |code| event::KeyEvent::new(code, KeyModifiers::CONTROL);
+        let mut keys = vec![ctrl(KeyCode::Char('t'))];
+        keys.extend(type_keys("rust ecs"));
+        let (rows, action) = run_keys(&mut app, &keys);
+        assert_eq!(action, None);
+        assert!(rows[2].ends_with("┐") && rows[2].contains(" 1:rust game engine  2:New search ┐"), "{}", rows[2]);
+        assert_eq!(panel_text(&rows[3..4]), ["rust ecs"]);
 
-        // We can't easily create a mock Repository without serde_json,
-        // but we can verify the logic: when there are results and a selection,
-        // get_selected_repo() should return the repo with its html_url.
-        // The key handler in run_tui() at line 170-178 returns Ok(Some(url))
-        // which is then handled in main.rs at line 142-145 without calling break.
+        // Back in the first tab, its results are still listed
+        let (rows, _) = run_keys(&mut app, &[ctrl(KeyCode::Tab)]);
+        assert!(panel_text(&rows[7..8])[0].contains("bevyengine/bevy"));
+        assert_eq!(panel_text(&rows[3..4]), [""]);
+    }
 
-        // Test 1: No results - should not crash
-        assert!(app.get_selected_repo().is_none());
+    #[test]
+    fn test_snapshot_details() {
+        let mut app = App::new();
+        app.set_results(fixture_repos(), 3);
+        let (rows, _) = run_keys(&mut app, &[key(KeyCode::Down)]);
+        assert!(panel_text(&rows[7..8])[0].starts_with("▶   bevyengine/bevy"));
+        let details = panel_text(&rows[20..30]);
+        assert_eq!(details[..4], [
+            "Description: A refreshingly simple data-driven game engine built in Rust",
+            "",
+            "⭐  Stars: 36k  🍴  Forks: 3.5k  💻  Language: Rust  📦  Size: 96.5 MiB",
+            "",
+        ]);
+        assert!(details[4].starts_with("🕒  Pushed: "), "{}", details[4]);
+        assert_eq!(details[5..], ["", "📁  Files: Press 'f' to count", "", "🔗  https://github.com/bevyengine/bevy", ""]);
 
-        // Test 2: Results exist and selection is made - selection should work
-        // (Full integration test would require actual Repository objects)
-        assert_eq!(app.results.len(), 0);
+        // Browsing keys move the selection back and Esc in the search box quits
+        let (rows, action) = run_keys(&mut app, &[key(KeyCode::Tab), key(KeyCode::Up), key(KeyCode::Tab)]);
+        assert_eq!(action, None);
+        assert_eq!(panel_text(&rows[28..29]), ["🔗  https://github.com/rust-lang/rust"]);
+        assert!(rows[39].ends_with("TYPING"));
     }
 }
@@ -1 +0,0 @@
-$e=@{'.js'='JavaScript';'.ts'='TypeScript';'.tsx'='TypeScript/React';'.jsx'='JavaScript/React';'.py'='Python';'.java'='Java';'.cs'='C#';'.cpp'='C++';'.c'='C';'.h'='C/C++ Header';'.php'='PHP';'.rb'='Ruby';'.go'='Go';'.rs'='Rust';'.swift'='Swift';'.kt'='Kotlin';'.scala'='Scala';'.r'='R';'.pl'='Perl';'.sh'='Shell';'.bash'='Bash';'.sql'='SQL';'.html'='HTML';'.htm'='HTML';'.css'='CSS';'.scss'='SCSS';'.sass'='Sass';'.less'='Less';'.json'='JSON';'.xml'='XML';'.yaml'='YAML';'.yml'='YAML';'.toml'='TOML';'.md'='Markdown';'.tex'='LaTeX';'.vue'='Vue';'.lua'='Lua';'.dart'='Dart';'.groovy'='Groovy';'.m'='Objective-C';'.mm'='Objective-C++';'.clj'='Clojure';'.ex'='Elixir';'.erl'='Erlang';'.hx'='Haxe';'.zig'='Zig';'.vb'='Visual Basic';'.gradle'='Gradle';'.tf'='Terraform'};$l=@{'.js'=1;'.ts'=1;'.tsx'=1;'.jsx'=1;'.py'=1;'.java'=1;'.cs'=1;'.cpp'=1;'.c'=1;'.h'=1;'.php'=1;'.rb'=1;'.go'=1;'.rs'=1;'.swift'=1;'.kt'=1;'.scala'=1;'.r'=1;'.pl'=1;'.sh'=1;'.bash'=1;'.sql'=1};$f=@{};$z=0;$ig='node_modules|\.git|\.next|dist|build|target|venv|\.venv|env|\.idea|vendor|__pycache__';Get-ChildItem -Recurse -File -ErrorAction SilentlyContinue|Where-Object{$_.FullName-notmatch $ig}|ForEach-Object{$x=$_.Extension.ToLower();if($e.ContainsKey($x)){$g=$e[$x];if(-not$f.ContainsKey($g)){$f[$g]=@{'c'=0;'l'=0}};$f[$g]['c']++;$z++;if($l.ContainsKey($x)){$lc=[System.IO.File]::ReadAllLines($_.FullName).Count;if($lc-gt0){$f[$g]['l']+=$lc}}}};Write-Host "`n========== Code Files Count ==========" -ForegroundColor Cyan;Write-Host "";$f.Keys|Sort-Object{$f[$_]['c']}-Descending|ForEach-Object{$c=$f[$_]['c'];$o=$f[$_]['l'];$n=$_;if($o-gt0){Write-Host ("{0,-30} Files: {1,-6} | LOC: {2}" -f "${n}:","$c","$o")-ForegroundColor Green}else{Write-Host ("{0,-30} Files: {1,-6} | LOC: N/A" -f "${n}:","$c")-ForegroundColor Green}};Write-Host "";Write-Host "========================================" -ForegroundColor Cyan;Write-Host ("Total Code Files: {0}" -f $z) -ForegroundColor Yellow;Write-Host "========================================" -ForegroundColor Cyan
\ No newline at end of file